@@ -12,10 +12,29 @@ use std::fs::OpenOptions;
 use std::io::Write;
 use crate::core::types::{ShardId, Transaction, SerializableTransaction};
 use crate::core::confidential::ConfidentialTx;
-use crate::core::bridge::BridgeTx;
+use crate::core::bridge::{Bridge, BridgeAction, BridgeTrust, BridgeTx, InboundProof, InboundTransfer, BRIDGE_SHARD};
+use crate::bridge::api::FabricEndorsementInput;
+use crate::bridge::fabric::Endorsement;
 use once_cell::sync::Lazy;
 use axum::http::{StatusCode, HeaderMap, Method};
 use crate::core::tx_pool::ShardedTxPool;
+use crate::core::tx_status::TxStatusRegistry;
+use crate::consensus::governance_executor::{ChainControl, GovernanceExecutor};
+use crate::consensus::staking::{StakingAction, StakingLedger, StakingTx, STAKING_SHARD};
+use crate::consensus::slashing::{Evidence, SlashingAction, SlashingEngine, SlashingTx};
+use crate::consensus::epoch::EpochManager;
+use crate::consensus::rewards::RewardDistributor;
+use crate::iso20022::settlement::{self, SettlementMatcher, SettlementProgress};
+use crate::core::dvp::SignedInstruction;
+use crate::core::idempotency::{Claim, IdempotencyStore};
+use crate::core::ingestion::{Adapter, GatewaySigner, IdentifierKind, IngestionRegistry};
+use crate::core::migration::{AddressMigrationTx, MIGRATION_SHARD};
+use crate::core::handle_registry::{is_handle, HandleOp, HandleRegistry, HandleTx, ResolvedHandle};
+use crate::iso20022::reporting::{self, AccountReport, PaymentStatus};
+use crate::consensus::validator_lifecycle::{LifecycleAction, RotationRequest, ValidatorLifecycle, ValidatorTx};
+use crate::consensus::governance_tx::{GovernanceAction, GovernanceTx};
+use crate::core::multi_leg::{MultiLegTransaction, PartySignature, TransferLeg};
+use crate::core::multisig::{MultisigOp, MultisigOpKind, MultisigPolicy, MultisigSigner};
 use crate::network::propagation::NetworkPropagator;
 use rand;
 use rand_core::RngCore;
@@ -79,6 +98,19 @@ pub struct AppState {
     pub tx_pool: Arc<ShardedTxPool>,
     pub network_propagator: Arc<NetworkPropagator>,
     pub ws_manager: Arc<WebSocketManager>,
+    pub tx_status: Arc<TxStatusRegistry>,
+    pub chain_control: Arc<ChainControl>,
+    pub governance_executor: Arc<GovernanceExecutor>,
+    pub staking_ledger: Arc<StakingLedger>,
+    pub slashing_engine: Arc<SlashingEngine>,
+    pub validator_lifecycle: Arc<ValidatorLifecycle>,
+    pub epoch_manager: Arc<EpochManager>,
+    pub handle_registry: Arc<Mutex<HandleRegistry>>,
+    pub ingestion: Arc<IngestionRegistry>,
+    pub gateway_signers: HashMap<Adapter, Arc<GatewaySigner>>,
+    pub idempotency: Arc<IdempotencyStore>,
+    pub settlement_matcher: Arc<SettlementMatcher>,
+    pub bridge: Arc<Bridge>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -88,6 +120,10 @@ pub struct ApiTransaction {
     pub amount: u64,
     pub currency: String,
     pub shard_id: Option<u16>, // Optional for API, default to 0
+    #[serde(default)]
+    pub valid_after: Option<u64>, // FinDAG Time before which the tx is not includable
+    #[serde(default)]
+    pub valid_until: Option<u64>, // FinDAG Time after which the tx expires
     // ... other fields ...
 }
 
@@ -102,6 +138,200 @@ pub struct SignedTransactionRequest {
     pub hashtimer: Vec<u8>,
     pub public_key: Vec<u8>,
     pub shard_id: u16,
+    #[serde(default)]
+    pub valid_after: Option<u64>,
+    #[serde(default)]
+    pub valid_until: Option<u64>,
+}
+
+/// Multi-leg (e.g. DvP) transaction submitted with one signature per debited party
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MultiLegTxRequest {
+    pub legs: Vec<TransferLeg>,
+    pub reference: String,
+    pub findag_time: u64,
+    #[serde(default)]
+    pub shard_id: u16,
+    #[serde(default)]
+    pub valid_after: Option<u64>,
+    #[serde(default)]
+    pub valid_until: Option<u64>,
+    pub signatures: Vec<PartySignatureReq>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PartySignatureReq {
+    pub public_key: String, // hex
+    pub signature: String,  // hex
+}
+
+/// Legacy address migration request (hex key and signature)
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddressMigrationRequest {
+    pub legacy_address: String,
+    pub findag_time: u64,
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// Multisig account operation: `op` is "create" or "update"
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MultisigOpRequest {
+    pub op: String,
+    pub account: Option<String>, // update only
+    pub nonce: Option<u64>,      // update only
+    pub salt: Option<String>,    // create only, hex
+    pub signers: Vec<MultisigSignerReq>,
+    pub threshold: u64,
+    pub findag_time: u64,
+    #[serde(default)]
+    pub shard_id: u16,
+    pub approvals: Vec<PartySignatureReq>,
+}
+
+/// Delegator-signed staking operation: `op` is "delegate", "undelegate",
+/// "set_commission" or "claim_rewards"
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StakingOpRequest {
+    pub op: String,
+    pub delegator: String,
+    pub validator: Option<String>, // delegate/undelegate only
+    #[serde(default)]
+    pub amount: u64,
+    pub commission_bps: Option<u64>, // set_commission only
+    pub findag_time: u64,
+    pub public_key: String, // hex
+    pub signature: String,  // hex
+}
+
+/// Outbound bridge lock, signed by the locking account
+#[derive(Deserialize, Debug)]
+pub struct BridgeLockRequest {
+    pub to_chain: String,
+    pub recipient: String, // address on the target chain
+    pub asset: String,
+    pub amount: u64,
+    pub findag_time: u64,
+    pub public_key: String, // hex
+    pub signature: String,  // hex
+}
+
+/// Settlement proof over an inbound transfer's commitment
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BridgeProofRequest {
+    Corda { notary_key: String, notary_signature: String }, // hex
+    Fabric { endorsements: Vec<FabricEndorsementInput> },
+}
+
+/// Inbound bridge release, signed by the relayer
+#[derive(Deserialize, Debug)]
+pub struct BridgeReleaseRequest {
+    pub transfer: InboundTransfer,
+    pub proof: BridgeProofRequest,
+    pub findag_time: u64,
+    pub public_key: String, // hex
+    pub signature: String,  // hex
+}
+
+/// Signed evidence report against a validator
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SlashReportRequest {
+    pub reporter: String,
+    pub evidence: Evidence,
+    pub findag_time: u64,
+    pub public_key: String, // hex
+    pub signature: String,  // hex
+}
+
+/// Unjail request signed by the jailed validator
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UnjailRequest {
+    pub findag_time: u64,
+    pub public_key: String, // hex
+    pub signature: String,  // hex
+}
+
+/// Validator registration signed with the new validator's consensus key
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegisterValidatorRequest {
+    pub address: String,
+    pub institution_name: String,
+    pub region: String,
+    pub findag_time: u64,
+    pub public_key: String, // hex
+    pub signature: String,  // hex
+}
+
+/// Consensus key rotation signed with the current key; `proof` is signed by the new key
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RotateKeyRequest {
+    pub new_public_key: String, // hex
+    pub activation_round: u64,
+    pub proof: String,          // hex
+    pub findag_time: u64,
+    pub public_key: String,     // hex
+    pub signature: String,      // hex
+}
+
+/// Handle operation with the signature of its authorizing key (parent, handle or ancestor)
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HandleOpRequest {
+    pub op: HandleOp,
+    pub signer: String, // hex public key that signed the instruction
+    pub findag_time: u64,
+}
+
+/// Outgoing committee member's signature over an epoch transition
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EpochSignatureRequest {
+    pub public_key: String, // hex
+    pub signature: String,  // hex
+}
+
+/// Payments of an inbound ISO 20022 message to report on in a pacs.002
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StatusReportRequest {
+    pub original_message_id: String,
+    pub original_message_type: String, // e.g. pacs.008.001.08
+    pub payments: Vec<PaymentStatusRequest>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PaymentStatusRequest {
+    pub end_to_end_id: String,
+    #[serde(default)]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    pub uetr: Option<String>,
+    pub tx_hash: String, // hex hash of the FinDAG transaction settling the payment
+}
+
+/// sese.023 instruction signed by the owner of its safekeeping account
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SettlementInstructionRequest {
+    pub xml: String,
+    pub public_key: String, // hex
+    pub signature: String,  // hex, over instruction_signing_message(xml)
+    #[serde(default)]
+    pub approvals: Vec<PartySignatureReq>, // Policy co-signatures when the account is a multisig account
+    pub findag_time: u64,
+    #[serde(default)]
+    pub shard_id: u16,
+}
+
+/// Voluntary exit request signed with the validator's current key
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValidatorExitRequest {
+    pub findag_time: u64,
+    pub public_key: String, // hex
+    pub signature: String,  // hex
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MultisigSignerReq {
+    pub public_key: String, // hex
+    pub weight: u32,
 }
 
 #[derive(Deserialize)]
@@ -128,15 +358,20 @@ struct ProposalSubmitReq {
     new_value: Option<String>,
     version: Option<String>,
     reason: Option<String>,
-    duration: Option<u64>, // Voting duration in seconds
+    voting_rounds: Option<u64>, // Voting window in rounds, defaults to the governance minimum
+    findag_time: u64,
+    signer_public_key: String, // Proposer's key (hex); must match `proposer`
+    signature: String, // Proposer's signature (hex) over the governance signing message
 }
 
 #[derive(Deserialize)]
 struct VoteReq {
     voter: String,
     approve: bool,
-    stake_weight: u64,
     reason: Option<String>,
+    findag_time: u64,
+    public_key: String, // Voter's key (hex); stake is looked up from the validator set
+    signature: String,
 }
 
 #[derive(Deserialize)]
@@ -685,9 +920,8 @@ fn check_rate_limit(client_id: &str, max_requests: u32, window: Duration) -> boo
 
 // Enhanced input validation
 fn validate_address(address: &str) -> bool {
-    // Sanitize and validate address
-    let address = address.trim();
-    address.starts_with("fdg1") && address.len() >= 10 && address.len() <= 100
+    // Canonical Bech32m address with the fdg HRP and a valid checksum
+    Address::parse(address.trim()).is_ok()
 }
 
 fn validate_amount(amount: u64) -> bool {
@@ -799,6 +1033,8 @@ async fn post_tx(
         }))));
     }
     
+    reject_if_paused(&state)?;
+    
     // Request size limit - 1MB
     let request_size = serde_json::to_string(&tx_data).unwrap_or_default().len();
     if request_size > 1_048_576 {
@@ -816,11 +1052,7 @@ async fn post_tx(
             }))));
         }
         
-        if !validate_address(&signed_tx.to) {
-            return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-                "error": "Invalid to address"
-            }))));
-        }
+        let (to_address, resolved_to) = resolve_recipient(&state.handle_registry, &signed_tx.to)?;
         
         if !validate_amount(signed_tx.amount) {
             return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
@@ -837,9 +1069,6 @@ async fn post_tx(
         println!("Received signed transaction: from={}, to={}, amount={}", 
                 signed_tx.from, signed_tx.to, signed_tx.amount);
         
-        // Validate signature
-        let message = format!("{}{}{}", signed_tx.from, signed_tx.to, signed_tx.amount);
-        println!("[DEBUG] Verifying signature for message: '{message}'");
         println!("[DEBUG] Public key length: {}, Signature length: {}", 
                 signed_tx.public_key.len(), signed_tx.signature.len());
         
@@ -861,20 +1090,11 @@ async fn post_tx(
         })?;
         let signature = ed25519_dalek::Signature::from_bytes(&signature_bytes);
         
-        // Verify signature
-        match public_key.verify(message.as_bytes(), &signature) {
-            Ok(_) => println!("[DEBUG] Signature verification successful"),
-            Err(e) => {
-                println!("[DEBUG] Signature verification failed: {e:?}");
-                return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Signature verification failed" }))));
-            }
-        }
-        
         // Create core Transaction
         let payload_len = signed_tx.payload.len();
         let core_tx = Transaction {
             from: Address(signed_tx.from.clone()),
-            to: Address(signed_tx.to.clone()),
+            to: to_address,
             amount: signed_tx.amount,
             payload: signed_tx.payload,
             findag_time: signed_tx.findag_time,
@@ -894,8 +1114,21 @@ async fn post_tx(
             dest_shard: None,
             target_chain: None,
             bridge_protocol: None,
+            valid_after: signed_tx.valid_after,
+            valid_until: signed_tx.valid_until,
+            multisig_signatures: Vec::new(),
         };
         
+        // Verify against the same message the wallet signs and peers re-verify, validity window included.
+        // A handle recipient is signed as the address it resolves to.
+        match public_key.verify(&core_tx.signing_message(), &signature) {
+            Ok(_) => println!("[DEBUG] Signature verification successful"),
+            Err(e) => {
+                println!("[DEBUG] Signature verification failed: {e:?}");
+                return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Signature verification failed" }))));
+            }
+        }
+        
         // Add comprehensive debugging for transaction processing
         println!("[DEBUG] Processing signed transaction:");
         println!("[DEBUG]   From: {}", signed_tx.from);
@@ -921,9 +1154,12 @@ async fn post_tx(
         
         // Add to transaction pool
         println!("[DEBUG] Adding transaction to pool...");
+        let raw_hash = core_tx.compute_hash();
+        let tx_hash = hex::encode(raw_hash);
         let added = state.tx_pool.add_transaction(core_tx.clone());
         
         if added {
+            let resolved_json = record_resolved_recipient(&state.tx_status, raw_hash, resolved_to);
             // Broadcast to network
             let stx: SerializableTransaction = core_tx.into();
             let msg = crate::network::propagation::GossipMsg::NewTransaction(stx);
@@ -932,7 +1168,7 @@ async fn post_tx(
             println!("[DEBUG] SUCCESS: Transaction added to pool");
             println!("Processed signed tx: from={}, to={}, amount={} (shard_id={})", 
                     signed_tx.from, signed_tx.to, signed_tx.amount, signed_tx.shard_id);
-            Ok(Json(serde_json::json!({ "status": "ok", "shard_id": signed_tx.shard_id, "message": "Signed transaction added to pool" })))
+            Ok(Json(serde_json::json!({ "status": "ok", "tx_hash": tx_hash, "shard_id": signed_tx.shard_id, "resolved_to": resolved_json, "message": "Signed transaction added to pool" })))
         } else {
             println!("[DEBUG] REJECTION: Transaction rejected by pool");
             
@@ -940,7 +1176,7 @@ async fn post_tx(
             let sender_balance = state.tx_pool.get_balance(signed_tx.shard_id, &signed_tx.from, "USD");
             println!("[DEBUG] Sender balance check: {} has {} USD", signed_tx.from, sender_balance);
             
-            Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Transaction rejected", "shard_id": signed_tx.shard_id }))))
+            Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Transaction rejected", "tx_hash": tx_hash, "shard_id": signed_tx.shard_id }))))
         }
     } else {
         // Try to parse as simple ApiTransaction
@@ -952,11 +1188,7 @@ async fn post_tx(
                 }))));
             }
             
-            if !validate_address(&tx.to) {
-                return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-                    "error": "Invalid to address"
-                }))));
-            }
+            let (to_address, resolved_to) = resolve_recipient(&state.handle_registry, &tx.to)?;
             
             if !validate_amount(tx.amount) {
                 return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
@@ -985,7 +1217,7 @@ async fn post_tx(
             // Convert ApiTransaction to core Transaction
             let core_tx = Transaction {
                 from: Address(tx.from.clone()),
-                to: Address(tx.to.clone()),
+                to: to_address,
                 amount: tx.amount,
                 payload: vec![], // Empty payload for simple transfers
                 findag_time: 0, // Will be set by the system
@@ -997,6 +1229,9 @@ async fn post_tx(
                 dest_shard: None,
                 target_chain: None,
                 bridge_protocol: None,
+                valid_after: tx.valid_after,
+                valid_until: tx.valid_until,
+                multisig_signatures: Vec::new(),
             };
             
             // Add comprehensive debugging for simple transaction processing
@@ -1009,9 +1244,12 @@ async fn post_tx(
             
             // Add to transaction pool
             println!("[DEBUG] Adding simple transaction to pool...");
+            let raw_hash = core_tx.compute_hash();
+            let tx_hash = hex::encode(raw_hash);
             let added = state.tx_pool.add_transaction(core_tx.clone());
             
             if added {
+                let resolved_json = record_resolved_recipient(&state.tx_status, raw_hash, resolved_to);
                 // Broadcast to network
                 let stx: SerializableTransaction = core_tx.into();
                 let msg = crate::network::propagation::GossipMsg::NewTransaction(stx);
@@ -1019,7 +1257,7 @@ async fn post_tx(
                 
                 println!("[DEBUG] SUCCESS: Simple transaction added to pool");
                 println!("Processed simple tx: {tx:?} (shard_id={shard_id})");
-                Ok(Json(serde_json::json!({ "status": "ok", "shard_id": shard_id, "message": "Transaction added to pool" })))
+                Ok(Json(serde_json::json!({ "status": "ok", "tx_hash": tx_hash, "shard_id": shard_id, "resolved_to": resolved_json, "message": "Transaction added to pool" })))
             } else {
                 println!("[DEBUG] REJECTION: Simple transaction rejected by pool");
                 
@@ -1027,97 +1265,1162 @@ async fn post_tx(
                 let sender_balance = state.tx_pool.get_balance(shard_id, &tx.from, "USD");
                 println!("[DEBUG] Sender balance check: {} has {} USD", tx.from, sender_balance);
                 
-                Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Transaction rejected", "shard_id": shard_id }))))
+                Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Transaction rejected", "tx_hash": tx_hash, "shard_id": shard_id }))))
+            }
+        } else {
+            println!("Invalid transaction format received");
+            Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Invalid transaction format" }))))
+        }
+    }
+}
+
+/// Resolve a recipient given as `@handle` to the address of its current key;
+/// anything else must be a valid address and is used as is
+fn resolve_recipient(handles: &Mutex<HandleRegistry>, to: &str) -> Result<(Address, Option<ResolvedHandle>), (StatusCode, Json<serde_json::Value>)> {
+    if !is_handle(to) {
+        if !validate_address(to) {
+            return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Invalid to address" }))));
+        }
+        return Ok((Address(to.to_string()), None));
+    }
+    let resolved = handles.lock().unwrap().resolve_recipient(to)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e }))))?;
+    println!("[DEBUG] Resolved recipient {} to {}", resolved.handle, resolved.address);
+    Ok((resolved.address.clone(), Some(resolved)))
+}
+
+/// Keep the handle resolution in the tx receipt; returns it for the API response
+fn record_resolved_recipient(tx_status: &TxStatusRegistry, tx_hash: [u8; 32], resolved: Option<ResolvedHandle>) -> serde_json::Value {
+    match resolved {
+        Some(resolved) => {
+            let json = serde_json::json!({
+                "handle": resolved.handle,
+                "public_key": hex::encode(resolved.public_key.to_bytes()),
+                "address": resolved.address.as_str(),
+            });
+            tx_status.record_resolved_recipient(tx_hash, resolved);
+            json
+        }
+        None => serde_json::Value::Null,
+    }
+}
+
+/// GET /tx/:hash - Returns the lifecycle status of a transaction
+async fn get_tx_status(
+    State(state): State<Arc<AppState>>,
+    Path(hash): Path<String>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let hash = hash.trim_start_matches("0x");
+    let tx_hash: [u8; 32] = match hex::decode(hash).ok().and_then(|b| b.try_into().ok()) {
+        Some(h) => h,
+        None => {
+            return (StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Invalid transaction hash"})));
+        }
+    };
+    match state.tx_status.get(&tx_hash) {
+        Some(record) => (StatusCode::OK, Json(record.to_json())),
+        None => (StatusCode::NOT_FOUND, Json(serde_json::json!({"error": "transaction not found"}))),
+    }
+}
+
+fn parse_public_key_hex(public_key: &str) -> Result<ed25519_dalek::VerifyingKey, String> {
+    hex::decode(public_key).ok()
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+        .and_then(|b| ed25519_dalek::VerifyingKey::from_bytes(&b).ok())
+        .ok_or_else(|| "Invalid public key".to_string())
+}
+
+fn parse_signature_hex(signature: &str) -> Result<ed25519_dalek::Signature, String> {
+    hex::decode(signature).ok()
+        .and_then(|b| <[u8; 64]>::try_from(b).ok())
+        .map(|b| ed25519_dalek::Signature::from_bytes(&b))
+        .ok_or_else(|| "Invalid signature".to_string())
+}
+
+fn parse_party_signatures(sigs: &[PartySignatureReq]) -> Result<Vec<PartySignature>, String> {
+    sigs.iter().map(|sig| {
+        let public_key = parse_public_key_hex(&sig.public_key)?;
+        let signature = parse_signature_hex(&sig.signature)?;
+        Ok(PartySignature { public_key, signature })
+    }).collect()
+}
+
+/// Build a governance transaction from a client-signed action, checking it is signed by `signer`
+fn signed_governance_tx(
+    action: GovernanceAction,
+    signer: &str,
+    public_key: &str,
+    signature: &str,
+    findag_time: u64,
+) -> Result<GovernanceTx, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let public_key = parse_public_key_hex(public_key).map_err(bad_request)?;
+    let signature = parse_signature_hex(signature).map_err(bad_request)?;
+    let gov_tx = GovernanceTx { action, findag_time, shard_id: ShardId(0), public_key, signature };
+    if Address::parse(signer).ok() != Some(gov_tx.signer()) {
+        return Err(bad_request("Public key does not match signer address".to_string()));
+    }
+    gov_tx.verify().map_err(bad_request)?;
+    Ok(gov_tx)
+}
+
+/// 503 while governance has the chain paused
+fn reject_if_paused(state: &AppState) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if state.chain_control.is_paused() {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, Json(serde_json::json!({
+            "error": "Chain paused by governance",
+            "reason": state.chain_control.pause_reason(),
+        }))));
+    }
+    Ok(())
+}
+
+/// Submit a transaction built by the API to the pool and gossip it, returning its hash
+async fn submit_to_pool(state: &AppState, core_tx: Transaction) -> Result<[u8; 32], (StatusCode, Json<serde_json::Value>)> {
+    // Governance transactions stay open while paused so the chain can be resumed
+    if !state.chain_control.admits(&core_tx) {
+        reject_if_paused(state)?;
+    }
+    let tx_hash = core_tx.compute_hash();
+    if state.tx_pool.add_transaction(core_tx.clone()) {
+        let stx: SerializableTransaction = core_tx.into();
+        state.network_propagator.broadcast(&crate::network::propagation::GossipMsg::NewTransaction(stx)).await;
+        Ok(tx_hash)
+    } else {
+        let reason = match state.tx_status.status(&tx_hash) {
+            Some(crate::core::tx_status::TxStatus::Rejected { reason }) => reason,
+            _ => "Transaction rejected".to_string(),
+        };
+        Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": reason, "tx_hash": hex::encode(tx_hash) }))))
+    }
+}
+
+/// POST /tx/multi-leg - Submit an atomic multi-leg transaction
+async fn post_multi_leg_tx(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<MultiLegTxRequest>
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |msg: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": msg })));
+
+    let signatures = parse_party_signatures(&req.signatures).map_err(bad_request)?;
+
+    let mut multi = MultiLegTransaction::new(req.legs, req.reference, req.findag_time, ShardId(req.shard_id));
+    multi.valid_after = req.valid_after;
+    multi.valid_until = req.valid_until;
+    multi.signatures = signatures;
+    let state_db = state.tx_pool.state_db();
+    multi.validate(&|address| state_db.get_multisig_account(address)).map_err(bad_request)?;
+
+    let core_tx = multi.to_transaction().map_err(bad_request)?;
+    let tx_hash = submit_to_pool(&state, core_tx).await?;
+    println!("[DEBUG] Multi-leg tx 0x{} added to pool ({} legs)", hex::encode(tx_hash), multi.legs.len());
+    Ok(Json(serde_json::json!({
+        "status": "ok",
+        "tx_hash": hex::encode(tx_hash),
+        "legs": multi.legs.len(),
+        "message": "Multi-leg transaction added to pool"
+    })))
+}
+
+/// POST /multisig/ops - Create a multisig account or change its key set
+async fn post_multisig_op(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<MultisigOpRequest>
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |msg: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": msg })));
+
+    let signers = req.signers.iter().map(|s| {
+        parse_public_key_hex(&s.public_key).map(|public_key| MultisigSigner { public_key, weight: s.weight })
+    }).collect::<Result<Vec<_>, String>>().map_err(bad_request)?;
+    let policy = MultisigPolicy { signers, threshold: req.threshold };
+
+    let kind = match req.op.as_str() {
+        "create" => {
+            let salt = hex::decode(req.salt.as_deref().unwrap_or(""))
+                .map_err(|_| bad_request("Invalid salt".to_string()))?;
+            MultisigOpKind::Create { policy, salt }
+        }
+        "update" => {
+            let account = req.account.as_deref().ok_or_else(|| bad_request("Missing account".to_string()))?;
+            let account = Address::parse(account).map_err(|e| bad_request(e.to_string()))?;
+            let nonce = req.nonce.ok_or_else(|| bad_request("Missing nonce".to_string()))?;
+            MultisigOpKind::UpdatePolicy { account, new_policy: policy, nonce }
+        }
+        other => return Err(bad_request(format!("Unknown multisig op '{other}'"))),
+    };
+
+    let mut op = MultisigOp::new(kind, req.findag_time, ShardId(req.shard_id));
+    op.approvals = parse_party_signatures(&req.approvals).map_err(bad_request)?;
+    op.verify_signatures().map_err(bad_request)?;
+
+    let core_tx = op.to_transaction().map_err(bad_request)?;
+    let tx_hash = submit_to_pool(&state, core_tx).await?;
+    Ok(Json(serde_json::json!({
+        "status": "ok",
+        "tx_hash": hex::encode(tx_hash),
+        "account": op.account_address().as_str(),
+        "message": "Multisig operation added to pool"
+    })))
+}
+
+/// POST /staking/ops - Delegate to or undelegate from a validator
+async fn post_staking_op(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<StakingOpRequest>
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |msg: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": msg })));
+
+    let validator = || {
+        let validator = req.validator.as_deref().ok_or_else(|| bad_request("Missing validator".to_string()))?;
+        Address::parse(validator).map_err(|e| bad_request(e.to_string()))
+    };
+    let action = match req.op.as_str() {
+        "delegate" => StakingAction::Delegate { validator: validator()?, amount: req.amount },
+        "undelegate" => StakingAction::Undelegate { validator: validator()?, amount: req.amount },
+        "set_commission" => StakingAction::SetCommission {
+            rate_bps: req.commission_bps.ok_or_else(|| bad_request("Missing commission_bps".to_string()))?,
+        },
+        "claim_rewards" => StakingAction::ClaimRewards,
+        other => return Err(bad_request(format!("Unknown staking op '{other}'"))),
+    };
+    let public_key = parse_public_key_hex(&req.public_key).map_err(bad_request)?;
+    let signature = parse_signature_hex(&req.signature).map_err(bad_request)?;
+    let staking_tx = StakingTx { action, findag_time: req.findag_time, shard_id: STAKING_SHARD, public_key, signature };
+    if Address::parse(&req.delegator).ok() != Some(staking_tx.delegator()) {
+        return Err(bad_request("Public key does not match delegator address".to_string()));
+    }
+    staking_tx.verify().map_err(bad_request)?;
+
+    let tx_hash = submit_to_pool(&state, staking_tx.to_transaction()).await?;
+    Ok(Json(serde_json::json!({
+        "status": "ok",
+        "tx_hash": hex::encode(tx_hash),
+        "message": "Staking operation added to pool"
+    })))
+}
+
+/// GET /staking/delegations/:address - Stake a delegator has bonded, per validator, and pending unbondings
+async fn get_delegations(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let delegator = match Address::parse(&address) {
+        Ok(delegator) => delegator,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))),
+    };
+    let delegations = state.staking_ledger.delegations_of(delegator.as_str());
+    let unbonding = state.staking_ledger.unbondings_of(delegator.as_str());
+    (StatusCode::OK, Json(serde_json::json!({
+        "delegator": delegator.as_str(),
+        "total_bonded": delegations.iter().map(|(_, amount)| amount).sum::<u64>(),
+        "delegations": delegations.iter().map(|(validator, amount)| serde_json::json!({
+            "validator": validator,
+            "amount": amount,
+        })).collect::<Vec<_>>(),
+        "unbonding": unbonding.iter().map(|(release_round, validator, amount)| serde_json::json!({
+            "validator": validator,
+            "amount": amount,
+            "release_round": release_round,
+        })).collect::<Vec<_>>(),
+    })))
+}
+
+/// GET /rewards/:address - Claimable rewards and reward history of an account
+async fn get_rewards(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let address = match Address::parse(&address) {
+        Ok(address) => address,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))),
+    };
+    let state_db = state.tx_pool.state_db();
+    let history = state_db.reward_history(address.as_str());
+    (StatusCode::OK, Json(serde_json::json!({
+        "address": address.as_str(),
+        "asset": crate::consensus::rewards::reward_asset(&state.chain_control),
+        "claimable": state_db.get_claimable_reward(address.as_str()),
+        "total_earned": history.iter().map(|entry| entry.amount).sum::<u64>(),
+        "history": history,
+    })))
+}
+
+/// GET /address/validate/:address - Check an address and return its canonical form
+async fn validate_address_endpoint(Path(address): Path<String>) -> Json<serde_json::Value> {
+    match Address::parse(address.trim()) {
+        Ok(canonical) => Json(serde_json::json!({ "valid": true, "address": canonical.as_str() })),
+        Err(e) => Json(serde_json::json!({
+            "valid": false,
+            "legacy": Address::new(address.trim().to_string()).is_legacy(),
+            "error": e.to_string(),
+        })),
+    }
+}
+
+/// POST /address/migrate - Submit a migration moving balances from a legacy `FD…` address to
+/// its canonical address. The owner signs `AddressMigrationTx::signing_message`; balances move
+/// when the transaction is executed in a block.
+async fn migrate_legacy_address(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<AddressMigrationRequest>
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |msg: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": msg })));
+
+    let public_key = parse_public_key_hex(&req.public_key).map_err(bad_request)?;
+    let canonical = Address::migrate_legacy(&req.legacy_address, &public_key)
+        .map_err(|e| bad_request(e.to_string()))?;
+    let signature = parse_party_signatures(&[PartySignatureReq {
+        public_key: req.public_key.clone(),
+        signature: req.signature.clone(),
+    }]).map_err(bad_request)?.remove(0).signature;
+    let migration = AddressMigrationTx {
+        legacy_address: req.legacy_address.clone(),
+        findag_time: req.findag_time,
+        shard_id: MIGRATION_SHARD,
+        public_key,
+        signature,
+    };
+    migration.verify().map_err(bad_request)?;
+
+    let tx_hash = submit_to_pool(&state, migration.to_transaction()).await?;
+    Ok(Json(serde_json::json!({
+        "status": "submitted",
+        "legacy_address": req.legacy_address,
+        "address": canonical.as_str(),
+        "tx_hash": hex::encode(tx_hash),
+    })))
+}
+
+/// GET /multisig/:address - Current key set and threshold of a multisig account
+async fn get_multisig_account(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>
+) -> (StatusCode, Json<serde_json::Value>) {
+    match state.tx_pool.state_db().get_multisig_account(&address) {
+        Some(account) => (StatusCode::OK, Json(serde_json::json!({
+            "address": account.address.as_str(),
+            "threshold": account.policy.threshold,
+            "total_weight": account.policy.total_weight(),
+            "nonce": account.nonce,
+            "signers": account.policy.signers.iter().map(|s| serde_json::json!({
+                "public_key": hex::encode(s.public_key.to_bytes()),
+                "weight": s.weight,
+            })).collect::<Vec<_>>(),
+        }))),
+        None => (StatusCode::NOT_FOUND, Json(serde_json::json!({"error": "multisig account not found"}))),
+    }
+}
+
+async fn get_validators(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    let set = state.validator_set.lock().unwrap().clone();
+    Json(serde_json::json!(set.validators))
+}
+
+// Add authentication to sensitive endpoints
+async fn add_validator(
+    State(_state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(req): Json<ValidatorAddReq>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    // Require admin authentication
+    let _user = authenticate_user(headers, "admin").await?;
+    
+    // Input validation
+    if !validate_address(&req.address) {
+        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
+            "error": "Invalid validator address"
+        }))));
+    }
+    
+    if !validate_public_key(&req.public_key) {
+        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
+            "error": "Invalid public key"
+        }))));
+    }
+    
+    // Add validator logic here
+    audit_log(&_user, "add_validator", &format!("address: {}", req.address));
+    
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "success",
+        "message": "Validator added successfully"
+    }))))
+}
+
+async fn remove_validator(
+    State(_state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    // Require admin authentication
+    let _user = authenticate_user(headers, "admin").await?;
+    
+    // Input validation
+    if !validate_address(&address) {
+        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
+            "error": "Invalid validator address"
+        }))));
+    }
+    
+    // Remove validator logic here
+    audit_log(&_user, "remove_validator", &format!("address: {}", address));
+    
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "success",
+        "message": "Validator removed successfully"
+    }))))
+}
+
+/// Build a slashing transaction from a client-signed action, checking it is signed by `sender`
+fn signed_slashing_tx(
+    action: SlashingAction,
+    sender: &str,
+    public_key: &str,
+    signature: &str,
+    findag_time: u64,
+) -> Result<SlashingTx, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let public_key = parse_public_key_hex(public_key).map_err(bad_request)?;
+    let signature = parse_signature_hex(signature).map_err(bad_request)?;
+    let slashing_tx = SlashingTx { action, findag_time, shard_id: STAKING_SHARD, public_key, signature };
+    if Address::parse(sender).ok() != Some(slashing_tx.sender()) {
+        return Err(bad_request("Public key does not match sender address".to_string()));
+    }
+    slashing_tx.verify().map_err(bad_request)?;
+    Ok(slashing_tx)
+}
+
+/// POST /validators/:address/slash - Submit signed evidence of a validator offense
+async fn slash_validator(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+    Json(req): Json<SlashReportRequest>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    // Require admin authentication
+    let _user = authenticate_user(headers, "admin").await?;
+    
+    // Input validation
+    if !validate_address(&address) || Address::parse(&address).ok() != Some(req.evidence.offender()) {
+        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
+            "error": "Evidence does not concern this validator"
+        }))));
+    }
+    
+    // The stake penalty and jail are applied when the evidence is included on chain
+    let offense = req.evidence.offense().name();
+    let action = SlashingAction::Report(Box::new(req.evidence));
+    let slashing_tx = signed_slashing_tx(action, &req.reporter, &req.public_key, &req.signature, req.findag_time)?;
+    let tx_hash = submit_to_pool(&state, slashing_tx.to_transaction()).await?;
+    audit_log(&_user, "slash_validator", &format!("address: {}, offense: {}", address, offense));
+    
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "submitted",
+        "message": "Slashing evidence submitted for inclusion",
+        "tx_hash": hex::encode(tx_hash)
+    }))))
+}
+
+/// POST /validators/:address/unjail - Submit a jailed validator's signed unjail request
+async fn unjail_validator(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Json(req): Json<UnjailRequest>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let slashing_tx = signed_slashing_tx(SlashingAction::Unjail, &address, &req.public_key, &req.signature, req.findag_time)?;
+    let tx_hash = submit_to_pool(&state, slashing_tx.to_transaction()).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "submitted",
+        "message": "Unjail request submitted for inclusion",
+        "tx_hash": hex::encode(tx_hash)
+    }))))
+}
+
+/// Build a lifecycle transaction from a client-signed action for `validator`
+fn signed_validator_tx(
+    validator: &str,
+    action: LifecycleAction,
+    public_key: &str,
+    signature: &str,
+    findag_time: u64,
+) -> Result<ValidatorTx, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let validator = Address::parse(validator).map_err(|e| bad_request(e.to_string()))?;
+    let public_key = parse_public_key_hex(public_key).map_err(bad_request)?;
+    let signature = parse_signature_hex(signature).map_err(bad_request)?;
+    let validator_tx = ValidatorTx { validator, action, findag_time, shard_id: STAKING_SHARD, public_key, signature };
+    validator_tx.verify().map_err(bad_request)?;
+    Ok(validator_tx)
+}
+
+/// POST /validators/register - Submit a signed validator registration
+async fn register_validator(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<RegisterValidatorRequest>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let action = LifecycleAction::Register { institution_name: req.institution_name, region: req.region };
+    let validator_tx = signed_validator_tx(&req.address, action, &req.public_key, &req.signature, req.findag_time)?;
+    let tx_hash = submit_to_pool(&state, validator_tx.to_transaction()).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "submitted",
+        "message": "Validator registration submitted for inclusion",
+        "tx_hash": hex::encode(tx_hash)
+    }))))
+}
+
+/// POST /validators/:address/rotate-key - Schedule a consensus key rotation
+async fn rotate_validator_key(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Json(req): Json<RotateKeyRequest>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let action = LifecycleAction::RotateKey(Box::new(RotationRequest {
+        new_public_key: parse_public_key_hex(&req.new_public_key).map_err(bad_request)?,
+        activation_round: req.activation_round,
+        proof: parse_signature_hex(&req.proof).map_err(bad_request)?,
+    }));
+    let validator_tx = signed_validator_tx(&address, action, &req.public_key, &req.signature, req.findag_time)?;
+    let tx_hash = submit_to_pool(&state, validator_tx.to_transaction()).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "submitted",
+        "message": format!("Key rotation for round {} submitted for inclusion", req.activation_round),
+        "tx_hash": hex::encode(tx_hash)
+    }))))
+}
+
+/// POST /validators/:address/exit - Queue a validator's voluntary exit
+async fn exit_validator(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Json(req): Json<ValidatorExitRequest>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let validator_tx = signed_validator_tx(&address, LifecycleAction::Exit, &req.public_key, &req.signature, req.findag_time)?;
+    let tx_hash = submit_to_pool(&state, validator_tx.to_transaction()).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "submitted",
+        "message": "Exit request submitted for inclusion",
+        "tx_hash": hex::encode(tx_hash)
+    }))))
+}
+
+/// POST /handles/ops - Submit a signed handle registration, key rotation, revocation or freeze
+async fn post_handle_op(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<HandleOpRequest>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let signer = parse_public_key_hex(&req.signer).map_err(bad_request)?;
+    let handle_tx = HandleTx::new(req.op, signer, req.findag_time);
+    handle_tx.verify().map_err(bad_request)?;
+    let handle = handle_tx.op.handle().to_string();
+    let tx_hash = submit_to_pool(&state, handle_tx.to_transaction().map_err(bad_request)?).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "submitted",
+        "message": format!("Handle operation for {handle} submitted for inclusion"),
+        "tx_hash": hex::encode(tx_hash)
+    }))))
+}
+
+#[derive(Deserialize)]
+struct IngestionBindingRequest {
+    adapter: String,
+    institution: String,
+}
+
+#[derive(Deserialize)]
+struct IngestionMappingRequest {
+    kind: String,
+    identifier: String,
+    institution: String,
+    target: String,
+}
+
+/// GET /ingestion - Gateway bindings and account mappings of the ISO 20022, SWIFT and FIX adapters
+async fn get_ingestion(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let _user = authenticate_user(headers, "admin").await?;
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "bindings": state.ingestion.bindings().iter().map(|b| serde_json::json!({
+            "adapter": b.adapter.protocol(),
+            "institution": b.institution,
+            "gateway_key": state.ingestion.gateway_key(b.adapter).ok().map(|(_, key)| hex::encode(key.to_bytes())),
+        })).collect::<Vec<_>>(),
+        "mappings": state.ingestion.mappings(),
+    }))))
+}
+
+/// POST /ingestion/bindings - Bind an adapter to the institution handle whose key signs its conversions
+async fn post_ingestion_binding(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(req): Json<IngestionBindingRequest>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let user = authenticate_user(headers, "admin").await?;
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let adapter = Adapter::parse(&req.adapter).map_err(bad_request)?;
+    let binding = state.ingestion.bind(adapter, &req.institution).map_err(bad_request)?;
+    audit_log(&user, "bind_ingestion_gateway", &format!("{} -> {}", adapter.protocol(), binding.institution));
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "adapter": adapter.protocol(),
+        "institution": binding.institution,
+    }))))
+}
+
+/// POST /ingestion/mappings - Map a BIC, LEI or account to an on-chain address owned by an institution
+async fn post_ingestion_mapping(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(req): Json<IngestionMappingRequest>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let user = authenticate_user(headers, "admin").await?;
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let kind = IdentifierKind::parse(&req.kind).map_err(bad_request)?;
+    let mapping = state.ingestion.map(kind, &req.identifier, &req.institution, &req.target).map_err(bad_request)?;
+    audit_log(&user, "map_ingestion_account", &format!("{}:{} -> {}", kind.name(), mapping.identifier, mapping.target.as_str()));
+    Ok((StatusCode::OK, Json(serde_json::json!(mapping))))
+}
+
+/// DELETE /ingestion/mappings/:kind/:identifier - Remove an account mapping
+async fn delete_ingestion_mapping(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path((kind, identifier)): Path<(String, String)>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let user = authenticate_user(headers, "admin").await?;
+    let kind = IdentifierKind::parse(&kind)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e }))))?;
+    let mapping = state.ingestion.unmap(kind, &identifier)
+        .map_err(|e| (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": e }))))?;
+    audit_log(&user, "unmap_ingestion_account", &format!("{}:{}", kind.name(), mapping.identifier));
+    Ok((StatusCode::OK, Json(serde_json::json!(mapping))))
+}
+
+/// GET /handles/:handle - Current key, address and status of a handle
+async fn get_handle(
+    State(state): State<Arc<AppState>>,
+    Path(handle): Path<String>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let registry = state.handle_registry.lock().unwrap();
+    match registry.resolve(&handle) {
+        Some(record) => (StatusCode::OK, Json(serde_json::json!({
+            "handle": record.handle,
+            "parent": record.parent,
+            "public_key": hex::encode(record.public_key.to_bytes()),
+            "address": Address::from_verifying_key(&record.public_key).as_str(),
+            "metadata": record.metadata,
+            "registered_at": record.registered_at,
+            "revoked": record.revoked,
+            "revocation_reason": record.revocation_reason,
+            "frozen_by": record.frozen_by,
+            "children": record.children,
+            "key_rotations": record.key_history.len(),
+        }))),
+        None => (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": format!("Unknown handle {handle}") }))),
+    }
+}
+
+/// GET /epochs?from=&limit= - Signed epoch transitions, for light clients following committee changes
+async fn get_epochs(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let from = params.get("from").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let limit = params.get("limit").and_then(|v| v.parse().ok()).unwrap_or(100).min(1000);
+    let transitions = state.epoch_manager.transitions(from, limit);
+    (StatusCode::OK, Json(serde_json::json!({
+        "from": from,
+        "count": transitions.len(),
+        "transitions": transitions,
+    })))
+}
+
+/// GET /epochs/:epoch - One epoch transition and its hash
+async fn get_epoch(
+    State(state): State<Arc<AppState>>,
+    Path(epoch): Path<u64>
+) -> (StatusCode, Json<serde_json::Value>) {
+    match state.epoch_manager.transition(epoch) {
+        Some(transition) => (StatusCode::OK, Json(serde_json::json!({
+            "hash": hex::encode(transition.hash()),
+            "transition": transition,
+        }))),
+        None => (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": format!("Unknown epoch {epoch}") }))),
+    }
+}
+
+/// POST /epochs/:epoch/signatures - Add an outgoing committee member's signature
+async fn post_epoch_signature(
+    State(state): State<Arc<AppState>>,
+    Path(epoch): Path<u64>,
+    Json(req): Json<EpochSignatureRequest>
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let public_key = parse_public_key_hex(&req.public_key).map_err(bad_request)?;
+    let signature = parse_signature_hex(&req.signature).map_err(bad_request)?;
+    let transition = state.epoch_manager.add_signature(epoch, public_key, signature).map_err(bad_request)?;
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "success",
+        "epoch": epoch,
+        "signatures": transition.signatures.len(),
+    }))))
+}
+
+static REPORT_SEQUENCE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn next_report_id(prefix: &str, now: u64) -> String {
+    let sequence = REPORT_SEQUENCE.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+    reporting::report_message_id(prefix, now, sequence)
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// GET /iso20022/camt053/:address?date=&currency= - camt.053 statement of one UTC day (default today)
+async fn get_camt053_statement(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<HashMap<String, String>>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let address = match Address::parse(&address) {
+        Ok(address) => address,
+        Err(e) => return bad_request(e.to_string()),
+    };
+    let now = unix_now();
+    let day = match params.get("date").map(|d| reporting::parse_day(d)) {
+        Some(Ok(day)) => day,
+        Some(Err(e)) => return bad_request(e.to_string()),
+        None => reporting::day_of(now),
+    };
+    let currency = params.get("currency").map(String::as_str).unwrap_or(crate::core::executor::DEFAULT_TRANSFER_ASSET);
+    let report = AccountReport::for_day(&state.tx_pool.state_db(), &state.tx_status, address.as_str(), currency, day);
+    match reporting::camt053_statement(&next_report_id("CAMT053", now), now, day + 1, &report) {
+        Ok(xml) => (StatusCode::OK, Json(serde_json::json!({
+            "message_type": "camt.053.001.08",
+            "address": address.as_str(),
+            "currency": currency,
+            "entries": report.entries.len(),
+            "opening_balance": report.opening_balance,
+            "closing_balance": report.closing_balance,
+            "xml": xml,
+        }))),
+        Err(e) => bad_request(e.to_string()),
+    }
+}
+
+/// GET /iso20022/camt054/:address?round=&currency= - camt.054 notification of the entries
+/// finalized in a round, or of all of today's entries when no round is given
+async fn get_camt054_notification(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<HashMap<String, String>>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let address = match Address::parse(&address) {
+        Ok(address) => address,
+        Err(e) => return bad_request(e.to_string()),
+    };
+    let round = match params.get("round").map(|r| r.parse::<u64>()) {
+        Some(Ok(round)) => Some(round),
+        Some(Err(_)) => return bad_request("Invalid round".to_string()),
+        None => None,
+    };
+    let now = unix_now();
+    let currency = params.get("currency").map(String::as_str).unwrap_or(crate::core::executor::DEFAULT_TRANSFER_ASSET);
+    let state_db = state.tx_pool.state_db();
+    let report = match round {
+        // Entries are booked shortly before their round finalizes; look back one day
+        Some(round) => AccountReport::build(&state_db, &state.tx_status, address.as_str(), currency, now.saturating_sub(86_400), now + 1)
+            .finalized_in(round),
+        None => AccountReport::for_day(&state_db, &state.tx_status, address.as_str(), currency, reporting::day_of(now)),
+    };
+    let notification_id = match round {
+        Some(round) => format!("round-{round}"),
+        None => format!("day-{}", reporting::day_of(now)),
+    };
+    match reporting::camt054_notification(&next_report_id("CAMT054", now), now, &notification_id, &report) {
+        Ok(xml) => (StatusCode::OK, Json(serde_json::json!({
+            "message_type": "camt.054.001.08",
+            "address": address.as_str(),
+            "currency": currency,
+            "round": round,
+            "entries": report.entries.len(),
+            "xml": xml,
+        }))),
+        Err(e) => bad_request(e.to_string()),
+    }
+}
+
+/// POST /iso20022/pacs002 - pacs.002 status report for the FinDAG transactions settling a message
+async fn post_pacs002_status_report(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<StatusReportRequest>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let mut payments = Vec::with_capacity(req.payments.len());
+    for payment in req.payments {
+        let tx_hash: [u8; 32] = match hex::decode(payment.tx_hash.trim_start_matches("0x")).ok().and_then(|b| b.try_into().ok()) {
+            Some(h) => h,
+            None => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Invalid transaction hash" }))),
+        };
+        payments.push(PaymentStatus {
+            end_to_end_id: payment.end_to_end_id,
+            transaction_id: payment.transaction_id,
+            uetr: payment.uetr,
+            tx_hash,
+            status: state.tx_status.status(&tx_hash),
+        });
+    }
+    let now = unix_now();
+    let xml = reporting::pacs002_status_report(
+        &next_report_id("PACS002", now),
+        now,
+        &req.original_message_id,
+        &req.original_message_type,
+        &payments,
+    );
+    (StatusCode::OK, Json(serde_json::json!({
+        "message_type": "pacs.002.001.10",
+        "statuses": payments.iter().map(|p| serde_json::json!({
+            "end_to_end_id": p.end_to_end_id,
+            "tx_hash": hex::encode(p.tx_hash),
+            "status": reporting::iso_status_code(p.status.as_ref()).0,
+        })).collect::<Vec<_>>(),
+        "xml": xml,
+    })))
+}
+
+/// sese.024 status advice for an instruction, plus its sese.025 confirmation once settled
+fn settlement_reports(state: &AppState, record: &settlement::InstructionRecord) -> serde_json::Value {
+    let now = unix_now();
+    let progress = record.progress(&state.tx_status);
+    let status_advice = settlement::sese024_status_advice(&next_report_id("SESE024", now), now, &record.instruction, &progress, record.tx_hash());
+    let confirmation = match (&progress, record.tx_hash()) {
+        (SettlementProgress::Settled { round }, Some(tx_hash)) => Some(settlement::sese025_confirmation(
+            &next_report_id("SESE025", now), now, &record.instruction, tx_hash, *round,
+        )),
+        _ => None,
+    };
+    let status = match &progress {
+        SettlementProgress::Unmatched => "unmatched",
+        SettlementProgress::Matched => "matched",
+        SettlementProgress::Settled { .. } => "settled",
+        SettlementProgress::Failed { .. } => "failed",
+    };
+    serde_json::json!({
+        "transaction_id": record.instruction.transaction_id,
+        "account": record.instruction.owner_account(),
+        "status": status,
+        "reason": match &progress {
+            SettlementProgress::Failed { reason } => Some(reason.clone()),
+            _ => None,
+        },
+        "tx_hash": record.tx_hash().map(hex::encode),
+        "sese024": status_advice,
+        "sese025": confirmation,
+    })
+}
+
+/// POST /iso20022/sese023 - Submit a signed sese.023 instruction. When the counterparty's
+/// matching instruction is already held, both settle atomically in one DvP transaction.
+async fn post_sese023_instruction(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<SettlementInstructionRequest>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let signed = match (parse_public_key_hex(&req.public_key), parse_signature_hex(&req.signature), parse_party_signatures(&req.approvals)) {
+        (Ok(public_key), Ok(signature), Ok(approvals)) => SignedInstruction { xml: req.xml, public_key, signature, approvals },
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return bad_request(e),
+    };
+    let state_db = state.tx_pool.state_db();
+    let multisig_account = |address: &str| state_db.get_multisig_account(address);
+    let (instruction, settlement) = match state.settlement_matcher.submit(signed, req.findag_time, ShardId(req.shard_id), &multisig_account) {
+        Ok(submitted) => submitted,
+        Err(e) => return bad_request(e),
+    };
+    if let Some(settlement) = settlement {
+        let outcome = match settlement.to_transaction(&multisig_account) {
+            Ok(core_tx) => submit_to_pool(&state, core_tx).await
+                .map_err(|(_, Json(body))| body["error"].as_str().unwrap_or("Transaction rejected").to_string()),
+            Err(e) => Err(e),
+        };
+        println!("[DEBUG] sese.023 {} matched, settlement: {:?}", instruction.transaction_id, outcome.as_ref().map(hex::encode));
+        state.settlement_matcher.record_settlement(&settlement, outcome);
+    }
+    match state.settlement_matcher.get(instruction.owner_account(), &instruction.transaction_id) {
+        Some(record) => (StatusCode::OK, Json(settlement_reports(&state, &record))),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({ "error": "Instruction not recorded" }))),
+    }
+}
+
+/// GET /iso20022/sese023/:account/:transaction_id - Current sese.024 status (and sese.025 once settled)
+async fn get_sese023_status(
+    State(state): State<Arc<AppState>>,
+    Path((account, transaction_id)): Path<(String, String)>
+) -> (StatusCode, Json<serde_json::Value>) {
+    match state.settlement_matcher.get(&account, &transaction_id) {
+        Some(record) => (StatusCode::OK, Json(settlement_reports(&state, &record))),
+        None => (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Unknown settlement instruction" }))),
+    }
+}
+
+#[derive(Deserialize)]
+struct Pacs008IngestRequest {
+    xml: String,
+}
+
+#[derive(Deserialize)]
+struct Mt103IngestRequest {
+    fin: String,
+}
+
+/// Convert and submit an ingested instruction once per (sender, format, reference).
+/// A replay returns the original transaction and its current status instead.
+async fn ingest_once(
+    state: &AppState,
+    sender: &str,
+    adapter: Adapter,
+    reference: &str,
+    convert: impl FnOnce(&GatewaySigner) -> Result<Transaction, String>,
+) -> (StatusCode, serde_json::Value) {
+    let mut result = serde_json::json!({ "sender": sender, "reference": reference });
+    let rejected = |mut result: serde_json::Value, code: StatusCode, error: String| {
+        result["status"] = serde_json::json!("rejected");
+        result["error"] = serde_json::json!(error);
+        (code, result)
+    };
+    let Some(signer) = state.gateway_signers.get(&adapter) else {
+        let error = format!("No {} gateway key configured", adapter.protocol());
+        return rejected(result, StatusCode::SERVICE_UNAVAILABLE, error);
+    };
+    match state.idempotency.claim(sender, adapter, reference, unix_now()) {
+        Ok(Claim::New) => {}
+        Ok(Claim::Replay(record)) => {
+            result["status"] = serde_json::json!("duplicate");
+            result["first_seen"] = serde_json::json!(record.first_seen);
+            result["tx_hash"] = serde_json::json!(record.tx_hash.map(hex::encode));
+            result["tx_status"] = match record.tx_hash.and_then(|hash| state.tx_status.get(&hash)) {
+                Some(status) => status.to_json(),
+                None if record.tx_hash.is_none() => serde_json::json!("in_progress"),
+                None => serde_json::json!("unknown"),
+            };
+            return (StatusCode::OK, result);
+        }
+        Err(e) => return rejected(result, StatusCode::BAD_REQUEST, e),
+    }
+    let submitted = match convert(signer) {
+        Ok(tx) => submit_to_pool(state, tx).await
+            .map_err(|(_, Json(body))| body["error"].as_str().unwrap_or("Transaction rejected").to_string()),
+        Err(e) => Err(e),
+    };
+    match submitted {
+        Ok(tx_hash) => {
+            if let Err(e) = state.idempotency.complete(sender, adapter, reference, tx_hash) {
+                println!("[DEBUG] Idempotency: {}", e);
             }
-        } else {
-            println!("Invalid transaction format received");
-            Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "Invalid transaction format" }))))
+            result["status"] = serde_json::json!("submitted");
+            result["tx_hash"] = serde_json::json!(hex::encode(tx_hash));
+            (StatusCode::OK, result)
+        }
+        Err(e) => {
+            // Nothing reached the pool, so the sender may correct and resend
+            state.idempotency.release(sender, adapter, reference);
+            rejected(result, StatusCode::BAD_REQUEST, e)
         }
     }
 }
 
-async fn get_validators(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
-    let set = state.validator_set.lock().unwrap().clone();
-    Json(serde_json::json!(set.validators))
+/// POST /iso20022/pacs008 - Ingest a pacs.008 (or pacs.009/pain.001). Each credit transfer is
+/// submitted once per sender and MsgId/EndToEndId; resends report the original transaction.
+async fn post_pacs008(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<Pacs008IngestRequest>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let message = match crate::iso20022::parse_message(&req.xml) {
+        Ok(message) => message,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))),
+    };
+    let mut results = Vec::new();
+    for credit_transfer in &message.transactions {
+        let sender = credit_transfer.debtor_agent.as_ref().and_then(|agent| agent.bic.as_deref())
+            .or(message.group_header.initiating_party.as_ref().and_then(|party| party.identifier()))
+            .unwrap_or_default();
+        let reference = format!("{}/{}", message.group_header.message_id, credit_transfer.end_to_end_id);
+        let (_, result) = ingest_once(&state, sender, Adapter::Iso20022, &reference, |signer| {
+            let iso_tx = crate::iso20022::ISO20022Transaction::from_credit_transfer(&message, credit_transfer)
+                .map_err(|e| e.to_string())?;
+            crate::iso20022::handler::iso20022_to_findag_tx(&iso_tx, &state.ingestion, signer)
+        }).await;
+        results.push(result);
+    }
+    (StatusCode::OK, Json(serde_json::json!({
+        "message_id": message.group_header.message_id,
+        "results": results,
+    })))
 }
 
-// Add authentication to sensitive endpoints
-async fn add_validator(
-    State(_state): State<Arc<AppState>>,
-    headers: HeaderMap,
-    Json(req): Json<ValidatorAddReq>
-) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
-    // Require admin authentication
-    let _user = authenticate_user(headers, "admin").await?;
-    
-    // Input validation
-    if !validate_address(&req.address) {
-        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-            "error": "Invalid validator address"
-        }))));
-    }
-    
-    if !validate_public_key(&req.public_key) {
-        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-            "error": "Invalid public key"
-        }))));
+/// POST /swift/mt103 - Ingest an MT103 once per sender BIC and :20: reference
+async fn post_mt103(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<Mt103IngestRequest>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let mt = match crate::swift::parse_mt103(&req.fin) {
+        Ok(mt) => mt,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))),
+    };
+    let sender = mt.sender.as_deref()
+        .or(mt.ordering_institution.as_ref().and_then(|party| party.bic.as_deref()))
+        .unwrap_or_default();
+    let (code, result) = ingest_once(&state, sender, Adapter::Swift, &mt.reference, |signer| {
+        crate::swift::mt103_to_findag_tx(&mt, &state.ingestion, signer).map_err(|e| e.to_string())
+    }).await;
+    (code, Json(result))
+}
+
+/// Gateway keys of the adapters, from FINDAG_ISO20022_GATEWAY_KEY, FINDAG_SWIFT_GATEWAY_KEY
+/// and FINDAG_FIX_GATEWAY_KEY (32-byte hex secrets)
+fn load_gateway_signers() -> HashMap<Adapter, Arc<GatewaySigner>> {
+    let mut signers = HashMap::new();
+    for adapter in Adapter::ALL {
+        let Ok(secret) = env::var(format!("FINDAG_{}_GATEWAY_KEY", adapter.protocol())) else {
+            continue;
+        };
+        match GatewaySigner::from_hex(adapter, &secret) {
+            Ok(signer) => {
+                signers.insert(adapter, Arc::new(signer));
+            }
+            Err(e) => println!("[DEBUG] {} gateway key ignored: {}", adapter.protocol(), e),
+        }
     }
-    
-    // Add validator logic here
-    audit_log(&_user, "add_validator", &format!("address: {}", req.address));
-    
-    Ok((StatusCode::OK, Json(serde_json::json!({
-        "status": "success",
-        "message": "Validator added successfully"
-    }))))
+    signers
 }
 
-async fn remove_validator(
-    State(_state): State<Arc<AppState>>,
-    headers: HeaderMap,
-    Path(address): Path<String>
-) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
-    // Require admin authentication
-    let _user = authenticate_user(headers, "admin").await?;
-    
-    // Input validation
-    if !validate_address(&address) {
-        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-            "error": "Invalid validator address"
-        }))));
-    }
-    
-    // Remove validator logic here
-    audit_log(&_user, "remove_validator", &format!("address: {}", address));
-    
-    Ok((StatusCode::OK, Json(serde_json::json!({
-        "status": "success",
-        "message": "Validator removed successfully"
-    }))))
+/// Idempotency records of ingested instructions, kept for FINDAG_IDEMPOTENCY_RETENTION_SECONDS
+/// (7 days by default) and pruned hourly
+fn load_idempotency_store(storage: Arc<crate::storage::persistent::PersistentStorage>) -> Arc<IdempotencyStore> {
+    let retention = env::var("FINDAG_IDEMPOTENCY_RETENTION_SECONDS").ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(crate::core::idempotency::DEFAULT_RETENTION_SECS);
+    let store = Arc::new(IdempotencyStore::load(storage, retention, unix_now()));
+    let pruned = store.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            let removed = pruned.prune(unix_now());
+            if removed > 0 {
+                println!("[DEBUG] Idempotency: pruned {removed} expired records");
+            }
+        }
+    });
+    store
 }
 
-async fn slash_validator(
-    State(_state): State<Arc<AppState>>,
-    headers: HeaderMap,
+/// Start the scheduled camt.053/camt.054 export when FINDAG_ISO20022_EXPORT_DIR is set
+fn start_iso20022_export(tx_pool: &ShardedTxPool) {
+    let Ok(dir) = env::var("FINDAG_ISO20022_EXPORT_DIR") else {
+        return;
+    };
+    let interval = env::var("FINDAG_ISO20022_EXPORT_INTERVAL_SECONDS").ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60);
+    println!("[DEBUG] ISO 20022 export to {dir} every {interval}s");
+    crate::iso20022::export::ReportExporter::new(tx_pool.state_db(), tx_pool.status_registry(), dir)
+        .spawn(Duration::from_secs(interval));
+}
+
+/// Start the FIX 4.4 order gateway when FINDAG_FIX_PORT is set. Matched trades are
+/// submitted as settlement transactions and reported as fills once finalized.
+fn start_fix_acceptor(state: &Arc<AppState>) {
+    let Some(port) = env::var("FINDAG_FIX_PORT").ok().and_then(|v| v.parse::<u16>().ok()) else {
+        return;
+    };
+    let store_dir = env::var("FINDAG_FIX_STORE_DIR").unwrap_or_else(|_| "fix_store".to_string());
+    let store = match crate::fix::store::MessageStore::open(&store_dir) {
+        Ok(store) => Arc::new(store),
+        Err(e) => {
+            println!("[DEBUG] FIX acceptor not started: {}", e);
+            return;
+        }
+    };
+    let config = crate::fix::session::SessionConfig {
+        begin_string: crate::fix::message::FIX_4_4.to_string(),
+        sender_comp_id: env::var("FINDAG_FIX_COMP_ID").unwrap_or_else(|_| "FINDAG".to_string()),
+        allowed_targets: env::var("FINDAG_FIX_TARGETS").ok()
+            .map(|v| v.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect())
+            .unwrap_or_default(),
+    };
+    let Some(signer) = state.gateway_signers.get(&Adapter::Fix).cloned() else {
+        println!("[DEBUG] FIX acceptor not started: FINDAG_FIX_GATEWAY_KEY is not set");
+        return;
+    };
+    let (gateway, mut trades) = crate::fix::gateway::FixOrderGateway::new(&unix_now().to_string());
+    let acceptor = crate::fix::acceptor::FixAcceptor::new(config, store, gateway.clone());
+    gateway.attach(acceptor.clone());
+    gateway.use_idempotency(state.idempotency.clone());
+    gateway.spawn_status_listener(state.tx_status.clone());
+
+    let submit_state = state.clone();
+    let submit_gateway = gateway.clone();
+    tokio::spawn(async move {
+        while let Some(trade) = trades.recv().await {
+            let tx = match crate::fix::fix_trade_to_findag_tx(&trade, &submit_state.ingestion, &signer) {
+                Ok(tx) => tx,
+                Err(e) => {
+                    submit_gateway.settlement_failed(&trade.match_id, &e.to_string());
+                    continue;
+                }
+            };
+            match submit_to_pool(&submit_state, tx).await {
+                Ok(tx_hash) => submit_gateway.settlement_submitted(&trade.match_id, tx_hash),
+                Err((_, Json(error))) => submit_gateway.settlement_failed(
+                    &trade.match_id,
+                    error["error"].as_str().unwrap_or("Transaction rejected"),
+                ),
+            }
+        }
+    });
+    tokio::spawn(async move {
+        match tokio::net::TcpListener::bind(("0.0.0.0", port)).await {
+            Ok(listener) => acceptor.run(listener).await,
+            Err(e) => println!("[DEBUG] FIX acceptor failed to bind port {}: {}", port, e),
+        }
+    });
+}
+
+/// GET /validators/:address/slashing - Jail status and slash history of a validator
+async fn get_validator_slashing(
+    State(state): State<Arc<AppState>>,
     Path(address): Path<String>
-) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
-    // Require admin authentication
-    let _user = authenticate_user(headers, "admin").await?;
-    
-    // Input validation
-    if !validate_address(&address) {
-        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-            "error": "Invalid validator address"
-        }))));
-    }
-    
-    // Slash validator logic here
-    audit_log(&_user, "slash_validator", &format!("address: {}", address));
-    
-    Ok((StatusCode::OK, Json(serde_json::json!({
-        "status": "success",
-        "message": "Validator slashed successfully"
-    }))))
+) -> (StatusCode, Json<serde_json::Value>) {
+    let validator = match Address::parse(&address) {
+        Ok(validator) => validator,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))),
+    };
+    let jailed_until = state.validator_set.lock().unwrap()
+        .get_validator(&validator)
+        .and_then(|v| v.jailed_until);
+    let records = state.tx_pool.state_db().slash_records_of(validator.as_str());
+    (StatusCode::OK, Json(serde_json::json!({
+        "validator": validator.as_str(),
+        "jailed_until": jailed_until,
+        "records": records.iter().map(|r| serde_json::json!({
+            "offense": r.offense.name(),
+            "evidence_id": hex::encode(r.evidence_id),
+            "reporter": r.reporter,
+            "round": r.round,
+            "stake_slashed": r.stake_slashed,
+            "reporter_reward": r.reporter_reward,
+            "burned": r.burned,
+            "jailed_until": r.jailed_until,
+        })).collect::<Vec<_>>(),
+    })))
 }
 
 async fn submit_proposal(
@@ -1179,10 +2482,19 @@ async fn submit_proposal(
             }
         },
         "parameter_change" => {
-            if req.parameter.is_none() || req.new_value.is_none() {
+            let (Some(parameter), Some(new_value)) = (&req.parameter, &req.new_value) else {
                 return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
                     "error": "Parameter and new_value required for parameter_change proposal"
                 }))));
+            };
+            // Every parameter is typed; values are checked against the schema before they reach a vote
+            let checked = crate::consensus::parameters::param_spec(parameter)
+                .ok_or_else(|| format!("Unknown parameter {parameter}"))
+                .and_then(|spec| spec.parse(new_value));
+            if let Err(e) = checked {
+                return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
+                    "error": e
+                }))));
             }
         },
         "upgrade_protocol" => {
@@ -1250,29 +2562,26 @@ async fn submit_proposal(
         _ => unreachable!(),
     };
     
-    // Submit proposal
-    let mut state_guard = state.governance_state.lock().unwrap();
-    let proposal_id = match state_guard.create_proposal(
-        req.proposer,
-        req.title,
-        req.description,
+    // Proposals are signed transactions; they take effect when included in a block
+    let voting_rounds = req.voting_rounds
+        .unwrap_or_else(|| state.governance_state.lock().unwrap().config.min_voting_rounds);
+    let action = GovernanceAction::Propose {
+        title: req.title,
+        description: req.description,
         proposal_type,
-        req.duration,
-    ) {
-        Ok(id) => id,
-        Err(e) => {
-            return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-                "error": e
-            }))));
-        }
+        voting_rounds,
     };
+    let gov_tx = signed_governance_tx(action, &req.proposer, &req.signer_public_key, &req.signature, req.findag_time)?;
+    let proposal_id = gov_tx.proposal_id();
+    let tx_hash = submit_to_pool(&state, gov_tx.to_transaction()).await?;
     
     audit_log(&user, "submit_proposal", &format!("proposal_id: {}, type: {}", proposal_id, req.proposal_type));
     
     Ok((StatusCode::OK, Json(serde_json::json!({
-        "status": "success",
-        "message": "Proposal submitted successfully",
-        "proposal_id": proposal_id
+        "status": "submitted",
+        "message": "Proposal submitted for inclusion",
+        "proposal_id": proposal_id,
+        "tx_hash": hex::encode(tx_hash)
     }))))
 }
 
@@ -1292,38 +2601,29 @@ async fn vote_proposal(
         }))));
     }
     
-    if req.stake_weight == 0 {
-        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-            "error": "Stake weight must be greater than 0"
+    let proposal_id = format!("proposal_{}", id);
+    if !state.governance_state.lock().unwrap().proposals.contains_key(&proposal_id) {
+        return Err((StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "error": "Proposal not found"
         }))));
     }
     
-    let proposal_id = format!("proposal_{}", id);
+    // Votes are signed transactions; the weight is the voter's stake at the proposal snapshot
+    let action = GovernanceAction::Vote {
+        proposal_id: proposal_id.clone(),
+        approve: req.approve,
+        reason: req.reason,
+    };
+    let gov_tx = signed_governance_tx(action, &req.voter, &req.public_key, &req.signature, req.findag_time)?;
+    let tx_hash = submit_to_pool(&state, gov_tx.to_transaction()).await?;
     
-    // Submit vote
-    let voter = req.voter.clone();
-    let mut state_guard = state.governance_state.lock().unwrap();
-    match state_guard.submit_vote(
-        &proposal_id,
-        req.voter,
-        req.approve,
-        req.stake_weight,
-        req.reason,
-    ) {
-        Ok(()) => {
-            audit_log(&user, "vote_proposal", &format!("proposal_id: {}, voter: {}, approve: {}", proposal_id, voter, req.approve));
-            
-            Ok((StatusCode::OK, Json(serde_json::json!({
-                "status": "success",
-                "message": "Vote recorded successfully"
-            }))))
-        },
-        Err(e) => {
-            Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-                "error": e
-            }))))
-        }
-    }
+    audit_log(&user, "vote_proposal", &format!("proposal_id: {}, voter: {}, approve: {}", proposal_id, req.voter, req.approve));
+    
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "status": "submitted",
+        "message": "Vote submitted for inclusion",
+        "tx_hash": hex::encode(tx_hash)
+    }))))
 }
 
 async fn execute_proposal(
@@ -1344,23 +2644,66 @@ async fn execute_proposal(
     
     let proposal_id = format!("proposal_{}", id);
     
-    // Execute proposal
-    let mut state_guard = state.governance_state.lock().unwrap();
-    match state_guard.execute_proposal(&proposal_id) {
-        Ok(()) => {
-            audit_log(&user, "execute_proposal", &format!("proposal_id: {}, executor: {}", proposal_id, req.executor));
-            
-            Ok((StatusCode::OK, Json(serde_json::json!({
-                "status": "success",
-                "message": "Proposal executed successfully"
-            }))))
-        },
-        Err(e) => {
-            Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
-                "error": e
-            }))))
-        }
-    }
+    // Passed proposals are scheduled by the tally when voting closes; report where this one stands
+    let governance = state.governance_state.lock().unwrap();
+    let response = if let Some(record) = governance.execution_records.iter().find(|r| r.proposal_id == proposal_id) {
+        serde_json::json!({
+            "status": if record.success { "executed" } else { "failed" },
+            "activation_round": record.activation_round,
+            "executed_at": record.executed_at,
+            "error": record.error
+        })
+    } else if let Some(scheduled) = governance.scheduled_executions.iter().find(|s| s.proposal_id == proposal_id) {
+        serde_json::json!({
+            "status": "scheduled",
+            "message": "Proposal is applied when its activation round is finalized",
+            "activation_round": scheduled.activation_round
+        })
+    } else {
+        return Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({
+            "error": "Proposal has not passed or is still being voted on"
+        }))));
+    };
+    audit_log(&user, "execute_proposal", &format!("proposal_id: {}, executor: {}, status: {}", proposal_id, req.executor, response["status"]));
+    
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// GET /params: protocol parameter schema, current values and activation history
+async fn get_params(
+    State(state): State<Arc<AppState>>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let registry = state.chain_control.parameters();
+    let params: Vec<serde_json::Value> = crate::consensus::parameters::PARAMETER_SCHEMA.iter()
+        .map(|spec| {
+            let mut entry = spec.to_json();
+            entry["value"] = registry.get(spec.name).map(|v| v.to_json()).unwrap_or_default();
+            entry["versions"] = serde_json::json!(registry.versions(spec.name).iter()
+                .map(|(round, value)| serde_json::json!({ "activation_round": round, "value": value.to_json() }))
+                .collect::<Vec<_>>());
+            entry
+        })
+        .collect();
+    
+    (StatusCode::OK, Json(serde_json::json!({
+        "current_round": registry.current_round(),
+        "params": params
+    })))
+}
+
+/// GET /governance/executions: scheduled and completed proposal executions
+async fn get_governance_executions(
+    State(state): State<Arc<AppState>>
+) -> (StatusCode, Json<serde_json::Value>) {
+    let state_guard = state.governance_state.lock().unwrap();
+    
+    (StatusCode::OK, Json(serde_json::json!({
+        "current_round": state.governance_executor.current_round(),
+        "paused": state.chain_control.is_paused(),
+        "protocol_version": state.chain_control.protocol_version(),
+        "scheduled": state_guard.scheduled_executions,
+        "records": state_guard.execution_records
+    })))
 }
 
 async fn cancel_proposal(
@@ -1443,32 +2786,74 @@ async fn get_governance_config(
     })))
 }
 
-/// POST /bridge/outbound: Submit outbound bridge transaction
+/// POST /bridge/outbound: Lock funds in the bridge escrow for another chain
 async fn outbound_bridge(
-    State(_state): State<Arc<AppState>>,
-    Json(req): Json<BridgeTx>
-) -> Json<serde_json::Value> {
-    // TODO: Validate and process outbound bridge transaction
-    println!("[Bridge] Outbound: {req:?}");
-    Json(serde_json::json!({ "status": "pending", "details": "Bridge logic not yet implemented" }))
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<BridgeLockRequest>
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |msg: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": msg })));
+
+    let action = BridgeAction::Lock { to_chain: req.to_chain, recipient: req.recipient, asset: req.asset, amount: req.amount };
+    let public_key = parse_public_key_hex(&req.public_key).map_err(bad_request)?;
+    let signature = parse_signature_hex(&req.signature).map_err(bad_request)?;
+    let bridge_tx = BridgeTx { action, findag_time: req.findag_time, shard_id: BRIDGE_SHARD, public_key, signature };
+    state.bridge.check(&bridge_tx).map_err(bad_request)?;
+
+    let tx_hash = submit_to_pool(&state, bridge_tx.to_transaction()).await?;
+    Ok(Json(serde_json::json!({
+        "status": "ok",
+        "tx_hash": hex::encode(tx_hash),
+    })))
 }
 
-/// POST /bridge/inbound: Submit inbound bridge transaction
+/// POST /bridge/inbound: Release a source-chain transfer against its settlement proof
 async fn inbound_bridge(
-    State(_state): State<Arc<AppState>>,
-    Json(req): Json<BridgeTx>
-) -> Json<serde_json::Value> {
-    // TODO: Validate and process inbound bridge transaction
-    println!("[Bridge] Inbound: {req:?}");
-    Json(serde_json::json!({ "status": "pending", "details": "Bridge logic not yet implemented" }))
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<BridgeReleaseRequest>
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |msg: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": msg })));
+    let decode = |field: &str, value: &str| hex::decode(value).map_err(|_| bad_request(format!("Invalid hex in {field}")));
+
+    let proof = match req.proof {
+        BridgeProofRequest::Corda { notary_key, notary_signature } => InboundProof::Corda {
+            notary_key: decode("notary_key", &notary_key)?,
+            notary_signature: decode("notary_signature", &notary_signature)?,
+        },
+        BridgeProofRequest::Fabric { endorsements } => InboundProof::Fabric {
+            endorsements: endorsements.into_iter()
+                .map(|e| Ok(Endorsement {
+                    msp_id: e.msp_id,
+                    certificate: e.certificate.into_bytes(),
+                    signature: decode("signature", &e.signature)?,
+                }))
+                .collect::<Result<_, _>>()?,
+        },
+    };
+    let public_key = parse_public_key_hex(&req.public_key).map_err(bad_request)?;
+    let signature = parse_signature_hex(&req.signature).map_err(bad_request)?;
+    let action = BridgeAction::Release { transfer: req.transfer, proof };
+    let bridge_tx = BridgeTx { action, findag_time: req.findag_time, shard_id: BRIDGE_SHARD, public_key, signature };
+    state.bridge.check(&bridge_tx).map_err(bad_request)?;
+
+    let tx_hash = submit_to_pool(&state, bridge_tx.to_transaction()).await?;
+    Ok(Json(serde_json::json!({
+        "status": "ok",
+        "tx_hash": hex::encode(tx_hash),
+    })))
 }
 
-/// GET /bridge/status/:txid: Query bridge transaction status
-async fn bridge_status(Path(tx_id): Path<String>) -> Json<serde_json::Value> {
-    if let Some(_receipt) = Some(serde_json::json!({"status": "pending"})) {
-        Json(serde_json::json!({ "status": "pending", "tx_id": tx_id, "details": "Bridge logic not yet implemented" }))
-    } else {
-        Json(serde_json::json!({ "status": "not_found", "tx_id": tx_id }))
+/// GET /bridge/status/:txid: Bridge transfer state and, once finalized, its receipt
+async fn bridge_status(
+    State(state): State<Arc<AppState>>,
+    Path(tx_id): Path<String>
+) -> (StatusCode, Json<serde_json::Value>) {
+    match state.bridge.get_transfer(&tx_id) {
+        Some(transfer) => (StatusCode::OK, Json(serde_json::json!({
+            "transfer": transfer,
+            "receipt_valid": state.bridge.verify_receipt(&tx_id),
+            "paused": state.bridge.is_paused(),
+        }))),
+        None => (StatusCode::NOT_FOUND, Json(serde_json::json!({ "status": "not_found", "tx_id": tx_id }))),
     }
 }
 
@@ -1532,7 +2917,7 @@ async fn get_merkle_proof(
     };
     if let Some(block_id) = id_bytes {
         if let Some(block) = state.storage.load_block(&block_id) {
-            let tx_hashes: Vec<String> = block.transactions.iter().map(|tx| hex::encode(tx.hashtimer)).collect();
+            let tx_hashes = block.merkle_leaves();
             if let Some(idx) = tx_hashes.iter().position(|h| h == &tx_hash) {
                 use crate::core::bridge::merkle_proof;
                 let proof = merkle_proof(&tx_hashes, idx);
@@ -1803,6 +3188,9 @@ fn create_transaction_from_order(
         dest_shard: None,
         target_chain: None,
         bridge_protocol: None,
+        valid_after: None,
+        valid_until: None,
+        multisig_signatures: Vec::new(),
     }
 }
 
@@ -1817,6 +3205,144 @@ pub fn init_http_server(
     println!("Warning: init_http_server() is deprecated. Use create_app_state() instead.");
 }
 
+/// Protocol components shared by the DAG engine, the round chain and the HTTP handlers
+#[derive(Clone)]
+pub struct NodeServices {
+    pub storage: Arc<crate::storage::persistent::PersistentStorage>,
+    pub validator_set: Arc<Mutex<ValidatorSet>>,
+    pub governance_state: Arc<Mutex<crate::consensus::governance::GovernanceState>>,
+    pub tx_pool: Arc<ShardedTxPool>,
+    pub chain_control: Arc<ChainControl>,
+    pub governance_executor: Arc<GovernanceExecutor>,
+    pub staking_ledger: Arc<StakingLedger>,
+    pub slashing_engine: Arc<SlashingEngine>,
+    pub validator_lifecycle: Arc<ValidatorLifecycle>,
+    pub epoch_manager: Arc<EpochManager>,
+    pub reward_distributor: Arc<RewardDistributor>,
+    pub handle_registry: Arc<Mutex<HandleRegistry>>,
+    pub ingestion: Arc<IngestionRegistry>,
+    pub idempotency: Arc<IdempotencyStore>,
+    pub bridge: Arc<Bridge>,
+}
+
+impl NodeServices {
+    /// Build every protocol handler once, over the given storage, validator set and pool.
+    /// A validator passes its signing key so it co-signs the epoch transitions it takes part in.
+    pub fn new(
+        storage: Arc<crate::storage::persistent::PersistentStorage>,
+        validator_set: Arc<Mutex<ValidatorSet>>,
+        governance_state: Arc<Mutex<crate::consensus::governance::GovernanceState>>,
+        tx_pool: Arc<ShardedTxPool>,
+        signing_key: Option<ed25519_dalek::SigningKey>,
+    ) -> Self {
+        let chain_control = tx_pool.chain_control();
+        if let Ok(Some(registry)) = storage.load_parameter_registry() {
+            chain_control.set_parameters(registry);
+        }
+        let staking_ledger = Arc::new(StakingLedger::new(tx_pool.state_db(), validator_set.clone(), chain_control.clone())
+            .with_storage(storage.clone()));
+        let slashing_engine = Arc::new(SlashingEngine::new(staking_ledger.clone()));
+        let governance_executor = Arc::new(GovernanceExecutor::new(
+            governance_state.clone(),
+            validator_set.clone(),
+            tx_pool.asset_whitelist(),
+            chain_control.clone(),
+        ).with_storage(storage.clone()).with_slashing_engine(slashing_engine.clone()));
+        let validator_lifecycle = Arc::new(ValidatorLifecycle::new(staking_ledger.clone()).with_storage(storage.clone()));
+        let mut epoch_manager = EpochManager::new(staking_ledger.clone()).with_storage(storage.clone());
+        if let Some(signing_key) = signing_key {
+            epoch_manager = epoch_manager.with_signing_key(signing_key);
+        }
+        let epoch_manager = Arc::new(epoch_manager);
+        if let Err(e) = epoch_manager.record_genesis() {
+            println!("[DEBUG] Epochs: failed to record genesis epoch: {e}");
+        }
+        let reward_distributor = Arc::new(RewardDistributor::new(staking_ledger.clone()));
+        let handle_registry = Arc::new(Mutex::new(HandleRegistry::load(storage.clone())));
+        let ingestion = Arc::new(IngestionRegistry::load(handle_registry.clone(), storage.clone()));
+        tx_pool.set_ingestion_registry(ingestion.clone());
+        let idempotency = load_idempotency_store(storage.clone());
+        let bridge = Arc::new(Bridge::new(tx_pool.state_db(), chain_control.clone(), BridgeTrust {
+            corda_notaries: crate::bridge::api::corda_notaries_from_env(),
+            fabric: crate::bridge::api::fabric_trust_from_env(),
+        }));
+        Self {
+            storage,
+            validator_set,
+            governance_state,
+            tx_pool,
+            chain_control,
+            governance_executor,
+            staking_ledger,
+            slashing_engine,
+            validator_lifecycle,
+            epoch_manager,
+            reward_distributor,
+            handle_registry,
+            ingestion,
+            idempotency,
+            bridge,
+        }
+    }
+
+    /// Open the node's services, restoring the validator set from storage
+    pub fn open(
+        storage: Arc<crate::storage::persistent::PersistentStorage>,
+        tx_pool: Arc<ShardedTxPool>,
+        signing_key: ed25519_dalek::SigningKey,
+    ) -> Self {
+        let validator_set = Arc::new(Mutex::new(storage.load_validator_set().ok().flatten().unwrap_or_default()));
+        let governance_state = Arc::new(Mutex::new(crate::consensus::governance::GovernanceState {
+            proposals: HashMap::new(),
+            votes: HashMap::new(),
+            active_proposals: Vec::new(),
+            executed_proposals: Vec::new(),
+            config: crate::consensus::governance::GovernanceConfig::default(),
+            total_stake: 0,
+            proposal_counter: 0,
+            analytics: crate::consensus::governance::GovernanceAnalytics::default(),
+            events: Vec::new(),
+            voter_activity: HashMap::new(),
+            scheduled_executions: Vec::new(),
+            execution_records: Vec::new(),
+            stake_snapshots: HashMap::new(),
+        }));
+        Self::new(storage, validator_set, governance_state, tx_pool, Some(signing_key))
+    }
+}
+
+/// Build the HTTP state over the node's shared services and start the gateways that feed the pool
+fn build_app_state(
+    services: &NodeServices,
+    network_propagator: Arc<NetworkPropagator>,
+    ws_manager: Arc<WebSocketManager>,
+) -> Arc<AppState> {
+    start_iso20022_export(&services.tx_pool);
+    let state = Arc::new(AppState {
+        validator_set: services.validator_set.clone(),
+        storage: services.storage.clone(),
+        governance_state: services.governance_state.clone(),
+        tx_pool: services.tx_pool.clone(),
+        network_propagator,
+        ws_manager,
+        tx_status: services.tx_pool.status_registry(),
+        chain_control: services.chain_control.clone(),
+        governance_executor: services.governance_executor.clone(),
+        staking_ledger: services.staking_ledger.clone(),
+        slashing_engine: services.slashing_engine.clone(),
+        validator_lifecycle: services.validator_lifecycle.clone(),
+        epoch_manager: services.epoch_manager.clone(),
+        handle_registry: services.handle_registry.clone(),
+        ingestion: services.ingestion.clone(),
+        gateway_signers: load_gateway_signers(),
+        idempotency: services.idempotency.clone(),
+        settlement_matcher: Arc::new(SettlementMatcher::new()),
+        bridge: services.bridge.clone(),
+    });
+    start_fix_acceptor(&state);
+    state
+}
+
 pub fn create_app_state(
     validator_set: Arc<Mutex<ValidatorSet>>,
     storage: Arc<crate::storage::persistent::PersistentStorage>,
@@ -1826,14 +3352,8 @@ pub fn create_app_state(
 ) -> Arc<AppState> {
     let ws_manager = Arc::new(WebSocketManager::new());
     crate::api::websocket::spawn_realtime_mock_data(ws_manager.clone());
-    Arc::new(AppState {
-        validator_set,
-        storage,
-        governance_state,
-        tx_pool,
-        network_propagator,
-        ws_manager,
-    })
+    let services = NodeServices::new(storage, validator_set, governance_state, tx_pool, None);
+    build_app_state(&services, network_propagator, ws_manager)
 }
 
 // Removed load_tls_config - ServerConfig not available
@@ -1876,9 +3396,39 @@ pub fn create_router(state: Arc<AppState>) -> Router<Arc<AppState>> {
         .route("/ws", get(websocket_handler))
         .route("/balance/:address/:asset", get(get_balance))
         .route("/tx", post(post_tx))
+        .route("/tx/:hash", get(get_tx_status))
+        .route("/tx/multi-leg", post(post_multi_leg_tx))
+        .route("/multisig/ops", post(post_multisig_op))
+        .route("/address/validate/:address", get(validate_address_endpoint))
+        .route("/address/migrate", post(migrate_legacy_address))
+        .route("/multisig/:address", get(get_multisig_account))
+        .route("/staking/ops", post(post_staking_op))
+        .route("/staking/delegations/:address", get(get_delegations))
+        .route("/rewards/:address", get(get_rewards))
         .route("/validators", get(get_validators).post(add_validator))
         .route("/validators/:address", delete(remove_validator))
         .route("/validators/:address/slash", post(slash_validator))
+        .route("/validators/register", post(register_validator))
+        .route("/validators/:address/rotate-key", post(rotate_validator_key))
+        .route("/validators/:address/exit", post(exit_validator))
+        .route("/validators/:address/unjail", post(unjail_validator))
+        .route("/validators/:address/slashing", get(get_validator_slashing))
+        .route("/handles/ops", post(post_handle_op))
+        .route("/handles/:handle", get(get_handle))
+        .route("/ingestion", get(get_ingestion))
+        .route("/ingestion/bindings", post(post_ingestion_binding))
+        .route("/ingestion/mappings", post(post_ingestion_mapping))
+        .route("/ingestion/mappings/:kind/:identifier", delete(delete_ingestion_mapping))
+        .route("/epochs", get(get_epochs))
+        .route("/epochs/:epoch", get(get_epoch))
+        .route("/epochs/:epoch/signatures", post(post_epoch_signature))
+        .route("/iso20022/camt053/:address", get(get_camt053_statement))
+        .route("/iso20022/camt054/:address", get(get_camt054_notification))
+        .route("/iso20022/pacs002", post(post_pacs002_status_report))
+        .route("/iso20022/pacs008", post(post_pacs008))
+        .route("/iso20022/sese023", post(post_sese023_instruction))
+        .route("/swift/mt103", post(post_mt103))
+        .route("/iso20022/sese023/:account/:transaction_id", get(get_sese023_status))
         .route("/governance/proposals", post(submit_proposal).get(list_proposals))
         .route("/governance/proposals/:id", get(get_proposal))
         .route("/governance/proposals/:id/vote", post(vote_proposal))
@@ -1890,6 +3440,8 @@ pub fn create_router(state: Arc<AppState>) -> Router<Arc<AppState>> {
         .route("/governance/analytics", get(get_governance_analytics))
         .route("/governance/events", get(get_governance_events))
         .route("/governance/top-voters", get(get_top_voters))
+        .route("/governance/executions", get(get_governance_executions))
+        .route("/params", get(get_params))
         .route("/assets", get(get_assets))
         .route("/bridge/outbound", post(outbound_bridge))
         .route("/bridge/inbound", post(inbound_bridge))
@@ -1938,42 +3490,22 @@ pub fn create_router(state: Arc<AppState>) -> Router<Arc<AppState>> {
 
 pub async fn start(
     port: u16,
-    tx_pool: Arc<ShardedTxPool>,
+    services: NodeServices,
+    network_propagator: Arc<NetworkPropagator>,
 ) -> std::io::Result<()> {
     use tokio::net::TcpListener;
     
     // Start cache cleanup task
     start_cache_cleanup().await;
     
-    // Create a minimal AppState for the simple server
-    let validator_set = Arc::new(Mutex::new(ValidatorSet::new()));
-    let storage = Arc::new(crate::storage::persistent::PersistentStorage::new("simple_server").unwrap());
-    let governance_state = Arc::new(Mutex::new(crate::consensus::governance::GovernanceState {
-        proposals: HashMap::new(),
-        votes: HashMap::new(),
-        active_proposals: Vec::new(),
-        executed_proposals: Vec::new(),
-        config: crate::consensus::governance::GovernanceConfig::default(),
-        total_stake: 0,
-        proposal_counter: 0,
-        analytics: crate::consensus::governance::GovernanceAnalytics::default(),
-        events: Vec::new(),
-        voter_activity: HashMap::new(),
-    }));
-    let network_propagator = Arc::new(NetworkPropagator::new("127.0.0.1:8080", vec![], Address::new("testaddress00000000000000000000000000000000".to_string())).await?);
-    
-    let app_state = Arc::new(AppState {
-        validator_set,
-        storage,
-        governance_state,
-        tx_pool,
-        network_propagator,
-        ws_manager: Arc::new(WebSocketManager::new()),
-    });
+    let app_state = build_app_state(&services, network_propagator, Arc::new(WebSocketManager::new()));
     
     let app = Router::new()
         .route("/health", get(health))
         .route("/tx", post(post_tx))
+        .route("/tx/:hash", get(get_tx_status))
+        .route("/tx/multi-leg", post(post_multi_leg_tx))
+        .route("/params", get(get_params))
         .route("/ws", get(websocket_handler))
         .layer(create_cors_layer())
         .layer(middleware::from_fn(sanitize_request))
@@ -2386,12 +3918,21 @@ async fn submit_dag_transaction(
         })));
     }
 
+    let (to_address, resolved_to) = resolve_recipient(&state.handle_registry, &req.to).map_err(|(status, Json(error))| {
+        (status, Json(DagTransactionResponse {
+            tx_hash: "".to_string(),
+            block_id: "".to_string(),
+            status: "failed".to_string(),
+            message: error["error"].as_str().unwrap_or("Invalid recipient").to_string(),
+        }))
+    })?;
+
     // Create a wallet for signing (in production, load user's wallet)
     let wallet = crate::core::wallet::Wallet::new();
     let account = &wallet.accounts()[0];
     
     // Create transaction
-    let mut transaction = create_dag_transaction(&req, account);
+    let mut transaction = create_dag_transaction(&req, account, to_address);
     
     // Sign the transaction
     if let Err(e) = account.sign_transaction(&mut transaction) {
@@ -2407,6 +3948,7 @@ async fn submit_dag_transaction(
     let added = state.tx_pool.add_transaction(transaction.clone());
     
     if added {
+        record_resolved_recipient(&state.tx_status, transaction.compute_hash(), resolved_to);
         // Generate transaction hash
         let tx_hash = format!("0x{}", hex::encode(transaction.hashtimer));
         
@@ -2433,9 +3975,9 @@ async fn submit_dag_transaction(
 fn create_dag_transaction(
     req: &DagTransactionRequest,
     account: &crate::core::wallet::WalletAccount,
+    to_address: crate::core::address::Address,
 ) -> crate::core::types::Transaction {
     use crate::core::types::{Transaction, ShardId};
-    use crate::core::address::Address;
     use sha2::{Sha256, Digest};
     use chrono::Utc;
     
@@ -2461,7 +4003,6 @@ fn create_dag_transaction(
     
     // Use account address as from (in production, resolve from user's wallet)
     let from_address = account.address.clone();
-    let to_address = Address::new(req.to.clone());
     
     Transaction {
         from: from_address,
@@ -2477,6 +4018,9 @@ fn create_dag_transaction(
         dest_shard: None,
         target_chain: None,
         bridge_protocol: None,
+        valid_after: None,
+        valid_until: None,
+        multisig_signatures: Vec::new(),
     }
 }
 
@@ -3374,6 +4918,9 @@ fn create_cancellation_transaction(
         dest_shard: None,
         target_chain: None,
         bridge_protocol: None,
+        valid_after: None,
+        valid_until: None,
+        multisig_signatures: Vec::new(),
     }
 }
 
@@ -4062,4 +5609,62 @@ async fn get_performance_timeseries(Query(params): Query<TimeseriesQuery>) -> Js
 }
 
 // ... in the router setup (create_router or similar) ...
-// .route("/analytics/performance/timeseries", get(get_performance_timeseries))
\ No newline at end of file
+// .route("/analytics/performance/timeseries", get(get_performance_timeseries))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::handle_registry::{RegisterSubhandleInstruction, RevokeHandleInstruction};
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn test_handle_recipient_resolved_and_recorded() {
+        let (bank, desk) = (SigningKey::from_bytes(&[61u8; 32]), SigningKey::from_bytes(&[62u8; 32]));
+        let handles = Mutex::new(HandleRegistry::default());
+        handles.lock().unwrap().register_root("@bank", bank.verifying_key(), None, Utc::now()).unwrap();
+        let mut register = RegisterSubhandleInstruction {
+            handle: "@desk.bank".to_string(),
+            parent: "@bank".to_string(),
+            new_pubkey: STANDARD.encode(desk.verifying_key().to_bytes()),
+            metadata: None,
+            timestamp: "2026-01-05T09:00:00Z".to_string(),
+            parent_signature: String::new(),
+        };
+        register.parent_signature = STANDARD.encode(bank.sign(HandleRegistry::subhandle_payload_to_sign(&register).as_bytes()).to_bytes());
+        handles.lock().unwrap().register_subhandle(&register).unwrap();
+
+        // A handle is replaced by the address of its current key; addresses pass through
+        let (address, resolved) = resolve_recipient(&handles, "@desk.bank").unwrap();
+        assert_eq!(address, Address::from_signing_key(&desk));
+        let plain = Address::from_signing_key(&bank);
+        assert_eq!(resolve_recipient(&handles, plain.as_str()).unwrap(), (plain, None));
+        assert_eq!(resolve_recipient(&handles, "not-an-address").unwrap_err().0, StatusCode::BAD_REQUEST);
+
+        // The receipt keeps the handle the payment was addressed to
+        let tx_status = TxStatusRegistry::default();
+        let tx_hash = [7u8; 32];
+        tx_status.mark_pending(tx_hash);
+        let json = record_resolved_recipient(&tx_status, tx_hash, resolved);
+        assert_eq!(json["handle"], "@desk.bank");
+        let receipt = tx_status.get(&tx_hash).unwrap().to_json();
+        assert_eq!(receipt["resolved_recipient"]["address"], address.as_str());
+        assert_eq!(receipt["resolved_recipient"]["public_key"], hex::encode(desk.verifying_key().to_bytes()));
+        assert_eq!(record_resolved_recipient(&tx_status, [8u8; 32], None), serde_json::Value::Null);
+
+        // Unknown and revoked handles are rejected at submission
+        let (status, Json(error)) = resolve_recipient(&handles, "@nobody.bank").unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(error["error"].as_str().unwrap().contains("Unknown handle"));
+        let mut revoke = RevokeHandleInstruction {
+            handle: "@desk.bank".to_string(),
+            reason: "desk closed".to_string(),
+            timestamp: "2026-01-06T09:00:00Z".to_string(),
+            parent_signature: String::new(),
+        };
+        revoke.parent_signature = STANDARD.encode(bank.sign(HandleRegistry::revoke_handle_payload_to_sign(&revoke).as_bytes()).to_bytes());
+        handles.lock().unwrap().revoke_handle(&revoke).unwrap();
+        let (status, Json(error)) = resolve_recipient(&handles, "@desk.bank").unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(error["error"].as_str().unwrap().contains("revoked"));
+    }
+}
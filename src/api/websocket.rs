@@ -60,6 +60,13 @@ pub enum WebSocketMessage {
         timestamp: DateTime<Utc>,
     },
     
+    // Transaction lifecycle updates
+    TxStatusUpdate {
+        tx_hash: String,
+        status: serde_json::Value,
+        timestamp: DateTime<Utc>,
+    },
+    
     // System messages
     SystemMessage {
         message: String,
@@ -197,6 +204,7 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
     let mut trade_receiver = state.ws_manager.trade_sender.subscribe();
     let mut market_data_receiver = state.ws_manager.market_data_sender.subscribe();
     let mut dag_receiver = state.ws_manager.dag_sender.subscribe();
+    let mut tx_status_receiver = state.tx_status.subscribe();
     
     // Track subscribed channels for this connection
     let mut subscribed_channels: Vec<String> = Vec::new();
@@ -312,6 +320,22 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
                     }
                 }
             },
+            
+            // Handle transaction status updates ("tx_status" or "tx_status:<hash>")
+            Ok(update) = tx_status_receiver.recv() => {
+                let tx_hash = hex::encode(update.tx_hash);
+                if subscribed_channels.contains(&"tx_status".to_string())
+                    || subscribed_channels.contains(&format!("tx_status:{tx_hash}")) {
+                    let msg = WebSocketMessage::TxStatusUpdate {
+                        tx_hash,
+                        status: update.status.to_json(),
+                        timestamp: Utc::now(),
+                    };
+                    if let Ok(msg_str) = serde_json::to_string(&msg) {
+                        let _ = sender.send(Message::Text(msg_str)).await;
+                    }
+                }
+            },
         }
     }
     
@@ -3,6 +3,7 @@
 
 use findag::consensus::roundchain::RoundChain;
 use findag::consensus::validator_set::ValidatorSet;
+use std::sync::{Arc, Mutex};
 use findag::core::address::generate_address;
 use findag::core::types::{Block, Transaction, ShardId};
 use ed25519_dalek::{SigningKey, VerifyingKey, Signer};
@@ -32,6 +33,9 @@ fn create_test_transaction(from: &SigningKey, to: &VerifyingKey, amount: u64) ->
         dest_shard: None,
         target_chain: None,
         bridge_protocol: None,
+        valid_after: None,
+        valid_until: None,
+        multisig_signatures: Vec::new(),
     }
 }
 
@@ -79,7 +83,7 @@ fn main() {
     }
     
     // Create RoundChain
-    let mut roundchain = RoundChain::new(validator_set);
+    let mut roundchain = RoundChain::new(Arc::new(Mutex::new(validator_set)));
     
     // Create test keypairs
     let (proposer_keypair, proposer_address) = generate_address();
@@ -144,7 +148,7 @@ fn main() {
     println!("\n🔐 Simulating quorum signing for Round 1...");
     
     // Get committee for round 1
-    let committee = roundchain.validator_set.select_committee(1);
+    let committee = roundchain.validator_set.lock().unwrap().select_committee(1);
     println!("   Committee size: {}", committee.validators.len());
     
     // Create dummy signatures (in real implementation, these would be actual validator signatures)
@@ -211,7 +215,7 @@ mod tests {
     #[test]
     fn test_roundchain_creation() {
         let validator_set = ValidatorSet::new();
-        let roundchain = RoundChain::new(validator_set);
+        let roundchain = RoundChain::new(Arc::new(Mutex::new(validator_set)));
         
         assert_eq!(roundchain.latest_round_number, 0);
         assert_eq!(roundchain.rounds.len(), 0);
@@ -220,7 +224,7 @@ mod tests {
     #[test]
     fn test_sequential_round_creation() {
         let validator_set = ValidatorSet::new();
-        let mut roundchain = RoundChain::new(validator_set);
+        let mut roundchain = RoundChain::new(Arc::new(Mutex::new(validator_set)));
         let (keypair, address) = generate_address();
         
         // Create test block
@@ -72,6 +72,9 @@ async fn main() {
             dest_shard: None,
             target_chain: None,
             bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
         };
         
         // Send transaction to node
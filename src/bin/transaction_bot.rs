@@ -80,37 +80,35 @@ impl TransactionBot {
         let mut hashtimer = [0u8; 32];
         hashtimer[0..8].copy_from_slice(&findag_time.to_le_bytes());
         
-        // Create the message to sign (must match what the API expects)
-        // The API expects: format!("{}{}{}", signed_tx.from, signed_tx.to, signed_tx.amount)
-        // where from, to are strings, not Address objects
-        let message = format!("{}{}{}", from.as_str(), to.as_str(), amount);
-        let signature_bytes = self.keypair.sign(message.as_bytes()).unwrap();
-        
-        // Convert to ed25519::Signature
-        let signature = Signature::from_bytes(&signature_bytes.clone().try_into().unwrap());
-        
         // Convert public key
         let public_key_bytes = self.keypair.public().encode_protobuf();
         let public_key = VerifyingKey::from_bytes(&public_key_bytes[..32].try_into().unwrap()).unwrap();
         
         println!("[Bot-01] Sending transaction: from={from}, to={to}, amount={amount}");
         
-        let _tx = Transaction {
+        let mut tx = Transaction {
             from: from.clone(),
             to: to.clone(),
             amount,
             payload: payload.clone(),
             findag_time,
             hashtimer,
-            signature,
+            signature: Signature::from_bytes(&[0u8; 64]),
             public_key,
             shard_id: findag::core::types::ShardId(0),
             source_shard: None,
             dest_shard: None,
             target_chain: None,
             bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
         };
         
+        // Sign the canonical transaction message the API verifies
+        let signature_bytes = self.keypair.sign(&tx.signing_message()).unwrap();
+        tx.signature = Signature::from_bytes(&signature_bytes.clone().try_into().unwrap());
+        
         // Create the request payload that matches TransactionRequest struct
         let request_payload = json!({
             "from": from.as_str(),
@@ -147,14 +145,10 @@ impl TransactionBot {
     async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
         let from_address = self.get_from_address();
         
-        // Test recipient addresses
-        let test_addresses = vec![
-            Address::new("fdg1qalice1234567890".to_string()),
-            Address::new("fdg1qbob1234567890".to_string()),
-            Address::new("fdg1qcharlie1234567890".to_string()),
-            Address::new("fdg1qdiana1234567890".to_string()),
-            Address::new("fdg1qedward1234567890".to_string()),
-        ];
+        // Test recipient addresses (alice, bob, charlie, diana, edward from fixed devnet seeds)
+        let test_addresses: Vec<Address> = (1u8..=5)
+            .map(|seed| Address::from_signing_key(&ed25519_dalek::SigningKey::from_bytes(&[seed; 32])))
+            .collect();
         
         let mut transaction_count = 0;
         let mut successful_count = 0;
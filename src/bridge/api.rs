@@ -1,8 +1,9 @@
 // src/bridge/api.rs
 
-use crate::bridge::corda::CordaSettlementProof;
+use crate::bridge::corda::{CordaSettlementProof, NotaryKeySet};
+use crate::bridge::fabric::{Endorsement, FabricEndorsementProof, FabricTrust, FabricTrustConfig};
 use crate::bridge::proofs::SettlementProof;
-use crate::fix::{parse_order_single, fix_order_to_findag_tx};
+use crate::fix::{parse_order_single, FixOrderSingle};
 use axum::{
     extract::Json,
     http::StatusCode,
@@ -11,15 +12,57 @@ use axum::{
     Router,
 };
 use serde::Deserialize;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use once_cell::sync::Lazy;
 use std::sync::Arc;
 use crate::core::tx_pool::ShardedTxPool;
 
+static CORDA_NOTARIES: Lazy<NotaryKeySet> = Lazy::new(corda_notaries_from_env);
+static FABRIC_TRUST: Lazy<Option<FabricTrust>> = Lazy::new(fabric_trust_from_env);
+
+/// Trusted Corda notaries, from FINDAG_CORDA_NOTARY_KEYS (comma-separated hex keys)
+pub fn corda_notaries_from_env() -> NotaryKeySet {
+    let keys = std::env::var("FINDAG_CORDA_NOTARY_KEYS").unwrap_or_default();
+    NotaryKeySet::from_hex_list(&keys).unwrap_or_else(|e| {
+        eprintln!("Ignoring FINDAG_CORDA_NOTARY_KEYS: {:?}", e);
+        NotaryKeySet::default()
+    })
+}
+
+/// Trusted Fabric MSPs and endorsement policy, from the JSON file at FINDAG_FABRIC_TRUST_FILE
+pub fn fabric_trust_from_env() -> Option<FabricTrust> {
+    let path = std::env::var("FINDAG_FABRIC_TRUST_FILE").ok()?;
+    let loaded = std::fs::read_to_string(&path).map_err(anyhow::Error::from)
+        .and_then(|json| Ok(serde_json::from_str::<FabricTrustConfig>(&json)?))
+        .and_then(|config| FabricTrust::from_config(&config));
+    match loaded {
+        Ok(trust) => Some(trust),
+        Err(e) => {
+            eprintln!("Ignoring Fabric trust file {}: {:?}", path, e);
+            None
+        }
+    }
+}
+
 // --- For JSON input ---
 #[derive(Debug, Deserialize)]
 pub struct CordaProofInput {
-    pub state_hash: String,         // base64 or hex string
-    pub notary_signature: String,   // base64 or hex string
+    pub state_hash: String,         // hex transaction id
+    pub notary_key: String,         // hex Ed25519 or uncompressed secp256r1 key
+    pub notary_signature: String,   // hex string
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FabricEndorsementInput {
+    pub msp_id: String,
+    pub certificate: String,        // PEM
+    pub signature: String,          // hex DER ECDSA signature
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FabricProofInput {
+    pub state_root: String,         // hex string
+    pub endorsements: Vec<FabricEndorsementInput>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -48,7 +91,7 @@ pub async fn submit_fix_order(Json(proof_input): Json<FixProofInput>) -> impl In
 
     match process_fix_order(proof_input).await {
         Ok(_) => {
-            println!("FIX Order accepted and converted to FinDAG transaction!");
+            println!("FIX Order accepted; settlement is submitted by the FIX session gateway");
             (StatusCode::OK, "FIX Order Accepted").into_response()
         }
         Err(e) => {
@@ -58,6 +101,21 @@ pub async fn submit_fix_order(Json(proof_input): Json<FixProofInput>) -> impl In
     }
 }
 
+pub async fn submit_fabric_proof(Json(proof_input): Json<FabricProofInput>) -> impl IntoResponse {
+    println!("Received Fabric proof: {:?}", proof_input);
+
+    match process_fabric_proof(proof_input).await {
+        Ok(_) => {
+            println!("Fabric proof verified and accepted!");
+            (StatusCode::OK, "Proof Accepted").into_response()
+        }
+        Err(e) => {
+            eprintln!("Bridge error: {:?}", e);
+            (StatusCode::BAD_REQUEST, "Invalid Proof").into_response()
+        }
+    }
+}
+
 // --- Stateful handlers for integration with tx_pool ---
 pub async fn submit_corda_proof_with_state(
     axum::extract::State(_tx_pool): axum::extract::State<Arc<ShardedTxPool>>,
@@ -85,10 +143,8 @@ pub async fn submit_fix_order_with_state(
     println!("Received FIX Order with state: {:?}", proof_input.fix_raw);
 
     match process_fix_order(proof_input).await {
-        Ok(_findag_tx) => {
-            println!("FIX Order accepted and converted to FinDAG transaction!");
-            // TODO: Store the transaction in tx_pool
-            // tx_pool.add_transaction(findag_tx).await?;
+        Ok(_order) => {
+            println!("FIX Order accepted; settlement is submitted by the FIX session gateway");
             (StatusCode::OK, "FIX Order Accepted").into_response()
         }
         Err(e) => {
@@ -100,42 +156,54 @@ pub async fn submit_fix_order_with_state(
 
 async fn process_corda_proof(proof_input: CordaProofInput) -> Result<()> {
     let state_hash = hex::decode(&proof_input.state_hash)?;
+    let notary_key = hex::decode(&proof_input.notary_key)?;
     let notary_signature = hex::decode(&proof_input.notary_signature)?;
 
     let proof = CordaSettlementProof {
         state_hash,
+        notary_key,
         notary_signature,
     };
 
-    let trusted_notary_key = vec![0u8; 32]; // Load your real notary pubkey!
-    proof.verify(&trusted_notary_key)?;
+    proof.verify(&CORDA_NOTARIES)?;
 
     // ✅ TODO: store the verified proof in your DAG or mempool.
     Ok(())
 }
 
-async fn process_fix_order(proof_input: FixProofInput) -> Result<crate::core::types::Transaction> {
-    let order = parse_order_single(&proof_input.fix_raw)?;
-    println!("Parsed Order: {:?}", order);
+async fn process_fabric_proof(proof_input: FabricProofInput) -> Result<()> {
+    let trust = FABRIC_TRUST.as_ref().ok_or_else(|| anyhow!("No Fabric trust configured"))?;
+    let endorsements = proof_input.endorsements.into_iter()
+        .map(|e| Ok(Endorsement {
+            msp_id: e.msp_id,
+            certificate: e.certificate.into_bytes(),
+            signature: hex::decode(&e.signature)?,
+        }))
+        .collect::<Result<Vec<_>>>()?;
+
+    let proof = FabricEndorsementProof {
+        state_root: hex::decode(&proof_input.state_root)?,
+        endorsements,
+    };
 
-    let findag_tx = fix_order_to_findag_tx(&order);
-    println!("Converted FinDAGTransaction: {:?}", findag_tx);
+    proof.verify(trust)?;
+    Ok(())
+}
 
-    // TODO: insert into your DAG or mempool!
-    // In a real implementation, you would:
-    // 1. Validate the FIX message checksum
-    // 2. Check if the order is within acceptable limits
-    // 3. Verify the account has sufficient balance
-    // 4. Store the transaction in the mempool
-    // 5. Trigger settlement processing
+async fn process_fix_order(proof_input: FixProofInput) -> Result<FixOrderSingle> {
+    let order = parse_order_single(&proof_input.fix_raw)?;
+    println!("Parsed Order: {:?}", order);
 
-    Ok(findag_tx)
+    // Conversion to a FinDAG transaction needs the FIX gateway key and the
+    // account mappings, so signed settlement goes through the FIX session acceptor
+    Ok(order)
 }
 
 // --- Stateless Axum router (for main server) ---
 pub fn bridge_routes() -> Router {
     Router::new()
         .route("/bridge/corda/submit", post(submit_corda_proof))
+        .route("/bridge/fabric/submit", post(submit_fabric_proof))
         .route("/bridge/fix/submit", post(submit_fix_order))
 }
 
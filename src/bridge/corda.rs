@@ -1,28 +1,108 @@
 use crate::bridge::proofs::SettlementProof;
 use anyhow::{Result, anyhow};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1};
 
+/// Corda signature schemes accepted for notaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotaryScheme {
+    EddsaEd25519,    // EDDSA_ED25519_SHA512, raw 32-byte key and 64-byte signature
+    EcdsaSecp256r1,  // ECDSA_SECP256R1_SHA256, uncompressed SEC1 key and DER signature
+}
+
+/// A notary public key; the scheme follows from the encoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotaryKey {
+    pub scheme: NotaryScheme,
+    pub public_key: Vec<u8>,
+}
+
+impl NotaryKey {
+    pub fn from_bytes(public_key: &[u8]) -> Result<Self> {
+        let scheme = match (public_key.len(), public_key.first()) {
+            (32, _) => {
+                VerifyingKey::from_bytes(public_key.try_into()?)
+                    .map_err(|e| anyhow!("Invalid Ed25519 notary key: {}", e))?;
+                NotaryScheme::EddsaEd25519
+            }
+            (65, Some(0x04)) => NotaryScheme::EcdsaSecp256r1,
+            (len, _) => return Err(anyhow!("Unsupported notary key encoding ({} bytes)", len)),
+        };
+        Ok(NotaryKey { scheme, public_key: public_key.to_vec() })
+    }
+
+    pub fn from_hex(public_key: &str) -> Result<Self> {
+        Self::from_bytes(&hex::decode(public_key.trim())?)
+    }
+
+    /// Check `signature` over `message` under this key
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        match self.scheme {
+            NotaryScheme::EddsaEd25519 => {
+                let key = VerifyingKey::from_bytes(self.public_key.as_slice().try_into()?)?;
+                let signature = Signature::from_slice(signature)
+                    .map_err(|_| anyhow!("Ed25519 notary signature must be 64 bytes"))?;
+                key.verify(message, &signature).map_err(|_| anyhow!("Invalid Ed25519 notary signature"))
+            }
+            NotaryScheme::EcdsaSecp256r1 => UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, &self.public_key)
+                .verify(message, signature)
+                .map_err(|_| anyhow!("Invalid ECDSA secp256r1 notary signature")),
+        }
+    }
+}
+
+/// Notary keys trusted to finalize Corda transactions
+#[derive(Debug, Clone, Default)]
+pub struct NotaryKeySet {
+    keys: Vec<NotaryKey>,
+}
+
+impl NotaryKeySet {
+    pub fn new(keys: Vec<NotaryKey>) -> Self {
+        NotaryKeySet { keys }
+    }
+
+    /// Comma-separated hex keys, as in FINDAG_CORDA_NOTARY_KEYS
+    pub fn from_hex_list(keys: &str) -> Result<Self> {
+        let keys = keys.split(',')
+            .filter(|k| !k.trim().is_empty())
+            .map(NotaryKey::from_hex)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(keys))
+    }
+
+    pub fn get(&self, public_key: &[u8]) -> Option<&NotaryKey> {
+        self.keys.iter().find(|k| k.public_key == public_key)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// Notary signature finalizing a Corda transaction
 pub struct CordaSettlementProof {
-    pub state_hash: Vec<u8>,
+    pub state_hash: Vec<u8>,       // Corda transaction id (SHA-256 Merkle root)
+    pub notary_key: Vec<u8>,
     pub notary_signature: Vec<u8>,
 }
 
 impl SettlementProof for CordaSettlementProof {
-    fn verify(&self, trusted_pubkey: &[u8]) -> Result<()> {
+    type Trust = NotaryKeySet;
+
+    fn verify(&self, notaries: &NotaryKeySet) -> Result<()> {
         if self.state_hash.is_empty() || self.notary_signature.is_empty() {
             return Err(anyhow!("Invalid proof: missing fields"));
         }
-
-        // Placeholder: Replace with real crypto verification!
-        println!(
-            "Verifying Corda proof with trusted notary key: {:?}",
-            trusted_pubkey
-        );
-
-        // Use ed25519_dalek, ring, or openssl to check signature.
-        Ok(())
+        if self.state_hash.len() != 32 {
+            return Err(anyhow!("Corda transaction id must be 32 bytes"));
+        }
+        let notary = notaries.get(&self.notary_key)
+            .ok_or_else(|| anyhow!("Notary key {} is not trusted", hex::encode(&self.notary_key)))?;
+        notary.verify(&self.state_hash, &self.notary_signature)
     }
 
     fn state_hash(&self) -> &[u8] {
         &self.state_hash
     }
-} 
\ No newline at end of file
+}
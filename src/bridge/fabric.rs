@@ -1,27 +1,372 @@
 use crate::bridge::proofs::SettlementProof;
 use anyhow::{Result, anyhow};
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use x509_parser::certificate::X509Certificate;
+use x509_parser::oid_registry::OID_SIG_ECDSA_WITH_SHA256;
 
+/// Half the order of P-256; Fabric rejects ECDSA signatures whose s is above it
+const P256_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0x80, 0x00, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xde, 0x73, 0x7d, 0x56, 0xd3, 0x8b, 0xcf, 0x42, 0x79, 0xdc, 0xe5, 0x61, 0x7e, 0x31, 0x92, 0xa8,
+];
+
+/// Longest intermediate CA chain followed below an MSP root
+const MAX_CHAIN_DEPTH: usize = 3;
+
+/// `'MSPID.role'` principal of a signature policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub msp_id: String,
+    pub role: String, // member, peer, client, admin or orderer
+}
+
+impl Principal {
+    pub fn parse(principal: &str) -> Result<Self> {
+        let (msp_id, role) = principal.rsplit_once('.')
+            .ok_or_else(|| anyhow!("Principal '{}' must be MSPID.role", principal))?;
+        if msp_id.is_empty() || !["member", "peer", "client", "admin", "orderer"].contains(&role) {
+            return Err(anyhow!("Invalid principal '{}'", principal));
+        }
+        Ok(Principal { msp_id: msp_id.to_string(), role: role.to_string() })
+    }
+
+    fn matches(&self, identity: &EndorserIdentity) -> bool {
+        identity.msp_id == self.msp_id && (self.role == "member" || identity.roles.contains(&self.role))
+    }
+}
+
+/// Fabric signature policy, e.g. `AND('Org1MSP.peer', OR('Org2MSP.peer', 'Org3MSP.peer'))`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EndorsementPolicy {
+    SignedBy(Principal),
+    OutOf(usize, Vec<EndorsementPolicy>),
+}
+
+impl EndorsementPolicy {
+    /// Parse the policy language of Fabric's `--signature-policy`: AND, OR and OutOf
+    pub fn parse(policy: &str) -> Result<Self> {
+        let mut parser = PolicyParser { input: policy, pos: 0 };
+        let parsed = parser.expression()?;
+        parser.skip_whitespace();
+        if parser.pos != policy.len() {
+            return Err(anyhow!("Unexpected input at {} in policy", parser.pos));
+        }
+        Ok(parsed)
+    }
+
+    /// Like Fabric's cauthdsl, each endorsement satisfies at most one principal
+    fn evaluate(&self, identities: &[EndorserIdentity], used: &mut [bool]) -> bool {
+        match self {
+            EndorsementPolicy::SignedBy(principal) => {
+                match (0..identities.len()).find(|&i| !used[i] && principal.matches(&identities[i])) {
+                    Some(i) => {
+                        used[i] = true;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            EndorsementPolicy::OutOf(required, rules) => {
+                let mut satisfied = 0;
+                for rule in rules {
+                    let mut attempt = used.to_vec();
+                    if rule.evaluate(identities, &mut attempt) {
+                        used.copy_from_slice(&attempt);
+                        satisfied += 1;
+                    }
+                }
+                satisfied >= *required
+            }
+        }
+    }
+}
+
+struct PolicyParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl PolicyParser<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        self.skip_whitespace();
+        if !self.input[self.pos..].starts_with(c) {
+            return Err(anyhow!("Expected '{}' at {} in policy", c, self.pos));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn word(&mut self) -> &str {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.input[self.pos..].starts_with(|c: char| c.is_ascii_alphanumeric()) {
+            self.pos += 1;
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn expression(&mut self) -> Result<EndorsementPolicy> {
+        self.skip_whitespace();
+        if let Some(quote) = self.input[self.pos..].chars().next().filter(|c| *c == '\'' || *c == '"') {
+            let rest = &self.input[self.pos + 1..];
+            let end = rest.find(quote).ok_or_else(|| anyhow!("Unterminated principal in policy"))?;
+            let principal = Principal::parse(&rest[..end])?;
+            self.pos += end + 2;
+            return Ok(EndorsementPolicy::SignedBy(principal));
+        }
+        let operator = self.word().to_ascii_lowercase();
+        self.expect('(')?;
+        let required = match operator.as_str() {
+            "outof" => {
+                let n = self.word().parse::<usize>().map_err(|_| anyhow!("OutOf needs a count"))?;
+                self.expect(',')?;
+                Some(n)
+            }
+            "and" | "or" => None,
+            _ => return Err(anyhow!("Unknown policy operator '{}'", operator)),
+        };
+        let mut rules = vec![self.expression()?];
+        loop {
+            self.skip_whitespace();
+            if self.input[self.pos..].starts_with(',') {
+                self.pos += 1;
+                rules.push(self.expression()?);
+            } else {
+                self.expect(')')?;
+                break;
+            }
+        }
+        let required = match operator.as_str() {
+            "and" => rules.len(),
+            "or" => 1,
+            _ => required.unwrap_or_default(),
+        };
+        Ok(EndorsementPolicy::OutOf(required, rules))
+    }
+}
+
+/// One MSP's CA certificates (DER)
+#[derive(Debug, Clone)]
+struct Msp {
+    root_certs: Vec<Vec<u8>>,
+    intermediate_certs: Vec<Vec<u8>>,
+}
+
+/// JSON form of the Fabric trust configuration (FINDAG_FABRIC_TRUST_FILE)
+#[derive(Debug, Deserialize)]
+pub struct FabricTrustConfig {
+    pub msps: Vec<MspConfig>,
+    pub policy: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MspConfig {
+    pub msp_id: String,
+    pub root_certs: Vec<String>, // PEM
+    #[serde(default)]
+    pub intermediate_certs: Vec<String>,
+}
+
+/// Trusted MSPs and the endorsement policy a Fabric settlement must satisfy
+#[derive(Debug, Clone)]
+pub struct FabricTrust {
+    msps: HashMap<String, Msp>,
+    pub policy: EndorsementPolicy,
+}
+
+impl FabricTrust {
+    pub fn new(policy: EndorsementPolicy) -> Self {
+        FabricTrust { msps: HashMap::new(), policy }
+    }
+
+    pub fn from_config(config: &FabricTrustConfig) -> Result<Self> {
+        let mut trust = Self::new(EndorsementPolicy::parse(&config.policy)?);
+        for msp in &config.msps {
+            let roots: Vec<&str> = msp.root_certs.iter().map(String::as_str).collect();
+            let intermediates: Vec<&str> = msp.intermediate_certs.iter().map(String::as_str).collect();
+            trust.add_msp(&msp.msp_id, &roots, &intermediates)?;
+        }
+        Ok(trust)
+    }
+
+    /// Trust an MSP through its PEM CA certificates
+    pub fn add_msp(&mut self, msp_id: &str, root_certs: &[&str], intermediate_certs: &[&str]) -> Result<()> {
+        let decode_ca = |cert: &&str| -> Result<Vec<u8>> {
+            let der = decode_certificate(cert.as_bytes())?;
+            let (_, parsed) = x509_parser::parse_x509_certificate(&der)
+                .map_err(|e| anyhow!("Invalid CA certificate for {}: {}", msp_id, e))?;
+            if !parsed.is_ca() {
+                return Err(anyhow!("Certificate {} of {} is not a CA", parsed.subject(), msp_id));
+            }
+            Ok(der)
+        };
+        let msp = Msp {
+            root_certs: root_certs.iter().map(decode_ca).collect::<Result<_>>()?,
+            intermediate_certs: intermediate_certs.iter().map(decode_ca).collect::<Result<_>>()?,
+        };
+        if msp.root_certs.is_empty() {
+            return Err(anyhow!("MSP {} needs at least one root certificate", msp_id));
+        }
+        self.msps.insert(msp_id.to_string(), msp);
+        Ok(())
+    }
+
+    /// Check an endorser certificate chains to its MSP; returns its NodeOU roles
+    fn validate_identity(&self, msp_id: &str, cert: &X509Certificate) -> Result<Vec<String>> {
+        let msp = self.msps.get(msp_id).ok_or_else(|| anyhow!("MSP {} is not trusted", msp_id))?;
+        if !cert.validity().is_valid() {
+            return Err(anyhow!("Certificate {} is expired or not yet valid", cert.subject()));
+        }
+        chains_to_root(msp, cert, MAX_CHAIN_DEPTH)?;
+        Ok(cert.subject().iter_organizational_unit()
+            .filter_map(|ou| ou.as_str().ok())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+/// Accept a certificate as PEM or DER
+fn decode_certificate(cert: &[u8]) -> Result<Vec<u8>> {
+    if cert.starts_with(b"-----BEGIN") {
+        let (_, pem) = x509_parser::pem::parse_x509_pem(cert).map_err(|e| anyhow!("Invalid PEM certificate: {}", e))?;
+        return Ok(pem.contents);
+    }
+    Ok(cert.to_vec())
+}
+
+/// Verify `cert` was signed by `issuer` with ECDSA P-256/SHA-256
+fn issued_by(cert: &X509Certificate, issuer: &X509Certificate) -> Result<()> {
+    if cert.issuer().as_raw() != issuer.subject().as_raw() || !issuer.is_ca() || !issuer.validity().is_valid() {
+        return Err(anyhow!("{} is not issued by {}", cert.subject(), issuer.subject()));
+    }
+    if cert.signature_algorithm.algorithm != OID_SIG_ECDSA_WITH_SHA256 {
+        return Err(anyhow!("Certificate {} is not signed with ecdsa-with-SHA256", cert.subject()));
+    }
+    UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, &issuer.public_key().subject_public_key.data)
+        .verify(cert.tbs_certificate.as_ref(), &cert.signature_value.data)
+        .map_err(|_| anyhow!("Bad CA signature on {}", cert.subject()))
+}
+
+fn chains_to_root(msp: &Msp, cert: &X509Certificate, depth: usize) -> Result<()> {
+    for root in &msp.root_certs {
+        let (_, root) = x509_parser::parse_x509_certificate(root)?;
+        if issued_by(cert, &root).is_ok() {
+            return Ok(());
+        }
+    }
+    if depth > 0 {
+        for intermediate in &msp.intermediate_certs {
+            let (_, intermediate) = x509_parser::parse_x509_certificate(intermediate)?;
+            if issued_by(cert, &intermediate).is_ok() && chains_to_root(msp, &intermediate, depth - 1).is_ok() {
+                return Ok(());
+            }
+        }
+    }
+    Err(anyhow!("Certificate {} does not chain to an MSP root", cert.subject()))
+}
+
+/// The s value of a DER ECDSA-Sig-Value
+fn ecdsa_s(signature: &[u8]) -> Option<&[u8]> {
+    let [0x30, len, body @ ..] = signature else { return None };
+    if *len as usize != body.len() {
+        return None;
+    }
+    let [0x02, r_len, rest @ ..] = body else { return None };
+    match rest.get(*r_len as usize..)? {
+        [0x02, s_len, s @ ..] if *s_len as usize == s.len() => Some(s),
+        _ => None,
+    }
+}
+
+fn is_low_s(signature: &[u8]) -> bool {
+    let Some(s) = ecdsa_s(signature) else { return false };
+    let s = &s[s.iter().take_while(|b| **b == 0).count()..];
+    s.len() < 32 || (s.len() == 32 && s <= &P256_HALF_ORDER[..])
+}
+
+/// An endorsement: the endorser's identity and its ECDSA P-256 signature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Endorsement {
+    pub msp_id: String,
+    pub certificate: Vec<u8>, // PEM or DER
+    pub signature: Vec<u8>,   // DER, low-S
+}
+
+impl Endorsement {
+    /// Endorsers sign the payload followed by their serialized identity, as
+    /// Fabric peers sign the proposal response payload concatenated with the endorser
+    pub fn signed_message(payload: &[u8], msp_id: &str, certificate_der: &[u8]) -> Vec<u8> {
+        let mut message = payload.to_vec();
+        message.extend_from_slice(msp_id.as_bytes());
+        message.extend_from_slice(certificate_der);
+        message
+    }
+}
+
+struct EndorserIdentity {
+    msp_id: String,
+    roles: Vec<String>,
+    certificate: Vec<u8>,
+}
+
+/// Endorsements of the read-write set a Fabric settlement committed
 pub struct FabricEndorsementProof {
     pub state_root: Vec<u8>,
-    pub endorsement_signatures: Vec<Vec<u8>>,
+    pub endorsements: Vec<Endorsement>,
 }
 
-impl SettlementProof for FabricEndorsementProof {
-    fn verify(&self, trusted_msp_pubkeys: &[u8]) -> Result<()> {
-        if self.state_root.is_empty() || self.endorsement_signatures.is_empty() {
-            return Err(anyhow!("Invalid Fabric proof: missing fields"));
+impl FabricEndorsementProof {
+    fn verify_endorsement(&self, trust: &FabricTrust, endorsement: &Endorsement) -> Result<EndorserIdentity> {
+        let der = decode_certificate(&endorsement.certificate)?;
+        let (_, cert) = x509_parser::parse_x509_certificate(&der)
+            .map_err(|e| anyhow!("Invalid endorser certificate: {}", e))?;
+        let roles = trust.validate_identity(&endorsement.msp_id, &cert)?;
+        if !is_low_s(&endorsement.signature) {
+            return Err(anyhow!("Endorsement signature is not a low-S ECDSA signature"));
         }
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, &cert.public_key().subject_public_key.data)
+            .verify(&Endorsement::signed_message(&self.state_root, &endorsement.msp_id, &der), &endorsement.signature)
+            .map_err(|_| anyhow!("Invalid endorsement signature from {}", cert.subject()))?;
+        Ok(EndorserIdentity { msp_id: endorsement.msp_id.clone(), roles, certificate: der })
+    }
+}
 
-        // Placeholder: Replace with actual MSP certificate chain verification.
-        println!(
-            "Verifying Fabric endorsement proof with trusted MSP keys: {:?}",
-            trusted_msp_pubkeys
-        );
+impl SettlementProof for FabricEndorsementProof {
+    type Trust = FabricTrust;
 
+    fn verify(&self, trust: &FabricTrust) -> Result<()> {
+        if self.state_root.is_empty() || self.endorsements.is_empty() {
+            return Err(anyhow!("Invalid Fabric proof: missing fields"));
+        }
+        // As in Fabric, invalid endorsements are ignored and the policy decides
+        let mut identities: Vec<EndorserIdentity> = Vec::new();
+        let mut rejected = Vec::new();
+        for endorsement in &self.endorsements {
+            match self.verify_endorsement(trust, endorsement) {
+                Ok(identity) if identities.iter().all(|i| i.certificate != identity.certificate) => identities.push(identity),
+                Ok(_) => rejected.push("duplicate endorser".to_string()),
+                Err(e) => rejected.push(e.to_string()),
+            }
+        }
+        let mut used = vec![false; identities.len()];
+        if !trust.policy.evaluate(&identities, &mut used) {
+            return Err(anyhow!(
+                "Endorsement policy not satisfied by {} valid endorsements{}",
+                identities.len(),
+                if rejected.is_empty() { String::new() } else { format!(" (rejected: {})", rejected.join("; ")) }
+            ));
+        }
         Ok(())
     }
 
     fn state_hash(&self) -> &[u8] {
         &self.state_root
     }
-} 
\ No newline at end of file
+}
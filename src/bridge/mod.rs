@@ -4,32 +4,106 @@ pub mod api;
 pub mod corda;
 pub mod fabric;
 pub mod proofs;
+#[cfg(test)]
+mod test_vectors;
 
 #[cfg(test)]
 mod tests {
-    use super::corda::CordaSettlementProof;
-    use super::fabric::FabricEndorsementProof;
+    use super::corda::{CordaSettlementProof, NotaryKey, NotaryKeySet, NotaryScheme};
+    use super::fabric::{Endorsement, EndorsementPolicy, FabricEndorsementProof, FabricTrust, Principal};
     use super::proofs::SettlementProof;
+    use super::test_vectors::*;
+
+    fn corda_proof(key: &str, signature: &str) -> CordaSettlementProof {
+        CordaSettlementProof {
+            state_hash: hex::decode(CORDA_TX_ID).unwrap(),
+            notary_key: hex::decode(key).unwrap(),
+            notary_signature: hex::decode(signature).unwrap(),
+        }
+    }
 
     #[test]
     fn test_corda_proof() {
-        let proof = CordaSettlementProof {
-            state_hash: vec![1, 2, 3, 4],
-            notary_signature: vec![9, 9, 9],
-        };
-        let trusted_key = vec![0u8; 32];
-        proof.verify(&trusted_key).unwrap();
-        assert_eq!(proof.state_hash(), &[1, 2, 3, 4]);
+        let notaries = NotaryKeySet::from_hex_list(&format!("{NOTARY_ED25519_KEY},{NOTARY_P256_KEY}")).unwrap();
+        assert_eq!(NotaryKey::from_hex(NOTARY_P256_KEY).unwrap().scheme, NotaryScheme::EcdsaSecp256r1);
+
+        let proof = corda_proof(NOTARY_ED25519_KEY, NOTARY_ED25519_SIGNATURE);
+        proof.verify(&notaries).unwrap();
+        assert_eq!(proof.state_hash(), hex::decode(CORDA_TX_ID).unwrap().as_slice());
+        corda_proof(NOTARY_P256_KEY, NOTARY_P256_SIGNATURE).verify(&notaries).unwrap();
+
+        // Wrong key for the signature, untrusted notary, tampered id, garbage
+        assert!(corda_proof(NOTARY_P256_KEY, NOTARY_ED25519_SIGNATURE).verify(&notaries).is_err());
+        let only_ecdsa = NotaryKeySet::from_hex_list(NOTARY_P256_KEY).unwrap();
+        assert!(corda_proof(NOTARY_ED25519_KEY, NOTARY_ED25519_SIGNATURE).verify(&only_ecdsa).is_err());
+        let mut tampered = corda_proof(NOTARY_ED25519_KEY, NOTARY_ED25519_SIGNATURE);
+        tampered.state_hash[0] ^= 1;
+        assert!(tampered.verify(&notaries).is_err());
+        let garbage = CordaSettlementProof { state_hash: vec![1, 2, 3, 4], notary_key: vec![0; 32], notary_signature: vec![9, 9, 9] };
+        assert!(garbage.verify(&notaries).is_err());
+    }
+
+    fn endorsement(msp_id: &str, certificate: &str, signature: &str) -> Endorsement {
+        Endorsement {
+            msp_id: msp_id.to_string(),
+            certificate: certificate.as_bytes().to_vec(),
+            signature: hex::decode(signature).unwrap(),
+        }
+    }
+
+    fn fabric_trust(policy: &str) -> FabricTrust {
+        let mut trust = FabricTrust::new(EndorsementPolicy::parse(policy).unwrap());
+        trust.add_msp("Org1MSP", &[ORG1_CA_PEM], &[]).unwrap();
+        trust.add_msp("Org2MSP", &[ORG2_CA_PEM], &[]).unwrap();
+        trust
+    }
+
+    fn fabric_proof(endorsements: Vec<Endorsement>) -> FabricEndorsementProof {
+        FabricEndorsementProof { state_root: hex::decode(FABRIC_STATE_ROOT).unwrap(), endorsements }
     }
 
     #[test]
     fn test_fabric_proof() {
-        let proof = FabricEndorsementProof {
-            state_root: vec![5, 6, 7, 8],
-            endorsement_signatures: vec![vec![8, 8, 8]],
-        };
-        let trusted_keys = vec![1u8; 32];
-        proof.verify(&trusted_keys).unwrap();
-        assert_eq!(proof.state_hash(), &[5, 6, 7, 8]);
+        let both_peers = fabric_trust("AND('Org1MSP.peer', 'Org2MSP.peer')");
+        let org1_peer = endorsement("Org1MSP", ORG1_PEER_PEM, ORG1_PEER_SIGNATURE);
+        let org2_peer = endorsement("Org2MSP", ORG2_PEER_PEM, ORG2_PEER_SIGNATURE);
+
+        let proof = fabric_proof(vec![org1_peer.clone(), org2_peer.clone()]);
+        proof.verify(&both_peers).unwrap();
+        assert_eq!(proof.state_hash(), hex::decode(FABRIC_STATE_ROOT).unwrap().as_slice());
+        assert!(fabric_proof(vec![org1_peer.clone()]).verify(&both_peers).is_err());
+
+        // A client is a member but not a peer
+        let org1_client = endorsement("Org1MSP", ORG1_CLIENT_PEM, ORG1_CLIENT_SIGNATURE);
+        assert!(fabric_proof(vec![org1_client.clone(), org2_peer.clone()]).verify(&both_peers).is_err());
+        fabric_proof(vec![org1_client, org2_peer.clone()])
+            .verify(&fabric_trust("AND('Org1MSP.member', 'Org2MSP.peer')"))
+            .unwrap();
+
+        // The same endorser counts once
+        let twice = fabric_trust("OutOf(2, 'Org1MSP.peer', 'Org1MSP.member')");
+        assert!(fabric_proof(vec![org1_peer.clone(), org1_peer.clone()]).verify(&twice).is_err());
+
+        // Certificates outside the MSP, a relabeled MSP, high-S and tampered state roots are refused
+        let either = fabric_trust("OR('Org1MSP.peer', 'Org2MSP.peer')");
+        assert!(fabric_proof(vec![endorsement("Org1MSP", ROGUE_PEER_PEM, ROGUE_PEER_SIGNATURE)]).verify(&either).is_err());
+        assert!(fabric_proof(vec![endorsement("Org2MSP", ORG1_PEER_PEM, ORG1_PEER_SIGNATURE)]).verify(&either).is_err());
+        assert!(fabric_proof(vec![endorsement("Org1MSP", ORG1_PEER_PEM, ORG1_PEER_SIGNATURE_HIGH_S)]).verify(&either).is_err());
+        let mut tampered = fabric_proof(vec![org1_peer]);
+        tampered.state_root[0] ^= 1;
+        assert!(tampered.verify(&either).is_err());
+    }
+
+    #[test]
+    fn test_endorsement_policy_parsing() {
+        let policy = EndorsementPolicy::parse("OutOf(2, 'A.peer', OR(\"B.member\", 'C.admin'))").unwrap();
+        let principal = |p: &str| EndorsementPolicy::SignedBy(Principal::parse(p).unwrap());
+        assert_eq!(policy, EndorsementPolicy::OutOf(2, vec![
+            principal("A.peer"),
+            EndorsementPolicy::OutOf(1, vec![principal("B.member"), principal("C.admin")]),
+        ]));
+        assert!(EndorsementPolicy::parse("AND('A.peer'").is_err());
+        assert!(EndorsementPolicy::parse("XOR('A.peer')").is_err());
+        assert!(EndorsementPolicy::parse("'A.auditor'").is_err());
     }
-} 
\ No newline at end of file
+}
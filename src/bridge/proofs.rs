@@ -2,9 +2,12 @@ use anyhow::Result;
 
 /// Trait for a generic cross-chain settlement proof.
 pub trait SettlementProof {
+    /// Keys or certificates the proof is checked against (notary keys, MSP roots).
+    type Trust;
+
     /// Verify the proof cryptographically.
-    fn verify(&self, trusted_pubkey: &[u8]) -> Result<()>;
+    fn verify(&self, trust: &Self::Trust) -> Result<()>;
 
     /// Return the state hash or root that the proof represents.
     fn state_hash(&self) -> &[u8];
-} 
\ No newline at end of file
+}
@@ -0,0 +1,111 @@
+//! Corda notary and Fabric MSP test vectors, generated locally with the OpenSSL CLI:
+//! Ed25519 and P-256 notary keys signing a transaction id, and two Fabric orgs whose
+//! P-256 CAs issue peer and client certificates (NodeOU in the subject OU), plus a
+//! rogue CA outside both MSPs. Fabric endorsers sign `state_root || msp_id || cert DER`.
+
+/// SHA-256 of "findag corda test transaction"
+pub const CORDA_TX_ID: &str = "1849d1461d68d993ea93ce413d578a464bea8a4e134abe333035e36d89ca7b81";
+pub const NOTARY_ED25519_KEY: &str = "fb3e7cb70b1bab52ce0f80acf499a0b3268e6b5a58ad705df62c481177ff5618";
+pub const NOTARY_ED25519_SIGNATURE: &str = "7a7c4d401e401da9c42c591b47eb764c60a04cb57fffab8a4c5b715d355cc9b56175db0d1d07bfdd324a83a24e4ba8cc40add239bd6babe90e1381ed9953f804";
+/// Uncompressed SEC1 point
+pub const NOTARY_P256_KEY: &str = "04574d1aeeb999929c2710b5199689121bdc277a883e9ce3424ae6bdcdd30470c7ce7019552c5802ec6f959078b3cc16cd13a9d11c1f7ed0ea4c1b7ee6018d8649";
+/// DER ECDSA-Sig-Value
+pub const NOTARY_P256_SIGNATURE: &str = "30450220296de7906f9967b7e47055cfa5eba656c31457a63b7ec9b9ffa108287cb18898022100eec0dab5c8d2a36eab16763e7a3a5da379e261c667d615fdcbd4343c3e724568";
+
+/// SHA-256 of "findag fabric test rwset"
+pub const FABRIC_STATE_ROOT: &str = "7f5bba1c3642260de137d065fca645d5881ffe10acbb8f09f430deb595175bcb";
+pub const ORG1_PEER_SIGNATURE: &str = "304502210084ef3dd688ef63755bf999983d212d2af577711f9e35f27734bb043af3c7600102201ab5e4fb1e2574cc54206194b356cf52ec76952d24d74dd6adde2b4905ba0710";
+/// Same signature with s replaced by n - s
+pub const ORG1_PEER_SIGNATURE_HIGH_S: &str = "304602210084ef3dd688ef63755bf999983d212d2af577711f9e35f27734bb043af3c76001022100e54a1b03e1da8b34abdf9e6b4ca930acd0706580824050ae45db9f79f6a91e41";
+pub const ORG2_PEER_SIGNATURE: &str = "30440220572e0948d5cfc00d4644c88c370aed8405b85ff0eb5f6f15d2c8904f672c74b702202334476077de802863493eed2d2b6bcb792c5eb29ae28775a682108a47203c62";
+pub const ORG1_CLIENT_SIGNATURE: &str = "304402207d833abd62db53b6f021645d544ec88ff869e64259f2379279fcb473baf3a33602204c50787633db0e818efab47e710a94da543d0937e8aba7c022e717bf1d888743";
+/// Signed by a peer of the rogue CA, claiming Org1MSP
+pub const ROGUE_PEER_SIGNATURE: &str = "30440220595d5b8768790a5cec7519d3c3f7b0f57fc36cc5c705102b6ab2dce16b6be3d002205eee4558876a06ae4b4b70b2cbc70046adbe5f706693875315170cf72bd4e0f7";
+
+pub const ORG1_CA_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBqDCCAU+gAwIBAgIUWpKIPYW/oXUQlxV4VKSn0oqxeVAwCgYIKoZIzj0EAwIw
+ITENMAsGA1UECgwEb3JnMTEQMA4GA1UEAwwHY2Eub3JnMTAgFw0yNjEwMTgxOTIy
+MzhaGA8yMTI2MDkyNDE5MjIzOFowITENMAsGA1UECgwEb3JnMTEQMA4GA1UEAwwH
+Y2Eub3JnMTBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABKJFiuPgJs6Cqxmm01FL
+IicDlj6UZYan9FFuiGkaRqd1UIct8lq3uhbcrin3iqXegIZNXypg+65S/bARaPcz
+n4qjYzBhMB0GA1UdDgQWBBTv0Ljp/XFJ/3NnlnFvZLNm11WdOzAfBgNVHSMEGDAW
+gBTv0Ljp/XFJ/3NnlnFvZLNm11WdOzAPBgNVHRMBAf8EBTADAQH/MA4GA1UdDwEB
+/wQEAwICBDAKBggqhkjOPQQDAgNHADBEAiBA7CV/8dsyZpVSh3l47Gmw1yPLqovH
+NyjyG3tkQpzcQwIgBTsWX6zcaH8Em6xwe2jkSi7tGeADsTBOe/Tc1Si5vuw=
+-----END CERTIFICATE-----
+";
+
+pub const ORG2_CA_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBqDCCAU+gAwIBAgIUMarIKTdB4mRfm+05rjQOItpiKUUwCgYIKoZIzj0EAwIw
+ITENMAsGA1UECgwEb3JnMjEQMA4GA1UEAwwHY2Eub3JnMjAgFw0yNjEwMTgxOTIy
+MzhaGA8yMTI2MDkyNDE5MjIzOFowITENMAsGA1UECgwEb3JnMjEQMA4GA1UEAwwH
+Y2Eub3JnMjBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABLipcbtByOSQV9uXVwJt
+wtkldaRnpcZpxPW2RUKR307cDQzzQXPjVRtkOQpGEDTZSuvFkqLAF8Pm1tqr93Ed
+sn2jYzBhMB0GA1UdDgQWBBRTLN9ASiZLNi0bhVZyqwhwT2uLljAfBgNVHSMEGDAW
+gBRTLN9ASiZLNi0bhVZyqwhwT2uLljAPBgNVHRMBAf8EBTADAQH/MA4GA1UdDwEB
+/wQEAwICBDAKBggqhkjOPQQDAgNHADBEAiBUcXeXJTPAPpTljLO4/Q0uks2SkRjE
+HEc75X4SjxVGWgIgaEAD2AoyNpPnlDAJOf+03mDHQouwCI/cFY6Pc7X35aY=
+-----END CERTIFICATE-----
+";
+
+pub const ORG1_PEER_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBuzCCAWKgAwIBAgIUIwk/bZnBJNcCA5b9xnCq/AdL/ocwCgYIKoZIzj0EAwIw
+ITENMAsGA1UECgwEb3JnMTEQMA4GA1UEAwwHY2Eub3JnMTAgFw0yNjEwMTgxOTIy
+MzhaGA8yMTI2MDkyNDE5MjIzOFowNzENMAsGA1UECgwEb3JnMTENMAsGA1UECwwE
+cGVlcjEXMBUGA1UEAwwOcGVlcjBvcmcxLm9yZzEwWTATBgcqhkjOPQIBBggqhkjO
+PQMBBwNCAATT46xZJ/R5MVKnRLTGqab/P3IZatyqiSEociVTXAegx40/ZjE954lb
+q1YkwjWFUP/OqHEnGWhBXk1kbUOy7XEho2AwXjAMBgNVHRMBAf8EAjAAMA4GA1Ud
+DwEB/wQEAwIHgDAdBgNVHQ4EFgQUxrGr1ZbGZh0sLfAAksbhqlCYrIwwHwYDVR0j
+BBgwFoAU79C46f1xSf9zZ5Zxb2SzZtdVnTswCgYIKoZIzj0EAwIDRwAwRAIgIHvt
+torvnRQh4ETLClbXcu4LYTpK9qkQLp+cKgahxW8CIFA2HqJovgsehHOfQvLbzQvK
+3ERpYVREpZc7yVXx/hvm
+-----END CERTIFICATE-----
+";
+
+pub const ORG2_PEER_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBvDCCAWKgAwIBAgIUT+36tw7h6an3ee7WLEw1tBdsAJ4wCgYIKoZIzj0EAwIw
+ITENMAsGA1UECgwEb3JnMjEQMA4GA1UEAwwHY2Eub3JnMjAgFw0yNjEwMTgxOTIy
+MzhaGA8yMTI2MDkyNDE5MjIzOFowNzENMAsGA1UECgwEb3JnMjENMAsGA1UECwwE
+cGVlcjEXMBUGA1UEAwwOcGVlcjBvcmcyLm9yZzIwWTATBgcqhkjOPQIBBggqhkjO
+PQMBBwNCAAQoApiyXt/cYXuXddyfDcBHyAQoQHbENSKxdHEqaICQipYWIr5Vjdlr
+NQaLZOR2nR21YKma8/6EvqepcwrL7sOeo2AwXjAMBgNVHRMBAf8EAjAAMA4GA1Ud
+DwEB/wQEAwIHgDAdBgNVHQ4EFgQUDPT7stgzNJcY+f7UWWPUcuLpD5AwHwYDVR0j
+BBgwFoAUUyzfQEomSzYtG4VWcqsIcE9ri5YwCgYIKoZIzj0EAwIDSAAwRQIhAOi+
+BU2bb35jQfTrIVRf71K5ms4Zi4g6LpOlL0OVfLK4AiBwm1WWNHbLDdMW0W81XmkX
+r09JpmaVPSwZr6Hi6uVskA==
+-----END CERTIFICATE-----
+";
+
+pub const ORG1_CLIENT_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBvzCCAWSgAwIBAgIUIwk/bZnBJNcCA5b9xnCq/AdL/ogwCgYIKoZIzj0EAwIw
+ITENMAsGA1UECgwEb3JnMTEQMA4GA1UEAwwHY2Eub3JnMTAgFw0yNjEwMTgxOTIy
+MzhaGA8yMTI2MDkyNDE5MjIzOFowOTENMAsGA1UECgwEb3JnMTEPMA0GA1UECwwG
+Y2xpZW50MRcwFQYDVQQDDA51c2VyMW9yZzEub3JnMTBZMBMGByqGSM49AgEGCCqG
+SM49AwEHA0IABDg4zCHVau/Uj9PqlmweJCVJGLvRJCP+JjpHJfzD3wpgxGBwi3hu
+FlyDJNw+0ZuZCzEIluWsXGiq3bZ2UPUG4vKjYDBeMAwGA1UdEwEB/wQCMAAwDgYD
+VR0PAQH/BAQDAgeAMB0GA1UdDgQWBBQsbBs87jExVhnUw81On0+u7RmeWzAfBgNV
+HSMEGDAWgBTv0Ljp/XFJ/3NnlnFvZLNm11WdOzAKBggqhkjOPQQDAgNJADBGAiEA
+zk/ai30580XTypRDIzbwhzZsaz21zcpvMpwnQ8jw7S4CIQCd/J4HnY67alcJ8yux
+IB1IY6NFnA9G+V2Clw1D+a5f6A==
+-----END CERTIFICATE-----
+";
+
+pub const ROGUE_PEER_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBwjCCAWegAwIBAgIUT/qzt7ydRGKmutm2P+3WcnNoBUAwCgYIKoZIzj0EAwIw
+IzEOMAwGA1UECgwFcm9ndWUxETAPBgNVBAMMCGNhLnJvZ3VlMCAXDTI2MTAxODE5
+MjIzOFoYDzIxMjYwOTI0MTkyMjM4WjA6MQ4wDAYDVQQKDAVyb2d1ZTENMAsGA1UE
+CwwEcGVlcjEZMBcGA1UEAwwQcGVlcjByb2d1ZS5yb2d1ZTBZMBMGByqGSM49AgEG
+CCqGSM49AwEHA0IABI9DpG1T5nkHW4LE4VMgyLCh6B9FOi4wnyuH9IwqANBBjSsg
+GSeXdKCh2wvd0uRG1tf7mkAqcf2hHSjKAB3vrbijYDBeMAwGA1UdEwEB/wQCMAAw
+DgYDVR0PAQH/BAQDAgeAMB0GA1UdDgQWBBRrCA/sRFw5AeUks0dlAfhCtLfqujAf
+BgNVHSMEGDAWgBTACtRZ+7g9SjNME5SiGUWF7YiaRzAKBggqhkjOPQQDAgNJADBG
+AiEA7EAmTUdtcoT1rwm77Tzv5dAhkDiaFRsNOMzqe4jYhsoCIQCuGUwWHdtfwT73
+EfpLAwkh9+YyMB0cBMprpcrvJ1tNMg==
+-----END CERTIFICATE-----
+";
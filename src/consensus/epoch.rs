@@ -0,0 +1,318 @@
+use crate::consensus::parameters;
+use crate::consensus::staking::StakingLedger;
+use crate::consensus::validator_set::{EpochCommittee, ValidatorSet};
+use crate::core::address::Address;
+use crate::storage::persistent::PersistentStorage;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Domain prefix of the message committee members sign for an epoch transition
+pub const EPOCH_TRANSITION_TAG: &[u8] = b"FDG:EPOCH:1";
+
+/// Committee member as fixed in an epoch transition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochMember {
+    pub address: Address,
+    pub public_key: VerifyingKey,
+    pub stake: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochSignature {
+    pub public_key: VerifyingKey,
+    pub signature: Signature,
+}
+
+/// Record of an epoch change, chained to the previous transition by hash and
+/// signed by the outgoing committee. Epoch 0 is the genesis record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochTransition {
+    pub epoch: u64,
+    pub start_round: u64,
+    pub length_rounds: u64,
+    pub seed: [u8; 32], // Hash of the last round of the previous epoch
+    pub committee: Vec<EpochMember>,
+    pub committee_hash: [u8; 32],
+    pub previous_hash: [u8; 32],
+    pub signatures: Vec<EpochSignature>,
+}
+
+/// Hash of a committee's members, keys and stakes, in committee order
+pub fn committee_hash(committee: &[EpochMember]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for member in committee {
+        hasher.update(member.address.as_str().as_bytes());
+        hasher.update(member.public_key.as_bytes());
+        hasher.update(member.stake.to_be_bytes());
+    }
+    hasher.finalize().into()
+}
+
+impl EpochTransition {
+    /// Canonical message signed by the outgoing committee
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = EPOCH_TRANSITION_TAG.to_vec();
+        message.extend_from_slice(&self.epoch.to_be_bytes());
+        message.extend_from_slice(&self.start_round.to_be_bytes());
+        message.extend_from_slice(&self.length_rounds.to_be_bytes());
+        message.extend_from_slice(&self.seed);
+        message.extend_from_slice(&self.committee_hash);
+        message.extend_from_slice(&self.previous_hash);
+        message
+    }
+
+    /// Hash the next transition chains to; signatures are not part of it
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.signing_message()).into()
+    }
+
+    pub fn end_round(&self) -> u64 {
+        self.start_round + self.length_rounds - 1
+    }
+
+    pub fn committee_stake(&self) -> u128 {
+        self.committee.iter().map(|m| m.stake as u128).sum()
+    }
+
+    fn member_by_key(&self, public_key: &VerifyingKey) -> Option<&EpochMember> {
+        self.committee.iter().find(|m| m.public_key == *public_key)
+    }
+
+    fn to_committee(&self) -> EpochCommittee {
+        EpochCommittee {
+            epoch: self.epoch,
+            start_round: self.start_round,
+            length_rounds: self.length_rounds,
+            validators: self.committee.iter().map(|m| m.address.clone()).collect(),
+        }
+    }
+}
+
+/// Check `next` follows `previous`: the hash chain, the committee hash, and
+/// signatures from more than two thirds of the previous committee's stake
+pub fn verify_transition(previous: &EpochTransition, next: &EpochTransition) -> Result<(), String> {
+    if next.epoch != previous.epoch + 1 || next.start_round != previous.end_round() + 1 {
+        return Err(format!("Epoch {} does not follow epoch {}", next.epoch, previous.epoch));
+    }
+    if next.previous_hash != previous.hash() {
+        return Err(format!("Epoch {} is not chained to epoch {}", next.epoch, previous.epoch));
+    }
+    if next.committee_hash != committee_hash(&next.committee) {
+        return Err(format!("Committee hash mismatch in epoch {}", next.epoch));
+    }
+    let message = next.signing_message();
+    let mut signers = HashSet::new();
+    let mut signed_stake: u128 = 0;
+    for sig in &next.signatures {
+        let member = previous.member_by_key(&sig.public_key)
+            .ok_or_else(|| format!("Epoch {} signed by a non-member of epoch {}", next.epoch, previous.epoch))?;
+        sig.public_key.verify(&message, &sig.signature)
+            .map_err(|_| format!("Invalid signature on epoch {} from {}", next.epoch, member.address))?;
+        if signers.insert(member.address.clone()) {
+            signed_stake += member.stake as u128;
+        }
+    }
+    if signed_stake * 3 <= previous.committee_stake() * 2 {
+        return Err(format!("Epoch {} lacks a two-thirds stake quorum", next.epoch));
+    }
+    Ok(())
+}
+
+/// Follow epoch transitions from a trusted record (usually genesis), returning the latest
+pub fn follow_epochs(trusted: &EpochTransition, transitions: &[EpochTransition]) -> Result<EpochTransition, String> {
+    let mut current = trusted;
+    for next in transitions {
+        verify_transition(current, next)?;
+        current = next;
+    }
+    Ok(current.clone())
+}
+
+/// Fixes the committee of epoch N+1 at the last round of epoch N from on-chain
+/// validator state, and keeps the signed transition records from genesis
+pub struct EpochManager {
+    ledger: Arc<StakingLedger>,
+    storage: Option<Arc<PersistentStorage>>,
+    signing_key: Option<SigningKey>,
+}
+
+impl EpochManager {
+    pub fn new(ledger: Arc<StakingLedger>) -> Self {
+        Self { ledger, storage: None, signing_key: None }
+    }
+
+    /// Persist the validator set through `store_validator_set` when the committee changes
+    pub fn with_storage(mut self, storage: Arc<PersistentStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Sign transitions with this node's validator key while it sits on the outgoing committee
+    pub fn with_signing_key(mut self, signing_key: SigningKey) -> Self {
+        self.signing_key = Some(signing_key);
+        self
+    }
+
+    /// Record epoch 0 from the genesis validator set, starting at round 1. Idempotent.
+    pub fn record_genesis(&self) -> Result<EpochTransition, String> {
+        let state_db = self.ledger.state_db();
+        if let Some(genesis) = state_db.get_epoch_transition(0) {
+            return Ok(genesis);
+        }
+        let genesis = self.build_transition(0, 1, [0u8; 32], [0u8; 32])?;
+        state_db.put_epoch_transition(&genesis)?;
+        self.install(&genesis);
+        println!("[DEBUG] Epochs: genesis committee of {} validators", genesis.committee.len());
+        Ok(genesis)
+    }
+
+    /// Close the current epoch if `round` is its last round
+    pub fn on_round_finalized(&self, round: u64, round_hash: [u8; 32]) {
+        let state_db = self.ledger.state_db();
+        let current = match state_db.latest_epoch_transition() {
+            Some(current) if current.end_round() == round => current,
+            _ => return,
+        };
+        let mut next = match self.build_transition(current.epoch + 1, round + 1, round_hash, current.hash()) {
+            Ok(next) => next,
+            Err(e) => {
+                println!("[DEBUG] Epochs: failed to build epoch {}: {e}", current.epoch + 1);
+                return;
+            }
+        };
+        if let Some(key) = &self.signing_key {
+            if current.member_by_key(&key.verifying_key()).is_some() {
+                let signature = key.sign(&next.signing_message());
+                next.signatures.push(EpochSignature { public_key: key.verifying_key(), signature });
+            }
+        }
+        if let Err(e) = state_db.put_epoch_transition(&next) {
+            println!("[DEBUG] Epochs: failed to record epoch {}: {e}", next.epoch);
+            return;
+        }
+        self.install(&next);
+        println!(
+            "[DEBUG] Epochs: epoch {} starts at round {} with {} validators (committee 0x{})",
+            next.epoch, next.start_round, next.committee.len(), hex::encode(next.committee_hash)
+        );
+    }
+
+    /// Add an outgoing committee member's signature to a recorded transition
+    pub fn add_signature(&self, epoch: u64, public_key: VerifyingKey, signature: Signature) -> Result<EpochTransition, String> {
+        let state_db = self.ledger.state_db();
+        let mut transition = state_db.get_epoch_transition(epoch)
+            .ok_or_else(|| format!("Unknown epoch {epoch}"))?;
+        let previous = epoch.checked_sub(1)
+            .and_then(|e| state_db.get_epoch_transition(e))
+            .ok_or("The genesis epoch is not signed")?;
+        if previous.member_by_key(&public_key).is_none() {
+            return Err(format!("Signer is not on the committee of epoch {}", previous.epoch));
+        }
+        public_key.verify(&transition.signing_message(), &signature)
+            .map_err(|_| "Invalid epoch transition signature".to_string())?;
+        if !transition.signatures.iter().any(|s| s.public_key == public_key) {
+            transition.signatures.push(EpochSignature { public_key, signature });
+            state_db.put_epoch_transition(&transition)?;
+        }
+        Ok(transition)
+    }
+
+    pub fn transition(&self, epoch: u64) -> Option<EpochTransition> {
+        self.ledger.state_db().get_epoch_transition(epoch)
+    }
+
+    pub fn transitions(&self, from_epoch: u64, limit: usize) -> Vec<EpochTransition> {
+        self.ledger.state_db().epoch_transitions(from_epoch, limit)
+    }
+
+    fn build_transition(&self, epoch: u64, start_round: u64, seed: [u8; 32], previous_hash: [u8; 32]) -> Result<EpochTransition, String> {
+        let length_rounds = self.ledger.control().param_u64(parameters::CONSENSUS_EPOCH_LENGTH)?;
+        let validator_set = self.ledger.validator_set();
+        let validator_set = validator_set.lock().unwrap();
+        let committee: Vec<EpochMember> = validator_set.sample_epoch_committee(epoch, &seed)
+            .into_iter()
+            .filter_map(|address| validator_set.get_validator(&address))
+            .map(|v| EpochMember { address: v.address.clone(), public_key: v.public_key, stake: v.stake })
+            .collect();
+        Ok(EpochTransition {
+            epoch,
+            start_round,
+            length_rounds,
+            seed,
+            committee_hash: committee_hash(&committee),
+            committee,
+            previous_hash,
+            signatures: Vec::new(),
+        })
+    }
+
+    fn install(&self, transition: &EpochTransition) {
+        let validator_set = self.ledger.validator_set();
+        let mut validator_set = validator_set.lock().unwrap();
+        validator_set.set_epoch_committee(transition.to_committee());
+        self.persist(&validator_set);
+    }
+
+    fn persist(&self, validator_set: &ValidatorSet) {
+        if let Some(storage) = &self.storage {
+            if let Err(e) = storage.store_validator_set(validator_set) {
+                println!("[DEBUG] Epochs: failed to persist validator set: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::governance_executor::ChainControl;
+    use crate::storage::state::StateDB;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_epoch_transitions_are_deterministic_and_verifiable() {
+        let keys: Vec<SigningKey> = (41..45u8).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+        let node = |dir: &std::path::Path, key: &SigningKey| {
+            let state_db = Arc::new(StateDB::new(dir.to_str().unwrap()));
+            let mut set = ValidatorSet::new();
+            for (i, k) in keys.iter().enumerate() {
+                set.add_validator(Address::from_verifying_key(&k.verifying_key()), k.verifying_key(), 1_000 * (i as u64 + 1));
+            }
+            let validator_set = Arc::new(Mutex::new(set));
+            let control = Arc::new(ChainControl::new());
+            let ledger = Arc::new(StakingLedger::new(state_db, validator_set.clone(), control));
+            (EpochManager::new(ledger).with_signing_key(key.clone()), validator_set)
+        };
+        let (dir_a, dir_b) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let (node_a, set_a) = node(dir_a.path(), &keys[0]);
+        let (node_b, set_b) = node(dir_b.path(), &keys[1]);
+
+        // Local reputation differs between nodes but does not affect the committee
+        set_b.lock().unwrap().validators.values_mut().for_each(|v| v.reputation.reputation_score = 0.0);
+        let genesis = node_a.record_genesis().unwrap();
+        assert_eq!(node_b.record_genesis().unwrap(), genesis);
+        assert_eq!(genesis.end_round(), 100);
+
+        node_a.on_round_finalized(99, [7u8; 32]);
+        assert!(node_a.transition(1).is_none());
+        node_a.on_round_finalized(100, [7u8; 32]);
+        node_b.on_round_finalized(100, [7u8; 32]);
+        let (epoch_a, epoch_b) = (node_a.transition(1).unwrap(), node_b.transition(1).unwrap());
+        assert_eq!(epoch_a.committee_hash, epoch_b.committee_hash);
+        assert_eq!(set_a.lock().unwrap().select_committee(150).validators, set_b.lock().unwrap().select_committee(101).validators);
+
+        // A light client needs more than two thirds of the genesis committee's stake
+        assert!(follow_epochs(&genesis, std::slice::from_ref(&epoch_a)).is_err());
+        for signer in [&keys[1], &keys[2], &keys[3]] {
+            node_a.add_signature(1, signer.verifying_key(), signer.sign(&epoch_a.signing_message())).unwrap();
+        }
+        let outsider = SigningKey::from_bytes(&[99u8; 32]);
+        assert!(node_a.add_signature(1, outsider.verifying_key(), outsider.sign(&epoch_a.signing_message())).is_err());
+        let signed = node_a.transitions(0, 10);
+        assert_eq!(signed.len(), 2);
+        assert_eq!(follow_epochs(&genesis, &signed[1..]).unwrap().epoch, 1);
+    }
+}
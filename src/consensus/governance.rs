@@ -15,6 +15,21 @@ pub enum ProposalType {
     EmergencyResume { reason: String },
 }
 
+impl ProposalType {
+    /// Short name used in analytics and execution records
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProposalType::AddValidator { .. } => "add_validator",
+            ProposalType::RemoveValidator { .. } => "remove_validator",
+            ProposalType::SlashValidator { .. } => "slash_validator",
+            ProposalType::ParameterChange { .. } => "parameter_change",
+            ProposalType::UpgradeProtocol { .. } => "upgrade_protocol",
+            ProposalType::EmergencyPause { .. } => "emergency_pause",
+            ProposalType::EmergencyResume { .. } => "emergency_resume",
+        }
+    }
+}
+
 /// Governance proposal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Proposal {
@@ -29,6 +44,12 @@ pub struct Proposal {
     pub status: ProposalStatus,
     pub required_quorum: u64,
     pub required_approval_percentage: f64,
+    #[serde(default)]
+    pub snapshot_round: u64, // Round whose validator stake weights the votes
+    #[serde(default)]
+    pub voting_start_round: u64,
+    #[serde(default)]
+    pub voting_end_round: u64, // 0 for legacy wall-clock proposals
 }
 
 /// Proposal status
@@ -78,6 +99,18 @@ pub struct GovernanceConfig {
     pub proposal_fee: u64, // Fee required to submit a proposal
     pub emergency_threshold: u64, // Stake required for emergency proposals
     pub max_active_proposals: usize, // Maximum number of active proposals
+    #[serde(default = "default_min_voting_rounds")]
+    pub min_voting_rounds: u64, // Minimum voting window for on-chain proposals
+    #[serde(default = "default_max_voting_rounds")]
+    pub max_voting_rounds: u64, // Maximum voting window for on-chain proposals
+}
+
+fn default_min_voting_rounds() -> u64 {
+    10
+}
+
+fn default_max_voting_rounds() -> u64 {
+    10_000
 }
 
 impl Default for GovernanceConfig {
@@ -90,6 +123,8 @@ impl Default for GovernanceConfig {
             proposal_fee: 1000, // 1000 base units
             emergency_threshold: 100000, // 100k base units
             max_active_proposals: 10,
+            min_voting_rounds: default_min_voting_rounds(),
+            max_voting_rounds: default_max_voting_rounds(),
         }
     }
 }
@@ -138,6 +173,25 @@ pub struct GovernanceEvent {
     pub proposal_id: Option<String>,
 }
 
+/// Passed proposal waiting for its activation round
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduledExecution {
+    pub proposal_id: String,
+    pub activation_round: u64,
+}
+
+/// Audit record of an applied (or failed) proposal execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    pub proposal_id: String,
+    pub proposal_type: String,
+    pub activation_round: u64,
+    pub executed_at: u64, // Finalized round the execution was applied in
+    pub success: bool,
+    pub changes: Vec<String>, // Human-readable description of each state change
+    pub error: Option<String>,
+}
+
 /// Governance state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[derive(Default)]
@@ -152,6 +206,12 @@ pub struct GovernanceState {
     pub analytics: GovernanceAnalytics,
     pub events: Vec<GovernanceEvent>,
     pub voter_activity: HashMap<String, VoterActivity>,
+    #[serde(default)]
+    pub scheduled_executions: Vec<ScheduledExecution>,
+    #[serde(default)]
+    pub execution_records: Vec<ExecutionRecord>,
+    #[serde(default)]
+    pub stake_snapshots: HashMap<String, HashMap<String, u64>>, // proposal id -> voter -> stake
 }
 
 impl GovernanceState {
@@ -199,6 +259,9 @@ impl GovernanceState {
             status: ProposalStatus::Active,
             required_quorum: (self.total_stake as f64 * self.config.min_quorum_percentage) as u64,
             required_approval_percentage: self.config.min_approval_percentage,
+            snapshot_round: 0,
+            voting_start_round: 0,
+            voting_end_round: 0,
         };
 
         self.proposals.insert(proposal_id.clone(), proposal);
@@ -209,16 +272,7 @@ impl GovernanceState {
         self.analytics.total_proposals_created += 1;
         
         // Update proposal type distribution
-        let type_key = match &proposal_type {
-            ProposalType::AddValidator { .. } => "add_validator",
-            ProposalType::RemoveValidator { .. } => "remove_validator",
-            ProposalType::SlashValidator { .. } => "slash_validator",
-            ProposalType::ParameterChange { .. } => "parameter_change",
-            ProposalType::UpgradeProtocol { .. } => "upgrade_protocol",
-            ProposalType::EmergencyPause { .. } => "emergency_pause",
-            ProposalType::EmergencyResume { .. } => "emergency_resume",
-        };
-        *self.analytics.proposal_type_distribution.entry(type_key.to_string()).or_insert(0) += 1;
+        *self.analytics.proposal_type_distribution.entry(proposal_type.name().to_string()).or_insert(0) += 1;
 
         // Record event
         self.record_event(
@@ -366,7 +420,12 @@ impl GovernanceState {
         let yes_stake: u64 = votes.iter().filter(|v| v.vote).map(|v| v.stake_weight).sum();
         let no_stake: u64 = votes.iter().filter(|v| !v.vote).map(|v| v.stake_weight).sum();
 
-        let quorum_achieved = total_stake_voted as f64 >= self.config.min_quorum_percentage * self.total_stake as f64;
+        // On-chain proposals measure quorum against the stake snapshot taken at proposal time
+        let total_stake = match self.stake_snapshots.get(proposal_id) {
+            Some(snapshot) => snapshot.values().sum(),
+            None => self.total_stake,
+        };
+        let quorum_achieved = total_stake_voted as f64 >= self.config.min_quorum_percentage * total_stake as f64;
         let approval_percentage = if total_stake_voted > 0 {
             yes_stake as f64 / total_stake_voted as f64
         } else {
@@ -387,6 +446,170 @@ impl GovernanceState {
         }
     }
 
+    /// Record a proposal included on-chain. Voting opens on the next round and
+    /// votes are weighted by `stakes`, the validator stake at `snapshot_round`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_signed_proposal(
+        &mut self,
+        proposal_id: String,
+        proposer: String,
+        title: String,
+        description: String,
+        proposal_type: ProposalType,
+        voting_rounds: u64,
+        snapshot_round: u64,
+        stakes: HashMap<String, u64>,
+    ) -> Result<(), String> {
+        if self.proposals.contains_key(&proposal_id) {
+            return Err("Proposal already exists".to_string());
+        }
+        if stakes.get(&proposer).copied().unwrap_or(0) == 0 {
+            return Err("Proposer has no validator stake".to_string());
+        }
+        if self.active_proposals.len() >= self.config.max_active_proposals {
+            return Err("Maximum number of active proposals reached".to_string());
+        }
+        if voting_rounds < self.config.min_voting_rounds || voting_rounds > self.config.max_voting_rounds {
+            return Err(format!(
+                "Voting window must be between {} and {} rounds",
+                self.config.min_voting_rounds, self.config.max_voting_rounds
+            ));
+        }
+
+        let total_stake: u64 = stakes.values().sum();
+        let proposal = Proposal {
+            id: proposal_id.clone(),
+            title,
+            description,
+            proposer: proposer.clone(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            voting_start: 0,
+            voting_end: 0,
+            status: ProposalStatus::Active,
+            required_quorum: (total_stake as f64 * self.config.min_quorum_percentage) as u64,
+            required_approval_percentage: self.config.min_approval_percentage,
+            snapshot_round,
+            voting_start_round: snapshot_round + 1,
+            voting_end_round: snapshot_round + voting_rounds,
+            proposal_type,
+        };
+
+        *self.analytics.proposal_type_distribution.entry(proposal.proposal_type.name().to_string()).or_insert(0) += 1;
+        self.analytics.total_proposals_created += 1;
+        self.proposals.insert(proposal_id.clone(), proposal);
+        self.active_proposals.push(proposal_id.clone());
+        self.votes.insert(proposal_id.clone(), Vec::new());
+        self.stake_snapshots.insert(proposal_id.clone(), stakes);
+
+        self.record_event(
+            "proposal_created".to_string(),
+            proposer,
+            format!("Created proposal: {proposal_id} (snapshot round {snapshot_round})"),
+            Some(proposal_id),
+        );
+        Ok(())
+    }
+
+    /// Record a vote included on-chain in `round`; the weight comes from the proposal's stake snapshot
+    pub fn apply_signed_vote(
+        &mut self,
+        proposal_id: &str,
+        voter: String,
+        vote: bool,
+        reason: Option<String>,
+        round: u64,
+    ) -> Result<(), String> {
+        let proposal = self.proposals.get(proposal_id)
+            .ok_or("Proposal not found")?;
+
+        if proposal.status != ProposalStatus::Active {
+            return Err("Proposal is not active for voting".to_string());
+        }
+        if round < proposal.voting_start_round || round > proposal.voting_end_round {
+            return Err(format!(
+                "Round {round} is outside the voting window {}..={}",
+                proposal.voting_start_round, proposal.voting_end_round
+            ));
+        }
+        let stake_weight = self.stake_snapshots.get(proposal_id)
+            .and_then(|snapshot| snapshot.get(&voter))
+            .copied()
+            .unwrap_or(0);
+        if stake_weight == 0 {
+            return Err("Voter has no stake at the proposal snapshot".to_string());
+        }
+
+        let votes = self.votes.entry(proposal_id.to_string()).or_default();
+        if votes.iter().any(|v| v.voter == voter) {
+            return Err("Voter has already voted on this proposal".to_string());
+        }
+        votes.push(Vote {
+            proposal_id: proposal_id.to_string(),
+            voter: voter.clone(),
+            vote,
+            timestamp: round,
+            stake_weight,
+            reason,
+        });
+
+        self.analytics.total_votes_cast += 1;
+        self.update_voter_activity(&voter, stake_weight);
+        self.record_event(
+            "vote_cast".to_string(),
+            voter,
+            format!("Voted {} on proposal {} in round {round}", if vote { "YES" } else { "NO" }, proposal_id),
+            Some(proposal_id.to_string()),
+        );
+        Ok(())
+    }
+
+    /// Tally on-chain proposals whose voting window ends at or before the finalized `round`.
+    /// Returns the ids that passed.
+    pub fn tally_closed_proposals(&mut self, round: u64) -> Vec<String> {
+        let mut closed: Vec<String> = self.active_proposals.iter()
+            .filter(|id| {
+                self.proposals.get(*id)
+                    .map(|p| p.voting_end_round > 0 && round >= p.voting_end_round)
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect();
+        closed.sort();
+
+        let mut passed = Vec::new();
+        for proposal_id in closed {
+            let results = self.calculate_voting_results(&proposal_id);
+            let (status, event_type) = if results.passed {
+                self.analytics.total_proposals_passed += 1;
+                passed.push(proposal_id.clone());
+                (ProposalStatus::Passed, "proposal_passed")
+            } else if results.quorum_achieved {
+                self.analytics.total_proposals_failed += 1;
+                (ProposalStatus::Failed, "proposal_failed")
+            } else {
+                (ProposalStatus::Expired, "proposal_expired")
+            };
+            if let Some(proposal) = self.proposals.get_mut(&proposal_id) {
+                proposal.status = status;
+            }
+            self.active_proposals.retain(|id| id != &proposal_id);
+            self.record_event(
+                event_type.to_string(),
+                "system".to_string(),
+                format!(
+                    "Proposal {} closed at round {round} with {}% approval",
+                    proposal_id,
+                    (results.approval_percentage * 100.0) as u64
+                ),
+                Some(proposal_id),
+            );
+        }
+        passed
+    }
+
     /// Execute a passed proposal
     pub fn execute_proposal(&mut self, proposal_id: &str) -> Result<(), String> {
         let proposal = self.proposals.get(proposal_id)
@@ -406,6 +629,66 @@ impl GovernanceState {
         Ok(())
     }
 
+    /// Schedule a passed proposal to take effect at `activation_round`
+    pub fn schedule_execution(&mut self, proposal_id: &str, activation_round: u64) -> Result<(), String> {
+        let proposal = self.proposals.get(proposal_id)
+            .ok_or("Proposal not found")?;
+
+        if proposal.status != ProposalStatus::Passed {
+            return Err("Proposal has not been passed".to_string());
+        }
+        if self.scheduled_executions.iter().any(|s| s.proposal_id == proposal_id) {
+            return Err("Proposal is already scheduled for execution".to_string());
+        }
+
+        self.scheduled_executions.push(ScheduledExecution {
+            proposal_id: proposal_id.to_string(),
+            activation_round,
+        });
+        self.record_event(
+            "proposal_scheduled".to_string(),
+            "system".to_string(),
+            format!("Scheduled for activation at round {activation_round}"),
+            Some(proposal_id.to_string()),
+        );
+        Ok(())
+    }
+
+    /// Remove and return executions due at or before `round`, in deterministic order
+    pub fn take_due_executions(&mut self, round: u64) -> Vec<(ScheduledExecution, ProposalType)> {
+        let (mut due, pending): (Vec<_>, Vec<_>) = self.scheduled_executions.drain(..)
+            .partition(|s| s.activation_round <= round);
+        self.scheduled_executions = pending;
+        due.sort_by(|a, b| (a.activation_round, &a.proposal_id).cmp(&(b.activation_round, &b.proposal_id)));
+        due.into_iter()
+            .filter_map(|s| {
+                let proposal_type = self.proposals.get(&s.proposal_id)?.proposal_type.clone();
+                Some((s, proposal_type))
+            })
+            .collect()
+    }
+
+    /// Store the outcome of an execution; successful ones mark the proposal Executed
+    pub fn record_execution(&mut self, record: ExecutionRecord) {
+        if record.success {
+            if let Some(proposal) = self.proposals.get_mut(&record.proposal_id) {
+                proposal.status = ProposalStatus::Executed;
+            }
+            self.executed_proposals.push(record.proposal_id.clone());
+        }
+        let details = match &record.error {
+            Some(error) => format!("Execution at round {} failed: {error}", record.activation_round),
+            None => format!("Executed at round {}: {}", record.activation_round, record.changes.join("; ")),
+        };
+        self.record_event(
+            if record.success { "proposal_executed" } else { "proposal_execution_failed" }.to_string(),
+            "system".to_string(),
+            details,
+            Some(record.proposal_id.clone()),
+        );
+        self.execution_records.push(record);
+    }
+
     /// Cancel a proposal (only proposer or emergency threshold can cancel)
     pub fn cancel_proposal(&mut self, proposal_id: &str, canceller: &str, canceller_stake: u64) -> Result<(), String> {
         let proposal = self.proposals.get(proposal_id)
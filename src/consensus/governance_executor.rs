@@ -0,0 +1,605 @@
+use crate::consensus::governance::{ExecutionRecord, GovernanceState, ProposalType};
+use crate::consensus::governance_tx::{GovernanceAction, GovernanceTx};
+use crate::consensus::parameters::{self, ParamValue, ParameterRegistry};
+use crate::consensus::slashing::SlashingEngine;
+use crate::consensus::validator_set::ValidatorSet;
+use crate::core::address::Address;
+use crate::core::types::Transaction;
+use crate::storage::persistent::PersistentStorage;
+use ed25519_dalek::VerifyingKey;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Rounds between scheduling a proposal and applying it, so every node switches together
+pub const DEFAULT_ACTIVATION_DELAY_ROUNDS: u64 = 10;
+
+/// Chain-wide state controlled by governance (emergency pause, protocol version, parameters)
+#[derive(Debug, Default)]
+pub struct ChainControl {
+    paused: AtomicBool,
+    pause_reason: Mutex<Option<String>>,
+    protocol_version: Mutex<Option<String>>,
+    parameters: Mutex<ParameterRegistry>,
+}
+
+impl ChainControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn pause_reason(&self) -> Option<String> {
+        self.pause_reason.lock().unwrap().clone()
+    }
+
+    pub fn pause(&self, reason: &str) {
+        *self.pause_reason.lock().unwrap() = Some(reason.to_string());
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+        *self.pause_reason.lock().unwrap() = None;
+    }
+
+    /// Whether `tx` may be admitted and included now. While paused only governance
+    /// transactions go through, so a resume proposal can still be voted and executed.
+    pub fn admits(&self, tx: &Transaction) -> bool {
+        !self.is_paused() || GovernanceTx::from_payload(&tx.payload).is_some()
+    }
+
+    /// Protocol version activated by the last executed upgrade proposal
+    pub fn protocol_version(&self) -> Option<String> {
+        self.protocol_version.lock().unwrap().clone()
+    }
+
+    pub fn set_protocol_version(&self, version: &str) {
+        *self.protocol_version.lock().unwrap() = Some(version.to_string());
+    }
+
+    /// Snapshot of the protocol parameter registry
+    pub fn parameters(&self) -> ParameterRegistry {
+        self.parameters.lock().unwrap().clone()
+    }
+
+    /// Replace the registry, e.g. with the one loaded from storage at startup
+    pub fn set_parameters(&self, registry: ParameterRegistry) {
+        *self.parameters.lock().unwrap() = registry;
+    }
+
+    /// Current value of a numeric protocol parameter
+    pub fn param_u64(&self, name: &str) -> Result<u64, String> {
+        self.parameters.lock().unwrap().get_u64(name)
+    }
+}
+
+/// Applies passed governance proposals to live chain state at their activation round
+pub struct GovernanceExecutor {
+    governance_state: Arc<Mutex<GovernanceState>>,
+    validator_set: Arc<Mutex<ValidatorSet>>,
+    asset_whitelist: Arc<Mutex<Vec<String>>>,
+    control: Arc<ChainControl>,
+    activation_delay_rounds: u64,
+    current_round: AtomicU64,
+    storage: Option<Arc<PersistentStorage>>,
+    slashing_engine: Option<Arc<SlashingEngine>>,
+    included: Mutex<HashMap<[u8; 32], Vec<GovernanceTx>>>,
+}
+
+impl GovernanceExecutor {
+    pub fn new(
+        governance_state: Arc<Mutex<GovernanceState>>,
+        validator_set: Arc<Mutex<ValidatorSet>>,
+        asset_whitelist: Arc<Mutex<Vec<String>>>,
+        control: Arc<ChainControl>,
+    ) -> Self {
+        Self {
+            governance_state,
+            validator_set,
+            asset_whitelist,
+            control,
+            activation_delay_rounds: DEFAULT_ACTIVATION_DELAY_ROUNDS,
+            current_round: AtomicU64::new(0),
+            storage: None,
+            slashing_engine: None,
+            included: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Persist the parameter registry whenever governance changes it
+    pub fn with_storage(mut self, storage: Arc<PersistentStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Slash validators through the engine that handles evidence, when a SlashValidator proposal passes
+    pub fn with_slashing_engine(mut self, engine: Arc<SlashingEngine>) -> Self {
+        self.slashing_engine = Some(engine);
+        self
+    }
+
+    pub fn with_activation_delay(mut self, rounds: u64) -> Self {
+        self.activation_delay_rounds = rounds;
+        self
+    }
+
+    pub fn control(&self) -> Arc<ChainControl> {
+        self.control.clone()
+    }
+
+    /// Latest finalized round seen by the executor
+    pub fn current_round(&self) -> u64 {
+        self.current_round.load(Ordering::SeqCst)
+    }
+
+    /// Rounds between the close of voting and activation.
+    /// Emergency pause/resume activate on the next round.
+    fn activation_delay(&self, proposal_type: &ProposalType) -> u64 {
+        match proposal_type {
+            ProposalType::EmergencyPause { .. } | ProposalType::EmergencyResume { .. } => 1,
+            _ => self.activation_delay_rounds,
+        }
+    }
+
+    /// Hold a signed proposal or vote included in `block_id` until that block's round reaches quorum
+    pub fn include_governance_tx(&self, block_id: [u8; 32], gov_tx: &GovernanceTx) -> Result<(), String> {
+        gov_tx.verify()?;
+        self.included.lock().unwrap().entry(block_id).or_default().push(gov_tx.clone());
+        Ok(())
+    }
+
+    /// Apply the governance transactions of the blocks finalized in `round`, in block order
+    pub fn apply_included(&self, round: u64, block_ids: &[[u8; 32]]) {
+        let txs: Vec<GovernanceTx> = {
+            let mut included = self.included.lock().unwrap();
+            block_ids.iter().filter_map(|id| included.remove(id)).flatten().collect()
+        };
+        for gov_tx in txs {
+            if let Err(e) = self.apply_governance_tx(&gov_tx, round) {
+                println!("[DEBUG] Governance: tx from {} in round {round} rejected: {e}", gov_tx.signer());
+            }
+        }
+    }
+
+    /// Apply a signed proposal or vote included in `round`. Proposals snapshot
+    /// the active validator stake at that round; votes count toward it.
+    pub fn apply_governance_tx(&self, gov_tx: &GovernanceTx, round: u64) -> Result<(), String> {
+        gov_tx.verify()?;
+        let signer = gov_tx.signer().as_str().to_string();
+        match &gov_tx.action {
+            GovernanceAction::Propose { title, description, proposal_type, voting_rounds } => {
+                let stakes: HashMap<String, u64> = self.validator_set.lock().unwrap()
+                    .get_active_validators()
+                    .into_iter()
+                    .filter(|v| v.stake > 0)
+                    .map(|v| (v.address.as_str().to_string(), v.stake))
+                    .collect();
+                self.governance_state.lock().unwrap().apply_signed_proposal(
+                    gov_tx.proposal_id(),
+                    signer,
+                    title.clone(),
+                    description.clone(),
+                    proposal_type.clone(),
+                    *voting_rounds,
+                    round,
+                    stakes,
+                )
+            }
+            GovernanceAction::Vote { proposal_id, approve, reason } => {
+                self.governance_state.lock().unwrap()
+                    .apply_signed_vote(proposal_id, signer, *approve, reason.clone(), round)
+            }
+        }
+    }
+
+    /// Close voting windows ending at `round`, then apply every execution due.
+    /// Called once per finalized round.
+    pub fn on_round_finalized(&self, round: u64) -> Vec<ExecutionRecord> {
+        self.current_round.fetch_max(round, Ordering::SeqCst);
+        self.control.parameters.lock().unwrap().advance_to(round);
+        // Release the governance lock before touching the validator set
+        let due = {
+            let mut governance = self.governance_state.lock().unwrap();
+            // Activation depends only on the proposal's voting window, so every node schedules it identically
+            for proposal_id in governance.tally_closed_proposals(round) {
+                let proposal = &governance.proposals[&proposal_id];
+                let activation_round = proposal.voting_end_round + self.activation_delay(&proposal.proposal_type);
+                match governance.schedule_execution(&proposal_id, activation_round) {
+                    Ok(()) => println!("[DEBUG] Governance: {proposal_id} passed at round {round}, activates at round {activation_round}"),
+                    Err(e) => println!("[DEBUG] Governance: failed to schedule {proposal_id}: {e}"),
+                }
+            }
+            governance.take_due_executions(round)
+        };
+        let mut records = Vec::with_capacity(due.len());
+        for (scheduled, proposal_type) in due {
+            let result = self.apply(&scheduled.proposal_id, &proposal_type, round);
+            let record = ExecutionRecord {
+                proposal_id: scheduled.proposal_id,
+                proposal_type: proposal_type.name().to_string(),
+                activation_round: scheduled.activation_round,
+                executed_at: round,
+                success: result.is_ok(),
+                changes: result.clone().unwrap_or_default(),
+                error: result.err(),
+            };
+            println!("[DEBUG] Governance: executed {} at round {round}: success={}", record.proposal_id, record.success);
+            self.governance_state.lock().unwrap().record_execution(record.clone());
+            records.push(record);
+        }
+        records
+    }
+
+    /// Deterministic state change for one proposal; returns the applied changes
+    fn apply(&self, proposal_id: &str, proposal_type: &ProposalType, round: u64) -> Result<Vec<String>, String> {
+        match proposal_type {
+            ProposalType::AddValidator { address, public_key } => {
+                let address = Address::parse(address).map_err(|e| e.to_string())?;
+                let public_key = parse_verifying_key(public_key)?;
+                if Address::from_verifying_key(&public_key) != address {
+                    return Err("Validator public key does not match address".to_string());
+                }
+                let mut validator_set = self.validator_set.lock().unwrap();
+                if validator_set.get_validator(&address).is_some() {
+                    return Err(format!("Validator {address} already exists"));
+                }
+                // Stake is bonded separately; governance only admits the key
+                validator_set.add_validator(address.clone(), public_key, 0);
+                Ok(vec![format!("added validator {address}")])
+            }
+            ProposalType::RemoveValidator { address } => {
+                let address = Address::parse(address).map_err(|e| e.to_string())?;
+                let mut validator_set = self.validator_set.lock().unwrap();
+                if validator_set.get_validator(&address).is_none() {
+                    return Err(format!("Unknown validator {address}"));
+                }
+                validator_set.remove_validator(&address);
+                Ok(vec![format!("removed validator {address}")])
+            }
+            ProposalType::SlashValidator { address, reason } => {
+                // Same penalty, jailing and record as evidence-based slashing
+                let address = Address::parse(address).map_err(|e| e.to_string())?;
+                let engine = self.slashing_engine.as_ref().ok_or("No slashing engine attached")?;
+                let record = engine.apply_governance_slash(&address, proposal_id, round)?;
+                Ok(vec![format!(
+                    "slashed validator {address} by {} and jailed until round {}: {reason}",
+                    record.stake_slashed, record.jailed_until
+                )])
+            }
+            ProposalType::ParameterChange { parameter, new_value } => {
+                self.apply_parameter(parameter, new_value, round)
+            }
+            ProposalType::UpgradeProtocol { version, .. } => {
+                self.control.set_protocol_version(version);
+                Ok(vec![format!("activated protocol version {version}")])
+            }
+            ProposalType::EmergencyPause { reason } => {
+                self.control.pause(reason);
+                Ok(vec![format!("paused chain: {reason}")])
+            }
+            ProposalType::EmergencyResume { reason } => {
+                self.control.resume();
+                Ok(vec![format!("resumed chain: {reason}")])
+            }
+        }
+    }
+
+    fn apply_parameter(&self, parameter: &str, value: &str, round: u64) -> Result<Vec<String>, String> {
+        let value = {
+            let mut registry = self.control.parameters.lock().unwrap();
+            let typed = registry.set_from_str(parameter, value, round)?;
+            if let Some(storage) = &self.storage {
+                if let Err(e) = storage.store_parameter_registry(&registry) {
+                    println!("[DEBUG] Governance: failed to persist parameters: {e}");
+                }
+            }
+            typed
+        };
+
+        // Push the new value into the components that cache it
+        match (parameter, &value) {
+            (parameters::COMMITTEE_SIZE, ParamValue::U64(v)) => {
+                let mut validator_set = self.validator_set.lock().unwrap();
+                let mut config = validator_set.quorum_manager.config.clone();
+                config.committee_size = *v as usize;
+                validator_set.update_committee_config(config);
+            }
+            (parameters::COMMITTEE_MIN_QUORUM, ParamValue::U64(v)) => {
+                let mut validator_set = self.validator_set.lock().unwrap();
+                let mut config = validator_set.quorum_manager.config.clone();
+                config.min_quorum_size = *v as usize;
+                validator_set.update_committee_config(config);
+            }
+            (parameters::STAKING_MIN_SELF_BOND, ParamValue::U64(v)) => {
+                let mut validator_set = self.validator_set.lock().unwrap();
+                let mut config = validator_set.quorum_manager.config.clone();
+                config.min_self_bond = *v;
+                validator_set.update_committee_config(config);
+            }
+            (parameters::ASSET_WHITELIST, ParamValue::List(assets)) => {
+                *self.asset_whitelist.lock().unwrap() = assets.clone();
+            }
+            // Governance rules are cached in GovernanceState's config
+            (parameters::GOVERNANCE_MIN_QUORUM_BPS, ParamValue::U64(v)) => {
+                self.governance_state.lock().unwrap().config.min_quorum_percentage = *v as f64 / 10_000.0;
+            }
+            (parameters::GOVERNANCE_MIN_APPROVAL_BPS, ParamValue::U64(v)) => {
+                self.governance_state.lock().unwrap().config.min_approval_percentage = *v as f64 / 10_000.0;
+            }
+            (parameters::GOVERNANCE_PROPOSAL_FEE, ParamValue::U64(v)) => {
+                self.governance_state.lock().unwrap().config.proposal_fee = *v;
+            }
+            (parameters::GOVERNANCE_EMERGENCY_THRESHOLD, ParamValue::U64(v)) => {
+                self.governance_state.lock().unwrap().config.emergency_threshold = *v;
+            }
+            (parameters::GOVERNANCE_MAX_ACTIVE_PROPOSALS, ParamValue::U64(v)) => {
+                self.governance_state.lock().unwrap().config.max_active_proposals = *v as usize;
+            }
+            (parameters::GOVERNANCE_MIN_VOTING_ROUNDS, ParamValue::U64(v)) => {
+                self.governance_state.lock().unwrap().config.min_voting_rounds = *v;
+            }
+            (parameters::GOVERNANCE_MAX_VOTING_ROUNDS, ParamValue::U64(v)) => {
+                self.governance_state.lock().unwrap().config.max_voting_rounds = *v;
+            }
+            _ => {}
+        }
+        Ok(vec![format!("set {parameter} = {value} from round {round}")])
+    }
+}
+
+fn parse_verifying_key(hex_key: &str) -> Result<VerifyingKey, String> {
+    let bytes: [u8; 32] = hex::decode(hex_key)
+        .map_err(|_| "Invalid public key hex".to_string())?
+        .try_into()
+        .map_err(|_| "Public key must be 32 bytes".to_string())?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| "Invalid public key".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::governance::{Proposal, ProposalStatus, Vote};
+    use crate::consensus::governance_tx::GovernanceAction;
+    use crate::core::types::ShardId;
+    use ed25519_dalek::SigningKey;
+
+    /// Open proposal with a single yes vote; it passes when `voting_end_round` is tallied
+    fn passed(governance: &Arc<Mutex<GovernanceState>>, id: &str, proposal_type: ProposalType, voting_end_round: u64) {
+        let mut governance = governance.lock().unwrap();
+        governance.proposals.insert(id.to_string(), Proposal {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: String::new(),
+            proposer: "proposer".to_string(),
+            proposal_type,
+            timestamp: 0,
+            voting_start: 0,
+            voting_end: 0,
+            status: ProposalStatus::Active,
+            required_quorum: 0,
+            required_approval_percentage: 0.0,
+            snapshot_round: 0,
+            voting_start_round: 0,
+            voting_end_round,
+        });
+        governance.active_proposals.push(id.to_string());
+        governance.votes.insert(id.to_string(), vec![Vote {
+            proposal_id: id.to_string(),
+            voter: "voter".to_string(),
+            vote: true,
+            timestamp: 0,
+            stake_weight: 1,
+            reason: None,
+        }]);
+    }
+
+    fn executor() -> (GovernanceExecutor, Arc<Mutex<GovernanceState>>, Arc<Mutex<ValidatorSet>>) {
+        let governance = Arc::new(Mutex::new(GovernanceState::default()));
+        let validator_set = Arc::new(Mutex::new(ValidatorSet::new()));
+        let executor = GovernanceExecutor::new(
+            governance.clone(),
+            validator_set.clone(),
+            Arc::new(Mutex::new(vec!["USD".to_string()])),
+            Arc::new(ChainControl::new()),
+        ).with_activation_delay(5);
+        (executor, governance, validator_set)
+    }
+
+    #[test]
+    fn test_applies_at_activation_round() {
+        let (executor, governance, validator_set) = executor();
+        let key = SigningKey::from_bytes(&[4u8; 32]).verifying_key();
+        let address = Address::from_verifying_key(&key);
+        passed(&governance, "proposal_1", ProposalType::AddValidator {
+            address: address.to_string(),
+            public_key: hex::encode(key.to_bytes()),
+        }, 10);
+
+        // Scheduled by the tally at voting_end_round + delay, whichever round closes it
+        assert!(executor.on_round_finalized(12).is_empty());
+        assert_eq!(governance.lock().unwrap().scheduled_executions[0].activation_round, 15);
+        assert!(governance.lock().unwrap().schedule_execution("proposal_1", 20).is_err());
+
+        assert!(executor.on_round_finalized(14).is_empty());
+        assert!(validator_set.lock().unwrap().get_validator(&address).is_none());
+
+        let records = executor.on_round_finalized(15);
+        assert_eq!(records.len(), 1);
+        assert!(records[0].success);
+        assert_eq!(records[0].executed_at, 15);
+        assert!(validator_set.lock().unwrap().get_validator(&address).is_some());
+        let governance = governance.lock().unwrap();
+        assert_eq!(governance.proposals["proposal_1"].status, ProposalStatus::Executed);
+        assert_eq!(governance.execution_records.len(), 1);
+    }
+
+    #[test]
+    fn test_emergency_pause_and_failed_parameter() {
+        let (executor, governance, _) = executor();
+        passed(&governance, "proposal_1", ProposalType::EmergencyPause { reason: "incident".to_string() }, 1);
+        passed(&governance, "proposal_2", ProposalType::ParameterChange {
+            parameter: "committee.unknown".to_string(),
+            new_value: "1".to_string(),
+        }, 1);
+
+        executor.on_round_finalized(1);
+        assert!(!executor.control().is_paused());
+        executor.on_round_finalized(2);
+        assert!(executor.control().is_paused());
+
+        let records = executor.on_round_finalized(6);
+        assert!(!records[0].success);
+        assert_eq!(governance.lock().unwrap().proposals["proposal_2"].status, ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_signed_votes_weighted_by_snapshot_stake() {
+        let (executor, governance, validator_set) = executor();
+        let big = SigningKey::from_bytes(&[7u8; 32]);
+        let small = SigningKey::from_bytes(&[8u8; 32]);
+        for (key, stake) in [(&big, 700), (&small, 300)] {
+            validator_set.lock().unwrap()
+                .add_validator(Address::from_verifying_key(&key.verifying_key()), key.verifying_key(), stake);
+        }
+
+        let propose = GovernanceTx::sign(
+            GovernanceAction::Propose {
+                title: "Pause".to_string(),
+                description: "Incident".to_string(),
+                proposal_type: ProposalType::EmergencyPause { reason: "incident".to_string() },
+                voting_rounds: 10,
+            },
+            1,
+            ShardId(0),
+            &small,
+        );
+        executor.apply_governance_tx(&propose, 0).unwrap();
+        let proposal_id = propose.proposal_id();
+
+        // Stake added after the snapshot does not count
+        let late = SigningKey::from_bytes(&[9u8; 32]);
+        validator_set.lock().unwrap()
+            .add_validator(Address::from_verifying_key(&late.verifying_key()), late.verifying_key(), 10_000);
+
+        let vote = |key: &SigningKey, approve: bool| GovernanceTx::sign(
+            GovernanceAction::Vote { proposal_id: proposal_id.clone(), approve, reason: None },
+            2,
+            ShardId(0),
+            key,
+        );
+        assert!(executor.apply_governance_tx(&vote(&late, false), 1).is_err());
+        executor.apply_governance_tx(&vote(&big, true), 1).unwrap();
+        executor.apply_governance_tx(&vote(&small, false), 1).unwrap();
+        assert!(executor.apply_governance_tx(&vote(&small, true), 1).is_err());
+
+        executor.on_round_finalized(9);
+        assert_eq!(governance.lock().unwrap().proposals[&proposal_id].status, ProposalStatus::Active);
+        executor.on_round_finalized(10);
+        let governance = governance.lock().unwrap();
+        assert_eq!(governance.proposals[&proposal_id].status, ProposalStatus::Passed);
+        assert_eq!(governance.calculate_voting_results(&proposal_id).yes_stake, 700);
+    }
+
+    #[test]
+    fn test_included_proposal_uses_including_round() {
+        let (executor, governance, validator_set) = executor();
+        let key = SigningKey::from_bytes(&[6u8; 32]);
+        validator_set.lock().unwrap()
+            .add_validator(Address::from_verifying_key(&key.verifying_key()), key.verifying_key(), 500);
+        let propose = GovernanceTx::sign(
+            GovernanceAction::Propose {
+                title: "Pause".to_string(),
+                description: "Incident".to_string(),
+                proposal_type: ProposalType::EmergencyPause { reason: "incident".to_string() },
+                voting_rounds: 10,
+            },
+            1,
+            ShardId(0),
+            &key,
+        );
+        executor.include_governance_tx([1u8; 32], &propose).unwrap();
+        // This node has seen fewer rounds than the one that finalizes the block
+        executor.on_round_finalized(3);
+        executor.apply_included(5, &[[1u8; 32]]);
+
+        let governance = governance.lock().unwrap();
+        let proposal = &governance.proposals[&propose.proposal_id()];
+        assert_eq!(proposal.snapshot_round, 5);
+        assert_eq!(proposal.voting_start_round, 6);
+        assert_eq!(proposal.voting_end_round, 15);
+    }
+
+    #[test]
+    fn test_slash_proposal_goes_through_slashing_engine() {
+        use crate::consensus::staking::StakingLedger;
+        use crate::storage::state::StateDB;
+
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(StateDB::new(dir.path().to_str().unwrap()));
+        let governance = Arc::new(Mutex::new(GovernanceState::default()));
+        let validator_set = Arc::new(Mutex::new(ValidatorSet::new()));
+        let control = Arc::new(ChainControl::new());
+        let ledger = Arc::new(StakingLedger::new(state_db.clone(), validator_set.clone(), control.clone()));
+        let executor = GovernanceExecutor::new(
+            governance.clone(),
+            validator_set.clone(),
+            Arc::new(Mutex::new(vec!["USD".to_string()])),
+            control,
+        ).with_activation_delay(5).with_slashing_engine(Arc::new(SlashingEngine::new(ledger)));
+
+        let key = SigningKey::from_bytes(&[10u8; 32]).verifying_key();
+        let address = Address::from_verifying_key(&key);
+        validator_set.lock().unwrap().add_validator(address.clone(), key, 1_000);
+        passed(&governance, "proposal_1", ProposalType::SlashValidator {
+            address: address.to_string(),
+            reason: "misconduct".to_string(),
+        }, 10);
+        executor.on_round_finalized(10);
+        let records = executor.on_round_finalized(15);
+        assert!(records[0].success, "{:?}", records[0].error);
+
+        // Penalized, jailed and recorded like evidence, at the governance fraction and jail length
+        let slashes = state_db.slash_records_of(address.as_str());
+        assert_eq!(slashes.len(), 1);
+        assert_eq!(slashes[0].offense, crate::consensus::slashing::Offense::Governance);
+        assert_eq!(slashes[0].stake_slashed, 50);
+        assert_eq!(slashes[0].reporter_reward, 0);
+        let validator_set = validator_set.lock().unwrap();
+        let validator = validator_set.get_validator(&address).unwrap();
+        assert_eq!(validator.stake, 950);
+        assert_eq!(validator.jailed_until, Some(10_015));
+    }
+
+    #[test]
+    fn test_parameter_change_is_typed_and_versioned() {
+        let (executor, governance, validator_set) = executor();
+        passed(&governance, "proposal_1", ProposalType::ParameterChange {
+            parameter: parameters::COMMITTEE_SIZE.to_string(),
+            new_value: "30".to_string(),
+        }, 1);
+        passed(&governance, "proposal_2", ProposalType::ParameterChange {
+            parameter: parameters::BLOCK_MAX_TXS.to_string(),
+            new_value: "0".to_string(),
+        }, 1);
+        passed(&governance, "proposal_3", ProposalType::ParameterChange {
+            parameter: parameters::GOVERNANCE_MIN_APPROVAL_BPS.to_string(),
+            new_value: "7500".to_string(),
+        }, 1);
+        executor.on_round_finalized(1);
+
+        let records = executor.on_round_finalized(6);
+        assert!(records[0].success);
+        assert!(!records[1].success);
+        assert!(records[2].success);
+        assert_eq!(governance.lock().unwrap().config.min_approval_percentage, 0.75);
+        let control = executor.control();
+        assert_eq!(control.param_u64(parameters::COMMITTEE_SIZE), Ok(30));
+        assert_eq!(control.param_u64(parameters::BLOCK_MAX_TXS), Ok(1_000));
+        assert_eq!(control.parameters().get_at(parameters::COMMITTEE_SIZE, 4), Some(ParamValue::U64(20)));
+        assert_eq!(validator_set.lock().unwrap().quorum_manager.config.committee_size, 30);
+    }
+}
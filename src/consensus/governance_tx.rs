@@ -0,0 +1,151 @@
+use crate::consensus::governance::ProposalType;
+use crate::core::address::Address;
+use crate::core::types::{ShardId, Transaction};
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+
+/// Payload prefix identifying a governance action carried in `Transaction.payload`
+pub const GOVERNANCE_PAYLOAD_TAG: &[u8] = b"FDG:GOV:1";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GovernanceAction {
+    /// Open a proposal; voting runs for `voting_rounds` rounds after inclusion
+    Propose {
+        title: String,
+        description: String,
+        proposal_type: ProposalType,
+        voting_rounds: u64,
+    },
+    /// Cast a vote; the weight is the voter's stake at the proposal snapshot
+    Vote {
+        proposal_id: String,
+        approve: bool,
+        reason: Option<String>,
+    },
+}
+
+/// Governance action signed by a validator key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceTx {
+    pub action: GovernanceAction,
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+    pub public_key: VerifyingKey,
+    pub signature: Signature,
+}
+
+impl GovernanceTx {
+    /// Canonical message the signer signs
+    pub fn signing_message(action: &GovernanceAction, findag_time: u64, shard_id: ShardId) -> Vec<u8> {
+        let mut message = GOVERNANCE_PAYLOAD_TAG.to_vec();
+        message.extend_from_slice(&bincode::serialize(action).expect("governance action serialization"));
+        message.extend_from_slice(&findag_time.to_be_bytes());
+        message.extend_from_slice(&shard_id.0.to_be_bytes());
+        message
+    }
+
+    pub fn sign(action: GovernanceAction, findag_time: u64, shard_id: ShardId, signing_key: &SigningKey) -> Self {
+        let signature = signing_key.sign(&Self::signing_message(&action, findag_time, shard_id));
+        Self { action, findag_time, shard_id, public_key: signing_key.verifying_key(), signature }
+    }
+
+    /// Address of the validator that signed the action
+    pub fn signer(&self) -> Address {
+        Address::from_verifying_key(&self.public_key)
+    }
+
+    pub fn verify(&self) -> Result<(), String> {
+        let message = Self::signing_message(&self.action, self.findag_time, self.shard_id);
+        self.public_key.verify(&message, &self.signature)
+            .map_err(|_| "Invalid governance signature".to_string())
+    }
+
+    /// Id a `Propose` action registers under, derived from the signed content
+    pub fn proposal_id(&self) -> String {
+        let digest = Sha256::digest(Self::signing_message(&self.action, self.findag_time, self.shard_id));
+        let mut id = [0u8; 8];
+        id.copy_from_slice(&digest[..8]);
+        // Keep ids below 2^53 so JSON clients can round-trip them
+        format!("proposal_{}", u64::from_be_bytes(id) >> 11)
+    }
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = GOVERNANCE_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&bincode::serialize(self).expect("governance tx serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a governance action
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(GOVERNANCE_PAYLOAD_TAG)?;
+        Some(bincode::deserialize(body).map_err(|e| format!("Invalid governance payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the signed body
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|gov| {
+            if gov.shard_id != tx.shard_id
+                || gov.findag_time != tx.findag_time
+                || gov.public_key != tx.public_key
+                || gov.signer() != tx.from
+            {
+                return Err("Governance envelope does not match its payload".to_string());
+            }
+            Ok(gov)
+        }))
+    }
+
+    /// Wrap in a zero-amount `Transaction` from the signer to itself
+    pub fn to_transaction(&self) -> Transaction {
+        let address = self.signer();
+        let payload = self.to_payload();
+        Transaction {
+            from: address.clone(),
+            to: address,
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature: self.signature,
+            public_key: self.public_key,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_vote_roundtrip() {
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let vote = GovernanceTx::sign(
+            GovernanceAction::Vote { proposal_id: "proposal_1".to_string(), approve: true, reason: None },
+            7,
+            ShardId(0),
+            &key,
+        );
+        assert!(vote.verify().is_ok());
+
+        let envelope = vote.to_transaction();
+        let decoded = GovernanceTx::from_transaction(&envelope).unwrap().unwrap();
+        assert!(decoded.verify().is_ok());
+        assert_eq!(decoded.signer(), Address::from_verifying_key(&key.verifying_key()));
+
+        // A vote re-signed by another key cannot pose as the original voter
+        let mut forged = envelope.clone();
+        forged.from = Address::from_verifying_key(&SigningKey::from_bytes(&[6u8; 32]).verifying_key());
+        assert!(GovernanceTx::from_transaction(&forged).unwrap().is_err());
+    }
+}
@@ -3,4 +3,12 @@ pub mod governance;
 pub mod round_finalizer;
 pub mod mempool;
 pub mod round_aggregator;
-pub mod roundchain; 
\ No newline at end of file
+pub mod roundchain;
+pub mod governance_executor;
+pub mod governance_tx;
+pub mod parameters;
+pub mod staking;
+pub mod slashing;
+pub mod rewards;
+pub mod validator_lifecycle;
+pub mod epoch;
\ No newline at end of file
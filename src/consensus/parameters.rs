@@ -0,0 +1,518 @@
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+pub const BLOCK_MAX_TXS: &str = "block.max_txs";
+pub const BLOCK_INTERVAL_MS: &str = "block.interval_ms";
+pub const ROUND_INTERVAL_MS: &str = "round.interval_ms";
+pub const COMMITTEE_SIZE: &str = "committee.size";
+pub const COMMITTEE_MIN_QUORUM: &str = "committee.min_quorum";
+pub const MEMPOOL_MAX_SIZE: &str = "mempool.max_size_per_shard";
+pub const FEE_MIN_TX_FEE: &str = "fee.min_tx_fee";
+pub const ASSET_WHITELIST: &str = "assets.whitelist";
+pub const STAKING_MIN_SELF_BOND: &str = "staking.min_self_bond";
+pub const STAKING_UNBONDING_ROUNDS: &str = "staking.unbonding_rounds";
+pub const SLASHING_DOUBLE_SIGN_BPS: &str = "slashing.double_sign.fraction_bps";
+pub const SLASHING_DOUBLE_SIGN_JAIL_ROUNDS: &str = "slashing.double_sign.jail_rounds";
+pub const SLASHING_INVALID_BLOCK_BPS: &str = "slashing.invalid_block.fraction_bps";
+pub const SLASHING_INVALID_BLOCK_JAIL_ROUNDS: &str = "slashing.invalid_block.jail_rounds";
+pub const SLASHING_DOWNTIME_BPS: &str = "slashing.downtime.fraction_bps";
+pub const SLASHING_DOWNTIME_JAIL_ROUNDS: &str = "slashing.downtime.jail_rounds";
+pub const SLASHING_DOWNTIME_MIN_MISSED: &str = "slashing.downtime.min_missed";
+pub const SLASHING_GOVERNANCE_BPS: &str = "slashing.governance.fraction_bps";
+pub const SLASHING_GOVERNANCE_JAIL_ROUNDS: &str = "slashing.governance.jail_rounds";
+pub const SLASHING_REPORTER_REWARD_BPS: &str = "slashing.reporter_reward_bps";
+pub const REWARDS_ASSET: &str = "rewards.asset";
+pub const REWARDS_ISSUANCE_PER_ROUND: &str = "rewards.issuance_per_round";
+pub const REWARDS_ISSUANCE_HALVING_ROUNDS: &str = "rewards.issuance_halving_rounds";
+pub const REWARDS_PROPOSER_BPS: &str = "rewards.proposer_bps";
+pub const VALIDATORS_KEY_ROTATION_DELAY: &str = "validators.key_rotation_min_delay_rounds";
+pub const VALIDATORS_EXIT_DRAIN_ROUNDS: &str = "validators.exit_drain_rounds";
+pub const CONSENSUS_EPOCH_LENGTH: &str = "consensus.epoch_length_rounds";
+pub const BRIDGE_PAUSED: &str = "bridge.paused";
+pub const BRIDGE_RATE_LIMIT_WINDOW_ROUNDS: &str = "bridge.rate_limit.window_rounds";
+pub const BRIDGE_RATE_LIMIT_PER_ASSET: &str = "bridge.rate_limit.max_per_asset";
+pub const GOVERNANCE_MIN_QUORUM_BPS: &str = "governance.min_quorum_bps";
+pub const GOVERNANCE_MIN_APPROVAL_BPS: &str = "governance.min_approval_bps";
+pub const GOVERNANCE_PROPOSAL_FEE: &str = "governance.proposal_fee";
+pub const GOVERNANCE_EMERGENCY_THRESHOLD: &str = "governance.emergency_threshold";
+pub const GOVERNANCE_MAX_ACTIVE_PROPOSALS: &str = "governance.max_active_proposals";
+pub const GOVERNANCE_MIN_VOTING_ROUNDS: &str = "governance.min_voting_rounds";
+pub const GOVERNANCE_MAX_VOTING_ROUNDS: &str = "governance.max_voting_rounds";
+
+/// Typed protocol parameter value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParamValue {
+    U64(u64),
+    List(Vec<String>),
+}
+
+impl ParamValue {
+    /// Plain JSON number or array, as served by the API
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            ParamValue::U64(v) => serde_json::json!(v),
+            ParamValue::List(items) => serde_json::json!(items),
+        }
+    }
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamValue::U64(v) => write!(f, "{v}"),
+            ParamValue::List(items) => write!(f, "{}", items.join(",")),
+        }
+    }
+}
+
+/// Value type and bounds of a parameter
+#[derive(Debug, Clone, Copy)]
+pub enum ParamKind {
+    U64 { min: u64, max: u64 },
+    AssetList { max_len: usize },
+}
+
+/// Schema entry for one protocol parameter
+#[derive(Debug, Clone, Copy)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub kind: ParamKind,
+    default: ParamDefault,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ParamDefault {
+    U64(u64),
+    List(&'static [&'static str]),
+}
+
+impl ParamSpec {
+    /// Schema description for the API
+    pub fn to_json(&self) -> serde_json::Value {
+        let (kind, bounds) = match self.kind {
+            ParamKind::U64 { min, max } => ("u64", serde_json::json!({ "min": min, "max": max })),
+            ParamKind::AssetList { max_len } => ("asset_list", serde_json::json!({ "max_len": max_len })),
+        };
+        serde_json::json!({
+            "name": self.name,
+            "description": self.description,
+            "type": kind,
+            "bounds": bounds,
+            "default": self.default_value().to_json(),
+        })
+    }
+
+    pub fn default_value(&self) -> ParamValue {
+        match self.default {
+            ParamDefault::U64(v) => ParamValue::U64(v),
+            ParamDefault::List(items) => ParamValue::List(items.iter().map(|s| s.to_string()).collect()),
+        }
+    }
+
+    /// Check a value against the parameter's type and bounds
+    pub fn check(&self, value: &ParamValue) -> Result<(), String> {
+        match (self.kind, value) {
+            (ParamKind::U64 { min, max }, ParamValue::U64(v)) => {
+                if *v < min || *v > max {
+                    return Err(format!("{} must be between {min} and {max}", self.name));
+                }
+                Ok(())
+            }
+            (ParamKind::AssetList { max_len }, ParamValue::List(items)) => {
+                if items.is_empty() || items.len() > max_len {
+                    return Err(format!("{} must list between 1 and {max_len} assets", self.name));
+                }
+                for item in items {
+                    let valid = !item.is_empty()
+                        && item.len() <= 12
+                        && item.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+                    if !valid {
+                        return Err(format!("Invalid asset code '{item}'"));
+                    }
+                    if items.iter().filter(|other| *other == item).count() > 1 {
+                        return Err(format!("Duplicate asset code '{item}'"));
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(format!("Wrong value type for {}", self.name)),
+        }
+    }
+
+    /// Parse the string form used by governance proposals (lists are comma-separated)
+    pub fn parse(&self, raw: &str) -> Result<ParamValue, String> {
+        let value = match self.kind {
+            ParamKind::U64 { .. } => ParamValue::U64(
+                raw.trim().parse().map_err(|_| format!("Invalid value '{raw}' for {}", self.name))?,
+            ),
+            ParamKind::AssetList { .. } => ParamValue::List(
+                raw.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect(),
+            ),
+        };
+        self.check(&value)?;
+        Ok(value)
+    }
+}
+
+/// Every governable protocol parameter with its bounds and genesis default
+pub const PARAMETER_SCHEMA: &[ParamSpec] = &[
+    ParamSpec {
+        name: BLOCK_MAX_TXS,
+        description: "Maximum transactions per block",
+        kind: ParamKind::U64 { min: 1, max: 100_000 },
+        default: ParamDefault::U64(1_000),
+    },
+    ParamSpec {
+        name: BLOCK_INTERVAL_MS,
+        description: "Target block production interval in milliseconds",
+        kind: ParamKind::U64 { min: 10, max: 60_000 },
+        default: ParamDefault::U64(100),
+    },
+    ParamSpec {
+        name: ROUND_INTERVAL_MS,
+        description: "Round checkpoint interval in milliseconds",
+        kind: ParamKind::U64 { min: 50, max: 600_000 },
+        default: ParamDefault::U64(200),
+    },
+    ParamSpec {
+        name: COMMITTEE_SIZE,
+        description: "Number of validators in a round committee",
+        kind: ParamKind::U64 { min: 1, max: 1_000 },
+        default: ParamDefault::U64(20),
+    },
+    ParamSpec {
+        name: COMMITTEE_MIN_QUORUM,
+        description: "Signatures required for round finality",
+        kind: ParamKind::U64 { min: 1, max: 1_000 },
+        default: ParamDefault::U64(12),
+    },
+    ParamSpec {
+        name: MEMPOOL_MAX_SIZE,
+        description: "Maximum pending transactions per shard",
+        kind: ParamKind::U64 { min: 100, max: 10_000_000 },
+        default: ParamDefault::U64(100_000),
+    },
+    ParamSpec {
+        name: FEE_MIN_TX_FEE,
+        description: "Minimum fee per transaction in base units",
+        kind: ParamKind::U64 { min: 0, max: 1_000_000_000 },
+        default: ParamDefault::U64(0),
+    },
+    ParamSpec {
+        name: ASSET_WHITELIST,
+        description: "Assets accepted for transfer",
+        kind: ParamKind::AssetList { max_len: 256 },
+        default: ParamDefault::List(&["USD"]),
+    },
+    ParamSpec {
+        name: STAKING_MIN_SELF_BOND,
+        description: "Stake a validator must bond itself to be selected or accept delegations",
+        kind: ParamKind::U64 { min: 0, max: 1_000_000_000_000 },
+        default: ParamDefault::U64(1_000),
+    },
+    ParamSpec {
+        name: STAKING_UNBONDING_ROUNDS,
+        description: "Rounds undelegated stake stays locked before it is returned",
+        kind: ParamKind::U64 { min: 1, max: 10_000_000 },
+        default: ParamDefault::U64(100),
+    },
+    ParamSpec {
+        name: SLASHING_DOUBLE_SIGN_BPS,
+        description: "Stake slashed for signing two different rounds at one height, in basis points",
+        kind: ParamKind::U64 { min: 0, max: 10_000 },
+        default: ParamDefault::U64(500),
+    },
+    ParamSpec {
+        name: SLASHING_DOUBLE_SIGN_JAIL_ROUNDS,
+        description: "Rounds a validator is jailed for double signing",
+        kind: ParamKind::U64 { min: 0, max: 100_000_000 },
+        default: ParamDefault::U64(10_000),
+    },
+    ParamSpec {
+        name: SLASHING_INVALID_BLOCK_BPS,
+        description: "Stake slashed for proposing an invalid block, in basis points",
+        kind: ParamKind::U64 { min: 0, max: 10_000 },
+        default: ParamDefault::U64(100),
+    },
+    ParamSpec {
+        name: SLASHING_INVALID_BLOCK_JAIL_ROUNDS,
+        description: "Rounds a validator is jailed for proposing an invalid block",
+        kind: ParamKind::U64 { min: 0, max: 100_000_000 },
+        default: ParamDefault::U64(1_000),
+    },
+    ParamSpec {
+        name: SLASHING_DOWNTIME_BPS,
+        description: "Stake slashed for prolonged downtime, in basis points",
+        kind: ParamKind::U64 { min: 0, max: 10_000 },
+        default: ParamDefault::U64(10),
+    },
+    ParamSpec {
+        name: SLASHING_DOWNTIME_JAIL_ROUNDS,
+        description: "Rounds a validator is jailed for prolonged downtime",
+        kind: ParamKind::U64 { min: 0, max: 100_000_000 },
+        default: ParamDefault::U64(100),
+    },
+    ParamSpec {
+        name: SLASHING_DOWNTIME_MIN_MISSED,
+        description: "Consecutive missed committee signatures that count as downtime",
+        kind: ParamKind::U64 { min: 1, max: 1_000_000 },
+        default: ParamDefault::U64(50),
+    },
+    ParamSpec {
+        name: SLASHING_GOVERNANCE_BPS,
+        description: "Stake slashed by a passed SlashValidator proposal, in basis points",
+        kind: ParamKind::U64 { min: 0, max: 10_000 },
+        default: ParamDefault::U64(500),
+    },
+    ParamSpec {
+        name: SLASHING_GOVERNANCE_JAIL_ROUNDS,
+        description: "Rounds a validator is jailed by a passed SlashValidator proposal",
+        kind: ParamKind::U64 { min: 0, max: 100_000_000 },
+        default: ParamDefault::U64(10_000),
+    },
+    ParamSpec {
+        name: SLASHING_REPORTER_REWARD_BPS,
+        description: "Share of slashed stake paid to the evidence reporter; the rest is burned",
+        kind: ParamKind::U64 { min: 0, max: 10_000 },
+        default: ParamDefault::U64(1_000),
+    },
+    ParamSpec {
+        name: REWARDS_ASSET,
+        description: "Asset validator rewards are paid in",
+        kind: ParamKind::AssetList { max_len: 1 },
+        default: ParamDefault::List(&["USD"]),
+    },
+    ParamSpec {
+        name: REWARDS_ISSUANCE_PER_ROUND,
+        description: "New reward units issued per finalized round, on top of collected fees",
+        kind: ParamKind::U64 { min: 0, max: 1_000_000_000 },
+        default: ParamDefault::U64(10),
+    },
+    ParamSpec {
+        name: REWARDS_ISSUANCE_HALVING_ROUNDS,
+        description: "Rounds after which issuance halves (0 never halves)",
+        kind: ParamKind::U64 { min: 0, max: 1_000_000_000 },
+        default: ParamDefault::U64(0),
+    },
+    ParamSpec {
+        name: REWARDS_PROPOSER_BPS,
+        description: "Share of each round's rewards paid to the round proposer, in basis points",
+        kind: ParamKind::U64 { min: 0, max: 10_000 },
+        default: ParamDefault::U64(2_000),
+    },
+    ParamSpec {
+        name: VALIDATORS_KEY_ROTATION_DELAY,
+        description: "Minimum rounds between a key rotation's inclusion and its activation",
+        kind: ParamKind::U64 { min: 1, max: 1_000_000 },
+        default: ParamDefault::U64(10),
+    },
+    ParamSpec {
+        name: VALIDATORS_EXIT_DRAIN_ROUNDS,
+        description: "Rounds an exiting validator is kept out of committees before removal",
+        kind: ParamKind::U64 { min: 1, max: 1_000_000 },
+        default: ParamDefault::U64(100),
+    },
+    ParamSpec {
+        name: CONSENSUS_EPOCH_LENGTH,
+        description: "Rounds per committee epoch; a change takes effect from the next epoch",
+        kind: ParamKind::U64 { min: 1, max: 1_000_000 },
+        default: ParamDefault::U64(100),
+    },
+    ParamSpec {
+        name: BRIDGE_PAUSED,
+        description: "Set to 1 to stop all bridge locks and releases",
+        kind: ParamKind::U64 { min: 0, max: 1 },
+        default: ParamDefault::U64(0),
+    },
+    ParamSpec {
+        name: BRIDGE_RATE_LIMIT_WINDOW_ROUNDS,
+        description: "Rounds per bridge rate-limit window",
+        kind: ParamKind::U64 { min: 1, max: 10_000_000 },
+        default: ParamDefault::U64(1_000),
+    },
+    ParamSpec {
+        name: BRIDGE_RATE_LIMIT_PER_ASSET,
+        description: "Maximum amount of each asset bridged in or out per window",
+        kind: ParamKind::U64 { min: 1, max: 1_000_000_000_000_000 },
+        default: ParamDefault::U64(1_000_000_000),
+    },
+    ParamSpec {
+        name: GOVERNANCE_MIN_QUORUM_BPS,
+        description: "Share of snapshot stake that must vote, in basis points",
+        kind: ParamKind::U64 { min: 1, max: 10_000 },
+        default: ParamDefault::U64(4_000),
+    },
+    ParamSpec {
+        name: GOVERNANCE_MIN_APPROVAL_BPS,
+        description: "Share of voted stake that must approve, in basis points",
+        kind: ParamKind::U64 { min: 5_000, max: 10_000 },
+        default: ParamDefault::U64(6_000),
+    },
+    ParamSpec {
+        name: GOVERNANCE_PROPOSAL_FEE,
+        description: "Fee required to submit a proposal in base units",
+        kind: ParamKind::U64 { min: 0, max: 1_000_000_000_000 },
+        default: ParamDefault::U64(1_000),
+    },
+    ParamSpec {
+        name: GOVERNANCE_EMERGENCY_THRESHOLD,
+        description: "Stake needed to cancel another validator's proposal",
+        kind: ParamKind::U64 { min: 0, max: 1_000_000_000_000_000 },
+        default: ParamDefault::U64(100_000),
+    },
+    ParamSpec {
+        name: GOVERNANCE_MAX_ACTIVE_PROPOSALS,
+        description: "Proposals open for voting at once",
+        kind: ParamKind::U64 { min: 1, max: 1_000 },
+        default: ParamDefault::U64(10),
+    },
+    ParamSpec {
+        name: GOVERNANCE_MIN_VOTING_ROUNDS,
+        description: "Shortest voting window of an on-chain proposal in rounds",
+        kind: ParamKind::U64 { min: 1, max: 10_000_000 },
+        default: ParamDefault::U64(10),
+    },
+    ParamSpec {
+        name: GOVERNANCE_MAX_VOTING_ROUNDS,
+        description: "Longest voting window of an on-chain proposal in rounds",
+        kind: ParamKind::U64 { min: 1, max: 10_000_000 },
+        default: ParamDefault::U64(10_000),
+    },
+];
+
+pub fn param_spec(name: &str) -> Option<&'static ParamSpec> {
+    PARAMETER_SCHEMA.iter().find(|spec| spec.name == name)
+}
+
+/// Protocol parameters versioned by activation round. Values only change
+/// through executed governance proposals.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParameterRegistry {
+    // name -> (activation round -> value); absent names use the schema default
+    history: BTreeMap<String, BTreeMap<u64, ParamValue>>,
+    current_round: u64,
+}
+
+impl ParameterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Value in force at `round`
+    pub fn get_at(&self, name: &str, round: u64) -> Option<ParamValue> {
+        let spec = param_spec(name)?;
+        let value = self.history.get(name)
+            .and_then(|versions| versions.range(..=round).next_back())
+            .map(|(_, v)| v.clone());
+        Some(value.unwrap_or_else(|| spec.default_value()))
+    }
+
+    /// Value in force at the latest finalized round
+    pub fn get(&self, name: &str) -> Option<ParamValue> {
+        self.get_at(name, self.current_round)
+    }
+
+    /// Current value of a numeric parameter; errors if the name is unknown or holds a list
+    pub fn get_u64(&self, name: &str) -> Result<u64, String> {
+        match self.get(name) {
+            Some(ParamValue::U64(v)) => Ok(v),
+            Some(_) => Err(format!("{name} is not a u64 parameter")),
+            None => Err(format!("Unknown parameter {name}")),
+        }
+    }
+
+    /// Current value of a list parameter; errors if the name is unknown or holds a number
+    pub fn get_list(&self, name: &str) -> Result<Vec<String>, String> {
+        match self.get(name) {
+            Some(ParamValue::List(v)) => Ok(v),
+            Some(_) => Err(format!("{name} is not a list parameter")),
+            None => Err(format!("Unknown parameter {name}")),
+        }
+    }
+
+    pub fn current_round(&self) -> u64 {
+        self.current_round
+    }
+
+    pub fn advance_to(&mut self, round: u64) {
+        self.current_round = self.current_round.max(round);
+    }
+
+    /// Record a new value taking effect at `activation_round`, after schema and cross-parameter checks
+    pub fn set(&mut self, name: &str, value: ParamValue, activation_round: u64) -> Result<(), String> {
+        let spec = param_spec(name).ok_or_else(|| format!("Unknown parameter {name}"))?;
+        spec.check(&value)?;
+
+        // (upper, lower) pairs that must stay ordered
+        let bounded_pair = match (name, &value) {
+            (COMMITTEE_SIZE, ParamValue::U64(size)) => Some((COMMITTEE_SIZE, *size, COMMITTEE_MIN_QUORUM, self.u64_at(COMMITTEE_MIN_QUORUM, activation_round))),
+            (COMMITTEE_MIN_QUORUM, ParamValue::U64(quorum)) => Some((COMMITTEE_SIZE, self.u64_at(COMMITTEE_SIZE, activation_round), COMMITTEE_MIN_QUORUM, *quorum)),
+            (GOVERNANCE_MAX_VOTING_ROUNDS, ParamValue::U64(max)) => Some((GOVERNANCE_MAX_VOTING_ROUNDS, *max, GOVERNANCE_MIN_VOTING_ROUNDS, self.u64_at(GOVERNANCE_MIN_VOTING_ROUNDS, activation_round))),
+            (GOVERNANCE_MIN_VOTING_ROUNDS, ParamValue::U64(min)) => Some((GOVERNANCE_MAX_VOTING_ROUNDS, self.u64_at(GOVERNANCE_MAX_VOTING_ROUNDS, activation_round), GOVERNANCE_MIN_VOTING_ROUNDS, *min)),
+            _ => None,
+        };
+        if let Some((upper_name, upper, lower_name, lower)) = bounded_pair {
+            if lower > upper {
+                return Err(format!("{lower_name} ({lower}) cannot exceed {upper_name} ({upper})"));
+            }
+        }
+
+        self.history.entry(name.to_string()).or_default().insert(activation_round, value);
+        Ok(())
+    }
+
+    /// Parse a governance proposal's string value and record it
+    pub fn set_from_str(&mut self, name: &str, raw: &str, activation_round: u64) -> Result<ParamValue, String> {
+        let spec = param_spec(name).ok_or_else(|| format!("Unknown parameter {name}"))?;
+        let value = spec.parse(raw)?;
+        self.set(name, value.clone(), activation_round)?;
+        Ok(value)
+    }
+
+    /// Activation history of one parameter, oldest first
+    pub fn versions(&self, name: &str) -> Vec<(u64, ParamValue)> {
+        self.history.get(name)
+            .map(|versions| versions.iter().map(|(r, v)| (*r, v.clone())).collect())
+            .unwrap_or_default()
+    }
+
+    fn u64_at(&self, name: &str, round: u64) -> u64 {
+        match self.get_at(name, round) {
+            Some(ParamValue::U64(v)) => v,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_bounds_and_versions() {
+        let mut registry = ParameterRegistry::new();
+        assert_eq!(registry.get_u64(BLOCK_MAX_TXS), Ok(1_000));
+        assert_eq!(registry.get_list(ASSET_WHITELIST), Ok(vec!["USD".to_string()]));
+        assert!(registry.get_u64(ASSET_WHITELIST).is_err());
+        assert!(registry.get_list(BLOCK_MAX_TXS).is_err());
+        assert!(registry.get_u64("block.unknown").is_err());
+
+        assert!(registry.set_from_str(BLOCK_MAX_TXS, "0", 5).is_err());
+        assert!(registry.set_from_str(BLOCK_MAX_TXS, "abc", 5).is_err());
+        assert!(registry.set_from_str("block.unknown", "1", 5).is_err());
+        assert!(registry.set_from_str(COMMITTEE_MIN_QUORUM, "25", 5).is_err());
+        assert!(registry.set_from_str(GOVERNANCE_MIN_VOTING_ROUNDS, "20000", 5).is_err());
+
+        registry.set_from_str(BLOCK_MAX_TXS, "500", 5).unwrap();
+        registry.set_from_str(ASSET_WHITELIST, "USD, EUR", 8).unwrap();
+        assert_eq!(registry.get_u64(BLOCK_MAX_TXS), Ok(1_000));
+        registry.advance_to(5);
+        assert_eq!(registry.get_u64(BLOCK_MAX_TXS), Ok(500));
+        assert_eq!(registry.get_list(ASSET_WHITELIST), Ok(vec!["USD".to_string()]));
+        registry.advance_to(8);
+        assert_eq!(registry.get_list(ASSET_WHITELIST).unwrap().len(), 2);
+        assert_eq!(registry.get_at(BLOCK_MAX_TXS, 4), Some(ParamValue::U64(1_000)));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::consensus::governance_executor::ChainControl;
+use crate::consensus::parameters;
+use crate::consensus::staking::{StakingLedger, STAKING_SHARD};
+use crate::consensus::validator_set::Validator;
+use crate::core::address::Address;
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Account collecting fees to be paid out as validator rewards
+pub const REWARD_POOL_ADDRESS: &str = "rewards:pool";
+
+/// Account holding credited rewards until they are claimed
+pub const REWARD_ESCROW_ADDRESS: &str = "rewards:escrow";
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Asset rewards are currently paid in
+pub fn reward_asset(control: &ChainControl) -> String {
+    control.parameters().get_list(parameters::REWARDS_ASSET)
+        .ok()
+        .and_then(|assets| assets.into_iter().next())
+        .unwrap_or_else(|| "USD".to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RewardKind {
+    /// Proposer share of the round reward (plus commission)
+    Proposer,
+    /// Committee signer share, weighted by stake (plus commission)
+    Signer,
+    /// Delegator's share of a validator's reward, after commission
+    Delegation,
+}
+
+/// One reward credit, kept in the recipient's history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardEntry {
+    pub round: u64,
+    pub recipient: String,
+    pub validator: String,
+    pub kind: RewardKind,
+    pub asset: String,
+    pub amount: u64,
+}
+
+/// Credits the proposer and committee signers of each finalized round from the
+/// fee pool and the issuance schedule. Delegators share in their validator's
+/// reward pro rata to their delegation, less the validator's commission.
+pub struct RewardDistributor {
+    ledger: Arc<StakingLedger>,
+}
+
+impl RewardDistributor {
+    pub fn new(ledger: Arc<StakingLedger>) -> Self {
+        Self { ledger }
+    }
+
+    /// Units issued for `round` under the halving schedule
+    pub fn issuance_at(&self, round: u64) -> Result<u64, String> {
+        let control = self.ledger.control();
+        let base = control.param_u64(parameters::REWARDS_ISSUANCE_PER_ROUND)?;
+        let halving = control.param_u64(parameters::REWARDS_ISSUANCE_HALVING_ROUNDS)?;
+        if halving == 0 {
+            return Ok(base);
+        }
+        Ok(base.checked_shr((round / halving).min(64) as u32).unwrap_or(0))
+    }
+
+    /// Distribute the rewards of a round that reached quorum
+    pub fn distribute_round(&self, round: u64, proposer: &Address, signers: &[Address]) -> Result<Vec<RewardEntry>, String> {
+        let state_db = self.ledger.state_db();
+        let control = self.ledger.control();
+        if state_db.is_round_rewarded(round) {
+            return Err(format!("Round {round} already rewarded"));
+        }
+        let asset = reward_asset(&control);
+        let pool = state_db.get_balance(STAKING_SHARD.0, REWARD_POOL_ADDRESS, &asset);
+        let total = pool.checked_add(self.issuance_at(round)?).ok_or("Reward overflow")?;
+
+        let validator_set = self.ledger.validator_set();
+        let validator_set = validator_set.lock().unwrap();
+        let mut entries = Vec::new();
+
+        let proposer_bps = control.param_u64(parameters::REWARDS_PROPOSER_BPS)? as u128;
+        let proposer_cut = match validator_set.get_validator(proposer) {
+            Some(validator) => {
+                let cut = (total as u128 * proposer_bps / BPS_DENOMINATOR) as u64;
+                self.split(validator, cut, RewardKind::Proposer, round, &asset, &mut entries);
+                cut
+            }
+            None => 0,
+        };
+
+        // Signers share the rest by stake; duplicates and unknown addresses are ignored
+        let signing: BTreeMap<&str, &Validator> = signers.iter()
+            .filter_map(|address| validator_set.get_validator(address))
+            .filter(|validator| validator.stake > 0)
+            .map(|validator| (validator.address.as_str(), validator))
+            .collect();
+        let signer_pool = (total - proposer_cut) as u128;
+        let stake_sum: u128 = signing.values().map(|v| v.stake as u128).sum();
+        for validator in signing.values() {
+            let share = (signer_pool * validator.stake as u128 / stake_sum) as u64;
+            self.split(validator, share, RewardKind::Signer, round, &asset, &mut entries);
+        }
+
+        // Fees are paid out first; the rest is newly issued. Rounding dust stays in the pool.
+        let credited: u64 = entries.iter().map(|e| e.amount).sum();
+        let from_pool = credited.min(pool);
+        state_db.set_balance(STAKING_SHARD.0, REWARD_POOL_ADDRESS, &asset, pool - from_pool)?;
+        let escrow = state_db.get_balance(STAKING_SHARD.0, REWARD_ESCROW_ADDRESS, &asset);
+        state_db.set_balance(STAKING_SHARD.0, REWARD_ESCROW_ADDRESS, &asset, escrow + credited)?;
+        state_db.credit_round_rewards(round, &entries)?;
+        println!(
+            "[DEBUG] Rewards: round {round} paid {credited} {asset} ({from_pool} from fees, {} issued) to {} recipients",
+            credited - from_pool, entries.len()
+        );
+        Ok(entries)
+    }
+
+    /// Split one validator's reward between its commission, its own stake and its delegators
+    fn split(&self, validator: &Validator, amount: u64, kind: RewardKind, round: u64, asset: &str, entries: &mut Vec<RewardEntry>) {
+        if amount == 0 || validator.stake == 0 {
+            return;
+        }
+        let entry = |recipient: &str, kind, amount| RewardEntry {
+            round,
+            recipient: recipient.to_string(),
+            validator: validator.address.to_string(),
+            kind,
+            asset: asset.to_string(),
+            amount,
+        };
+        let commission = (amount as u128 * validator.commission_bps as u128 / BPS_DENOMINATOR) as u64;
+        let distributable = (amount - commission) as u128;
+        let mut delegated_out = 0;
+        for (delegator, delegation) in self.ledger.state_db().delegations_to(validator.address.as_str()) {
+            if delegator == validator.address.as_str() {
+                continue;
+            }
+            let share = (distributable * delegation as u128 / validator.stake as u128) as u64;
+            if share > 0 {
+                entries.push(entry(&delegator, RewardKind::Delegation, share));
+                delegated_out += share;
+            }
+        }
+        entries.push(entry(validator.address.as_str(), kind, amount - delegated_out));
+    }
+
+    /// Claimable balance and reward history of an account
+    pub fn rewards_of(&self, address: &str) -> (u64, Vec<RewardEntry>) {
+        let state_db = self.ledger.state_db();
+        (state_db.get_claimable_reward(address), state_db.reward_history(address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::staking::{StakingAction, StakingTx};
+    use crate::consensus::validator_set::ValidatorSet;
+    use crate::storage::state::StateDB;
+    use ed25519_dalek::SigningKey;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_round_rewards_commission_and_claim() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(StateDB::new(dir.path().to_str().unwrap()));
+        let keys: Vec<SigningKey> = (21..24u8).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+        let [proposer, signer, delegator] = [0, 1, 2].map(|i| Address::from_verifying_key(&keys[i].verifying_key()));
+        state_db.set_balance(0, delegator.as_str(), "USD", 1_000).unwrap();
+        state_db.set_balance(0, REWARD_POOL_ADDRESS, "USD", 990).unwrap();
+
+        let validator_set = Arc::new(Mutex::new(ValidatorSet::new()));
+        {
+            let mut set = validator_set.lock().unwrap();
+            set.add_validator(proposer.clone(), keys[0].verifying_key(), 1_000);
+            set.add_validator(signer.clone(), keys[1].verifying_key(), 3_000);
+        }
+        let ledger = Arc::new(StakingLedger::new(state_db.clone(), validator_set.clone(), Arc::new(ChainControl::new())));
+        ledger.apply_staking_tx(&StakingTx::sign(StakingAction::SetCommission { rate_bps: 1_000 }, 1, &keys[1])).unwrap();
+        let delegate = StakingAction::Delegate { validator: signer.clone(), amount: 1_000 };
+        ledger.apply_staking_tx(&StakingTx::sign(delegate, 2, &keys[2])).unwrap();
+
+        // 990 in fees + 10 issued: 200 to the proposer, 800 split 1000:4000 by stake
+        let distributor = RewardDistributor::new(ledger.clone());
+        distributor.distribute_round(1, &proposer, &[proposer.clone(), signer.clone(), signer.clone()]).unwrap();
+        assert!(distributor.distribute_round(1, &proposer, &[]).is_err());
+
+        let (proposer_claimable, history) = distributor.rewards_of(proposer.as_str());
+        assert_eq!(proposer_claimable, 360);
+        assert_eq!(history.len(), 2);
+        // Signer earns 640; its delegator gets 1/4 of that after 10% commission
+        assert_eq!(distributor.rewards_of(delegator.as_str()).0, 144);
+        assert_eq!(distributor.rewards_of(signer.as_str()).0, 496);
+        assert_eq!(state_db.get_balance(0, REWARD_POOL_ADDRESS, "USD"), 0);
+        assert_eq!(state_db.get_balance(0, REWARD_ESCROW_ADDRESS, "USD"), 1_000);
+
+        ledger.apply_staking_tx(&StakingTx::sign(StakingAction::ClaimRewards, 3, &keys[2])).unwrap();
+        assert_eq!(state_db.get_balance(0, delegator.as_str(), "USD"), 144);
+        assert_eq!(distributor.rewards_of(delegator.as_str()).0, 0);
+        assert!(ledger.apply_staking_tx(&StakingTx::sign(StakingAction::ClaimRewards, 4, &keys[2])).is_err());
+    }
+}
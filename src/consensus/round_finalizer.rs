@@ -279,6 +279,33 @@ mod tests {
         assert_eq!(committee.validators.len(), 20); // Default committee size
     }
 
+    #[test]
+    fn test_committee_sampling_is_seeded_and_stake_weighted() {
+        let mut validator_set = ValidatorSet::new();
+        let mut whale = None;
+        for i in 0..30u8 {
+            let key = SigningKey::from_bytes(&[i + 1; 32]);
+            let address = crate::core::address::Address::from_verifying_key(&key.verifying_key());
+            let stake = if i == 0 { 1_000_000 } else { 1_000 };
+            validator_set.add_validator(address.clone(), key.verifying_key(), stake);
+            whale.get_or_insert(address);
+        }
+        // Below the minimum self-bond: never selected
+        let (small_key, small) = generate_address();
+        validator_set.add_validator(small.clone(), small_key.verifying_key(), 999);
+
+        validator_set.set_committee_seed([1u8; 32]);
+        let first = validator_set.select_committee(5);
+        let again = validator_set.select_committee(5);
+        assert_eq!(first.validators, again.validators);
+        assert_eq!(first.validators.len(), 20);
+        assert!(first.validators.contains(&whale.unwrap()));
+        assert!(!first.validators.contains(&small));
+
+        validator_set.set_committee_seed([2u8; 32]);
+        assert_ne!(validator_set.select_committee(5).validators, first.validators);
+    }
+
     #[test]
     fn test_finality_detection() {
         let mut validator_set = ValidatorSet::new();
@@ -9,7 +9,15 @@ use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 use crate::core::address::Address;
 use crate::core::types::Block;
+use crate::core::bridge::Bridge;
+use crate::core::tx_status::TxStatusRegistry;
+use std::sync::{Arc, Mutex};
 use crate::consensus::validator_set::{ValidatorSet, Committee};
+use crate::consensus::governance_executor::GovernanceExecutor;
+use crate::consensus::rewards::RewardDistributor;
+use crate::consensus::staking::StakingLedger;
+use crate::consensus::validator_lifecycle::ValidatorLifecycle;
+use crate::consensus::epoch::EpochManager;
 use sha2::{Sha256, Digest};
 
 /// Represents a simple, linear Round in the FinDAG RoundChain
@@ -82,12 +90,19 @@ pub struct RoundChain {
     pub rounds: HashMap<u64, Round>,          // round_number -> Round
     pub latest_round_number: u64,             // Latest finalized round
     pub genesis_round_hash: [u8; 32],         // Hash of genesis round
-    pub validator_set: ValidatorSet,          // Validator set for quorum signatures
+    pub validator_set: Arc<Mutex<ValidatorSet>>, // Node's shared validator set, for committees and quorum signatures
+    pub status_registry: Option<Arc<TxStatusRegistry>>, // Notified when blocks are finalized
+    pub governance_executor: Option<Arc<GovernanceExecutor>>, // Applies proposals due at each round
+    pub staking_ledger: Option<Arc<StakingLedger>>, // Releases matured unbondings at each round
+    pub reward_distributor: Option<Arc<RewardDistributor>>, // Pays proposer and signers once a round reaches quorum
+    pub validator_lifecycle: Option<Arc<ValidatorLifecycle>>, // Activates key rotations and removes exited validators
+    pub epoch_manager: Option<Arc<EpochManager>>, // Fixes the next epoch's committee at each epoch end
+    pub bridge: Option<Arc<Bridge>>, // Issues receipts for outbound locks in finalized blocks
 }
 
 impl RoundChain {
-    /// Create a new RoundChain
-    pub fn new(validator_set: ValidatorSet) -> Self {
+    /// Create a new RoundChain over the node's shared validator set
+    pub fn new(validator_set: Arc<Mutex<ValidatorSet>>) -> Self {
         let genesis_round_hash = [0u8; 32]; // Genesis round hash
         
         Self {
@@ -95,9 +110,51 @@ impl RoundChain {
             latest_round_number: 0,
             genesis_round_hash,
             validator_set,
+            status_registry: None,
+            governance_executor: None,
+            staking_ledger: None,
+            reward_distributor: None,
+            validator_lifecycle: None,
+            epoch_manager: None,
+            bridge: None,
         }
     }
 
+    /// Attach a transaction status registry, notified once a round reaches quorum
+    pub fn set_status_registry(&mut self, registry: Arc<TxStatusRegistry>) {
+        self.status_registry = Some(registry);
+    }
+
+    /// Attach the governance executor, run once a round reaches quorum
+    pub fn set_governance_executor(&mut self, executor: Arc<GovernanceExecutor>) {
+        self.governance_executor = Some(executor);
+    }
+
+    /// Attach the staking ledger, advanced once a round reaches quorum
+    pub fn set_staking_ledger(&mut self, ledger: Arc<StakingLedger>) {
+        self.staking_ledger = Some(ledger);
+    }
+
+    /// Attach the reward distributor, run when a round's quorum signature is set
+    pub fn set_reward_distributor(&mut self, distributor: Arc<RewardDistributor>) {
+        self.reward_distributor = Some(distributor);
+    }
+
+    /// Attach the validator lifecycle handler, advanced once a round reaches quorum
+    pub fn set_validator_lifecycle(&mut self, lifecycle: Arc<ValidatorLifecycle>) {
+        self.validator_lifecycle = Some(lifecycle);
+    }
+
+    /// Attach the epoch manager, run after the other round hooks so it sees this round's validator changes
+    pub fn set_epoch_manager(&mut self, manager: Arc<EpochManager>) {
+        self.epoch_manager = Some(manager);
+    }
+
+    /// Attach the bridge, which issues outbound receipts once a round reaches quorum
+    pub fn set_bridge(&mut self, bridge: Arc<Bridge>) {
+        self.bridge = Some(bridge);
+    }
+
     /// Create a new Round with the specified finalized blocks
     pub fn create_round(
         &mut self,
@@ -180,8 +237,10 @@ impl RoundChain {
             }
         }
 
-        // Store the round
+        // Store the round; its hash seeds the next committee selection
         let round_number = round.round_number;
+        let round_hash = self.compute_round_hash(&round);
+        self.validator_set.lock().unwrap().set_committee_seed(round_hash);
         self.rounds.insert(round_number, round);
         self.latest_round_number = round_number;
 
@@ -209,7 +268,8 @@ impl RoundChain {
         };
 
         // Validate we have enough signatures for quorum
-        let quorum_threshold = self.validator_set.quorum_manager.config.min_quorum_size;
+        let validator_set = self.validator_set.lock().unwrap();
+        let quorum_threshold = validator_set.quorum_manager.config.min_quorum_size;
         if signatures.len() < quorum_threshold {
             return Err(format!("Insufficient signatures: {} < {}", 
                              signatures.len(), quorum_threshold));
@@ -223,7 +283,7 @@ impl RoundChain {
             }
 
             // Verify signature
-            if let Some(validator) = self.validator_set.get_validator(validator_addr) {
+            if let Some(validator) = validator_set.get_validator(validator_addr) {
                 let round_content = self.create_round_content(
                     round_data.0,
                     &round_data.1,
@@ -239,6 +299,8 @@ impl RoundChain {
             }
         }
 
+        drop(validator_set);
+
         // Create threshold signature (simplified - in production use proper threshold signing)
         let mut signature_data = Vec::new();
         for (_, signature) in signatures {
@@ -248,9 +310,40 @@ impl RoundChain {
         let quorum_signature = signature_hash.to_vec();
 
         // Now set the quorum_signature field
+        let round_hash = self.compute_round_hash(self.rounds.get(&round_number).ok_or("Round not found")?);
         let round = self.rounds.get_mut(&round_number).ok_or("Round not found")?;
         round.quorum_signature = quorum_signature;
 
+        // Proposals, unbondings, validator changes and epochs advance only on rounds that reached quorum
+        if let Some(executor) = &self.governance_executor {
+            executor.apply_included(round_number, &round.finalized_block_hashes);
+            executor.on_round_finalized(round_number);
+        }
+        if let Some(ledger) = &self.staking_ledger {
+            ledger.on_round_finalized(round_number);
+        }
+        if let Some(lifecycle) = &self.validator_lifecycle {
+            lifecycle.on_round_finalized(round_number);
+        }
+        if let Some(manager) = &self.epoch_manager {
+            manager.on_round_finalized(round_number, round_hash);
+        }
+
+        // Blocks are final only once the committee has signed their round
+        if let Some(registry) = &self.status_registry {
+            registry.mark_round_finalized(round_number, &round.finalized_block_hashes);
+        }
+        if let Some(bridge) = &self.bridge {
+            bridge.on_round_finalized(round_number, &round.finalized_block_hashes);
+        }
+
+        if let Some(distributor) = &self.reward_distributor {
+            let signers: Vec<Address> = signatures.iter().map(|(address, _)| address.clone()).collect();
+            if let Err(e) = distributor.distribute_round(round_number, &round.proposer, &signers) {
+                println!("[DEBUG] RoundChain: reward distribution for round {round_number} failed: {e}");
+            }
+        }
+
         Ok(())
     }
 
@@ -326,31 +419,7 @@ impl RoundChain {
         block_hashtimers: &[[u8; 32]],
         findag_time: u64,
     ) -> Vec<u8> {
-        let mut content = Vec::new();
-        
-        // Round number
-        content.extend_from_slice(&round_number.to_be_bytes());
-        
-        // Parent round hash
-        content.extend_from_slice(parent_round_hash);
-        
-        // Number of blocks
-        content.extend_from_slice(&(finalized_block_hashes.len() as u32).to_be_bytes());
-        
-        // Block hashes
-        for hash in finalized_block_hashes {
-            content.extend_from_slice(hash);
-        }
-        
-        // Block hashtimers
-        for hashtimer in block_hashtimers {
-            content.extend_from_slice(hashtimer);
-        }
-        
-        // FinDAG Time
-        content.extend_from_slice(&findag_time.to_be_bytes());
-        
-        content
+        round_signing_content(round_number, parent_round_hash, finalized_block_hashes, block_hashtimers, findag_time)
     }
 
     /// Get the total number of finalized blocks
@@ -379,6 +448,41 @@ impl RoundChain {
     }
 }
 
+/// Content validators sign for a Round
+pub fn round_signing_content(
+    round_number: u64,
+    parent_round_hash: &[u8; 32],
+    finalized_block_hashes: &[[u8; 32]],
+    block_hashtimers: &[[u8; 32]],
+    findag_time: u64,
+) -> Vec<u8> {
+    let mut content = Vec::new();
+    
+    // Round number
+    content.extend_from_slice(&round_number.to_be_bytes());
+    
+    // Parent round hash
+    content.extend_from_slice(parent_round_hash);
+    
+    // Number of blocks
+    content.extend_from_slice(&(finalized_block_hashes.len() as u32).to_be_bytes());
+    
+    // Block hashes
+    for hash in finalized_block_hashes {
+        content.extend_from_slice(hash);
+    }
+    
+    // Block hashtimers
+    for hashtimer in block_hashtimers {
+        content.extend_from_slice(hashtimer);
+    }
+    
+    // FinDAG Time
+    content.extend_from_slice(&findag_time.to_be_bytes());
+    
+    content
+}
+
 /// Statistics about the RoundChain
 #[derive(Debug, Clone)]
 pub struct RoundChainStats {
@@ -393,6 +497,7 @@ mod tests {
     use super::*;
     use crate::core::address::generate_address;
     use crate::consensus::validator_set::ValidatorSet;
+    use crate::core::tx_status::TxStatus;
 
     fn create_test_block(block_id: [u8; 32], hashtimer: [u8; 32]) -> Block {
         let (keypair, address) = generate_address();
@@ -413,7 +518,7 @@ mod tests {
     #[test]
     fn test_round_creation() {
         let validator_set = ValidatorSet::new();
-        let mut roundchain = RoundChain::new(validator_set);
+        let mut roundchain = RoundChain::new(Arc::new(Mutex::new(validator_set)));
         let (keypair, address) = generate_address();
 
         let block1 = create_test_block([1u8; 32], [10u8; 32]);
@@ -433,7 +538,7 @@ mod tests {
     #[test]
     fn test_sequential_round_numbers() {
         let validator_set = ValidatorSet::new();
-        let mut roundchain = RoundChain::new(validator_set);
+        let mut roundchain = RoundChain::new(Arc::new(Mutex::new(validator_set)));
         let (keypair, address) = generate_address();
 
         // Create first round
@@ -459,7 +564,7 @@ mod tests {
     #[test]
     fn test_block_finalization_tracking() {
         let validator_set = ValidatorSet::new();
-        let mut roundchain = RoundChain::new(validator_set);
+        let mut roundchain = RoundChain::new(Arc::new(Mutex::new(validator_set)));
         let (keypair, address) = generate_address();
 
         let block1 = create_test_block([1u8; 32], [10u8; 32]);
@@ -478,4 +583,126 @@ mod tests {
         assert_eq!(roundchain.get_block_finalization_round(&block2.block_id), Some(1));
         assert_eq!(roundchain.get_block_finalization_round(&[99u8; 32]), None);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_finality_waits_for_quorum() {
+        let (keypair, address) = generate_address();
+        let mut validator_set = ValidatorSet::new();
+        validator_set.add_validator(address.clone(), keypair.verifying_key(), 1_000);
+        validator_set.quorum_manager.config.min_quorum_size = 1;
+        let validator_set = Arc::new(Mutex::new(validator_set));
+        let mut roundchain = RoundChain::new(validator_set.clone());
+        let registry = Arc::new(TxStatusRegistry::default());
+        roundchain.set_status_registry(registry.clone());
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(crate::storage::state::StateDB::new(dir.path().to_str().unwrap()));
+        let ledger = Arc::new(StakingLedger::new(state_db, validator_set, Arc::new(crate::consensus::governance_executor::ChainControl::new())));
+        roundchain.set_staking_ledger(ledger.clone());
+        let tx_hash = [5u8; 32];
+        registry.mark_included([1u8; 32], &[tx_hash]);
+
+        let round = roundchain.create_round(1, vec![create_test_block([1u8; 32], [10u8; 32])], 1000, &keypair, address.clone())
+            .expect("Failed to create round");
+        let proposer_signature = round.proposer_signature;
+        roundchain.add_round(round).expect("Failed to add round");
+        assert_eq!(registry.status(&tx_hash), Some(TxStatus::Included { block_id: [1u8; 32] }));
+        assert_eq!(ledger.current_round(), 0);
+
+        let committee = Committee {
+            round_number: 1,
+            validators: vec![address.clone()],
+            start_time: 0,
+            end_time: None,
+            signatures_received: Vec::new(),
+            quorum_achieved: false,
+            fallback_triggered: false,
+            seed: [0u8; 32],
+        };
+        assert!(roundchain.sign_round_with_quorum(1, &committee, &[]).is_err());
+        assert_eq!(ledger.current_round(), 0);
+        assert_eq!(registry.status(&tx_hash), Some(TxStatus::Included { block_id: [1u8; 32] }));
+
+        roundchain.sign_round_with_quorum(1, &committee, &[(address, proposer_signature)])
+            .expect("Failed to sign round");
+        assert_eq!(registry.status(&tx_hash), Some(TxStatus::Finalized { round_number: 1, block_id: [1u8; 32] }));
+        assert_eq!(ledger.current_round(), 1);
+    }
+
+    #[test]
+    fn test_executed_fees_paid_out_at_quorum() {
+        use crate::consensus::governance_executor::ChainControl;
+        use crate::consensus::rewards::REWARD_POOL_ADDRESS;
+        use crate::core::executor::{apply_transaction_with_fee, DEFAULT_TRANSFER_ASSET};
+        use crate::core::types::Transaction;
+        use crate::storage::state::StateDB;
+
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(StateDB::new(dir.path().to_str().unwrap()));
+        let (keypair, address) = generate_address();
+        let (payer_key, payer) = generate_address();
+        state_db.set_balance(0, payer.as_str(), DEFAULT_TRANSFER_ASSET, 1_000).unwrap();
+        let tx = Transaction {
+            from: payer.clone(),
+            to: address.clone(),
+            amount: 10,
+            payload: vec![],
+            findag_time: 1,
+            hashtimer: [0u8; 32],
+            signature: payer_key.sign(b"envelope"),
+            public_key: payer_key.verifying_key(),
+            shard_id: crate::core::types::ShardId(0),
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        };
+        apply_transaction_with_fee(&state_db, &tx, 90).unwrap();
+        assert_eq!(state_db.get_balance(0, REWARD_POOL_ADDRESS, DEFAULT_TRANSFER_ASSET), 90);
+
+        let mut validator_set = ValidatorSet::new();
+        validator_set.add_validator(address.clone(), keypair.verifying_key(), 1_000);
+        validator_set.quorum_manager.config.min_quorum_size = 1;
+        let validator_set = Arc::new(Mutex::new(validator_set));
+        let ledger = Arc::new(StakingLedger::new(state_db.clone(), validator_set.clone(), Arc::new(ChainControl::new())));
+        let distributor = Arc::new(RewardDistributor::new(ledger));
+        let mut roundchain = RoundChain::new(validator_set.clone());
+        roundchain.set_reward_distributor(distributor.clone());
+
+        let round = roundchain.create_round(1, vec![create_test_block([1u8; 32], [10u8; 32])], 1000, &keypair, address.clone())
+            .expect("Failed to create round");
+        let proposer_signature = round.proposer_signature;
+        roundchain.add_round(round).expect("Failed to add round");
+        let committee = validator_set.lock().unwrap().select_committee(1);
+        roundchain.sign_round_with_quorum(1, &committee, &[(address.clone(), proposer_signature)])
+            .expect("Failed to sign round");
+
+        // The 90 collected in fees plus 10 issued all go to the sole proposer and signer
+        assert_eq!(distributor.rewards_of(address.as_str()).0, 100);
+        assert_eq!(state_db.get_balance(0, REWARD_POOL_ADDRESS, DEFAULT_TRANSFER_ASSET), 0);
+    }
+
+    #[test]
+    fn test_validator_changes_reach_round_chain() {
+        let (keypair, address) = generate_address();
+        let validator_set = Arc::new(Mutex::new(ValidatorSet::new()));
+        let mut roundchain = RoundChain::new(validator_set.clone());
+        let round = roundchain.create_round(1, vec![create_test_block([1u8; 32], [10u8; 32])], 1000, &keypair, address.clone())
+            .expect("Failed to create round");
+        let proposer_signature = round.proposer_signature;
+        roundchain.add_round(round).expect("Failed to add round");
+
+        // A validator bonded after the round chain was built is sampled into the committee
+        {
+            let mut set = validator_set.lock().unwrap();
+            set.add_validator(address.clone(), keypair.verifying_key(), 1_000);
+            set.quorum_manager.config.min_quorum_size = 1;
+        }
+        let committee = roundchain.validator_set.lock().unwrap().select_committee(1);
+        assert!(committee.validators.contains(&address));
+        roundchain.sign_round_with_quorum(1, &committee, &[(address, proposer_signature)])
+            .expect("Failed to sign round");
+    }
+}
@@ -0,0 +1,502 @@
+use crate::consensus::parameters;
+use crate::consensus::roundchain::round_signing_content;
+use crate::consensus::staking::{StakingLedger, STAKING_ASSET, STAKING_ESCROW_ADDRESS, STAKING_SHARD};
+use crate::consensus::validator_set::{Validator, ValidatorSet};
+use crate::core::address::Address;
+use crate::core::types::{Block, ShardId, Transaction};
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+use std::sync::Arc;
+
+/// Payload prefix identifying a slashing report or unjail request carried in `Transaction.payload`
+pub const SLASHING_PAYLOAD_TAG: &[u8] = b"FDG:SLASH:1";
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Round header a validator signed, as carried in double-sign evidence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedRoundHeader {
+    pub parent_round_hash: [u8; 32],
+    pub finalized_block_hashes: Vec<[u8; 32]>,
+    pub block_hashtimers: Vec<[u8; 32]>,
+    pub findag_time: u64,
+    pub signature: Signature,
+}
+
+impl SignedRoundHeader {
+    fn content(&self, round_number: u64) -> Vec<u8> {
+        round_signing_content(
+            round_number,
+            &self.parent_round_hash,
+            &self.finalized_block_hashes,
+            &self.block_hashtimers,
+            self.findag_time,
+        )
+    }
+}
+
+/// Proof of a validator offense
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Evidence {
+    /// Two different round headers for the same round number, both signed by the validator
+    DoubleSign {
+        validator: Address,
+        round_number: u64,
+        first: SignedRoundHeader,
+        second: SignedRoundHeader,
+    },
+    /// A block signed by its proposer whose body fails validation
+    InvalidBlock { block: Block },
+    /// Consecutive missed committee signatures, as recorded in `ValidatorReputation`
+    Downtime {
+        validator: Address,
+        consecutive_missed: u32,
+        observed_round: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Offense {
+    DoubleSign,
+    InvalidBlock,
+    Downtime,
+    /// Decided by a passed SlashValidator proposal rather than reported evidence
+    Governance,
+}
+
+impl Offense {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Offense::DoubleSign => "double_sign",
+            Offense::InvalidBlock => "invalid_block",
+            Offense::Downtime => "downtime",
+            Offense::Governance => "governance",
+        }
+    }
+
+    /// (slash fraction in basis points, jail rounds) parameter names
+    fn penalty_params(&self) -> (&'static str, &'static str) {
+        match self {
+            Offense::DoubleSign => (parameters::SLASHING_DOUBLE_SIGN_BPS, parameters::SLASHING_DOUBLE_SIGN_JAIL_ROUNDS),
+            Offense::InvalidBlock => (parameters::SLASHING_INVALID_BLOCK_BPS, parameters::SLASHING_INVALID_BLOCK_JAIL_ROUNDS),
+            Offense::Downtime => (parameters::SLASHING_DOWNTIME_BPS, parameters::SLASHING_DOWNTIME_JAIL_ROUNDS),
+            Offense::Governance => (parameters::SLASHING_GOVERNANCE_BPS, parameters::SLASHING_GOVERNANCE_JAIL_ROUNDS),
+        }
+    }
+}
+
+impl Evidence {
+    pub fn offense(&self) -> Offense {
+        match self {
+            Evidence::DoubleSign { .. } => Offense::DoubleSign,
+            Evidence::InvalidBlock { .. } => Offense::InvalidBlock,
+            Evidence::Downtime { .. } => Offense::Downtime,
+        }
+    }
+
+    pub fn offender(&self) -> Address {
+        match self {
+            Evidence::DoubleSign { validator, .. } | Evidence::Downtime { validator, .. } => validator.clone(),
+            Evidence::InvalidBlock { block } => block.proposer.clone(),
+        }
+    }
+
+    /// Canonical id, so the same offense cannot be slashed twice
+    pub fn id(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.offense().name());
+        hasher.update(self.offender().as_str());
+        match self {
+            Evidence::DoubleSign { round_number, .. } => hasher.update(round_number.to_be_bytes()),
+            Evidence::InvalidBlock { block } => hasher.update(block.block_id),
+            Evidence::Downtime { observed_round, .. } => hasher.update(observed_round.to_be_bytes()),
+        }
+        hasher.finalize().into()
+    }
+
+    /// Check the evidence against the offender's registered key
+    pub fn verify(&self, offender_key: &VerifyingKey) -> Result<(), String> {
+        match self {
+            Evidence::DoubleSign { round_number, first, second, .. } => {
+                if first.content(*round_number) == second.content(*round_number) {
+                    return Err("Double-sign evidence needs two different round headers".to_string());
+                }
+                for header in [first, second] {
+                    offender_key.verify(&header.content(*round_number), &header.signature)
+                        .map_err(|_| "Round header not signed by the validator".to_string())?;
+                }
+                Ok(())
+            }
+            Evidence::InvalidBlock { block } => {
+                if &block.public_key != offender_key {
+                    return Err("Block not proposed with the validator's key".to_string());
+                }
+                offender_key.verify(&block.block_id, &block.signature)
+                    .map_err(|_| "Block not signed by the validator".to_string())?;
+                if block.validate_merkle_root() && block.validate_tx_validity_windows() {
+                    return Err("Block is valid".to_string());
+                }
+                Ok(())
+            }
+            // Checked against the validator set's reputation record when applied
+            Evidence::Downtime { .. } => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SlashingAction {
+    /// Report an offense; the reporter earns a share of the slashed stake
+    Report(Box<Evidence>),
+    /// Re-activate the signing validator after its jail period
+    Unjail,
+}
+
+/// Slashing report or unjail request signed by the sender
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashingTx {
+    pub action: SlashingAction,
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+    pub public_key: VerifyingKey,
+    pub signature: Signature,
+}
+
+impl SlashingTx {
+    /// Canonical message the sender signs
+    pub fn signing_message(action: &SlashingAction, findag_time: u64, shard_id: ShardId) -> Vec<u8> {
+        let mut message = SLASHING_PAYLOAD_TAG.to_vec();
+        message.extend_from_slice(&bincode::serialize(action).expect("slashing action serialization"));
+        message.extend_from_slice(&findag_time.to_be_bytes());
+        message.extend_from_slice(&shard_id.0.to_be_bytes());
+        message
+    }
+
+    pub fn sign(action: SlashingAction, findag_time: u64, signing_key: &SigningKey) -> Self {
+        let signature = signing_key.sign(&Self::signing_message(&action, findag_time, STAKING_SHARD));
+        Self { action, findag_time, shard_id: STAKING_SHARD, public_key: signing_key.verifying_key(), signature }
+    }
+
+    /// Address of the reporter, or of the validator asking to be unjailed
+    pub fn sender(&self) -> Address {
+        Address::from_verifying_key(&self.public_key)
+    }
+
+    pub fn verify(&self) -> Result<(), String> {
+        let message = Self::signing_message(&self.action, self.findag_time, self.shard_id);
+        self.public_key.verify(&message, &self.signature)
+            .map_err(|_| "Invalid slashing signature".to_string())?;
+        if self.shard_id != STAKING_SHARD {
+            return Err(format!("Slashing transactions must be on shard {}", STAKING_SHARD.0));
+        }
+        Ok(())
+    }
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = SLASHING_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&bincode::serialize(self).expect("slashing tx serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a slashing transaction
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(SLASHING_PAYLOAD_TAG)?;
+        Some(bincode::deserialize(body).map_err(|e| format!("Invalid slashing payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the signed body
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|slashing| {
+            if slashing.shard_id != tx.shard_id
+                || slashing.findag_time != tx.findag_time
+                || slashing.public_key != tx.public_key
+                || slashing.sender() != tx.from
+            {
+                return Err("Slashing envelope does not match its payload".to_string());
+            }
+            Ok(slashing)
+        }))
+    }
+
+    /// Wrap in a zero-amount `Transaction` from the sender to itself
+    pub fn to_transaction(&self) -> Transaction {
+        let address = self.sender();
+        let payload = self.to_payload();
+        Transaction {
+            from: address.clone(),
+            to: address,
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature: self.signature,
+            public_key: self.public_key,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        }
+    }
+}
+
+/// Outcome of one applied piece of evidence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlashRecord {
+    pub validator: String,
+    pub offense: Offense,
+    pub evidence_id: [u8; 32],
+    pub reporter: String,
+    pub round: u64,
+    pub stake_slashed: u64,
+    pub reporter_reward: u64,
+    pub burned: u64,
+    pub jailed_until: u64,
+}
+
+/// Verifies evidence included on chain, slashes the offender's bonded stake
+/// (including delegations and pending unbondings), pays the reporter, burns the
+/// rest and jails the validator. Every input comes from the chain, so replaying
+/// the same transactions reproduces the same records.
+pub struct SlashingEngine {
+    ledger: Arc<StakingLedger>,
+}
+
+impl SlashingEngine {
+    pub fn new(ledger: Arc<StakingLedger>) -> Self {
+        Self { ledger }
+    }
+
+    /// Apply a slashing transaction included in a block
+    pub fn apply_slashing_tx(&self, slashing_tx: &SlashingTx) -> Result<Option<SlashRecord>, String> {
+        slashing_tx.verify()?;
+        let round = self.ledger.current_round();
+        match &slashing_tx.action {
+            SlashingAction::Unjail => {
+                let validator = slashing_tx.sender();
+                let validator_set = self.ledger.validator_set();
+                let mut validator_set = validator_set.lock().unwrap();
+                validator_set.unjail(&validator, round)?;
+                self.ledger.persist_validator_set(&validator_set);
+                println!("[DEBUG] Slashing: {validator} unjailed at round {round}");
+                Ok(None)
+            }
+            SlashingAction::Report(evidence) => self.slash(evidence, &slashing_tx.sender(), round).map(Some),
+        }
+    }
+
+    fn slash(&self, evidence: &Evidence, reporter: &Address, round: u64) -> Result<SlashRecord, String> {
+        let state_db = self.ledger.state_db();
+        let control = self.ledger.control();
+        let offender = evidence.offender();
+        let offense = evidence.offense();
+        let evidence_id = evidence.id();
+        if state_db.has_slash_record(&evidence_id) {
+            return Err("Evidence already processed".to_string());
+        }
+
+        let validator_set = self.ledger.validator_set();
+        let mut validator_set = validator_set.lock().unwrap();
+        let validator = validator_set.get_validator(&offender)
+            .ok_or_else(|| format!("Unknown validator {offender}"))?
+            .clone();
+        evidence.verify(&validator.public_key)?;
+        if let Evidence::Downtime { consecutive_missed, .. } = evidence {
+            let min_missed = control.param_u64(parameters::SLASHING_DOWNTIME_MIN_MISSED)?;
+            let recorded = validator.reputation.consecutive_failures;
+            if !validator.is_active || (*consecutive_missed as u64) < min_missed || recorded < *consecutive_missed {
+                return Err(format!("Downtime of {offender} not established ({recorded} consecutive misses recorded)"));
+            }
+        }
+        self.penalize(&mut validator_set, &validator, offense, evidence_id, Some(reporter), round)
+    }
+
+    /// Slash a validator as decided by a passed SlashValidator proposal, with the same
+    /// penalty, jailing and record as evidence. Nobody is paid a reporter reward.
+    pub fn apply_governance_slash(&self, offender: &Address, proposal_id: &str, round: u64) -> Result<SlashRecord, String> {
+        let state_db = self.ledger.state_db();
+        let evidence_id: [u8; 32] = Sha256::new()
+            .chain_update(Offense::Governance.name())
+            .chain_update(offender.as_str())
+            .chain_update(proposal_id)
+            .finalize()
+            .into();
+        if state_db.has_slash_record(&evidence_id) {
+            return Err(format!("Proposal {proposal_id} already slashed {offender}"));
+        }
+        let validator_set = self.ledger.validator_set();
+        let mut validator_set = validator_set.lock().unwrap();
+        let validator = validator_set.get_validator(offender)
+            .ok_or_else(|| format!("Unknown validator {offender}"))?
+            .clone();
+        self.penalize(&mut validator_set, &validator, Offense::Governance, evidence_id, None, round)
+    }
+
+    /// Cut the offender's stake, pay the reporter if any, burn the rest, jail and record
+    fn penalize(
+        &self,
+        validator_set: &mut ValidatorSet,
+        validator: &Validator,
+        offense: Offense,
+        evidence_id: [u8; 32],
+        reporter: Option<&Address>,
+        round: u64,
+    ) -> Result<SlashRecord, String> {
+        let state_db = self.ledger.state_db();
+        let control = self.ledger.control();
+        let offender = validator.address.clone();
+        let (fraction_param, jail_param) = offense.penalty_params();
+        let fraction_bps = control.param_u64(fraction_param)? as u128;
+        let cut = |amount: u64| (amount as u128 * fraction_bps / BPS_DENOMINATOR) as u64;
+
+        // Escrowed stake: delegations (self-bond included) and unbondings still pending
+        let delegations = state_db.delegations_to(offender.as_str());
+        let delegated: u64 = delegations.iter().map(|(_, amount)| amount).sum();
+        let mut escrow_slashed = 0u64;
+        let mut self_bond_slashed = 0u64;
+        for (delegator, amount) in delegations {
+            let slashed = cut(amount);
+            state_db.set_delegation(&delegator, offender.as_str(), amount - slashed)?;
+            escrow_slashed += slashed;
+            if delegator == offender.as_str() {
+                self_bond_slashed += slashed;
+            }
+        }
+        let mut unbonding_slashed = 0u64;
+        for (release_round, delegator, target, amount) in state_db.unbondings() {
+            if target != offender.as_str() || release_round <= round {
+                continue;
+            }
+            let slashed = cut(amount);
+            state_db.remove_unbonding(release_round, &delegator, &target)?;
+            state_db.add_unbonding(release_round, &delegator, &target, amount - slashed)?;
+            unbonding_slashed += slashed;
+        }
+
+        // Stake the validator was registered with is not escrowed; it is only written down
+        let unescrowed_slashed = cut(validator.stake.saturating_sub(delegated));
+        validator_set.remove_stake(&offender, self_bond_slashed + unescrowed_slashed, true)?;
+        validator_set.remove_stake(&offender, escrow_slashed - self_bond_slashed, false)?;
+
+        // Pay the reporter from escrow and burn the remainder
+        let total_escrow = escrow_slashed + unbonding_slashed;
+        let reporter_reward = match reporter {
+            Some(reporter) => {
+                let reward_bps = control.param_u64(parameters::SLASHING_REPORTER_REWARD_BPS)? as u128;
+                let reward = (total_escrow as u128 * reward_bps / BPS_DENOMINATOR) as u64;
+                state_db.transfer(STAKING_SHARD.0, STAKING_ESCROW_ADDRESS, reporter.as_str(), reward, STAKING_ASSET)?;
+                reward
+            }
+            None => 0,
+        };
+        let burned = total_escrow - reporter_reward;
+        let escrow_balance = state_db.get_balance(STAKING_SHARD.0, STAKING_ESCROW_ADDRESS, STAKING_ASSET);
+        state_db.set_balance(STAKING_SHARD.0, STAKING_ESCROW_ADDRESS, STAKING_ASSET, escrow_balance.saturating_sub(burned))?;
+
+        let jailed_until = round + control.param_u64(jail_param)?;
+        validator_set.jail(&offender, jailed_until)?;
+        self.ledger.persist_validator_set(validator_set);
+
+        let record = SlashRecord {
+            validator: offender.to_string(),
+            offense,
+            evidence_id,
+            reporter: reporter.map_or_else(|| "governance".to_string(), |r| r.to_string()),
+            round,
+            stake_slashed: total_escrow + unescrowed_slashed,
+            reporter_reward,
+            burned,
+            jailed_until,
+        };
+        state_db.put_slash_record(&record)?;
+        println!(
+            "[DEBUG] Slashing: {offender} slashed {} for {} (reward {reporter_reward}, burned {burned}), jailed until round {jailed_until}",
+            record.stake_slashed, offense.name()
+        );
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::governance_executor::ChainControl;
+    use crate::consensus::staking::{StakingAction, StakingTx};
+    use crate::consensus::validator_set::ValidatorSet;
+    use crate::storage::state::StateDB;
+    use std::sync::Mutex;
+
+    fn signed_header(key: &SigningKey, round_number: u64, findag_time: u64) -> SignedRoundHeader {
+        let mut header = SignedRoundHeader {
+            parent_round_hash: [9u8; 32],
+            finalized_block_hashes: vec![[findag_time as u8; 32]],
+            block_hashtimers: vec![[0u8; 32]],
+            findag_time,
+            signature: Signature::from_bytes(&[0u8; 64]),
+        };
+        header.signature = key.sign(&header.content(round_number));
+        header
+    }
+
+    #[test]
+    fn test_double_sign_slashes_burns_and_jails() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(StateDB::new(dir.path().to_str().unwrap()));
+        let validator_key = SigningKey::from_bytes(&[11u8; 32]);
+        let delegator_key = SigningKey::from_bytes(&[12u8; 32]);
+        let reporter_key = SigningKey::from_bytes(&[13u8; 32]);
+        let validator = Address::from_verifying_key(&validator_key.verifying_key());
+        let delegator = Address::from_verifying_key(&delegator_key.verifying_key());
+        let reporter = Address::from_verifying_key(&reporter_key.verifying_key());
+        state_db.set_balance(0, validator.as_str(), STAKING_ASSET, 2_000).unwrap();
+        state_db.set_balance(0, delegator.as_str(), STAKING_ASSET, 1_000).unwrap();
+
+        let validator_set = Arc::new(Mutex::new(ValidatorSet::new()));
+        validator_set.lock().unwrap().add_validator(validator.clone(), validator_key.verifying_key(), 0);
+        let ledger = Arc::new(StakingLedger::new(state_db.clone(), validator_set.clone(), Arc::new(ChainControl::new())));
+        let delegate = |amount| StakingAction::Delegate { validator: validator.clone(), amount };
+        ledger.apply_staking_tx(&StakingTx::sign(delegate(2_000), 1, &validator_key)).unwrap();
+        ledger.apply_staking_tx(&StakingTx::sign(delegate(1_000), 2, &delegator_key)).unwrap();
+        ledger.on_round_finalized(5);
+        let engine = SlashingEngine::new(ledger.clone());
+
+        // A downtime report the reputation record does not back is rejected
+        let downtime = Evidence::Downtime { validator: validator.clone(), consecutive_missed: 60, observed_round: 5 };
+        assert!(engine.apply_slashing_tx(&SlashingTx::sign(SlashingAction::Report(Box::new(downtime)), 3, &reporter_key)).is_err());
+
+        let evidence = Evidence::DoubleSign {
+            validator: validator.clone(),
+            round_number: 4,
+            first: signed_header(&validator_key, 4, 100),
+            second: signed_header(&validator_key, 4, 101),
+        };
+        let report = SlashingTx::sign(SlashingAction::Report(Box::new(evidence.clone())), 4, &reporter_key);
+        let record = engine.apply_slashing_tx(&report).unwrap().unwrap();
+        assert_eq!((record.stake_slashed, record.reporter_reward, record.burned), (150, 15, 135));
+        assert_eq!(record.jailed_until, 10_005);
+        assert_eq!(state_db.get_delegation(delegator.as_str(), validator.as_str()), 950);
+        assert_eq!(state_db.get_balance(0, STAKING_ESCROW_ADDRESS, STAKING_ASSET), 2_850);
+        assert_eq!(state_db.get_balance(0, reporter.as_str(), STAKING_ASSET), 15);
+        {
+            let set = validator_set.lock().unwrap();
+            let v = set.get_validator(&validator).unwrap();
+            assert_eq!((v.stake, v.self_bond, v.is_active), (2_850, 1_900, false));
+        }
+
+        // Replaying the same evidence, even re-signed, does not slash twice
+        let replay = SlashingTx::sign(SlashingAction::Report(Box::new(evidence)), 5, &reporter_key);
+        assert!(engine.apply_slashing_tx(&replay).is_err());
+
+        assert!(engine.apply_slashing_tx(&SlashingTx::sign(SlashingAction::Unjail, 6, &validator_key)).is_err());
+        ledger.on_round_finalized(10_005);
+        engine.apply_slashing_tx(&SlashingTx::sign(SlashingAction::Unjail, 7, &validator_key)).unwrap();
+        assert!(validator_set.lock().unwrap().get_validator(&validator).unwrap().is_active);
+        assert_eq!(state_db.slash_records_of(validator.as_str()), vec![record]);
+    }
+}
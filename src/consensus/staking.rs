@@ -0,0 +1,326 @@
+use crate::consensus::governance_executor::ChainControl;
+use crate::consensus::parameters;
+use crate::consensus::rewards::{self, REWARD_ESCROW_ADDRESS};
+use crate::consensus::validator_set::ValidatorSet;
+use crate::core::address::Address;
+use crate::core::types::{ShardId, Transaction};
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use crate::storage::persistent::PersistentStorage;
+use crate::storage::state::StateDB;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Serialize, Deserialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Payload prefix identifying a staking operation carried in `Transaction.payload`
+pub const STAKING_PAYLOAD_TAG: &[u8] = b"FDG:STAKE:1";
+
+/// Account holding bonded and unbonding stake
+pub const STAKING_ESCROW_ADDRESS: &str = "staking:escrow";
+
+/// Asset stake is bonded in
+pub const STAKING_ASSET: &str = "USD";
+
+/// Shard staking operations and escrow live on
+pub const STAKING_SHARD: ShardId = ShardId(0);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StakingAction {
+    /// Bond funds to a validator; a validator delegating to itself adds to its self-bond
+    Delegate { validator: Address, amount: u64 },
+    /// Start unbonding; funds return after the unbonding period
+    Undelegate { validator: Address, amount: u64 },
+    /// Set the signing validator's commission on delegators' rewards
+    SetCommission { rate_bps: u64 },
+    /// Withdraw the signer's claimable rewards
+    ClaimRewards,
+}
+
+/// Staking operation signed by the delegator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakingTx {
+    pub action: StakingAction,
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+    pub public_key: VerifyingKey,
+    pub signature: Signature,
+}
+
+impl StakingTx {
+    /// Canonical message the delegator signs
+    pub fn signing_message(action: &StakingAction, findag_time: u64, shard_id: ShardId) -> Vec<u8> {
+        let mut message = STAKING_PAYLOAD_TAG.to_vec();
+        message.extend_from_slice(&bincode::serialize(action).expect("staking action serialization"));
+        message.extend_from_slice(&findag_time.to_be_bytes());
+        message.extend_from_slice(&shard_id.0.to_be_bytes());
+        message
+    }
+
+    pub fn sign(action: StakingAction, findag_time: u64, signing_key: &SigningKey) -> Self {
+        let signature = signing_key.sign(&Self::signing_message(&action, findag_time, STAKING_SHARD));
+        Self { action, findag_time, shard_id: STAKING_SHARD, public_key: signing_key.verifying_key(), signature }
+    }
+
+    /// Address of the delegator
+    pub fn delegator(&self) -> Address {
+        Address::from_verifying_key(&self.public_key)
+    }
+
+    /// Check the signature and the operation's static rules
+    pub fn verify(&self) -> Result<(), String> {
+        let message = Self::signing_message(&self.action, self.findag_time, self.shard_id);
+        self.public_key.verify(&message, &self.signature)
+            .map_err(|_| "Invalid staking signature".to_string())?;
+        if self.shard_id != STAKING_SHARD {
+            return Err(format!("Staking operations must be on shard {}", STAKING_SHARD.0));
+        }
+        match self.action {
+            StakingAction::Delegate { amount: 0, .. } | StakingAction::Undelegate { amount: 0, .. } => {
+                Err("Staking amount must be positive".to_string())
+            }
+            StakingAction::SetCommission { rate_bps } if rate_bps > 10_000 => {
+                Err("Commission cannot exceed 10000 basis points".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = STAKING_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&bincode::serialize(self).expect("staking tx serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a staking operation
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(STAKING_PAYLOAD_TAG)?;
+        Some(bincode::deserialize(body).map_err(|e| format!("Invalid staking payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the signed body
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|op| {
+            if op.shard_id != tx.shard_id
+                || op.findag_time != tx.findag_time
+                || op.public_key != tx.public_key
+                || op.delegator() != tx.from
+            {
+                return Err("Staking envelope does not match its payload".to_string());
+            }
+            Ok(op)
+        }))
+    }
+
+    /// Wrap in a zero-amount `Transaction` from the delegator to itself
+    pub fn to_transaction(&self) -> Transaction {
+        let address = self.delegator();
+        let payload = self.to_payload();
+        Transaction {
+            from: address.clone(),
+            to: address,
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature: self.signature,
+            public_key: self.public_key,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        }
+    }
+}
+
+/// Applies staking operations: escrows bonded funds in the StateDB, keeps
+/// validator stake in sync and releases unbonded funds after the unbonding period
+pub struct StakingLedger {
+    state_db: Arc<StateDB>,
+    validator_set: Arc<Mutex<ValidatorSet>>,
+    control: Arc<ChainControl>,
+    current_round: AtomicU64,
+    storage: Option<Arc<PersistentStorage>>,
+}
+
+impl StakingLedger {
+    pub fn new(state_db: Arc<StateDB>, validator_set: Arc<Mutex<ValidatorSet>>, control: Arc<ChainControl>) -> Self {
+        Self { state_db, validator_set, control, current_round: AtomicU64::new(0), storage: None }
+    }
+
+    /// Persist validator stake through `store_validator_set` after every change
+    pub fn with_storage(mut self, storage: Arc<PersistentStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Persist the validator set so bonded, unbonded and slashed stake survives a restart
+    pub fn persist_validator_set(&self, validator_set: &ValidatorSet) {
+        if let Some(storage) = &self.storage {
+            if let Err(e) = storage.store_validator_set(validator_set) {
+                println!("[DEBUG] Staking: failed to persist validator set: {e}");
+            }
+        }
+    }
+
+    pub fn state_db(&self) -> Arc<StateDB> {
+        self.state_db.clone()
+    }
+
+    pub fn validator_set(&self) -> Arc<Mutex<ValidatorSet>> {
+        self.validator_set.clone()
+    }
+
+    pub fn control(&self) -> Arc<ChainControl> {
+        self.control.clone()
+    }
+
+    /// Latest finalized round seen by the ledger
+    pub fn current_round(&self) -> u64 {
+        self.current_round.load(Ordering::SeqCst)
+    }
+
+    /// Apply a staking operation included in a block
+    pub fn apply_staking_tx(&self, staking_tx: &StakingTx) -> Result<(), String> {
+        staking_tx.verify()?;
+        let delegator = staking_tx.delegator();
+        let shard = staking_tx.shard_id.0;
+
+        match &staking_tx.action {
+            StakingAction::Delegate { validator, amount } => {
+                let self_bond = &delegator == validator;
+                let mut validator_set = self.validator_set.lock().unwrap();
+                let target = validator_set.get_validator(validator)
+                    .ok_or_else(|| format!("Unknown validator {validator}"))?;
+                if !target.is_active {
+                    return Err(format!("Validator {validator} is not active"));
+                }
+                let min_self_bond = self.control.param_u64(parameters::STAKING_MIN_SELF_BOND)?;
+                if !self_bond && target.self_bond < min_self_bond {
+                    return Err(format!("Validator {validator} is below the minimum self-bond of {min_self_bond}"));
+                }
+                self.state_db.transfer(shard, delegator.as_str(), STAKING_ESCROW_ADDRESS, *amount, STAKING_ASSET)?;
+                let bonded = self.state_db.get_delegation(delegator.as_str(), validator.as_str());
+                self.state_db.set_delegation(delegator.as_str(), validator.as_str(), bonded + amount)?;
+                validator_set.add_stake(validator, *amount, self_bond)?;
+                self.persist_validator_set(&validator_set);
+                println!("[DEBUG] Staking: {delegator} bonded {amount} to {validator}");
+            }
+            StakingAction::Undelegate { validator, amount } => {
+                let self_bond = &delegator == validator;
+                let bonded = self.state_db.get_delegation(delegator.as_str(), validator.as_str());
+                if bonded < *amount {
+                    return Err(format!("Only {bonded} bonded to {validator}"));
+                }
+                let mut validator_set = self.validator_set.lock().unwrap();
+                // A removed validator's delegators can still withdraw
+                if validator_set.get_validator(validator).is_some() {
+                    validator_set.remove_stake(validator, *amount, self_bond)?;
+                    self.persist_validator_set(&validator_set);
+                }
+                self.state_db.set_delegation(delegator.as_str(), validator.as_str(), bonded - amount)?;
+                let release_round = self.current_round()
+                    + self.control.param_u64(parameters::STAKING_UNBONDING_ROUNDS)?;
+                self.state_db.add_unbonding(release_round, delegator.as_str(), validator.as_str(), *amount)?;
+                println!("[DEBUG] Staking: {delegator} unbonding {amount} from {validator} until round {release_round}");
+            }
+            StakingAction::SetCommission { rate_bps } => {
+                let mut validator_set = self.validator_set.lock().unwrap();
+                validator_set.set_commission(&delegator, *rate_bps)?;
+                self.persist_validator_set(&validator_set);
+                println!("[DEBUG] Staking: {delegator} set commission to {rate_bps} bps");
+            }
+            StakingAction::ClaimRewards => {
+                let amount = self.state_db.get_claimable_reward(delegator.as_str());
+                if amount == 0 {
+                    return Err("No rewards to claim".to_string());
+                }
+                let asset = rewards::reward_asset(&self.control);
+                self.state_db.transfer(shard, REWARD_ESCROW_ADDRESS, delegator.as_str(), amount, &asset)?;
+                self.state_db.set_claimable_reward(delegator.as_str(), 0)?;
+                println!("[DEBUG] Staking: {delegator} claimed {amount} {asset} in rewards");
+            }
+        }
+        Ok(())
+    }
+
+    /// Advance to a finalized round and return matured unbondings to their delegators
+    pub fn on_round_finalized(&self, round: u64) {
+        self.current_round.fetch_max(round, Ordering::SeqCst);
+        for (release_round, delegator, validator, amount) in self.state_db.unbondings() {
+            if release_round > round {
+                break;
+            }
+            let released = self.state_db
+                .transfer(STAKING_SHARD.0, STAKING_ESCROW_ADDRESS, &delegator, amount, STAKING_ASSET)
+                .and_then(|_| self.state_db.remove_unbonding(release_round, &delegator, &validator));
+            match released {
+                Ok(()) => println!("[DEBUG] Staking: released {amount} to {delegator} at round {round}"),
+                Err(e) => println!("[DEBUG] Staking: failed to release unbonding for {delegator}: {e}"),
+            }
+        }
+    }
+
+    /// Amounts `delegator` has bonded, per validator
+    pub fn delegations_of(&self, delegator: &str) -> Vec<(String, u64)> {
+        self.state_db.delegations_of(delegator)
+    }
+
+    /// Pending unbondings of `delegator` as (release_round, validator, amount)
+    pub fn unbondings_of(&self, delegator: &str) -> Vec<(u64, String, u64)> {
+        self.state_db.unbondings().into_iter()
+            .filter(|(_, d, _, _)| d == delegator)
+            .map(|(round, _, validator, amount)| (round, validator, amount))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delegation_min_self_bond_and_unbonding() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(StateDB::new(dir.path().to_str().unwrap()));
+        let validator_key = SigningKey::from_bytes(&[7u8; 32]);
+        let delegator_key = SigningKey::from_bytes(&[8u8; 32]);
+        let validator = Address::from_verifying_key(&validator_key.verifying_key());
+        let delegator = Address::from_verifying_key(&delegator_key.verifying_key());
+        state_db.set_balance(0, validator.as_str(), STAKING_ASSET, 5_000).unwrap();
+        state_db.set_balance(0, delegator.as_str(), STAKING_ASSET, 5_000).unwrap();
+
+        let validator_set = Arc::new(Mutex::new(ValidatorSet::new()));
+        validator_set.lock().unwrap().add_validator(validator.clone(), validator_key.verifying_key(), 0);
+        let ledger = StakingLedger::new(state_db.clone(), validator_set.clone(), Arc::new(ChainControl::new()));
+
+        let delegate = |amount| StakingAction::Delegate { validator: validator.clone(), amount };
+        // Delegations wait until the validator meets the minimum self-bond
+        assert!(ledger.apply_staking_tx(&StakingTx::sign(delegate(500), 1, &delegator_key)).is_err());
+        ledger.apply_staking_tx(&StakingTx::sign(delegate(1_000), 2, &validator_key)).unwrap();
+        ledger.apply_staking_tx(&StakingTx::sign(delegate(500), 3, &delegator_key)).unwrap();
+        {
+            let set = validator_set.lock().unwrap();
+            let v = set.get_validator(&validator).unwrap();
+            assert_eq!((v.stake, v.self_bond), (1_500, 1_000));
+        }
+        assert_eq!(ledger.delegations_of(delegator.as_str()), vec![(validator.to_string(), 500)]);
+        assert_eq!(state_db.get_balance(0, delegator.as_str(), STAKING_ASSET), 4_500);
+
+        ledger.on_round_finalized(10);
+        let undelegate = StakingAction::Undelegate { validator: validator.clone(), amount: 200 };
+        ledger.apply_staking_tx(&StakingTx::sign(undelegate, 4, &delegator_key)).unwrap();
+        assert_eq!(ledger.unbondings_of(delegator.as_str()), vec![(110, validator.to_string(), 200)]);
+        assert_eq!(validator_set.lock().unwrap().get_validator(&validator).unwrap().stake, 1_300);
+
+        ledger.on_round_finalized(109);
+        assert_eq!(state_db.get_balance(0, delegator.as_str(), STAKING_ASSET), 4_500);
+        ledger.on_round_finalized(110);
+        assert_eq!(state_db.get_balance(0, delegator.as_str(), STAKING_ASSET), 4_700);
+        assert!(ledger.unbondings_of(delegator.as_str()).is_empty());
+    }
+}
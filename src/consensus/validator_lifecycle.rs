@@ -0,0 +1,295 @@
+use crate::consensus::parameters;
+use crate::consensus::staking::{StakingLedger, STAKING_SHARD};
+use crate::consensus::validator_set::{KeyRotation, ValidatorSet};
+use crate::core::address::Address;
+use crate::core::types::{ShardId, Transaction};
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use crate::storage::persistent::PersistentStorage;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Serialize, Deserialize};
+use std::sync::Arc;
+
+/// Payload prefix identifying a validator lifecycle action carried in `Transaction.payload`
+pub const LIFECYCLE_PAYLOAD_TAG: &[u8] = b"FDG:VALIDATOR:1";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LifecycleAction {
+    /// Join the validator set; committee selection starts once the minimum self-bond is staked
+    Register { institution_name: String, region: String },
+    /// Replace the consensus key at the requested activation round
+    RotateKey(Box<RotationRequest>),
+    /// Leave the validator set after the exit drain period
+    Exit,
+}
+
+/// Requested consensus key rotation. `proof` is the new key's signature over
+/// `rotation_proof_message`, showing the operator holds it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationRequest {
+    pub new_public_key: VerifyingKey,
+    pub activation_round: u64,
+    pub proof: Signature,
+}
+
+/// Validator lifecycle action signed with the validator's current consensus key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorTx {
+    pub validator: Address,
+    pub action: LifecycleAction,
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+    pub public_key: VerifyingKey,
+    pub signature: Signature,
+}
+
+/// Message the incoming consensus key signs to prove possession
+pub fn rotation_proof_message(validator: &Address, new_public_key: &VerifyingKey, activation_round: u64) -> Vec<u8> {
+    let mut message = LIFECYCLE_PAYLOAD_TAG.to_vec();
+    message.extend_from_slice(b"rotate");
+    message.extend_from_slice(validator.as_str().as_bytes());
+    message.extend_from_slice(new_public_key.as_bytes());
+    message.extend_from_slice(&activation_round.to_be_bytes());
+    message
+}
+
+impl ValidatorTx {
+    /// Canonical message the validator signs
+    pub fn signing_message(validator: &Address, action: &LifecycleAction, findag_time: u64, shard_id: ShardId) -> Vec<u8> {
+        let mut message = LIFECYCLE_PAYLOAD_TAG.to_vec();
+        message.extend_from_slice(validator.as_str().as_bytes());
+        message.extend_from_slice(&bincode::serialize(action).expect("lifecycle action serialization"));
+        message.extend_from_slice(&findag_time.to_be_bytes());
+        message.extend_from_slice(&shard_id.0.to_be_bytes());
+        message
+    }
+
+    pub fn sign(validator: Address, action: LifecycleAction, findag_time: u64, signing_key: &SigningKey) -> Self {
+        let signature = signing_key.sign(&Self::signing_message(&validator, &action, findag_time, STAKING_SHARD));
+        Self { validator, action, findag_time, shard_id: STAKING_SHARD, public_key: signing_key.verifying_key(), signature }
+    }
+
+    /// Check the signature and the action's static rules. Whether the signer holds
+    /// the validator's current key is checked against the validator set when applied.
+    pub fn verify(&self) -> Result<(), String> {
+        let message = Self::signing_message(&self.validator, &self.action, self.findag_time, self.shard_id);
+        self.public_key.verify(&message, &self.signature)
+            .map_err(|_| "Invalid validator signature".to_string())?;
+        if self.shard_id != STAKING_SHARD {
+            return Err(format!("Validator transactions must be on shard {}", STAKING_SHARD.0));
+        }
+        match &self.action {
+            LifecycleAction::Register { institution_name, region } => {
+                if Address::from_verifying_key(&self.public_key) != self.validator {
+                    return Err("Registration must be signed by the validator's own key".to_string());
+                }
+                if institution_name.trim().is_empty() || region.trim().is_empty() {
+                    return Err("Institution name and region are required".to_string());
+                }
+                Ok(())
+            }
+            LifecycleAction::RotateKey(rotation) => {
+                let message = rotation_proof_message(&self.validator, &rotation.new_public_key, rotation.activation_round);
+                rotation.new_public_key.verify(&message, &rotation.proof)
+                    .map_err(|_| "Invalid proof of possession for the new key".to_string())
+            }
+            LifecycleAction::Exit => Ok(()),
+        }
+    }
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = LIFECYCLE_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&bincode::serialize(self).expect("validator tx serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a lifecycle action
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(LIFECYCLE_PAYLOAD_TAG)?;
+        Some(bincode::deserialize(body).map_err(|e| format!("Invalid validator payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the signed body
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|validator_tx| {
+            if validator_tx.shard_id != tx.shard_id
+                || validator_tx.findag_time != tx.findag_time
+                || validator_tx.public_key != tx.public_key
+                || validator_tx.validator != tx.from
+            {
+                return Err("Validator envelope does not match its payload".to_string());
+            }
+            Ok(validator_tx)
+        }))
+    }
+
+    /// Wrap in a zero-amount `Transaction` from the validator to itself
+    pub fn to_transaction(&self) -> Transaction {
+        let payload = self.to_payload();
+        Transaction {
+            from: self.validator.clone(),
+            to: self.validator.clone(),
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature: self.signature,
+            public_key: self.public_key,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        }
+    }
+}
+
+/// Applies validator registrations, key rotations and exits, and persists the
+/// validator set after every change
+pub struct ValidatorLifecycle {
+    ledger: Arc<StakingLedger>,
+    storage: Option<Arc<PersistentStorage>>,
+}
+
+impl ValidatorLifecycle {
+    pub fn new(ledger: Arc<StakingLedger>) -> Self {
+        Self { ledger, storage: None }
+    }
+
+    /// Persist the validator set through `store_validator_set`
+    pub fn with_storage(mut self, storage: Arc<PersistentStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Apply a lifecycle action included in a block
+    pub fn apply_validator_tx(&self, validator_tx: &ValidatorTx) -> Result<(), String> {
+        validator_tx.verify()?;
+        let address = &validator_tx.validator;
+        let round = self.ledger.current_round();
+        let control = self.ledger.control();
+        let validator_set = self.ledger.validator_set();
+        let mut validator_set = validator_set.lock().unwrap();
+
+        if let LifecycleAction::Register { institution_name, region } = &validator_tx.action {
+            if validator_set.get_validator(address).is_some() {
+                return Err(format!("Validator {address} is already registered"));
+            }
+            if validator_set.validators.values().any(|v| v.public_key == validator_tx.public_key) {
+                return Err("Consensus key already in use".to_string());
+            }
+            validator_set.add_validator_with_metadata(
+                address.clone(),
+                validator_tx.public_key,
+                0,
+                institution_name.clone(),
+                region.clone(),
+            );
+            println!("[DEBUG] Validators: registered {address} ({institution_name}, {region})");
+        } else {
+            let validator = validator_set.get_validator(address)
+                .ok_or_else(|| format!("Unknown validator {address}"))?;
+            if validator.public_key != validator_tx.public_key {
+                return Err("Not signed with the validator's current consensus key".to_string());
+            }
+            match &validator_tx.action {
+                LifecycleAction::RotateKey(rotation) => {
+                    let earliest = round + control.param_u64(parameters::VALIDATORS_KEY_ROTATION_DELAY)?;
+                    if rotation.activation_round < earliest {
+                        return Err(format!("Key rotation cannot activate before round {earliest}"));
+                    }
+                    validator_set.schedule_key_rotation(address, KeyRotation {
+                        public_key: rotation.new_public_key,
+                        activation_round: rotation.activation_round,
+                    })?;
+                    println!("[DEBUG] Validators: {address} rotates its key at round {}", rotation.activation_round);
+                }
+                LifecycleAction::Exit => {
+                    let exit_round = round + control.param_u64(parameters::VALIDATORS_EXIT_DRAIN_ROUNDS)?;
+                    validator_set.request_exit(address, exit_round)?;
+                    println!("[DEBUG] Validators: {address} exits at round {exit_round}");
+                }
+                LifecycleAction::Register { .. } => unreachable!("handled above"),
+            }
+        }
+
+        self.persist(&validator_set);
+        Ok(())
+    }
+
+    /// Activate key rotations and remove drained validators due at `round`
+    pub fn on_round_finalized(&self, round: u64) {
+        let validator_set = self.ledger.validator_set();
+        let mut validator_set = validator_set.lock().unwrap();
+        let rotated = validator_set.activate_key_rotations(round);
+        let exited = validator_set.remove_exited(round);
+        for address in &rotated {
+            println!("[DEBUG] Validators: {address} switched consensus key at round {round}");
+        }
+        for address in &exited {
+            println!("[DEBUG] Validators: {address} left the validator set at round {round}");
+        }
+        if !rotated.is_empty() || !exited.is_empty() {
+            self.persist(&validator_set);
+        }
+    }
+
+    fn persist(&self, validator_set: &ValidatorSet) {
+        if let Some(storage) = &self.storage {
+            if let Err(e) = storage.store_validator_set(validator_set) {
+                println!("[DEBUG] Validators: failed to persist validator set: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::governance_executor::ChainControl;
+    use crate::storage::state::StateDB;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_register_rotate_and_exit() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(StateDB::new(dir.path().to_str().unwrap()));
+        let validator_set = Arc::new(Mutex::new(ValidatorSet::new()));
+        let ledger = Arc::new(StakingLedger::new(state_db, validator_set.clone(), Arc::new(ChainControl::new())));
+        let lifecycle = ValidatorLifecycle::new(ledger.clone());
+
+        let key = SigningKey::from_bytes(&[31u8; 32]);
+        let new_key = SigningKey::from_bytes(&[32u8; 32]);
+        let address = Address::from_verifying_key(&key.verifying_key());
+        let register = LifecycleAction::Register { institution_name: "Example Bank".to_string(), region: "EU".to_string() };
+        lifecycle.apply_validator_tx(&ValidatorTx::sign(address.clone(), register.clone(), 1, &key)).unwrap();
+        assert!(lifecycle.apply_validator_tx(&ValidatorTx::sign(address.clone(), register, 2, &key)).is_err());
+        assert_eq!(validator_set.lock().unwrap().get_validator(&address).unwrap().region.as_deref(), Some("EU"));
+
+        // Rotation needs a proof from the new key and the minimum activation delay
+        let rotate = |activation_round, prover: &SigningKey| LifecycleAction::RotateKey(Box::new(RotationRequest {
+            new_public_key: new_key.verifying_key(),
+            activation_round,
+            proof: prover.sign(&rotation_proof_message(&address, &new_key.verifying_key(), activation_round)),
+        }));
+        assert!(lifecycle.apply_validator_tx(&ValidatorTx::sign(address.clone(), rotate(20, &key), 3, &key)).is_err());
+        assert!(lifecycle.apply_validator_tx(&ValidatorTx::sign(address.clone(), rotate(5, &new_key), 4, &key)).is_err());
+        lifecycle.apply_validator_tx(&ValidatorTx::sign(address.clone(), rotate(20, &new_key), 5, &key)).unwrap();
+        lifecycle.on_round_finalized(19);
+        assert_eq!(validator_set.lock().unwrap().get_validator(&address).unwrap().public_key, key.verifying_key());
+        ledger.on_round_finalized(20);
+        lifecycle.on_round_finalized(20);
+        assert_eq!(validator_set.lock().unwrap().get_validator(&address).unwrap().public_key, new_key.verifying_key());
+
+        // The old key no longer speaks for the validator
+        assert!(lifecycle.apply_validator_tx(&ValidatorTx::sign(address.clone(), LifecycleAction::Exit, 6, &key)).is_err());
+        lifecycle.apply_validator_tx(&ValidatorTx::sign(address.clone(), LifecycleAction::Exit, 7, &new_key)).unwrap();
+        lifecycle.on_round_finalized(119);
+        assert!(validator_set.lock().unwrap().get_validator(&address).is_some());
+        lifecycle.on_round_finalized(120);
+        assert!(validator_set.lock().unwrap().get_validator(&address).is_none());
+    }
+}
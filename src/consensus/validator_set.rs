@@ -4,6 +4,7 @@ use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 use crate::core::types::ShardId;
 use std::time::{SystemTime, UNIX_EPOCH};
+use sha2::{Sha256, Digest};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ValidatorStatus {
@@ -54,11 +55,28 @@ impl Default for ValidatorReputation {
 pub struct Validator {
     pub address: Address,
     pub public_key: VerifyingKey,
-    pub stake: u64,
+    pub stake: u64, // self-bond plus delegations
     pub is_active: bool,
     pub reputation: ValidatorReputation,
     pub institution_name: Option<String>,
     pub region: Option<String>,
+    #[serde(default)]
+    pub self_bond: u64, // Part of `stake` bonded by the validator itself
+    #[serde(default)]
+    pub jailed_until: Option<u64>, // Round from which the validator may unjail
+    #[serde(default)]
+    pub commission_bps: u64, // Share of delegators' rewards kept by the validator
+    #[serde(default)]
+    pub pending_rotation: Option<KeyRotation>,
+    #[serde(default)]
+    pub exit_round: Option<u64>, // Round the validator leaves the set, once it asked to exit
+}
+
+/// Consensus key scheduled to replace a validator's current key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation {
+    pub public_key: VerifyingKey,
+    pub activation_round: u64,
 }
 
 /// Committee configuration for quorum rotation
@@ -69,6 +87,12 @@ pub struct CommitteeConfig {
     pub rotation_interval_rounds: u64,
     pub fallback_timeout_ms: u64,
     pub reputation_threshold: f64, // Minimum reputation to be selected
+    #[serde(default = "default_min_self_bond")]
+    pub min_self_bond: u64, // Minimum self-bond to be selected
+}
+
+fn default_min_self_bond() -> u64 {
+    1_000
 }
 
 impl Default for CommitteeConfig {
@@ -79,6 +103,7 @@ impl Default for CommitteeConfig {
             rotation_interval_rounds: 10,
             fallback_timeout_ms: 5000, // 5 seconds
             reputation_threshold: 0.5,
+            min_self_bond: default_min_self_bond(),
         }
     }
 }
@@ -93,6 +118,23 @@ pub struct Committee {
     pub signatures_received: Vec<Address>,
     pub quorum_achieved: bool,
     pub fallback_triggered: bool,
+    #[serde(default)]
+    pub seed: [u8; 32], // Sampling seed (hash of the previous round)
+}
+
+/// Committee fixed for a whole epoch from on-chain data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochCommittee {
+    pub epoch: u64,
+    pub start_round: u64,
+    pub length_rounds: u64,
+    pub validators: Vec<Address>,
+}
+
+impl EpochCommittee {
+    pub fn covers(&self, round_number: u64) -> bool {
+        round_number >= self.start_round && round_number - self.start_round < self.length_rounds
+    }
 }
 
 /// Quorum rotation manager
@@ -103,6 +145,10 @@ pub struct QuorumRotationManager {
     pub current_committee: Option<Committee>,
     pub committee_history: Vec<Committee>,
     pub last_rotation_round: u64,
+    #[serde(default)]
+    pub committee_seed: [u8; 32], // Hash of the latest finalized round
+    #[serde(default)]
+    pub epoch_committee: Option<EpochCommittee>, // Takes precedence over per-round sampling
 }
 
 
@@ -133,6 +179,11 @@ impl ValidatorSet {
             reputation: ValidatorReputation::default(),
             institution_name: None,
             region: None,
+            self_bond: stake,
+            jailed_until: None,
+            commission_bps: 0,
+            pending_rotation: None,
+            exit_round: None,
         };
         self.validators.insert(address, validator);
     }
@@ -154,6 +205,11 @@ impl ValidatorSet {
             reputation: ValidatorReputation::default(),
             institution_name: Some(institution_name),
             region: Some(region),
+            self_bond: stake,
+            jailed_until: None,
+            commission_bps: 0,
+            pending_rotation: None,
+            exit_round: None,
         };
         self.validators.insert(address, validator);
     }
@@ -178,13 +234,164 @@ impl ValidatorSet {
         self.validators.values().filter(|v| v.is_active).collect()
     }
 
-    /// Get validators eligible for committee selection (active + good reputation)
+    /// Get validators eligible for committee selection (active, not exiting, good reputation, enough self-bond)
     pub fn get_eligible_validators(&self) -> Vec<&Validator> {
+        let config = &self.quorum_manager.config;
+        self.validators.values()
+            .filter(|v| v.is_active
+                && v.exit_round.is_none()
+                && v.reputation.reputation_score >= config.reputation_threshold
+                && v.self_bond >= config.min_self_bond
+                && v.stake > 0)
+            .collect()
+    }
+
+    /// Validators eligible for an epoch committee, judged on on-chain state only
+    /// (bond, jail and exit status), never on locally observed reputation
+    pub fn get_bonded_validators(&self) -> Vec<&Validator> {
+        let min_self_bond = self.quorum_manager.config.min_self_bond;
         self.validators.values()
-            .filter(|v| v.is_active && v.reputation.reputation_score >= self.quorum_manager.config.reputation_threshold)
+            .filter(|v| v.is_active
+                && v.jailed_until.is_none()
+                && v.exit_round.is_none()
+                && v.self_bond >= min_self_bond
+                && v.stake > 0)
             .collect()
     }
 
+    /// Sample the committee of `epoch` from bonded validators, weighted by stake alone
+    pub fn sample_epoch_committee(&self, epoch: u64, seed: &[u8; 32]) -> Vec<Address> {
+        let candidates = self.get_bonded_validators()
+            .iter()
+            .map(|v| (v.address.clone(), v.stake as u128))
+            .collect();
+        weighted_sample(candidates, seed, epoch, self.quorum_manager.config.committee_size)
+    }
+
+    /// Fix the committee used for every round of an epoch
+    pub fn set_epoch_committee(&mut self, committee: EpochCommittee) {
+        self.quorum_manager.epoch_committee = Some(committee);
+    }
+
+    /// Bond stake to a validator; `self_bond` marks the validator's own stake
+    pub fn add_stake(&mut self, address: &Address, amount: u64, self_bond: bool) -> Result<(), String> {
+        let validator = self.validators.get_mut(address)
+            .ok_or_else(|| format!("Unknown validator {address}"))?;
+        validator.stake = validator.stake.checked_add(amount).ok_or("Stake overflow")?;
+        if self_bond {
+            validator.self_bond += amount;
+        }
+        Ok(())
+    }
+
+    /// Unbond stake from a validator
+    pub fn remove_stake(&mut self, address: &Address, amount: u64, self_bond: bool) -> Result<(), String> {
+        let validator = self.validators.get_mut(address)
+            .ok_or_else(|| format!("Unknown validator {address}"))?;
+        if validator.stake < amount || (self_bond && validator.self_bond < amount) {
+            return Err(format!("Validator {address} has insufficient stake"));
+        }
+        validator.stake -= amount;
+        if self_bond {
+            validator.self_bond -= amount;
+        }
+        Ok(())
+    }
+
+    /// Schedule a consensus key rotation, replacing any pending one
+    pub fn schedule_key_rotation(&mut self, address: &Address, rotation: KeyRotation) -> Result<(), String> {
+        if self.validators.values().any(|v| v.public_key == rotation.public_key) {
+            return Err("Consensus key already in use".to_string());
+        }
+        let validator = self.validators.get_mut(address)
+            .ok_or_else(|| format!("Unknown validator {address}"))?;
+        validator.pending_rotation = Some(rotation);
+        Ok(())
+    }
+
+    /// Switch validators to rotated keys due at `round`, returning who rotated
+    pub fn activate_key_rotations(&mut self, round: u64) -> Vec<Address> {
+        let mut rotated = Vec::new();
+        for validator in self.validators.values_mut() {
+            if let Some(rotation) = validator.pending_rotation.take_if(|r| r.activation_round <= round) {
+                validator.public_key = rotation.public_key;
+                rotated.push(validator.address.clone());
+            }
+        }
+        rotated.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        rotated
+    }
+
+    /// Queue a voluntary exit; the validator leaves committee selection now and the set at `exit_round`
+    pub fn request_exit(&mut self, address: &Address, exit_round: u64) -> Result<(), String> {
+        let validator = self.validators.get_mut(address)
+            .ok_or_else(|| format!("Unknown validator {address}"))?;
+        if validator.exit_round.is_some() {
+            return Err(format!("Validator {address} is already exiting"));
+        }
+        validator.exit_round = Some(exit_round);
+        Ok(())
+    }
+
+    /// Remove validators whose exit round has been reached, returning who left
+    pub fn remove_exited(&mut self, round: u64) -> Vec<Address> {
+        let mut exited: Vec<Address> = self.validators.values()
+            .filter(|v| v.exit_round.is_some_and(|exit| exit <= round))
+            .map(|v| v.address.clone())
+            .collect();
+        exited.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        for address in &exited {
+            self.validators.remove(address);
+            for members in self.shard_assignments.values_mut() {
+                members.retain(|member| member != address);
+            }
+        }
+        exited
+    }
+
+    /// Set the commission the validator keeps from delegators' rewards
+    pub fn set_commission(&mut self, address: &Address, rate_bps: u64) -> Result<(), String> {
+        if rate_bps > 10_000 {
+            return Err("Commission cannot exceed 10000 basis points".to_string());
+        }
+        let validator = self.validators.get_mut(address)
+            .ok_or_else(|| format!("Unknown validator {address}"))?;
+        validator.commission_bps = rate_bps;
+        Ok(())
+    }
+
+    /// Jail a validator until `until_round`, extending any existing jail
+    pub fn jail(&mut self, address: &Address, until_round: u64) -> Result<(), String> {
+        let validator = self.validators.get_mut(address)
+            .ok_or_else(|| format!("Unknown validator {address}"))?;
+        validator.is_active = false;
+        validator.jailed_until = Some(validator.jailed_until.unwrap_or(0).max(until_round));
+        Ok(())
+    }
+
+    /// Release a jailed validator once its jail has expired and it meets the minimum self-bond
+    pub fn unjail(&mut self, address: &Address, round: u64) -> Result<(), String> {
+        let min_self_bond = self.quorum_manager.config.min_self_bond;
+        let validator = self.validators.get_mut(address)
+            .ok_or_else(|| format!("Unknown validator {address}"))?;
+        let until = validator.jailed_until.ok_or_else(|| format!("Validator {address} is not jailed"))?;
+        if round < until {
+            return Err(format!("Validator {address} is jailed until round {until}"));
+        }
+        if validator.self_bond < min_self_bond {
+            return Err(format!("Validator {address} is below the minimum self-bond of {min_self_bond}"));
+        }
+        validator.is_active = true;
+        validator.jailed_until = None;
+        validator.reputation.consecutive_failures = 0;
+        Ok(())
+    }
+
+    /// Seed the next committee selection with the hash of the latest finalized round
+    pub fn set_committee_seed(&mut self, round_hash: [u8; 32]) {
+        self.quorum_manager.committee_seed = round_hash;
+    }
+
     /// Get total stake
     pub fn get_total_stake(&self) -> u64 {
         self.validators.values().map(|v| v.stake).sum()
@@ -221,22 +428,22 @@ impl ValidatorSet {
         None
     }
 
-    /// Select committee for a round using deterministic weighted selection
+    /// Select committee for a round. Rounds inside a fixed epoch use the epoch
+    /// committee; otherwise validators are sampled without replacement, weighted by
+    /// stake x reputation. The draw is seeded with the previous round hash, so any
+    /// node can recompute it.
     pub fn select_committee(&mut self, round_number: u64) -> Committee {
-        let eligible = self.get_eligible_validators();
-        
-        // Sort by reputation score (descending) for deterministic selection
-        let mut sorted_validators: Vec<_> = eligible.iter().collect();
-        sorted_validators.sort_by(|a, b| {
-            b.reputation.reputation_score.partial_cmp(&a.reputation.reputation_score).unwrap()
-        });
-
-        // Take top validators up to committee size
-        let committee_size = self.quorum_manager.config.committee_size.min(sorted_validators.len());
-        let selected_validators: Vec<Address> = sorted_validators[..committee_size]
-            .iter()
-            .map(|v| v.address.clone())
-            .collect();
+        let seed = self.quorum_manager.committee_seed;
+        let selected_validators = match &self.quorum_manager.epoch_committee {
+            Some(epoch) if epoch.covers(round_number) => epoch.validators.clone(),
+            _ => {
+                let candidates = self.get_eligible_validators()
+                    .iter()
+                    .map(|v| (v.address.clone(), committee_weight(v)))
+                    .collect();
+                weighted_sample(candidates, &seed, round_number, self.quorum_manager.config.committee_size)
+            }
+        };
 
         let current_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -251,6 +458,7 @@ impl ValidatorSet {
             signatures_received: Vec::new(),
             quorum_achieved: false,
             fallback_triggered: false,
+            seed,
         };
 
         // Update current committee
@@ -362,6 +570,46 @@ impl ValidatorSet {
     }
 }
 
+/// Sampling weight: stake scaled by reputation (in thousandths, to stay integer)
+fn committee_weight(validator: &Validator) -> u128 {
+    let reputation = (validator.reputation.reputation_score.clamp(0.0, 1.0) * 1000.0).round() as u128;
+    validator.stake as u128 * reputation
+}
+
+/// Draw up to `size` addresses without replacement, with probability proportional to weight
+fn weighted_sample(mut candidates: Vec<(Address, u128)>, seed: &[u8; 32], nonce: u64, size: usize) -> Vec<Address> {
+    candidates.retain(|(_, weight)| *weight > 0);
+    candidates.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+    let size = size.min(candidates.len());
+    let mut selected = Vec::with_capacity(size);
+    for draw in 0..size as u64 {
+        let total: u128 = candidates.iter().map(|(_, weight)| weight).sum();
+        let mut target = sample_point(seed, nonce, draw) % total;
+        let index = candidates.iter()
+            .position(|(_, weight)| {
+                if target < *weight {
+                    return true;
+                }
+                target -= weight;
+                false
+            })
+            .unwrap_or(candidates.len() - 1);
+        selected.push(candidates.remove(index).0);
+    }
+    selected
+}
+
+fn sample_point(seed: &[u8; 32], round_number: u64, draw: u64) -> u128 {
+    let mut hasher = Sha256::new();
+    hasher.update(seed);
+    hasher.update(round_number.to_be_bytes());
+    hasher.update(draw.to_be_bytes());
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    u128::from_be_bytes(bytes)
+}
+
 impl Default for ValidatorSet {
     fn default() -> Self {
         Self::new()
@@ -1,14 +1,42 @@
+use bech32::{FromBase32, ToBase32, Variant};
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use rand::{rngs::OsRng, RngCore};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fmt;
 
-/// Represents a FinDAG address
+/// Human-readable part of FinDAG Bech32m addresses
+pub const ADDRESS_HRP: &str = "fdg";
+/// Length of the hash an address encodes
+pub const ADDRESS_HASH_LEN: usize = 32;
+
+/// Errors returned when parsing or migrating an address
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AddressError {
+    #[error("Invalid Bech32m encoding: {0}")]
+    InvalidEncoding(String),
+    #[error("Wrong address prefix '{0}', expected '{ADDRESS_HRP}'")]
+    WrongHrp(String),
+    #[error("Address must use the Bech32m checksum variant")]
+    WrongVariant,
+    #[error("Address must encode {ADDRESS_HASH_LEN} bytes, got {0}")]
+    WrongLength(usize),
+    #[error("Not a legacy address: {0}")]
+    NotLegacy(String),
+    #[error("Public key does not match legacy address {0}")]
+    KeyMismatch(String),
+}
+
+/// Represents a FinDAG address.
+///
+/// Canonical addresses are Bech32m strings with the `fdg` HRP over a 32-byte
+/// SHA-256 hash of the account's public key (or of a multisig policy).
+/// The inner string is kept for compatibility; use [`Address::parse`] at trust boundaries.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Address(pub String);
 
 impl Address {
-    /// Create a new address from a string
+    /// Create a new address from a string (unchecked; see [`Address::parse`])
     pub fn new(addr: String) -> Self {
         Self(addr)
     }
@@ -18,31 +46,88 @@ impl Address {
         &self.0
     }
 
+    /// Encode a 32-byte hash as a canonical address
+    pub fn from_hash(hash: &[u8; ADDRESS_HASH_LEN]) -> Self {
+        let encoded = bech32::encode(ADDRESS_HRP, hash.to_base32(), Variant::Bech32m)
+            .expect("fdg HRP is valid");
+        Self(encoded)
+    }
+
+    /// Parse and validate a canonical address (HRP, checksum, variant and length)
+    pub fn parse(s: &str) -> Result<Self, AddressError> {
+        Self::decode_hash(s).map(|hash| Self::from_hash(&hash))
+    }
+
+    /// Decode the 32-byte hash of a canonical address
+    pub fn decode_hash(s: &str) -> Result<[u8; ADDRESS_HASH_LEN], AddressError> {
+        let (hrp, data, variant) = bech32::decode(s)
+            .map_err(|e| AddressError::InvalidEncoding(e.to_string()))?;
+        if hrp != ADDRESS_HRP {
+            return Err(AddressError::WrongHrp(hrp));
+        }
+        if variant != Variant::Bech32m {
+            return Err(AddressError::WrongVariant);
+        }
+        let bytes = Vec::<u8>::from_base32(&data)
+            .map_err(|e| AddressError::InvalidEncoding(e.to_string()))?;
+        let len = bytes.len();
+        bytes.try_into().map_err(|_| AddressError::WrongLength(len))
+    }
+
+    /// Validate this address as a canonical address
+    pub fn validate(&self) -> Result<(), AddressError> {
+        Self::decode_hash(&self.0).map(|_| ())
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// True for the pre-Bech32m `FD` + 16 hex character format
+    pub fn is_legacy(&self) -> bool {
+        let s = self.0.as_str();
+        s.len() == 18 && s.starts_with("FD") && s[2..].chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    /// Map a legacy address to its canonical form. The legacy format only kept the first
+    /// 8 bytes of the public key, so the full key is needed to derive the new address.
+    pub fn migrate_legacy(legacy: &str, public_key: &VerifyingKey) -> Result<Self, AddressError> {
+        let legacy_addr = Address(legacy.to_string());
+        if !legacy_addr.is_legacy() {
+            return Err(AddressError::NotLegacy(legacy.to_string()));
+        }
+        if !legacy[2..].eq_ignore_ascii_case(&hex::encode(&public_key.to_bytes()[..8])) {
+            return Err(AddressError::KeyMismatch(legacy.to_string()));
+        }
+        Ok(Self::from_verifying_key(public_key))
+    }
+
     /// Generate a new random address
     pub fn random() -> Self {
         let mut rng = OsRng;
         let mut secret_bytes = [0u8; 32];
         rng.fill_bytes(&mut secret_bytes);
         let signing_key = SigningKey::from_bytes(&secret_bytes);
-        let verifying_key = signing_key.verifying_key();
-        let address_bytes = verifying_key.to_bytes();
-        let address_hex = hex::encode(&address_bytes[..8]); // Use first 8 bytes for shorter addresses
-        Self(format!("FD{address_hex}"))
+        Self::from_verifying_key(&signing_key.verifying_key())
     }
 
     /// Generate a new address from a signing key
     pub fn from_signing_key(signing_key: &SigningKey) -> Self {
-        let verifying_key = signing_key.verifying_key();
-        let address_bytes = verifying_key.to_bytes();
-        let address_hex = hex::encode(&address_bytes[..8]);
-        Self(format!("FD{address_hex}"))
+        Self::from_verifying_key(&signing_key.verifying_key())
     }
 
-    /// Generate address from verifying key
+    /// Generate address from verifying key (SHA-256 of the full key)
     pub fn from_verifying_key(verifying_key: &VerifyingKey) -> Self {
-        let addr_bytes = verifying_key.to_bytes();
-        let addr_hex = hex::encode(&addr_bytes[..8]); // Use first 8 bytes for shorter address
-        Address(format!("FD{addr_hex}"))
+        let hash: [u8; ADDRESS_HASH_LEN] = Sha256::digest(verifying_key.to_bytes()).into();
+        Self::from_hash(&hash)
+    }
+}
+
+impl std::str::FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
     }
 }
 
@@ -103,8 +188,8 @@ mod tests {
         let addr1 = Address::random();
         let addr2 = Address::random();
         assert_ne!(addr1, addr2);
-        assert!(addr1.as_str().starts_with("FD"));
-        assert!(addr2.as_str().starts_with("FD"));
+        assert!(addr1.as_str().starts_with("fdg1"));
+        assert!(addr2.as_str().starts_with("fdg1"));
     }
 
     #[test]
@@ -114,6 +199,39 @@ mod tests {
         rng.fill_bytes(&mut secret_bytes);
         let signing_key = SigningKey::from_bytes(&secret_bytes);
         let addr = Address::from_signing_key(&signing_key);
-        assert!(addr.as_str().starts_with("FD"));
+        assert!(addr.as_str().starts_with("fdg1"));
+        assert!(addr.is_valid());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        let addr = Address::from_signing_key(&SigningKey::from_bytes(&[5u8; 32]));
+        assert_eq!(Address::parse(addr.as_str()).unwrap(), addr);
+        assert_eq!(Address::parse(&addr.as_str().to_uppercase()).unwrap(), addr);
+
+        // Flipped character breaks the checksum
+        let mut corrupted = addr.as_str().to_string();
+        let last = corrupted.pop().unwrap();
+        corrupted.push(if last == 'q' { 'p' } else { 'q' });
+        assert!(Address::parse(&corrupted).is_err());
+
+        let hash = [7u8; 32];
+        let wrong_hrp = bech32::encode("abc", hash.to_base32(), Variant::Bech32m).unwrap();
+        assert_eq!(Address::parse(&wrong_hrp), Err(AddressError::WrongHrp("abc".to_string())));
+        let bech32_classic = bech32::encode(ADDRESS_HRP, hash.to_base32(), Variant::Bech32).unwrap();
+        assert_eq!(Address::parse(&bech32_classic), Err(AddressError::WrongVariant));
+        let short = bech32::encode(ADDRESS_HRP, [7u8; 8].to_base32(), Variant::Bech32m).unwrap();
+        assert_eq!(Address::parse(&short), Err(AddressError::WrongLength(8)));
+        assert!(Address::parse("EuroclearBank").is_err());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_migrate_legacy() {
+        let key = SigningKey::from_bytes(&[9u8; 32]).verifying_key();
+        let legacy = format!("FD{}", hex::encode(&key.to_bytes()[..8]));
+        assert_eq!(Address::migrate_legacy(&legacy, &key).unwrap(), Address::from_verifying_key(&key));
+
+        let other = SigningKey::from_bytes(&[10u8; 32]).verifying_key();
+        assert!(matches!(Address::migrate_legacy(&legacy, &other), Err(AddressError::KeyMismatch(_))));
+    }
+}
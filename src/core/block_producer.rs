@@ -109,12 +109,26 @@ impl<'a> BlockProducer<'a> {
         
         tracing::debug!(max_txs, "Starting block production");
         
-        let transactions = self.tx_pool
-            .get_transactions(max_txs, self.config.shard_id.0);
+        // Get current FinDAG Time
+        let findag_time = self.time_manager.get_findag_time();
+        
+        // While governance has paused the chain, blocks carry only governance transactions,
+        // picked from the whole pool so older transfers cannot crowd them out
+        let chain_control = self.tx_pool.chain_control();
+        let fetch = if chain_control.is_paused() {
+            self.tx_pool.size(self.config.shard_id.0)
+        } else {
+            max_txs
+        };
+        let mut transactions = self.tx_pool
+            .get_transactions(fetch, self.config.shard_id.0);
         if transactions.is_empty() {
             return Err(BlockProductionError::TxPoolEmpty);
         }
         
+        // Only include transactions whose validity window contains the block time
+        transactions.retain(|tx| tx.is_valid_at(findag_time) && chain_control.admits(tx));
+        transactions.truncate(max_txs);
         if transactions.is_empty() {
             tracing::debug!("No transactions available");
             return Err(BlockProductionError::NoTransactions);
@@ -123,9 +137,6 @@ impl<'a> BlockProducer<'a> {
         // Update transaction count
         self.transaction_count += transactions.len();
         
-        // Get current FinDAG Time
-        let findag_time = self.time_manager.get_findag_time();
-        
         // Generate HashTimer (simplified for now)
         let mut hashtimer = [0u8; 32];
         let time_bytes = findag_time.to_le_bytes();
@@ -5,16 +5,18 @@ use crate::core::{
     types::ShardId,
 };
 use crate::core::address::Address;
-use crate::consensus::round_finalizer::RoundFinalizer;
+use crate::consensus::parameters::{BLOCK_INTERVAL_MS, BLOCK_MAX_TXS};
 use crate::dagtimer::findag_time_manager::FinDAGTimeManager;
 use crate::storage::persistent::PersistMsg;
 use crate::metrics;
 use ed25519_dalek::SigningKey;
 use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Mutex;
 use tokio::time::{sleep, Duration, Instant};
 use hex;
 
-/// Configuration for block production loop
+/// Configuration for block production loop. The limits are local caps;
+/// governance parameters can only make them stricter.
 #[derive(Debug, Clone)]
 pub struct BlockProductionConfig {
     pub max_block_txs: usize,
@@ -22,24 +24,40 @@ pub struct BlockProductionConfig {
     pub shard_id: u16,
 }
 
-/// Runs the block production loop for a specific shard
+/// Runs the block production loop for a specific shard. The DAG is locked only
+/// while a block is produced, so gossip can keep adding peer blocks in between.
 pub async fn run_block_production_loop(
-    dag: &mut DagEngine,
+    dag: &Mutex<DagEngine>,
     tx_pool: &ShardedTxPool,
     proposer: Address,
     keypair: &SigningKey,
     config: BlockProductionConfig,
     time_manager: &FinDAGTimeManager,
     persist_tx: UnboundedSender<PersistMsg>,
-    _round_finalizer: RoundFinalizer<'_>,
 ) {
+    let chain_control = tx_pool.chain_control();
     loop {
+        let interval_ms = chain_control.param_u64(BLOCK_INTERVAL_MS)
+            .map_or(config.interval_ms, |v| config.interval_ms.max(v));
+        let max_block_txs = chain_control.param_u64(BLOCK_MAX_TXS)
+            .map_or(config.max_block_txs, |v| config.max_block_txs.min(v as usize));
+
+        // Governance emergency pause: blocks keep coming, but carry governance transactions only
+        if chain_control.is_paused() {
+            println!("[DEBUG] Chain paused ({}): producing governance-only blocks", chain_control.pause_reason().unwrap_or_default());
+        }
+
         let block_start = Instant::now();
         
+        // Drop transactions whose validity window has closed
+        tx_pool.purge_expired(time_manager.get_findag_time());
+        
         // DEBUG: Log mempool sizes
         let tx_pool_size = tx_pool.size(config.shard_id);
         println!("[DEBUG] TxPool size: {tx_pool_size}");
         
+        let mut dag = dag.lock().await;
+        
         // Get parent blocks before any mutable borrow
         let parent_blocks: Vec<[u8; 32]> = dag.get_tips().await;
         let tips_time = block_start.elapsed();
@@ -50,13 +68,13 @@ pub async fn run_block_production_loop(
         
         // Create block producer without holding mempool lock
         let mut block_producer = BlockProducer::new(
-            dag,
+            &mut dag,
             tx_pool,
             proposer.clone(),
             keypair,
             BlockProducerConfig {
-                max_txs_per_block: config.max_block_txs,
-                target_block_time_ms: config.interval_ms,
+                max_txs_per_block: max_block_txs,
+                target_block_time_ms: interval_ms,
                 shard_id: ShardId(config.shard_id),
             },
             time_manager,
@@ -84,6 +102,13 @@ pub async fn run_block_production_loop(
             // Log block producer stats
             println!("[BlockProducer] Round {}: {} transactions", 
                      block_producer.get_current_round(), block_producer.get_transaction_count());
+            // Insert into the local DAG and drop the included transactions from the pool
+            if let Err(e) = dag.add_block(block.clone()).await {
+                println!("[Shard {}] Failed to add produced block to DAG: {}", config.shard_id, e);
+            }
+            for tx in &block.transactions {
+                tx_pool.remove_transaction(&tx.compute_hash(), tx.findag_time, config.shard_id);
+            }
             // TODO: Use round_finalizer for consensus/finality in this shard
             // Persist the block asynchronously
             let _ = persist_tx.send(PersistMsg::Block(block.clone()));
@@ -104,10 +129,12 @@ pub async fn run_block_production_loop(
             }
         }
         
+        drop(dag);
+        
         // Calculate proper sleep time to maintain the intended interval
         let elapsed = block_start.elapsed();
-        let sleep_duration = if elapsed.as_millis() < config.interval_ms as u128 {
-            Duration::from_millis(config.interval_ms - elapsed.as_millis() as u64)
+        let sleep_duration = if elapsed.as_millis() < interval_ms as u128 {
+            Duration::from_millis(interval_ms - elapsed.as_millis() as u64)
         } else {
             Duration::from_millis(1) // Minimum sleep if we're already over the interval
         };
@@ -115,7 +142,7 @@ pub async fn run_block_production_loop(
         sleep(sleep_duration).await;
         let total_time = block_start.elapsed();
         println!("[Shard {}] Loop timing: total={:?}, sleep={:?}, interval={}ms", 
-            config.shard_id, total_time, sleep_duration, config.interval_ms);
+            config.shard_id, total_time, sleep_duration, interval_ms);
     }
 }
 
@@ -123,9 +150,9 @@ pub async fn run_block_production_loop(
 //
 // #[tokio::main]
 // async fn main() {
-//     let mut dag = DagEngine::new();
+//     let dag = Mutex::new(DagEngine::new().await);
 //     let tx_pool = ShardedTxPool::new(100_000);
 //     let (keypair, address) = generate_address();
 //     let time_manager = FinDAGTimeManager::new();
-//     run_block_production_loop(&mut dag, &tx_pool, address, &keypair, 100, 20, &time_manager).await;
+//     run_block_production_loop(&dag, &tx_pool, address, &keypair, config, &time_manager, persist_tx).await;
 // } 
\ No newline at end of file
@@ -1,93 +1,475 @@
 //! Cross-chain bridge module for FinDAG
 //
-// This module will handle outbound and inbound cross-chain transactions.
-// - Outbound: lock assets, generate proofs, relay to target chain
-// - Inbound: verify proofs, mint/unlock assets
-// - Support for protocols like IBC, custom bridges, etc.
-//
-// TODO: Implement bridge logic, proof formats, relayer integration, and security checks.
+// Lock-and-mint bridge state machine:
+// - Outbound: a signed `Lock` moves funds into the bridge escrow. Once the block
+//   carrying it is in a finalized round, the transfer gets a Merkle receipt that
+//   relayers present to the target chain.
+// - Inbound: a signed `Release` carries a Corda or Fabric settlement proof over the
+//   transfer's commitment. It unlocks escrowed funds, or mints when nothing is locked
+//   for that chain and asset.
+// Each source-chain transaction releases once, volumes are capped per asset and
+// rate-limit window, and governance can pause the bridge (`bridge.paused` or an
+// emergency pause). All bridge state lives in the StateDB.
 
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use crate::bridge::corda::{CordaSettlementProof, NotaryKeySet};
+use crate::bridge::fabric::{Endorsement, FabricEndorsementProof, FabricTrust};
+use crate::bridge::proofs::SettlementProof;
+use crate::consensus::governance_executor::ChainControl;
+use crate::consensus::parameters;
+use crate::core::address::Address;
+use crate::core::types::{Block, ShardId, Transaction};
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use crate::storage::state::StateDB;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Serialize, Deserialize};
 use sha2::{Sha256, Digest};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Payload prefix identifying a bridge operation carried in `Transaction.payload`
+pub const BRIDGE_PAYLOAD_TAG: &[u8] = b"FDG:BRIDGE:1";
+
+/// Account holding funds locked for other chains
+pub const BRIDGE_ESCROW_ADDRESS: &str = "bridge:escrow";
+
+/// Shard bridge operations and escrow live on
+pub const BRIDGE_SHARD: ShardId = ShardId(0);
 
+/// A source-chain transfer to be released on FinDAG
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InboundTransfer {
+    pub source_chain: String,
+    pub source_tx_id: String,
+    pub recipient: Address,
+    pub asset: String,
+    pub amount: u64,
+}
+
+impl InboundTransfer {
+    /// Hash the source-chain bridge contract records; the settlement proof must attest exactly this
+    pub fn commitment(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"FDG:BRIDGE:IN:1");
+        for field in [self.source_chain.as_str(), self.source_tx_id.as_str(), self.recipient.as_str(), self.asset.as_str()] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+        hasher.update(self.amount.to_be_bytes());
+        hasher.finalize().into()
+    }
+}
+
+/// Source-chain finality evidence for an inbound transfer; the attested state is the transfer's commitment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum InboundProof {
+    Corda { notary_key: Vec<u8>, notary_signature: Vec<u8> },
+    Fabric { endorsements: Vec<Endorsement> },
+}
+
+/// Notary keys and MSPs inbound proofs are checked against
+#[derive(Default)]
+pub struct BridgeTrust {
+    pub corda_notaries: NotaryKeySet,
+    pub fabric: Option<FabricTrust>,
+}
+
+impl BridgeTrust {
+    pub fn verify(&self, proof: &InboundProof, commitment: [u8; 32]) -> Result<(), String> {
+        let verified = match proof {
+            InboundProof::Corda { notary_key, notary_signature } => CordaSettlementProof {
+                state_hash: commitment.to_vec(),
+                notary_key: notary_key.clone(),
+                notary_signature: notary_signature.clone(),
+            }.verify(&self.corda_notaries),
+            InboundProof::Fabric { endorsements } => {
+                let trust = self.fabric.as_ref().ok_or("No Fabric trust configured")?;
+                FabricEndorsementProof { state_root: commitment.to_vec(), endorsements: endorsements.clone() }
+                    .verify(trust)
+            }
+        };
+        verified.map_err(|e| format!("Invalid settlement proof: {e}"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BridgeAction {
+    /// Escrow funds for `recipient` on `to_chain`
+    Lock { to_chain: String, recipient: String, asset: String, amount: u64 },
+    /// Unlock or mint funds for a proven source-chain transfer; anyone may relay it
+    Release { transfer: InboundTransfer, proof: InboundProof },
+}
+
+impl BridgeAction {
+    pub fn asset_amount(&self) -> (&str, u64) {
+        match self {
+            BridgeAction::Lock { asset, amount, .. } => (asset, *amount),
+            BridgeAction::Release { transfer, .. } => (&transfer.asset, transfer.amount),
+        }
+    }
+}
+
+/// Bridge operation signed by the locking account or the relayer
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeTx {
-    pub id: String,
-    pub from_chain: String,
-    pub to_chain: String,
-    pub amount: u64,
-    pub recipient: String,
-    pub timestamp: u64,
+    pub action: BridgeAction,
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+    pub public_key: VerifyingKey,
+    pub signature: Signature,
+}
+
+impl BridgeTx {
+    /// Canonical message the sender signs
+    pub fn signing_message(action: &BridgeAction, findag_time: u64, shard_id: ShardId) -> Vec<u8> {
+        let mut message = BRIDGE_PAYLOAD_TAG.to_vec();
+        message.extend_from_slice(&bincode::serialize(action).expect("bridge action serialization"));
+        message.extend_from_slice(&findag_time.to_be_bytes());
+        message.extend_from_slice(&shard_id.0.to_be_bytes());
+        message
+    }
+
+    pub fn sign(action: BridgeAction, findag_time: u64, signing_key: &SigningKey) -> Self {
+        let signature = signing_key.sign(&Self::signing_message(&action, findag_time, BRIDGE_SHARD));
+        Self { action, findag_time, shard_id: BRIDGE_SHARD, public_key: signing_key.verifying_key(), signature }
+    }
+
+    /// Address of the locking account or relayer
+    pub fn sender(&self) -> Address {
+        Address::from_verifying_key(&self.public_key)
+    }
+
+    /// Check the signature and the operation's static rules
+    pub fn verify(&self) -> Result<(), String> {
+        let message = Self::signing_message(&self.action, self.findag_time, self.shard_id);
+        self.public_key.verify(&message, &self.signature)
+            .map_err(|_| "Invalid bridge signature".to_string())?;
+        if self.shard_id != BRIDGE_SHARD {
+            return Err(format!("Bridge operations must be on shard {}", BRIDGE_SHARD.0));
+        }
+        let (asset, amount) = self.action.asset_amount();
+        if amount == 0 {
+            return Err("Bridge amount must be positive".to_string());
+        }
+        if asset.is_empty() {
+            return Err("Bridge asset is missing".to_string());
+        }
+        match &self.action {
+            BridgeAction::Lock { to_chain, recipient, .. } if to_chain.is_empty() || recipient.is_empty() => {
+                Err("Lock needs a target chain and recipient".to_string())
+            }
+            BridgeAction::Release { transfer, .. } if transfer.source_chain.is_empty() || transfer.source_tx_id.is_empty() => {
+                Err("Release needs a source chain and transaction id".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = BRIDGE_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&bincode::serialize(self).expect("bridge tx serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a bridge operation
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(BRIDGE_PAYLOAD_TAG)?;
+        Some(bincode::deserialize(body).map_err(|e| format!("Invalid bridge payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the signed body
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|op| {
+            if op.shard_id != tx.shard_id
+                || op.findag_time != tx.findag_time
+                || op.public_key != tx.public_key
+                || op.sender() != tx.from
+            {
+                return Err("Bridge envelope does not match its payload".to_string());
+            }
+            Ok(op)
+        }))
+    }
+
+    /// Wrap in a zero-amount `Transaction` from the sender to itself
+    pub fn to_transaction(&self) -> Transaction {
+        let address = self.sender();
+        let payload = self.to_payload();
+        Transaction {
+            from: address.clone(),
+            to: address,
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature: self.signature,
+            public_key: self.public_key,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: match &self.action {
+                BridgeAction::Lock { to_chain, .. } => Some(to_chain.clone()),
+                BridgeAction::Release { .. } => None,
+            },
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BridgeDirection {
+    Outbound,
+    Inbound,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BridgeStatus {
+    Locked,    // outbound funds escrowed, waiting for a finalized round
+    Finalized, // outbound receipt issued
+    Unlocked,  // inbound transfer paid from escrow
+    Minted,    // inbound transfer minted
+}
+
+/// Merkle proof that an outbound lock is in a block of a finalized round
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeReceipt {
     pub tx_id: String,
-    pub status: String,
+    pub block_id: String,
+    pub round: Option<u64>,     // set once the block's round is finalized
+    pub leaf: String,           // hex transaction hash, the transaction's leaf in the block
+    pub index: usize,
     pub merkle_root: String,
     pub merkle_proof: Vec<String>,
-    pub timestamp: u64,
 }
 
+impl BridgeReceipt {
+    pub fn verify(&self) -> bool {
+        self.round.is_some()
+            && self.leaf == self.tx_id
+            && verify_merkle_proof(&self.leaf, &self.merkle_proof, &self.merkle_root, self.index)
+    }
+}
+
+/// A bridge transfer as recorded on FinDAG
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeTransfer {
+    pub id: String,                   // FinDAG transaction hash, hex
+    pub direction: BridgeDirection,
+    pub chain: String,                // counterparty chain
+    pub source_tx_id: Option<String>, // inbound only
+    pub sender: String,
+    pub recipient: String,
+    pub asset: String,
+    pub amount: u64,
+    pub status: BridgeStatus,
+    pub receipt: Option<BridgeReceipt>,
+}
+
+/// Applies bridge operations: escrows outbound funds, issues receipts as rounds
+/// finalize and releases inbound transfers against verified settlement proofs
 pub struct Bridge {
-    transactions: Arc<Mutex<HashMap<String, BridgeTx>>>,
-    receipts: Arc<Mutex<HashMap<String, BridgeReceipt>>>,
+    state_db: Arc<StateDB>,
+    control: Arc<ChainControl>,
+    trust: BridgeTrust,
+    current_round: AtomicU64,
 }
 
 impl Bridge {
-    pub fn new() -> Self {
-        Self {
-            transactions: Arc::new(Mutex::new(HashMap::new())),
-            receipts: Arc::new(Mutex::new(HashMap::new())),
-        }
+    pub fn new(state_db: Arc<StateDB>, control: Arc<ChainControl>, trust: BridgeTrust) -> Self {
+        Self { state_db, control, trust, current_round: AtomicU64::new(0) }
     }
 
-    pub fn submit_transaction(&self, tx: BridgeTx) -> Result<String, String> {
-        let tx_id = format!("bridge_{}", tx.timestamp);
-        let mut transactions = self.transactions.lock().unwrap();
-        transactions.insert(tx_id.clone(), tx);
-        Ok(tx_id)
+    /// Latest finalized round seen by the bridge
+    pub fn current_round(&self) -> u64 {
+        self.current_round.load(Ordering::SeqCst)
     }
 
-    pub fn get_receipt(&self, tx_id: &str) -> Option<BridgeReceipt> {
-        let receipts = self.receipts.lock().unwrap();
-        receipts.get(tx_id).cloned()
+    /// Paused by governance, either for the bridge alone or chain-wide
+    pub fn is_paused(&self) -> bool {
+        // An unreadable flag keeps the bridge closed
+        self.control.is_paused() || self.control.param_u64(parameters::BRIDGE_PAUSED) != Ok(0)
     }
 
-    pub fn verify_proof(&self, _tx_id: &str) -> bool {
-        // TODO: Implement proof verification
-        let _hash_proof = "dummy_hash_proof".to_string();
-        let _root = "dummy_merkle_root".to_string();
-        let _proof = ["dummy_proof".to_string()];
-        
-        // For now, return true as placeholder
-        true
+    /// Check an inbound transfer's settlement proof
+    pub fn verify_proof(&self, transfer: &InboundTransfer, proof: &InboundProof) -> Result<(), String> {
+        self.trust.verify(proof, transfer.commitment())
     }
 
-    pub fn verify_receipt(&self, tx_id: &str) -> bool {
-        let receipts = self.receipts.lock().unwrap();
-        let (_merkle_root, _merkle_proof) = if let Some(_receipt) = receipts.get(tx_id) {
-            ("dummy_root".to_string(), vec!["dummy_proof".to_string()])
-        } else {
-            ("".to_string(), vec![])
+    /// Check a bridge operation against the current bridge state without applying it
+    pub fn check(&self, bridge_tx: &BridgeTx) -> Result<(), String> {
+        bridge_tx.verify()?;
+        if self.is_paused() {
+            return Err("Bridge is paused".to_string());
+        }
+        let (asset, amount) = bridge_tx.action.asset_amount();
+        let limit = self.control.param_u64(parameters::BRIDGE_RATE_LIMIT_PER_ASSET)?;
+        let used = self.state_db.bridge_volume(asset, self.window()?);
+        if used.saturating_add(amount) > limit {
+            return Err(format!("Bridge rate limit for {asset} reached: {used} of {limit} used this window"));
+        }
+        match &bridge_tx.action {
+            BridgeAction::Lock { .. } => {
+                let balance = self.state_db.get_balance(bridge_tx.shard_id.0, bridge_tx.sender().as_str(), asset);
+                if balance < amount {
+                    return Err(format!("Insufficient {asset} to lock {amount}"));
+                }
+            }
+            BridgeAction::Release { transfer, proof } => {
+                if let Some(id) = self.state_db.bridge_source_release(&transfer.source_chain, &transfer.source_tx_id) {
+                    return Err(format!("{} transaction {} was already released by {id}", transfer.source_chain, transfer.source_tx_id));
+                }
+                let locked = self.state_db.bridge_locked(&transfer.source_chain, asset);
+                if locked > 0 && locked < amount {
+                    return Err(format!("Release of {amount} {asset} exceeds the {locked} locked for {}", transfer.source_chain));
+                }
+                self.verify_proof(transfer, proof)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a bridge operation included in a block
+    pub fn apply_bridge_tx(&self, tx: &Transaction, bridge_tx: &BridgeTx) -> Result<(), String> {
+        self.check(bridge_tx)?;
+        let id = hex::encode(tx.compute_hash());
+        let shard = bridge_tx.shard_id.0;
+        let sender = bridge_tx.sender();
+
+        let transfer = match &bridge_tx.action {
+            BridgeAction::Lock { to_chain, recipient, asset, amount } => {
+                self.state_db.transfer(shard, sender.as_str(), BRIDGE_ESCROW_ADDRESS, *amount, asset)?;
+                let locked = self.state_db.bridge_locked(to_chain, asset);
+                self.state_db.set_bridge_locked(to_chain, asset, locked + amount)?;
+                println!("[DEBUG] Bridge: {sender} locked {amount} {asset} for {recipient} on {to_chain}");
+                BridgeTransfer {
+                    id,
+                    direction: BridgeDirection::Outbound,
+                    chain: to_chain.clone(),
+                    source_tx_id: None,
+                    sender: sender.as_str().to_string(),
+                    recipient: recipient.clone(),
+                    asset: asset.clone(),
+                    amount: *amount,
+                    status: BridgeStatus::Locked,
+                    receipt: None,
+                }
+            }
+            BridgeAction::Release { transfer, .. } => {
+                let (chain, asset, amount) = (&transfer.source_chain, &transfer.asset, transfer.amount);
+                let recipient = transfer.recipient.as_str();
+                let locked = self.state_db.bridge_locked(chain, asset);
+                let status = if locked >= amount {
+                    self.state_db.transfer(shard, BRIDGE_ESCROW_ADDRESS, recipient, amount, asset)?;
+                    self.state_db.set_bridge_locked(chain, asset, locked - amount)?;
+                    BridgeStatus::Unlocked
+                } else {
+                    let balance = self.state_db.get_balance(shard, recipient, asset);
+                    self.state_db.set_balance(shard, recipient, asset, balance + amount)?;
+                    BridgeStatus::Minted
+                };
+                println!("[DEBUG] Bridge: released {amount} {asset} to {recipient} for {chain} transaction {} ({status:?})", transfer.source_tx_id);
+                BridgeTransfer {
+                    id,
+                    direction: BridgeDirection::Inbound,
+                    chain: chain.clone(),
+                    source_tx_id: Some(transfer.source_tx_id.clone()),
+                    sender: sender.as_str().to_string(),
+                    recipient: recipient.to_string(),
+                    asset: asset.clone(),
+                    amount,
+                    status,
+                    receipt: None,
+                }
+            }
         };
-        
-        // TODO: Implement actual verification
-        true
+
+        let window = self.window()?;
+        let used = self.state_db.bridge_volume(&transfer.asset, window);
+        self.state_db.set_bridge_volume(&transfer.asset, window, used + transfer.amount)?;
+        self.state_db.put_bridge_transfer(&transfer)
+    }
+
+    /// Record the Merkle proof of each outbound lock in an applied block
+    pub fn on_block_applied(&self, block: &Block) {
+        if !block.validate_merkle_root() {
+            println!("[DEBUG] Bridge: block {} has an invalid Merkle root", hex::encode(block.block_id));
+            return;
+        }
+        // Leaves are transaction hashes, so each proof binds to the receipt's tx_id
+        let leaves = block.merkle_leaves();
+        let root = merkle_root(&leaves);
+        for (index, tx) in block.transactions.iter().enumerate() {
+            if !matches!(BridgeTx::from_transaction(tx), Some(Ok(BridgeTx { action: BridgeAction::Lock { .. }, .. }))) {
+                continue;
+            }
+            let id = hex::encode(tx.compute_hash());
+            let Some(mut transfer) = self.state_db.get_bridge_transfer(&id) else { continue };
+            if transfer.status != BridgeStatus::Locked || transfer.receipt.is_some() {
+                continue;
+            }
+            transfer.receipt = Some(BridgeReceipt {
+                tx_id: id.clone(),
+                block_id: hex::encode(block.block_id),
+                round: None,
+                leaf: leaves[index].clone(),
+                index,
+                merkle_root: root.clone(),
+                merkle_proof: merkle_proof(&leaves, index),
+            });
+            let stored = self.state_db.put_bridge_transfer(&transfer)
+                .and_then(|_| self.state_db.add_bridge_inflight(&block.block_id, &id));
+            if let Err(e) = stored {
+                println!("[DEBUG] Bridge: failed to record inclusion of {id}: {e}");
+            }
+        }
     }
-}
 
-impl Default for Bridge {
-    fn default() -> Self {
-        Self::new()
+    /// Advance to a finalized round and issue receipts for the locks in its blocks
+    pub fn on_round_finalized(&self, round: u64, block_hashes: &[[u8; 32]]) {
+        self.current_round.fetch_max(round, Ordering::SeqCst);
+        for block_id in block_hashes {
+            for id in self.state_db.bridge_inflight(block_id) {
+                let finalized = match self.state_db.get_bridge_transfer(&id) {
+                    Some(mut transfer) => {
+                        if let Some(receipt) = transfer.receipt.as_mut() {
+                            receipt.round = Some(round);
+                        }
+                        transfer.status = BridgeStatus::Finalized;
+                        self.state_db.put_bridge_transfer(&transfer)
+                    }
+                    None => Ok(()),
+                }.and_then(|_| self.state_db.remove_bridge_inflight(block_id, &id));
+                match finalized {
+                    Ok(()) => println!("[DEBUG] Bridge: receipt for {id} issued at round {round}"),
+                    Err(e) => println!("[DEBUG] Bridge: failed to finalize {id}: {e}"),
+                }
+            }
+        }
+    }
+
+    pub fn get_transfer(&self, tx_id: &str) -> Option<BridgeTransfer> {
+        self.state_db.get_bridge_transfer(tx_id)
+    }
+
+    /// Receipt of a finalized outbound transfer
+    pub fn get_receipt(&self, tx_id: &str) -> Option<BridgeReceipt> {
+        self.get_transfer(tx_id)
+            .filter(|transfer| transfer.status == BridgeStatus::Finalized)
+            .and_then(|transfer| transfer.receipt)
     }
-}
 
-// TODO: Integrate real cryptographic proof generation/verification
+    /// Whether the transfer has a finalized receipt whose Merkle proof checks out
+    pub fn verify_receipt(&self, tx_id: &str) -> bool {
+        self.get_receipt(tx_id).is_some_and(|receipt| receipt.tx_id == tx_id && receipt.verify())
+    }
 
-// TODO: Implement bridge logic, proof formats, relayer integration, and security checks.
+    fn window(&self) -> Result<u64, String> {
+        Ok(self.current_round() / self.control.param_u64(parameters::BRIDGE_RATE_LIMIT_WINDOW_ROUNDS)?.max(1))
+    }
+}
 
 // Simple Merkle tree for demo purposes
 pub fn merkle_root(leaves: &[String]) -> String {
@@ -164,4 +546,167 @@ pub fn verify_merkle_proof(leaf: &str, proof: &[String], root: &str, index: usiz
         idx /= 2;
     }
     hash == root
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::corda::NotaryKey;
+    use crate::consensus::parameters::{ParamValue, ParameterRegistry};
+
+    fn block_with(transactions: Vec<Transaction>, key: &SigningKey) -> Block {
+        let block_id = [42u8; 32];
+        Block {
+            block_id,
+            parent_blocks: vec![],
+            transactions,
+            findag_time: 10,
+            hashtimer: block_id,
+            proposer: Address::from_verifying_key(&key.verifying_key()),
+            signature: key.sign(&block_id),
+            public_key: key.verifying_key(),
+            shard_id: BRIDGE_SHARD,
+            merkle_root: None,
+        }
+    }
+
+    fn release(transfer: InboundTransfer, notary: &SigningKey, findag_time: u64, relayer: &SigningKey) -> BridgeTx {
+        let proof = InboundProof::Corda {
+            notary_key: notary.verifying_key().to_bytes().to_vec(),
+            notary_signature: notary.sign(&transfer.commitment()).to_bytes().to_vec(),
+        };
+        BridgeTx::sign(BridgeAction::Release { transfer, proof }, findag_time, relayer)
+    }
+
+    #[test]
+    fn test_lock_receipt_and_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(StateDB::new(dir.path().to_str().unwrap()));
+        let control = Arc::new(ChainControl::new());
+        let notary = SigningKey::from_bytes(&[3u8; 32]);
+        let rogue_notary = SigningKey::from_bytes(&[4u8; 32]);
+        let trust = BridgeTrust {
+            corda_notaries: NotaryKeySet::new(vec![NotaryKey::from_bytes(notary.verifying_key().as_bytes()).unwrap()]),
+            fabric: None,
+        };
+        let bridge = Bridge::new(state_db.clone(), control.clone(), trust);
+        let user_key = SigningKey::from_bytes(&[1u8; 32]);
+        let relayer_key = SigningKey::from_bytes(&[2u8; 32]);
+        let user = Address::from_verifying_key(&user_key.verifying_key());
+        state_db.set_balance(0, user.as_str(), "USD", 1_000).unwrap();
+
+        // Outbound: escrow, then a receipt once the block's round is final
+        let lock = BridgeTx::sign(BridgeAction::Lock {
+            to_chain: "corda".to_string(),
+            recipient: "O=Bank B, L=London, C=GB".to_string(),
+            asset: "USD".to_string(),
+            amount: 400,
+        }, 1, &user_key);
+        let lock_tx = lock.to_transaction();
+        let id = hex::encode(lock_tx.compute_hash());
+        bridge.apply_bridge_tx(&lock_tx, &lock).unwrap();
+        assert_eq!(state_db.get_balance(0, BRIDGE_ESCROW_ADDRESS, "USD"), 400);
+        assert_eq!(state_db.get_balance(0, user.as_str(), "USD"), 600);
+
+        let filler = BridgeTx::sign(BridgeAction::Lock {
+            to_chain: "corda".to_string(), recipient: "x".to_string(), asset: "EUR".to_string(), amount: 1,
+        }, 2, &relayer_key).to_transaction();
+        let block = block_with(vec![filler, lock_tx], &relayer_key);
+        bridge.on_block_applied(&block);
+        assert!(bridge.get_receipt(&id).is_none());
+        bridge.on_round_finalized(1, &[block.block_id]);
+        let receipt = bridge.get_receipt(&id).unwrap();
+        assert_eq!((receipt.round, receipt.index), (Some(1), 1));
+        assert_eq!(receipt.leaf, id);
+        assert!(bridge.verify_receipt(&id));
+        let mut forged_receipt = receipt.clone();
+        forged_receipt.tx_id = hex::encode([9u8; 32]);
+        assert!(!forged_receipt.verify());
+        assert_eq!(bridge.get_transfer(&id).unwrap().status, BridgeStatus::Finalized);
+
+        // Inbound: unlock what was locked for the source chain, once per source transaction
+        let unlock = InboundTransfer {
+            source_chain: "corda".to_string(),
+            source_tx_id: "A1B2".to_string(),
+            recipient: user.clone(),
+            asset: "USD".to_string(),
+            amount: 150,
+        };
+        let forged = release(unlock.clone(), &rogue_notary, 3, &relayer_key);
+        assert!(bridge.apply_bridge_tx(&forged.to_transaction(), &forged).is_err());
+        let mut inflated = release(unlock.clone(), &notary, 3, &relayer_key);
+        if let BridgeAction::Release { transfer, .. } = &mut inflated.action {
+            transfer.amount = 300;
+        }
+        let inflated = BridgeTx::sign(inflated.action, 3, &relayer_key);
+        assert!(bridge.apply_bridge_tx(&inflated.to_transaction(), &inflated).is_err());
+
+        let valid = release(unlock.clone(), &notary, 4, &relayer_key);
+        bridge.apply_bridge_tx(&valid.to_transaction(), &valid).unwrap();
+        assert_eq!(state_db.get_balance(0, user.as_str(), "USD"), 750);
+        assert_eq!(state_db.bridge_locked("corda", "USD"), 250);
+        let replay = release(unlock, &notary, 5, &relayer_key);
+        assert!(bridge.apply_bridge_tx(&replay.to_transaction(), &replay).is_err());
+
+        // Nothing locked for this chain and asset: the release mints
+        let mint = release(InboundTransfer {
+            source_chain: "corda".to_string(),
+            source_tx_id: "C3D4".to_string(),
+            recipient: user.clone(),
+            asset: "GBP".to_string(),
+            amount: 80,
+        }, &notary, 6, &relayer_key);
+        let mint_tx = mint.to_transaction();
+        bridge.apply_bridge_tx(&mint_tx, &mint).unwrap();
+        assert_eq!(state_db.get_balance(0, user.as_str(), "GBP"), 80);
+        assert_eq!(bridge.get_transfer(&hex::encode(mint_tx.compute_hash())).unwrap().status, BridgeStatus::Minted);
+    }
+
+    #[test]
+    fn test_pause_and_rate_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(StateDB::new(dir.path().to_str().unwrap()));
+        let control = Arc::new(ChainControl::new());
+        let mut registry = ParameterRegistry::new();
+        registry.set(parameters::BRIDGE_RATE_LIMIT_PER_ASSET, ParamValue::U64(500), 0).unwrap();
+        registry.set(parameters::BRIDGE_RATE_LIMIT_WINDOW_ROUNDS, ParamValue::U64(10), 0).unwrap();
+        control.set_parameters(registry.clone());
+        let bridge = Bridge::new(state_db.clone(), control.clone(), BridgeTrust::default());
+        let user_key = SigningKey::from_bytes(&[1u8; 32]);
+        let user = Address::from_verifying_key(&user_key.verifying_key());
+        state_db.set_balance(0, user.as_str(), "USD", 1_000).unwrap();
+        let lock = |amount, findag_time| BridgeTx::sign(BridgeAction::Lock {
+            to_chain: "fabric".to_string(), recipient: "Org2MSP".to_string(), asset: "USD".to_string(), amount,
+        }, findag_time, &user_key);
+        let apply = |bridge_tx: BridgeTx| bridge.apply_bridge_tx(&bridge_tx.to_transaction(), &bridge_tx);
+
+        apply(lock(300, 1)).unwrap();
+        assert!(apply(lock(300, 2)).is_err());
+        apply(lock(200, 3)).unwrap();
+
+        // A new window resets the cap
+        bridge.on_round_finalized(10, &[]);
+        control.pause("incident");
+        assert!(apply(lock(100, 4)).is_err());
+        control.resume();
+        registry.set(parameters::BRIDGE_PAUSED, ParamValue::U64(1), 0).unwrap();
+        control.set_parameters(registry.clone());
+        assert!(apply(lock(100, 5)).is_err());
+        registry.set(parameters::BRIDGE_PAUSED, ParamValue::U64(0), 0).unwrap();
+        control.set_parameters(registry);
+        apply(lock(100, 6)).unwrap();
+        assert_eq!(state_db.get_balance(0, BRIDGE_ESCROW_ADDRESS, "USD"), 600);
+
+        // Fabric releases need a configured trust
+        let release = BridgeTx::sign(BridgeAction::Release {
+            transfer: InboundTransfer {
+                source_chain: "fabric".to_string(),
+                source_tx_id: "tx1".to_string(),
+                recipient: user,
+                asset: "USD".to_string(),
+                amount: 10,
+            },
+            proof: InboundProof::Fabric { endorsements: vec![] },
+        }, 7, &user_key);
+        assert!(apply(release).is_err());
+    }
+}
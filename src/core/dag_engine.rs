@@ -1,7 +1,18 @@
 use crate::core::types::{Block, ShardId};
 use crate::core::address::Address;
+use crate::core::tx_status::TxStatusRegistry;
+use crate::core::executor;
+use crate::core::payload::ProtocolPayload;
+use crate::core::handle_registry::HandleRegistry;
+use crate::core::bridge::Bridge;
+use crate::consensus::governance_executor::GovernanceExecutor;
+use crate::consensus::parameters::FEE_MIN_TX_FEE;
+use crate::consensus::slashing::SlashingEngine;
+use crate::consensus::validator_lifecycle::ValidatorLifecycle;
+use crate::consensus::staking::StakingLedger;
+use crate::storage::state::StateDB;
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use sha2::{Sha256, Digest};
 use serde::{Serialize, Deserialize};
 use tokio::sync::Mutex as TokioMutex;
@@ -40,6 +51,14 @@ pub struct DagEngine {
     _max_depth: u64,
     shard_tips: Arc<TokioMutex<HashMap<ShardId, Vec<[u8; 32]>>>>,
     stats: Arc<TokioMutex<DagStats>>,
+    status_registry: Option<Arc<TxStatusRegistry>>,
+    state_db: Option<Arc<StateDB>>,
+    governance_executor: Option<Arc<GovernanceExecutor>>,
+    staking_ledger: Option<Arc<StakingLedger>>,
+    slashing_engine: Option<Arc<SlashingEngine>>,
+    validator_lifecycle: Option<Arc<ValidatorLifecycle>>,
+    handle_registry: Option<Arc<Mutex<HandleRegistry>>>,
+    bridge: Option<Arc<Bridge>>,
 }
 
 impl DagEngine {
@@ -56,6 +75,14 @@ impl DagEngine {
                 max_depth: 0,
                 avg_txs_per_block: 0.0,
             })),
+            status_registry: None,
+            state_db: None,
+            governance_executor: None,
+            staking_ledger: None,
+            slashing_engine: None,
+            validator_lifecycle: None,
+            handle_registry: None,
+            bridge: None,
         };
         engine.create_genesis_blocks().await;
         engine.update_stats().await;
@@ -99,12 +126,111 @@ impl DagEngine {
         }
     }
 
+    /// Attach a transaction status registry to be notified of block inclusion
+    pub fn set_status_registry(&mut self, registry: Arc<TxStatusRegistry>) {
+        self.status_registry = Some(registry);
+    }
+
+    /// Attach the state database that block transactions are executed against
+    pub fn set_state_db(&mut self, state_db: Arc<StateDB>) {
+        self.state_db = Some(state_db);
+    }
+
+    /// Attach the governance executor that on-chain proposals and votes are applied to
+    pub fn set_governance_executor(&mut self, executor: Arc<GovernanceExecutor>) {
+        self.governance_executor = Some(executor);
+    }
+
+    /// Attach the staking ledger that delegations and undelegations are applied to
+    pub fn set_staking_ledger(&mut self, ledger: Arc<StakingLedger>) {
+        self.staking_ledger = Some(ledger);
+    }
+
+    /// Attach the slashing engine that evidence reports and unjail requests are applied to
+    pub fn set_slashing_engine(&mut self, engine: Arc<SlashingEngine>) {
+        self.slashing_engine = Some(engine);
+    }
+
+    /// Attach the lifecycle handler that validator registrations, key rotations and exits are applied to
+    pub fn set_validator_lifecycle(&mut self, lifecycle: Arc<ValidatorLifecycle>) {
+        self.validator_lifecycle = Some(lifecycle);
+    }
+
+    /// Attach the handle registry that handle registrations, rotations, revocations and freezes are applied to
+    pub fn set_handle_registry(&mut self, registry: Arc<Mutex<HandleRegistry>>) {
+        self.handle_registry = Some(registry);
+    }
+
+    /// Attach the bridge that locks and releases are applied to
+    pub fn set_bridge(&mut self, bridge: Arc<Bridge>) {
+        self.bridge = Some(bridge);
+    }
+
+    /// Execute one block transaction, routing protocol payloads to their handlers
+    fn execute_transaction(&self, block_id: [u8; 32], tx: &crate::core::types::Transaction) -> Result<(), String> {
+        if let Some(payload) = ProtocolPayload::from_transaction(tx) {
+            if let Some(outcome) = self.apply_payload(block_id, tx, &payload?) {
+                return outcome;
+            }
+        }
+        // The governance minimum fee is charged on execution, not just checked at admission
+        let fee = self.governance_executor.as_ref()
+            .and_then(|executor| executor.control().param_u64(FEE_MIN_TX_FEE).ok())
+            .unwrap_or(0);
+        match &self.state_db {
+            Some(state_db) => executor::apply_transaction_with_fee(state_db, tx, fee),
+            None => Ok(()),
+        }
+    }
+
+    /// Apply a protocol payload through its attached handler; None if the executor settles it
+    /// Governance transactions are held until the block's round reaches quorum, which fixes their round
+    fn apply_payload(&self, block_id: [u8; 32], tx: &crate::core::types::Transaction, payload: &ProtocolPayload) -> Option<Result<(), String>> {
+        Some(match payload {
+            ProtocolPayload::Governance(gov_tx) => self.governance_executor.as_ref()?.include_governance_tx(block_id, gov_tx),
+            ProtocolPayload::Staking(staking_tx) => self.staking_ledger.as_ref()?.apply_staking_tx(staking_tx),
+            ProtocolPayload::Slashing(slashing_tx) => self.slashing_engine.as_ref()?.apply_slashing_tx(slashing_tx).map(|_| ()),
+            ProtocolPayload::Validator(validator_tx) => self.validator_lifecycle.as_ref()?.apply_validator_tx(validator_tx),
+            ProtocolPayload::Handle(handle_tx) => self.handle_registry.as_ref()?.lock().unwrap().apply_handle_tx(handle_tx),
+            ProtocolPayload::Bridge(bridge_tx) => self.bridge.as_ref()?.apply_bridge_tx(tx, bridge_tx),
+            ProtocolPayload::Dvp(_) | ProtocolPayload::Migration(_) => return None,
+        })
+    }
+
     /// Add a new block to the DAG
     pub async fn add_block(&self, block: Block) -> Result<(), String> {
-        let block_id = self.compute_block_id(&block);
+        if !block.validate_tx_validity_windows() {
+            return Err("Block contains transactions outside their validity window".to_string());
+        }
+        
+        // Keyed by the signed block id, the same id inclusion and finality are reported under
+        let block_id = block.block_id;
+        if self.vertices.lock().await.contains_key(&block_id) {
+            // Already applied (e.g. our own block echoed back by a peer)
+            return Ok(());
+        }
         let parents = self.select_parents(&block_id).await;
         let timestamp = self.get_current_timestamp();
         
+        // Execute transactions against the state; failures do not invalidate the block
+        let outcomes: Vec<([u8; 32], Result<(), String>)> = block.transactions.iter()
+            .map(|tx| (tx.compute_hash(), self.execute_transaction(block_id, tx)))
+            .collect();
+        
+        if let Some(registry) = &self.status_registry {
+            let mut included = Vec::with_capacity(outcomes.len());
+            for (tx_hash, outcome) in &outcomes {
+                match outcome {
+                    Ok(()) => included.push(*tx_hash),
+                    Err(e) => registry.mark_rejected(*tx_hash, &format!("execution failed: {e}")),
+                }
+            }
+            registry.mark_included(block.block_id, &included);
+        }
+        if let Some(bridge) = &self.bridge {
+            bridge.on_block_applied(&block);
+        }
+        
         let vertex = DAGVertex::new(block, parents, timestamp);
         
         let mut vertices = self.vertices.lock().await;
@@ -198,22 +324,6 @@ impl DagEngine {
         self.get_tips().await
     }
 
-    fn compute_block_id(&self, block: &Block) -> [u8; 32] {
-        let mut hasher = Sha256::new();
-        hasher.update(block.shard_id.0.to_be_bytes());
-        hasher.update(block.findag_time.to_be_bytes());
-        hasher.update(block.hashtimer);
-        for tx in &block.transactions {
-            hasher.update(tx.from.0.as_bytes());
-            hasher.update(tx.to.0.as_bytes());
-            hasher.update(tx.amount.to_be_bytes());
-        }
-        let result = hasher.finalize();
-        let mut block_id = [0u8; 32];
-        block_id.copy_from_slice(&result);
-        block_id
-    }
-
     async fn select_parents(&self, _block_id: &[u8; 32]) -> Vec<[u8; 32]> {
         // Simple parent selection - use all current tips
         let tips = self.tips.lock().await;
@@ -0,0 +1,140 @@
+use crate::core::address::Address;
+use crate::core::multisig::{authorize_party, MultisigAccount};
+use crate::core::types::{PartySignature, ShardId, Transaction};
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use crate::iso20022::settlement::{parse_sese023, MovementType, SettlementInstruction};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Payload prefix identifying a delivery-versus-payment settlement carried in `Transaction.payload`
+pub const DVP_PAYLOAD_TAG: &[u8] = b"FDG:DVP:1";
+
+/// Message a party signs to authorize its sese.023 instruction
+pub fn instruction_signing_message(xml: &str) -> Vec<u8> {
+    let mut message = DVP_PAYLOAD_TAG.to_vec();
+    message.extend_from_slice(&Sha256::digest(xml.as_bytes()));
+    message
+}
+
+/// A sese.023 document signed by the owner of its safekeeping account.
+/// When the account is a multisig account, the signer and `approvals` are its policy keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedInstruction {
+    pub xml: String,
+    pub public_key: VerifyingKey,
+    pub signature: Signature,
+    #[serde(default)]
+    pub approvals: Vec<PartySignature>, // Co-signatures of a multisig account's policy keys
+}
+
+impl SignedInstruction {
+    pub fn parse(&self) -> Result<SettlementInstruction, String> {
+        parse_sese023(&self.xml).map_err(|e| e.to_string())
+    }
+
+    /// Check the signature and that the instructing account authorized it
+    pub fn verify(&self, multisig_account: &dyn Fn(&str) -> Option<MultisigAccount>) -> Result<SettlementInstruction, String> {
+        let message = instruction_signing_message(&self.xml);
+        self.public_key
+            .verify(&message, &self.signature)
+            .map_err(|_| "Invalid settlement instruction signature".to_string())?;
+        let instruction = self.parse()?;
+        let mut signatures = vec![PartySignature { public_key: self.public_key, signature: self.signature }];
+        signatures.extend(self.approvals.iter().cloned());
+        authorize_party(instruction.owner_account(), multisig_account, &message, &signatures)
+            .map_err(|e| format!("Instruction {} is not authorized by its account owner: {e}", instruction.transaction_id))?;
+        Ok(instruction)
+    }
+}
+
+/// Matched pair of delivering and receiving instructions, settled in one transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DvpSettlement {
+    pub deliver: SignedInstruction,
+    pub receive: SignedInstruction,
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+}
+
+impl DvpSettlement {
+    pub fn new(deliver: SignedInstruction, receive: SignedInstruction, findag_time: u64, shard_id: ShardId) -> Self {
+        Self { deliver, receive, findag_time, shard_id }
+    }
+
+    /// Check both instructions are authorized by their owners and match each other.
+    /// Returns the delivering side's instruction.
+    pub fn verify(&self, multisig_account: &dyn Fn(&str) -> Option<MultisigAccount>) -> Result<SettlementInstruction, String> {
+        let deliver = self.deliver.verify(multisig_account)?;
+        let receive = self.receive.verify(multisig_account)?;
+        if deliver.movement != MovementType::Deliver || receive.movement != MovementType::Receive {
+            return Err("Settlement needs one delivering and one receiving instruction".to_string());
+        }
+        deliver.matches(&receive).map_err(|e| format!("Instructions do not match: {e}"))?;
+        for account in [&deliver.deliverer_account, &deliver.receiver_account] {
+            Address::parse(account).map_err(|e| format!("Invalid settlement account {account}: {e}"))?;
+        }
+        if deliver.deliverer_account == deliver.receiver_account {
+            return Err("Delivering and receiving accounts are the same".to_string());
+        }
+        Ok(deliver)
+    }
+
+    /// Securities and cash transfers, in the order they settle
+    pub fn transfers(&self, multisig_account: &dyn Fn(&str) -> Option<MultisigAccount>) -> Result<Vec<(String, String, String, u64)>, String> {
+        self.verify(multisig_account)?.transfers()
+    }
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = DVP_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&serde_json::to_vec(self).expect("dvp settlement serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a DvP settlement
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(DVP_PAYLOAD_TAG)?;
+        Some(serde_json::from_slice(body).map_err(|e| format!("Invalid DvP payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the deliverer's instruction
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|dvp| {
+            if dvp.shard_id != tx.shard_id
+                || dvp.findag_time != tx.findag_time
+                || dvp.deliver.public_key != tx.public_key
+                || Address::from_verifying_key(&dvp.deliver.public_key) != tx.from
+                || dvp.deliver.signature != tx.signature
+            {
+                return Err("DvP envelope does not match its payload".to_string());
+            }
+            Ok(dvp)
+        }))
+    }
+
+    /// Wrap in a zero-amount `Transaction` from the deliverer's signer to the receiver
+    pub fn to_transaction(&self, multisig_account: &dyn Fn(&str) -> Option<MultisigAccount>) -> Result<Transaction, String> {
+        let deliver = self.verify(multisig_account)?;
+        let payload = self.to_payload();
+        Ok(Transaction {
+            from: Address::from_verifying_key(&self.deliver.public_key),
+            to: Address::new(deliver.receiver_account),
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature: self.deliver.signature,
+            public_key: self.deliver.public_key,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        })
+    }
+}
+
@@ -0,0 +1,302 @@
+use crate::consensus::rewards::REWARD_POOL_ADDRESS;
+use crate::consensus::staking::STAKING_SHARD;
+use crate::core::multi_leg::MultiLegTransaction;
+use crate::core::multisig::MultisigOp;
+use crate::core::payload::ProtocolPayload;
+use crate::core::types::{Block, Transaction};
+use crate::dagtimer::findag_time_manager::findag_time_to_unix_secs;
+use crate::storage::state::{AccountEntry, EntryDirection, StateDB};
+use std::collections::BTreeMap;
+
+/// Asset moved by plain single-leg transactions (matches the TxPool balance check)
+pub const DEFAULT_TRANSFER_ASSET: &str = "USD";
+
+/// Apply one transaction to the state. Multi-leg transactions settle all-or-nothing.
+pub fn apply_transaction(state_db: &StateDB, tx: &Transaction) -> Result<(), String> {
+    apply_transaction_with_fee(state_db, tx, 0)
+}
+
+/// Apply one transaction, debiting `fee` from the sender of a plain transfer into the
+/// reward pool together with the transfer itself
+pub fn apply_transaction_with_fee(state_db: &StateDB, tx: &Transaction, fee: u64) -> Result<(), String> {
+    let multisig_account = |address: &str| state_db.get_multisig_account(address);
+    match ProtocolPayload::from_transaction(tx) {
+        // Matched sese.023 instructions settle securities and cash together
+        Some(Ok(ProtocolPayload::Dvp(dvp))) => {
+            let owned = dvp.transfers(&multisig_account)?;
+            let transfers: Vec<(&str, &str, &str, u64)> = owned.iter()
+                .map(|(from, to, asset, amount)| (from.as_str(), to.as_str(), asset.as_str(), *amount))
+                .collect();
+            state_db.transfer_batch(dvp.shard_id.0, &transfers)?;
+            return book_transfers(state_db, tx, dvp.shard_id.0, &transfers);
+        }
+        // Legacy balances move to the canonical address of the key that signed
+        Some(Ok(ProtocolPayload::Migration(migration))) => {
+            let canonical = migration.verify()?;
+            let moved = state_db.migrate_account(&migration.legacy_address, canonical.as_str())?;
+            println!("[DEBUG] Migrated legacy address {} -> {} ({} balances)", migration.legacy_address, canonical, moved);
+            return Ok(());
+        }
+        // Other protocol payloads move no funds here; their handlers (governance executor,
+        // staking ledger, slashing engine, validator lifecycle, handle registry, bridge) apply them
+        Some(payload) => return payload.and_then(|p| p.verify(&multisig_account)),
+        None => {}
+    }
+    if let Some(op) = MultisigOp::from_transaction(tx) {
+        let op = op?;
+        let current = state_db.get_multisig_account(op.account_address().as_str());
+        let account = op.apply(current.as_ref())?;
+        return state_db.put_multisig_account(&account);
+    }
+    match MultiLegTransaction::from_transaction(tx) {
+        // Each debited party, multisig or not, is authorized inside the payload
+        Some(multi) => {
+            let multi = multi?;
+            multi.validate(&multisig_account)?;
+            let transfers: Vec<(&str, &str, &str, u64)> = multi.legs.iter()
+                .map(|leg| (leg.from.as_str(), leg.to.as_str(), leg.asset.as_str(), leg.amount))
+                .collect();
+            state_db.transfer_batch(multi.shard_id.0, &transfers)?;
+            book_transfers(state_db, tx, multi.shard_id.0, &transfers)
+        }
+        None => {
+            // Spends from a multisig account need threshold approval, and use up the nonce
+            let multisig = multisig_account(tx.from.as_str());
+            if let Some(account) = &multisig {
+                account.authorize(tx)?;
+            }
+            let shard = tx.shard_id.0;
+            let mut batch = state_db.batch();
+            batch.transfer((shard, tx.from.as_str()), (shard, tx.to.as_str()), DEFAULT_TRANSFER_ASSET, tx.amount)?;
+            if fee > 0 {
+                batch.transfer((shard, tx.from.as_str()), (STAKING_SHARD.0, REWARD_POOL_ADDRESS), DEFAULT_TRANSFER_ASSET, fee)?;
+            }
+            // The nonce is used up in the same write as the funds move, so approvals cannot be replayed
+            if let Some(account) = multisig {
+                batch.put_multisig_account(&account.spent())?;
+            }
+            batch.commit()?;
+            let mut legs = vec![((shard, tx.from.as_str()), (shard, tx.to.as_str()), DEFAULT_TRANSFER_ASSET, tx.amount)];
+            if fee > 0 {
+                legs.push(((shard, tx.from.as_str()), (STAKING_SHARD.0, REWARD_POOL_ADDRESS), DEFAULT_TRANSFER_ASSET, fee));
+            }
+            book_legs(state_db, tx, &legs)
+        }
+    }
+}
+
+/// Record the debit and credit side of settled transfers in the account history
+fn book_transfers(state_db: &StateDB, tx: &Transaction, shard_id: u16, transfers: &[(&str, &str, &str, u64)]) -> Result<(), String> {
+    let legs: Vec<_> = transfers.iter()
+        .map(|&(from, to, asset, amount)| ((shard_id, from), (shard_id, to), asset, amount))
+        .collect();
+    book_legs(state_db, tx, &legs)
+}
+
+/// A settled ((shard, from), (shard, to), asset, amount) leg, each side on its own shard
+type BookedLeg<'a> = ((u16, &'a str), (u16, &'a str), &'a str, u64);
+
+/// Book settled legs in the account history of both sides, at the transaction's FinDAG Time
+/// so every node replaying the block records the same entries
+fn book_legs(state_db: &StateDB, tx: &Transaction, legs: &[BookedLeg]) -> Result<(), String> {
+    let tx_hash = tx.compute_hash();
+    let booked_at = findag_time_to_unix_secs(tx.findag_time);
+    let mut entries = Vec::with_capacity(legs.len() * 2);
+    for (leg, ((from_shard, from), (to_shard, to), asset, amount)) in legs.iter().enumerate() {
+        for (shard_id, address, counterparty, direction) in [(from_shard, from, to, EntryDirection::Debit), (to_shard, to, from, EntryDirection::Credit)] {
+            entries.push(AccountEntry {
+                tx_hash,
+                leg: leg as u32,
+                shard_id: *shard_id,
+                address: address.to_string(),
+                counterparty: counterparty.to_string(),
+                asset: asset.to_string(),
+                amount: *amount,
+                direction,
+                balance_after: 0,
+                booked_at,
+            });
+        }
+    }
+    // Rewind the settled balances to before this transaction, then replay leg by leg
+    let mut balances: BTreeMap<(u16, String, String), u64> = BTreeMap::new();
+    for entry in entries.iter().filter(|e| e.direction == EntryDirection::Debit) {
+        *balances.entry((entry.shard_id, entry.address.clone(), entry.asset.clone()))
+            .or_insert_with(|| state_db.get_balance(entry.shard_id, &entry.address, &entry.asset)) += entry.amount;
+    }
+    for entry in entries.iter().filter(|e| e.direction == EntryDirection::Credit) {
+        *balances.entry((entry.shard_id, entry.address.clone(), entry.asset.clone()))
+            .or_insert_with(|| state_db.get_balance(entry.shard_id, &entry.address, &entry.asset)) -= entry.amount;
+    }
+    for entry in &mut entries {
+        let balance = balances.get_mut(&(entry.shard_id, entry.address.clone(), entry.asset.clone())).unwrap();
+        match entry.direction {
+            EntryDirection::Debit => *balance -= entry.amount,
+            EntryDirection::Credit => *balance += entry.amount,
+        }
+        entry.balance_after = *balance;
+    }
+    state_db.record_account_entries(&entries)
+}
+
+/// Apply every transaction in a block, returning the outcome per transaction hash.
+/// A failed transaction leaves the state untouched and does not affect the others.
+pub fn apply_block(state_db: &StateDB, block: &Block) -> Vec<([u8; 32], Result<(), String>)> {
+    block.transactions.iter()
+        .map(|tx| (tx.compute_hash(), apply_transaction(state_db, tx)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::address::Address;
+    use crate::core::multi_leg::TransferLeg;
+    use crate::core::types::ShardId;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_multi_leg_settles_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::new(dir.path().to_str().unwrap());
+        let seller = SigningKey::from_bytes(&[1u8; 32]);
+        let buyer = SigningKey::from_bytes(&[2u8; 32]);
+        let seller_addr = Address::from_verifying_key(&seller.verifying_key());
+        let buyer_addr = Address::from_verifying_key(&buyer.verifying_key());
+        state_db.set_balance(0, seller_addr.as_str(), "BUND", 10).unwrap();
+        state_db.set_balance(0, buyer_addr.as_str(), "EUR", 500).unwrap();
+
+        let mut multi = MultiLegTransaction::new(
+            vec![
+                TransferLeg { from: seller_addr.clone(), to: buyer_addr.clone(), asset: "BUND".to_string(), amount: 10 },
+                TransferLeg { from: buyer_addr.clone(), to: seller_addr.clone(), asset: "EUR".to_string(), amount: 1_000 },
+            ],
+            "TRADE-2".to_string(),
+            1,
+            ShardId(0),
+        );
+        multi.sign(&seller);
+        multi.sign(&buyer);
+
+        // Cash leg is underfunded, so the securities leg must not settle either
+        assert!(apply_transaction(&state_db, &multi.to_transaction().unwrap()).is_err());
+        assert_eq!(state_db.get_balance(0, seller_addr.as_str(), "BUND"), 10);
+        assert_eq!(state_db.get_balance(0, buyer_addr.as_str(), "BUND"), 0);
+
+        state_db.set_balance(0, buyer_addr.as_str(), "EUR", 1_000).unwrap();
+        assert!(apply_transaction(&state_db, &multi.to_transaction().unwrap()).is_ok());
+        assert_eq!(state_db.get_balance(0, buyer_addr.as_str(), "BUND"), 10);
+        assert_eq!(state_db.get_balance(0, seller_addr.as_str(), "EUR"), 1_000);
+        assert_eq!(state_db.get_balance(0, buyer_addr.as_str(), "EUR"), 0);
+    }
+
+    #[test]
+    fn test_min_fee_debited_into_reward_pool() {
+        use crate::dagtimer::hashtimer::compute_hashtimer;
+        use ed25519_dalek::Signer;
+
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::new(dir.path().to_str().unwrap());
+        let sender = SigningKey::from_bytes(&[6u8; 32]);
+        let from = Address::from_signing_key(&sender);
+        let to = Address::from_signing_key(&SigningKey::from_bytes(&[7u8; 32]));
+        state_db.set_balance(0, from.as_str(), DEFAULT_TRANSFER_ASSET, 1_000).unwrap();
+        let transfer = |amount: u64, findag_time: u64| Transaction {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+            payload: vec![],
+            findag_time,
+            hashtimer: compute_hashtimer(findag_time, &[], 0),
+            signature: sender.sign(b"envelope"),
+            public_key: sender.verifying_key(),
+            shard_id: ShardId(0),
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        };
+
+        apply_transaction_with_fee(&state_db, &transfer(100, 1), 10).unwrap();
+        assert_eq!(state_db.get_balance(0, from.as_str(), DEFAULT_TRANSFER_ASSET), 890);
+        assert_eq!(state_db.get_balance(0, to.as_str(), DEFAULT_TRANSFER_ASSET), 100);
+        assert_eq!(state_db.get_balance(STAKING_SHARD.0, REWARD_POOL_ADDRESS, DEFAULT_TRANSFER_ASSET), 10);
+
+        // A transfer that cannot also cover the fee moves nothing
+        assert!(apply_transaction_with_fee(&state_db, &transfer(885, 2), 10).is_err());
+        assert_eq!(state_db.get_balance(0, from.as_str(), DEFAULT_TRANSFER_ASSET), 890);
+        assert_eq!(state_db.get_balance(STAKING_SHARD.0, REWARD_POOL_ADDRESS, DEFAULT_TRANSFER_ASSET), 10);
+    }
+
+    #[test]
+    fn test_multisig_spend_bound_to_tx_and_nonce() {
+        use crate::core::multisig::{MultisigAccount, MultisigPolicy, MultisigSigner};
+        use crate::core::types::PartySignature;
+        use crate::dagtimer::hashtimer::compute_hashtimer;
+        use ed25519_dalek::Signer;
+
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::new(dir.path().to_str().unwrap());
+        let (a, b) = (SigningKey::from_bytes(&[3u8; 32]), SigningKey::from_bytes(&[4u8; 32]));
+        let policy = MultisigPolicy {
+            signers: [&a, &b].iter().map(|k| MultisigSigner { public_key: k.verifying_key(), weight: 1 }).collect(),
+            threshold: 2,
+        };
+        let account = MultisigAccount { address: policy.derive_address(b"ops"), policy, nonce: 0 };
+        state_db.put_multisig_account(&account).unwrap();
+        state_db.set_balance(0, account.address.as_str(), DEFAULT_TRANSFER_ASSET, 1_000).unwrap();
+        let payee = Address::from_signing_key(&SigningKey::from_bytes(&[5u8; 32]));
+
+        let spend = |findag_time: u64, account: &MultisigAccount| {
+            let mut tx = Transaction {
+                from: account.address.clone(),
+                to: payee.clone(),
+                amount: 100,
+                payload: vec![],
+                findag_time,
+                hashtimer: compute_hashtimer(findag_time, &[], 0),
+                signature: a.sign(b"envelope"),
+                public_key: a.verifying_key(),
+                shard_id: ShardId(0),
+                source_shard: None,
+                dest_shard: None,
+                target_chain: None,
+                bridge_protocol: None,
+                valid_after: None,
+                valid_until: None,
+                multisig_signatures: Vec::new(),
+            };
+            let message = account.spend_message(&tx);
+            tx.multisig_signatures = [&a, &b].iter()
+                .map(|k| PartySignature { public_key: k.verifying_key(), signature: k.sign(&message) })
+                .collect();
+            tx
+        };
+
+        // A spend that cannot settle leaves the nonce unused
+        state_db.set_balance(0, account.address.as_str(), DEFAULT_TRANSFER_ASSET, 50).unwrap();
+        assert!(apply_transaction(&state_db, &spend(1, &account)).is_err());
+        assert_eq!(state_db.get_multisig_account(account.address.as_str()).unwrap().nonce, 0);
+        state_db.set_balance(0, account.address.as_str(), DEFAULT_TRANSFER_ASSET, 1_000).unwrap();
+
+        // Approvals do not carry over to a different shard or payload
+        let mut moved = spend(1, &account);
+        moved.shard_id = ShardId(1);
+        assert!(apply_transaction(&state_db, &moved).is_err());
+        let mut altered = spend(1, &account);
+        altered.payload = b"memo".to_vec();
+        assert!(apply_transaction(&state_db, &altered).is_err());
+
+        let tx = spend(1, &account);
+        apply_transaction(&state_db, &tx).unwrap();
+        assert_eq!(state_db.get_multisig_account(account.address.as_str()).unwrap().nonce, 1);
+        // Replays and approvals for the used nonce are rejected
+        assert!(apply_transaction(&state_db, &tx).is_err());
+        assert!(apply_transaction(&state_db, &spend(2, &account)).is_err());
+        apply_transaction(&state_db, &spend(2, &account.spent())).unwrap();
+        assert_eq!(state_db.get_balance(0, payee.as_str(), DEFAULT_TRANSFER_ASSET), 200);
+    }
+}
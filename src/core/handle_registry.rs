@@ -1,9 +1,20 @@
 use ed25519_dalek::{Signature, VerifyingKey, Verifier};
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
 use chrono::{DateTime, Utc};
+use crate::core::address::Address;
+use crate::core::types::{ShardId, Transaction};
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use crate::storage::persistent::PersistentStorage;
+
+/// Payload prefix identifying a handle operation carried in `Transaction.payload`
+pub const HANDLE_PAYLOAD_TAG: &[u8] = b"FDG:HANDLE:1";
+
+/// Shard handle operations are executed on
+pub const HANDLE_SHARD: ShardId = ShardId(0);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HandleRecord {
@@ -17,6 +28,27 @@ pub struct HandleRecord {
     pub revoked_at: Option<DateTime<Utc>>,
     pub revocation_reason: Option<String>,
     pub children: Vec<String>,
+    #[serde(default)]
+    pub frozen_by: Option<String>, // Ancestor handle that froze this handle
+}
+
+impl HandleRecord {
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_by.is_some()
+    }
+}
+
+/// Payment recipient resolved from a handle at admission, kept in the tx receipt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedHandle {
+    pub handle: String,
+    pub public_key: VerifyingKey,
+    pub address: Address,
+}
+
+/// True if a recipient string names a handle rather than an address
+pub fn is_handle(recipient: &str) -> bool {
+    recipient.starts_with('@')
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,7 +61,8 @@ pub struct KeyHistory {
 #[derive(Default)]
 pub struct HandleRegistry {
     pub handles: HashMap<String, HandleRecord>,
-    pub pubkey_to_handle: Vec<(VerifyingKey, String)>,
+    pub pubkey_to_handle: HashMap<VerifyingKey, String>,
+    storage: Option<Arc<PersistentStorage>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,52 +91,259 @@ pub struct RevokeHandleInstruction {
     pub parent_signature: String, // base64, signed by parent
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreezeHandleInstruction {
+    pub handle: String,
+    pub authority: String, // Ancestor handle freezing or unfreezing `handle`
+    pub frozen: bool,
+    pub reason: String,
+    pub timestamp: String,
+    pub signature: String, // base64, signed by the authority's current key
+}
+
+/// Handle operation; each instruction carries the signature that authorizes it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandleOp {
+    Register(RegisterSubhandleInstruction),
+    RotateKey(RotateKeyInstruction),
+    Revoke(RevokeHandleInstruction),
+    Freeze(FreezeHandleInstruction),
+}
+
+impl HandleOp {
+    pub fn handle(&self) -> &str {
+        match self {
+            HandleOp::Register(instr) => &instr.handle,
+            HandleOp::RotateKey(instr) => &instr.handle,
+            HandleOp::Revoke(instr) => &instr.handle,
+            HandleOp::Freeze(instr) => &instr.handle,
+        }
+    }
+
+    /// Message the instruction's signature covers
+    pub fn payload_to_sign(&self) -> String {
+        match self {
+            HandleOp::Register(instr) => HandleRegistry::subhandle_payload_to_sign(instr),
+            HandleOp::RotateKey(instr) => HandleRegistry::rotate_key_payload_to_sign(instr),
+            HandleOp::Revoke(instr) => HandleRegistry::revoke_handle_payload_to_sign(instr),
+            HandleOp::Freeze(instr) => HandleRegistry::freeze_handle_payload_to_sign(instr),
+        }
+    }
+
+    pub fn signature(&self) -> Result<Signature, String> {
+        decode_signature(match self {
+            HandleOp::Register(instr) => &instr.parent_signature,
+            HandleOp::RotateKey(instr) => &instr.signature,
+            HandleOp::Revoke(instr) => &instr.parent_signature,
+            HandleOp::Freeze(instr) => &instr.signature,
+        })
+    }
+}
+
+/// Handle operation submitted on chain. `signer` is the key that signed the
+/// instruction: the parent's for registration and revocation, the handle's own
+/// for rotation, the authority's for freezing. The registry checks it is the
+/// right key when the operation is applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandleTx {
+    pub op: HandleOp,
+    pub signer: VerifyingKey,
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+}
+
+impl HandleTx {
+    pub fn new(op: HandleOp, signer: VerifyingKey, findag_time: u64) -> Self {
+        Self { op, signer, findag_time, shard_id: HANDLE_SHARD }
+    }
+
+    /// Check the instruction is signed by `signer`
+    pub fn verify(&self) -> Result<(), String> {
+        if self.shard_id != HANDLE_SHARD {
+            return Err(format!("Handle operations must be on shard {}", HANDLE_SHARD.0));
+        }
+        let signature = self.op.signature()?;
+        self.signer.verify(self.op.payload_to_sign().as_bytes(), &signature)
+            .map_err(|_| "Invalid handle operation signature".to_string())
+    }
+
+    /// Encoded as JSON rather than bincode because handle metadata is free-form JSON
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = HANDLE_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&serde_json::to_vec(self).expect("handle tx serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a handle operation
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(HANDLE_PAYLOAD_TAG)?;
+        Some(serde_json::from_slice(body).map_err(|e| format!("Invalid handle payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the signed body
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|handle_tx| {
+            if handle_tx.shard_id != tx.shard_id
+                || handle_tx.findag_time != tx.findag_time
+                || handle_tx.signer != tx.public_key
+                || Address::from_verifying_key(&handle_tx.signer) != tx.from
+                || handle_tx.op.signature()? != tx.signature
+            {
+                return Err("Handle envelope does not match its payload".to_string());
+            }
+            Ok(handle_tx)
+        }))
+    }
+
+    /// Wrap in a zero-amount `Transaction` from the signer to itself
+    pub fn to_transaction(&self) -> Result<Transaction, String> {
+        let address = Address::from_verifying_key(&self.signer);
+        let signature = self.op.signature()?;
+        let payload = self.to_payload();
+        Ok(Transaction {
+            from: address.clone(),
+            to: address,
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature,
+            public_key: self.signer,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        })
+    }
+}
+
+fn decode_signature(signature: &str) -> Result<Signature, String> {
+    let sig_bytes = STANDARD.decode(signature)
+        .map_err(|_| "Invalid base64 signature")?;
+    Ok(Signature::from_bytes(&sig_bytes.try_into().map_err(|_| "Invalid signature length")?))
+}
+
+fn decode_pubkey(pubkey: &str) -> Result<VerifyingKey, String> {
+    let pubkey_bytes = STANDARD.decode(pubkey)
+        .map_err(|_| "Invalid base64 pubkey")?;
+    VerifyingKey::from_bytes(&pubkey_bytes.try_into().map_err(|_| "Invalid pubkey length")?)
+        .map_err(|_| "Invalid ed25519 public key format".to_string())
+}
+
+fn parse_timestamp(timestamp: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|_| "Invalid timestamp".to_string())
+}
+
 impl HandleRegistry {
+    /// Rebuild the registry from the handles persisted in `storage`, and keep persisting changes there
+    pub fn load(storage: Arc<PersistentStorage>) -> Self {
+        let mut registry = Self::default();
+        for record in storage.load_handles() {
+            if !record.revoked {
+                registry.pubkey_to_handle.insert(record.public_key, record.handle.clone());
+            }
+            registry.handles.insert(record.handle.clone(), record);
+        }
+        registry.storage = Some(storage);
+        registry
+    }
+
+    /// Apply a handle operation included in a block
+    pub fn apply_handle_tx(&mut self, tx: &HandleTx) -> Result<(), String> {
+        tx.verify()?;
+        match &tx.op {
+            HandleOp::Register(instr) => self.register_subhandle(instr),
+            HandleOp::RotateKey(instr) => self.rotate_key(instr),
+            HandleOp::Revoke(instr) => self.revoke_handle(instr),
+            HandleOp::Freeze(instr) => self.freeze_handle(instr),
+        }?;
+        println!("[DEBUG] Handles: applied {} for {}", match &tx.op {
+            HandleOp::Register(_) => "register",
+            HandleOp::RotateKey(_) => "rotate_key",
+            HandleOp::Revoke(_) => "revoke",
+            HandleOp::Freeze(instr) if instr.frozen => "freeze",
+            HandleOp::Freeze(_) => "unfreeze",
+        }, tx.op.handle());
+        Ok(())
+    }
+
+    /// Register a top-level handle (genesis configuration; there is no parent to sign)
+    pub fn register_root(
+        &mut self,
+        handle: &str,
+        public_key: VerifyingKey,
+        metadata: Option<serde_json::Value>,
+        registered_at: DateTime<Utc>,
+    ) -> Result<(), String> {
+        if !handle.starts_with('@') || handle.len() < 2 {
+            return Err("Handles must start with '@'".to_string());
+        }
+        if self.handles.contains_key(handle) {
+            return Err("Handle already exists".to_string());
+        }
+        if self.pubkey_to_handle.contains_key(&public_key) {
+            return Err("Public key already in use by another handle".to_string());
+        }
+        self.handles.insert(handle.to_string(), HandleRecord {
+            handle: handle.to_string(),
+            parent: None,
+            public_key,
+            key_history: vec![],
+            metadata,
+            registered_at,
+            revoked: false,
+            revoked_at: None,
+            revocation_reason: None,
+            children: vec![],
+            frozen_by: None,
+        });
+        self.pubkey_to_handle.insert(public_key, handle.to_string());
+        self.persist(&[handle]);
+        Ok(())
+    }
+
     /// Register a new subhandle (must be signed by parent)
     pub fn register_subhandle(
         &mut self,
         instr: &RegisterSubhandleInstruction,
     ) -> Result<(), String> {
-        // 1. Check parent exists
-        let parent_record = self.handles.get(&instr.parent)
-            .ok_or("Parent handle does not exist")?;
-        if parent_record.revoked {
-            return Err("Parent handle is revoked".to_string());
-        }
+        // 1. Check parent exists and may act
+        let parent_record = self.authority(&instr.parent)?;
 
-        // 2. Check handle doesn't already exist
+        // 2. Check handle doesn't already exist and is named under its parent
         if self.handles.contains_key(&instr.handle) {
             return Err("Handle already exists".to_string());
         }
+        let suffix = format!(".{}", instr.parent.trim_start_matches('@'));
+        match instr.handle.strip_prefix('@').and_then(|h| h.strip_suffix(&suffix)) {
+            Some(label) if !label.is_empty() && !label.contains('.') => {}
+            _ => return Err(format!("Subhandle must be named @<label>.{}", instr.parent.trim_start_matches('@'))),
+        }
 
         // 3. Verify parent signature
         let payload = Self::subhandle_payload_to_sign(instr);
-        let parent_pubkey = parent_record.public_key;
-        let sig_bytes = STANDARD.decode(&instr.parent_signature)
-            .map_err(|_| "Invalid base64 signature")?;
-        let signature = Signature::from_bytes(&sig_bytes.try_into().map_err(|_| "Invalid signature length")?);
-        parent_pubkey.verify(payload.as_bytes(), &signature)
+        let signature = decode_signature(&instr.parent_signature)?;
+        parent_record.public_key.verify(payload.as_bytes(), &signature)
             .map_err(|_| "Parent signature verification failed")?;
 
         // 4. Parse new pubkey
-        let new_pubkey_bytes = STANDARD.decode(&instr.new_pubkey)
-            .map_err(|_| "Invalid base64 pubkey")?;
-        // let _new_pubkey = PublicKey::try_decode_protobuf(&new_pubkey_bytes)
-        //     .map_err(|_| "Invalid pubkey bytes")?;
-        
-        // Convert to ed25519_dalek::VerifyingKey
-        let new_pubkey_ed25519 = VerifyingKey::from_bytes(&new_pubkey_bytes.try_into().map_err(|_| "Invalid pubkey length")?)
-            .map_err(|_| "Invalid ed25519 public key format")?;
+        let new_pubkey_ed25519 = decode_pubkey(&instr.new_pubkey)?;
 
         // 5. Check pubkey not already in use
-        if self.pubkey_to_handle.iter().any(|&(pubkey, _)| pubkey == new_pubkey_ed25519) {
+        if self.pubkey_to_handle.contains_key(&new_pubkey_ed25519) {
             return Err("Public key already in use by another handle".to_string());
         }
 
         // 6. Parse timestamp
-        let now = DateTime::parse_from_rfc3339(&instr.timestamp)
-            .map_err(|_| "Invalid timestamp")?
-            .with_timezone(&Utc);
+        let now = parse_timestamp(&instr.timestamp)?;
 
         // 7. Create record
         let record = HandleRecord {
@@ -121,16 +361,18 @@ impl HandleRegistry {
             revoked_at: None,
             revocation_reason: None,
             children: vec![],
+            frozen_by: None,
         };
 
         // 8. Insert record and update indexes
         self.handles.insert(instr.handle.clone(), record);
-        self.pubkey_to_handle.push((new_pubkey_ed25519, instr.handle.clone()));
+        self.pubkey_to_handle.insert(new_pubkey_ed25519, instr.handle.clone());
         
         // 9. Update parent's children
         let parent = self.handles.get_mut(&instr.parent).unwrap();
         parent.children.push(instr.handle.clone());
         
+        self.persist(&[&instr.handle, &instr.parent]);
         Ok(())
     }
 
@@ -139,51 +381,38 @@ impl HandleRegistry {
         &mut self,
         instr: &RotateKeyInstruction,
     ) -> Result<(), String> {
-        // 1. Check handle exists and not revoked
-        let record = self.handles.get_mut(&instr.handle)
-            .ok_or("Handle does not exist")?;
-        if record.revoked {
-            return Err("Handle is revoked".to_string());
-        }
+        // 1. Check handle exists and may act
+        let current_pubkey = self.authority(&instr.handle)?.public_key;
 
         // 2. Verify current key signature
         let payload = Self::rotate_key_payload_to_sign(instr);
-        let current_pubkey = record.public_key;
-        let sig_bytes = STANDARD.decode(&instr.signature)
-            .map_err(|_| "Invalid base64 signature")?;
-        let signature = Signature::from_bytes(&sig_bytes.try_into().map_err(|_| "Invalid signature length")?);
+        let signature = decode_signature(&instr.signature)?;
         current_pubkey.verify(payload.as_bytes(), &signature)
             .map_err(|_| "Current key signature verification failed")?;
 
         // 3. Parse new pubkey
-        let new_pubkey_bytes = STANDARD.decode(&instr.new_pubkey)
-            .map_err(|_| "Invalid base64 pubkey")?;
-        // let _new_pubkey = PublicKey::try_decode_protobuf(&new_pubkey_bytes)
-        //     .map_err(|_| "Invalid pubkey bytes")?;
+        let new_pubkey_ed25519 = decode_pubkey(&instr.new_pubkey)?;
         
-        // Convert to ed25519_dalek::VerifyingKey
-        let new_pubkey_ed25519 = VerifyingKey::from_bytes(&new_pubkey_bytes.try_into().map_err(|_| "Invalid pubkey length")?)
-            .map_err(|_| "Invalid ed25519 public key format")?;
-        
-        // 5. Check pubkey not already in use
-        if self.pubkey_to_handle.iter().any(|&(pubkey, _)| pubkey == new_pubkey_ed25519) {
+        // 4. Check pubkey not already in use
+        if self.pubkey_to_handle.contains_key(&new_pubkey_ed25519) {
             return Err("Public key already in use by another handle".to_string());
         }
+        let now = parse_timestamp(&instr.timestamp)?;
         
-        // 6. Update record
-        let now = Utc::now();
+        // 5. Update record
+        let record = self.handles.get_mut(&instr.handle).unwrap();
         record.key_history.push(KeyHistory {
             public_key: record.public_key,
             rotated_at: now,
             rotated_by: instr.handle.clone(),
         });
-        
-        // 7. Update indexes
-        self.pubkey_to_handle.push((new_pubkey_ed25519, instr.handle.clone()));
-        
-        // 8. Update record
         record.public_key = new_pubkey_ed25519;
+        
+        // 6. Update indexes
+        self.pubkey_to_handle.remove(&current_pubkey);
+        self.pubkey_to_handle.insert(new_pubkey_ed25519, instr.handle.clone());
 
+        self.persist(&[&instr.handle]);
         Ok(())
     }
 
@@ -203,24 +432,14 @@ impl HandleRegistry {
         };
 
         // 2. Verify parent signature
-        let parent_record = self.handles.get(&parent_handle)
-            .ok_or("Parent handle does not exist")?;
-        if parent_record.revoked {
-            return Err("Parent handle is revoked".to_string());
-        }
-
+        let parent_record = self.authority(&parent_handle)?;
         let payload = Self::revoke_handle_payload_to_sign(instr);
-        let parent_pubkey = parent_record.public_key;
-        let sig_bytes = STANDARD.decode(&instr.parent_signature)
-            .map_err(|_| "Invalid base64 signature")?;
-        let signature = Signature::from_bytes(&sig_bytes.try_into().map_err(|_| "Invalid signature length")?);
-        parent_pubkey.verify(payload.as_bytes(), &signature)
+        let signature = decode_signature(&instr.parent_signature)?;
+        parent_record.public_key.verify(payload.as_bytes(), &signature)
             .map_err(|_| "Parent signature verification failed")?;
 
         // 3. Parse timestamp
-        let now = DateTime::parse_from_rfc3339(&instr.timestamp)
-            .map_err(|_| "Invalid timestamp")?
-            .with_timezone(&Utc);
+        let now = parse_timestamp(&instr.timestamp)?;
 
         // 4. Update handle record
         if let Some(record) = self.handles.get_mut(&instr.handle) {
@@ -229,7 +448,7 @@ impl HandleRegistry {
             record.revocation_reason = Some(instr.reason.clone());
             
             // Remove from pubkey index
-            self.pubkey_to_handle.retain(|&(pubkey, _)| pubkey != record.public_key);
+            self.pubkey_to_handle.remove(&record.public_key);
         }
 
         // 5. Remove from parent's children
@@ -237,6 +456,44 @@ impl HandleRegistry {
             parent.children.retain(|h| h != &instr.handle);
         }
 
+        self.persist(&[&instr.handle, &parent_handle]);
+        Ok(())
+    }
+
+    /// Freeze or unfreeze a handle (must be signed by one of its ancestors)
+    pub fn freeze_handle(
+        &mut self,
+        instr: &FreezeHandleInstruction,
+    ) -> Result<(), String> {
+        // 1. The authority must be an ancestor of the handle
+        let record = self.handles.get(&instr.handle)
+            .ok_or("Handle does not exist")?;
+        if record.revoked {
+            return Err("Handle is revoked".to_string());
+        }
+        if !self.is_ancestor(&instr.authority, &instr.handle) {
+            return Err(format!("{} is not an ancestor of {}", instr.authority, instr.handle));
+        }
+        if !instr.frozen {
+            let frozen_by = record.frozen_by.as_deref().ok_or("Handle is not frozen")?;
+            if frozen_by != instr.authority && !self.is_ancestor(&instr.authority, frozen_by) {
+                return Err("Only the freezing handle or its ancestors can unfreeze".to_string());
+            }
+        }
+
+        // 2. Verify authority signature
+        let authority = self.authority(&instr.authority)?;
+        let payload = Self::freeze_handle_payload_to_sign(instr);
+        let signature = decode_signature(&instr.signature)?;
+        authority.public_key.verify(payload.as_bytes(), &signature)
+            .map_err(|_| "Authority signature verification failed")?;
+        parse_timestamp(&instr.timestamp)?;
+
+        // 3. Update handle record
+        let record = self.handles.get_mut(&instr.handle).unwrap();
+        record.frozen_by = instr.frozen.then(|| instr.authority.clone());
+
+        self.persist(&[&instr.handle]);
         Ok(())
     }
 
@@ -245,15 +502,28 @@ impl HandleRegistry {
         self.handles.get(handle)
     }
 
+    /// Resolve a handle to the address of its current key for a payment.
+    /// Revoked and frozen handles cannot receive funds.
+    pub fn resolve_recipient(&self, handle: &str) -> Result<ResolvedHandle, String> {
+        let record = self.resolve(handle)
+            .ok_or_else(|| format!("Unknown handle {handle}"))?;
+        if record.revoked {
+            return Err(format!("Handle {handle} is revoked"));
+        }
+        if let Some(by) = &record.frozen_by {
+            return Err(format!("Handle {handle} is frozen by {by}"));
+        }
+        Ok(ResolvedHandle {
+            handle: record.handle.clone(),
+            public_key: record.public_key,
+            address: Address::from_verifying_key(&record.public_key),
+        })
+    }
+
     /// Lookup by public key
     pub fn resolve_by_pubkey(&self, pubkey: &VerifyingKey) -> Option<&HandleRecord> {
-        self.pubkey_to_handle.iter().find_map(|(pk, handle)| {
-            if pk == pubkey {
-                self.handles.get(handle)
-            } else {
-                None
-            }
-        })
+        self.pubkey_to_handle.get(pubkey)
+            .and_then(|handle| self.handles.get(handle))
     }
 
     /// Get all children of a handle
@@ -265,13 +535,48 @@ impl HandleRegistry {
             .unwrap_or_default()
     }
 
-    /// Check if handle is valid (exists and not revoked)
+    /// Check if handle is valid (exists, not revoked and not frozen)
     pub fn is_valid(&self, handle: &str) -> bool {
         self.handles.get(handle)
-            .map(|record| !record.revoked)
+            .map(|record| !record.revoked && !record.is_frozen())
             .unwrap_or(false)
     }
 
+    /// True if `ancestor` is a strict ancestor of `handle`
+    pub fn is_ancestor(&self, ancestor: &str, handle: &str) -> bool {
+        let mut current = self.handles.get(handle).and_then(|r| r.parent.as_deref());
+        while let Some(parent) = current {
+            if parent == ancestor {
+                return true;
+            }
+            current = self.handles.get(parent).and_then(|r| r.parent.as_deref());
+        }
+        false
+    }
+
+    /// A handle that may sign operations: registered, not revoked and not frozen
+    fn authority(&self, handle: &str) -> Result<&HandleRecord, String> {
+        let record = self.handles.get(handle)
+            .ok_or_else(|| format!("Handle {handle} does not exist"))?;
+        if record.revoked {
+            return Err(format!("Handle {handle} is revoked"));
+        }
+        if let Some(by) = &record.frozen_by {
+            return Err(format!("Handle {handle} is frozen by {by}"));
+        }
+        Ok(record)
+    }
+
+    fn persist(&self, handles: &[&str]) {
+        if let Some(storage) = &self.storage {
+            for record in handles.iter().filter_map(|h| self.handles.get(*h)) {
+                if let Err(e) = storage.store_handle(record) {
+                    println!("[DEBUG] Handles: failed to persist {}: {e}", record.handle);
+                }
+            }
+        }
+    }
+
     // Payload generation methods for signing
 
     pub fn subhandle_payload_to_sign(instr: &RegisterSubhandleInstruction) -> String {
@@ -302,6 +607,17 @@ impl HandleRegistry {
             instr.timestamp
         )
     }
+
+    pub fn freeze_handle_payload_to_sign(instr: &FreezeHandleInstruction) -> String {
+        format!(
+            "freeze_handle|{}|{}|{}|{}|{}",
+            instr.handle,
+            instr.authority,
+            instr.frozen,
+            instr.reason,
+            instr.timestamp
+        )
+    }
 }
 
 #[cfg(test)]
@@ -336,9 +652,10 @@ mod tests {
             revoked_at: None,
             revocation_reason: None,
             children: vec![],
+            frozen_by: None,
         };
         registry.handles.insert(parent_handle.clone(), parent_record);
-        registry.pubkey_to_handle.push((parent_public_key, parent_handle.clone()));
+        registry.pubkey_to_handle.insert(parent_public_key, parent_handle.clone());
 
         // Create subhandle instruction
         let mut sub_secret_bytes = [0u8; 32];
@@ -375,4 +692,82 @@ mod tests {
         assert_eq!(record.public_key, sub_public_key);
         assert!(!record.revoked);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_handle_ops_on_chain_with_parent_freeze() {
+        let key = |i: u8| SigningKey::from_bytes(&[i; 32]);
+        let (bank, desk, desk_next) = (key(51), key(52), key(53));
+        let b64 = |k: &SigningKey| STANDARD.encode(k.verifying_key().to_bytes());
+        let timestamp = "2026-01-05T09:00:00Z".to_string();
+        let mut registry = HandleRegistry::default();
+        registry.register_root("@bank", bank.verifying_key(), None, Utc::now()).unwrap();
+
+        let submit = |registry: &mut HandleRegistry, op: HandleOp, signer: &SigningKey| {
+            let tx = HandleTx::new(op, signer.verifying_key(), 1).to_transaction()?;
+            let handle_tx = HandleTx::from_transaction(&tx).unwrap()?;
+            registry.apply_handle_tx(&handle_tx)
+        };
+        let sign = |k: &SigningKey, payload: String| STANDARD.encode(k.sign(payload.as_bytes()).to_bytes());
+
+        let mut register = RegisterSubhandleInstruction {
+            handle: "@desk.bank".to_string(),
+            parent: "@bank".to_string(),
+            new_pubkey: b64(&desk),
+            metadata: Some(json!({"lei": "5493001KJTIIGC8Y1R12"})),
+            timestamp: timestamp.clone(),
+            parent_signature: String::new(),
+        };
+        register.parent_signature = sign(&bank, HandleRegistry::subhandle_payload_to_sign(&register));
+        // Only the parent's key authorizes registration
+        assert!(submit(&mut registry, HandleOp::Register(register.clone()), &desk).is_err());
+        submit(&mut registry, HandleOp::Register(register), &bank).unwrap();
+        assert_eq!(registry.resolve_by_pubkey(&desk.verifying_key()).unwrap().handle, "@desk.bank");
+
+        // A parent can freeze its child, which then cannot rotate its key
+        let mut freeze = FreezeHandleInstruction {
+            handle: "@desk.bank".to_string(),
+            authority: "@bank".to_string(),
+            frozen: true,
+            reason: "compliance review".to_string(),
+            timestamp: timestamp.clone(),
+            signature: String::new(),
+        };
+        freeze.signature = sign(&bank, HandleRegistry::freeze_handle_payload_to_sign(&freeze));
+        submit(&mut registry, HandleOp::Freeze(freeze.clone()), &bank).unwrap();
+        assert!(!registry.is_valid("@desk.bank"));
+        assert!(registry.resolve_recipient("@desk.bank").unwrap_err().contains("frozen by @bank"));
+
+        let mut rotate = RotateKeyInstruction {
+            handle: "@desk.bank".to_string(),
+            new_pubkey: b64(&desk_next),
+            timestamp: timestamp.clone(),
+            signature: String::new(),
+        };
+        rotate.signature = sign(&desk, HandleRegistry::rotate_key_payload_to_sign(&rotate));
+        assert!(submit(&mut registry, HandleOp::RotateKey(rotate.clone()), &desk).is_err());
+
+        freeze.frozen = false;
+        freeze.signature = sign(&bank, HandleRegistry::freeze_handle_payload_to_sign(&freeze));
+        submit(&mut registry, HandleOp::Freeze(freeze), &bank).unwrap();
+        submit(&mut registry, HandleOp::RotateKey(rotate), &desk).unwrap();
+
+        // Resolution follows the current key; the old key no longer maps to the handle
+        assert_eq!(registry.resolve("@desk.bank").unwrap().public_key, desk_next.verifying_key());
+        assert!(registry.resolve_by_pubkey(&desk.verifying_key()).is_none());
+        assert_eq!(registry.resolve_by_pubkey(&desk_next.verifying_key()).unwrap().handle, "@desk.bank");
+
+        // Payments to the handle go to the current key until it is revoked
+        let resolved = registry.resolve_recipient("@desk.bank").unwrap();
+        assert_eq!(resolved.address, Address::from_verifying_key(&desk_next.verifying_key()));
+        let mut revoke = RevokeHandleInstruction {
+            handle: "@desk.bank".to_string(),
+            reason: "desk closed".to_string(),
+            timestamp,
+            parent_signature: String::new(),
+        };
+        revoke.parent_signature = sign(&bank, HandleRegistry::revoke_handle_payload_to_sign(&revoke));
+        submit(&mut registry, HandleOp::Revoke(revoke), &bank).unwrap();
+        assert!(registry.resolve_recipient("@desk.bank").unwrap_err().contains("revoked"));
+        assert!(registry.resolve_recipient("@unknown.bank").is_err());
+    }
+}
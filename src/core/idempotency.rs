@@ -0,0 +1,188 @@
+use crate::core::ingestion::Adapter;
+use crate::storage::persistent::PersistentStorage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Default retention of idempotency records: 7 days
+pub const DEFAULT_RETENTION_SECS: u64 = 7 * 24 * 3600;
+
+/// First ingestion of a business reference from a sender institution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    pub institution: String, // BIC, LEI or FIX SenderCompID of the sender
+    pub adapter: Adapter,
+    pub reference: String,   // MsgId/EndToEndId, MT :20: or ClOrdID
+    pub tx_hash: Option<[u8; 32]>, // None while the conversion is in flight, or for FIX orders
+    pub first_seen: u64,
+}
+
+impl IdempotencyRecord {
+    pub fn storage_key(&self) -> String {
+        storage_key(&self.institution, self.adapter, &self.reference)
+    }
+}
+
+fn storage_key(institution: &str, adapter: Adapter, reference: &str) -> String {
+    format!("{}:{}:{}", adapter.protocol(), institution, reference)
+}
+
+/// Outcome of claiming a business reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Claim {
+    New,
+    Replay(IdempotencyRecord),
+}
+
+/// Duplicate detection for ingested ISO 20022, SWIFT and FIX instructions, keyed by
+/// (sender institution, format, business reference). Records are kept for the
+/// retention window, after which a reference may be reused.
+pub struct IdempotencyStore {
+    records: Mutex<HashMap<(String, Adapter, String), IdempotencyRecord>>,
+    retention_secs: u64,
+    storage: Option<Arc<PersistentStorage>>,
+}
+
+impl IdempotencyStore {
+    pub fn new(retention_secs: u64) -> Self {
+        Self {
+            records: Mutex::new(HashMap::new()),
+            retention_secs,
+            storage: None,
+        }
+    }
+
+    /// Restore unexpired records from `storage`, and keep persisting changes there
+    pub fn load(storage: Arc<PersistentStorage>, retention_secs: u64, now: u64) -> Self {
+        let store = Self { storage: Some(storage.clone()), ..Self::new(retention_secs) };
+        {
+            let mut records = store.records.lock().unwrap();
+            for record in storage.load_idempotency_records() {
+                records.insert((record.institution.clone(), record.adapter, record.reference.clone()), record);
+            }
+        }
+        store.prune(now);
+        store
+    }
+
+    pub fn retention_secs(&self) -> u64 {
+        self.retention_secs
+    }
+
+    fn is_expired(&self, record: &IdempotencyRecord, now: u64) -> bool {
+        now >= record.first_seen.saturating_add(self.retention_secs)
+    }
+
+    /// Claim a reference before converting it. A reference seen within the retention
+    /// window is a replay and returns the original record.
+    pub fn claim(&self, institution: &str, adapter: Adapter, reference: &str, now: u64) -> Result<Claim, String> {
+        if institution.is_empty() || reference.is_empty() {
+            return Err("Sender institution and business reference are required".to_string());
+        }
+        let mut records = self.records.lock().unwrap();
+        let key = (institution.to_string(), adapter, reference.to_string());
+        if let Some(existing) = records.get(&key) {
+            if !self.is_expired(existing, now) {
+                println!("[DEBUG] Idempotency: replay of {} from {}", existing.storage_key(), institution);
+                return Ok(Claim::Replay(existing.clone()));
+            }
+        }
+        let record = IdempotencyRecord {
+            institution: institution.to_string(),
+            adapter,
+            reference: reference.to_string(),
+            tx_hash: None,
+            first_seen: now,
+        };
+        self.persist(&record)?;
+        records.insert(key, record);
+        Ok(Claim::New)
+    }
+
+    /// Record the transaction a claimed reference was converted to
+    pub fn complete(&self, institution: &str, adapter: Adapter, reference: &str, tx_hash: [u8; 32]) -> Result<(), String> {
+        let mut records = self.records.lock().unwrap();
+        let record = records.get_mut(&(institution.to_string(), adapter, reference.to_string()))
+            .ok_or_else(|| format!("No claim for {}", storage_key(institution, adapter, reference)))?;
+        record.tx_hash = Some(tx_hash);
+        self.persist(record)
+    }
+
+    /// Drop a claim whose conversion or submission failed, so the sender can retry
+    pub fn release(&self, institution: &str, adapter: Adapter, reference: &str) {
+        let removed = self.records.lock().unwrap().remove(&(institution.to_string(), adapter, reference.to_string()));
+        if let (Some(record), Some(storage)) = (removed, &self.storage) {
+            if let Err(e) = storage.remove_idempotency_record(&record.storage_key()) {
+                println!("[DEBUG] Idempotency: failed to remove {}: {}", record.storage_key(), e);
+            }
+        }
+    }
+
+    pub fn get(&self, institution: &str, adapter: Adapter, reference: &str, now: u64) -> Option<IdempotencyRecord> {
+        self.records.lock().unwrap()
+            .get(&(institution.to_string(), adapter, reference.to_string()))
+            .filter(|record| !self.is_expired(record, now))
+            .cloned()
+    }
+
+    /// Remove records older than the retention window; returns how many were removed
+    pub fn prune(&self, now: u64) -> usize {
+        let mut records = self.records.lock().unwrap();
+        let expired: Vec<_> = records.iter()
+            .filter(|(_, record)| self.is_expired(record, now))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            if let (Some(record), Some(storage)) = (records.remove(key), &self.storage) {
+                if let Err(e) = storage.remove_idempotency_record(&record.storage_key()) {
+                    println!("[DEBUG] Idempotency: failed to remove {}: {}", record.storage_key(), e);
+                }
+            }
+        }
+        expired.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn persist(&self, record: &IdempotencyRecord) -> Result<(), String> {
+        match &self.storage {
+            Some(storage) => storage.store_idempotency_record(record)
+                .map_err(|e| format!("Failed to persist idempotency record: {e}")),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replays_within_retention_window() {
+        let store = IdempotencyStore::new(100);
+        assert_eq!(store.claim("BNPAFRPP", Adapter::Swift, "PAY-1", 1_000), Ok(Claim::New));
+        store.complete("BNPAFRPP", Adapter::Swift, "PAY-1", [4; 32]).unwrap();
+
+        // Same reference from the same sender and format is a replay of the original
+        match store.claim("BNPAFRPP", Adapter::Swift, "PAY-1", 1_050).unwrap() {
+            Claim::Replay(record) => assert_eq!((record.tx_hash, record.first_seen), (Some([4; 32]), 1_000)),
+            Claim::New => panic!("expected a replay"),
+        }
+        // Other senders and formats have their own reference space
+        assert_eq!(store.claim("DEUTDEFF", Adapter::Swift, "PAY-1", 1_050), Ok(Claim::New));
+        assert_eq!(store.claim("BNPAFRPP", Adapter::Iso20022, "PAY-1", 1_050), Ok(Claim::New));
+
+        // A released claim can be retried; an expired one is forgotten
+        store.release("DEUTDEFF", Adapter::Swift, "PAY-1");
+        assert_eq!(store.claim("DEUTDEFF", Adapter::Swift, "PAY-1", 1_060), Ok(Claim::New));
+        assert_eq!(store.prune(1_100), 1);
+        assert!(store.get("BNPAFRPP", Adapter::Swift, "PAY-1", 1_100).is_none());
+        assert_eq!(store.claim("BNPAFRPP", Adapter::Swift, "PAY-1", 1_100), Ok(Claim::New));
+    }
+}
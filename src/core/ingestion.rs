@@ -0,0 +1,421 @@
+use crate::core::address::Address;
+use crate::core::handle_registry::{is_handle, HandleRegistry};
+use crate::core::types::Transaction;
+use crate::iso20022::messages::{is_valid_bic, is_valid_lei};
+use crate::storage::persistent::PersistentStorage;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Domain tag of the message a gateway signs for a converted transaction
+pub const INGESTION_SIGNING_TAG: &[u8] = b"FDG:INGEST:1";
+
+/// Message-format adapter that converts external instructions into transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Adapter {
+    Iso20022,
+    Swift,
+    Fix,
+}
+
+impl Adapter {
+    pub const ALL: [Adapter; 3] = [Adapter::Iso20022, Adapter::Swift, Adapter::Fix];
+
+    /// Value carried in `Transaction.bridge_protocol`
+    pub fn protocol(&self) -> &'static str {
+        match self {
+            Adapter::Iso20022 => "ISO20022",
+            Adapter::Swift => "SWIFT",
+            Adapter::Fix => "FIX",
+        }
+    }
+
+    pub fn from_protocol(protocol: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.protocol() == protocol)
+    }
+
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Self::ALL.into_iter()
+            .find(|a| a.protocol().eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Unknown adapter {name}"))
+    }
+}
+
+/// Kind of external party identifier in the mapping table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierKind {
+    Bic,
+    Lei,
+    Account,    // IBAN or other account number
+    FixAccount, // FIX Account(1)
+}
+
+impl IdentifierKind {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "bic" => Ok(IdentifierKind::Bic),
+            "lei" => Ok(IdentifierKind::Lei),
+            "account" => Ok(IdentifierKind::Account),
+            "fix_account" => Ok(IdentifierKind::FixAccount),
+            _ => Err(format!("Unknown identifier kind {name}")),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            IdentifierKind::Bic => "bic",
+            IdentifierKind::Lei => "lei",
+            IdentifierKind::Account => "account",
+            IdentifierKind::FixAccount => "fix_account",
+        }
+    }
+
+    /// Guess the kind of a free-form party string from a payment message
+    pub fn classify(identifier: &str) -> Self {
+        if is_valid_bic(identifier) {
+            IdentifierKind::Bic
+        } else if is_valid_lei(identifier) {
+            IdentifierKind::Lei
+        } else {
+            IdentifierKind::Account
+        }
+    }
+
+    /// Canonical form used as the table key
+    fn normalize(&self, identifier: &str) -> Result<String, String> {
+        let identifier = identifier.trim();
+        match self {
+            IdentifierKind::Bic if is_valid_bic(identifier) => {
+                // Primary office BICs are stored without the XXX branch code
+                Ok(identifier.strip_suffix("XXX").filter(|b| b.len() == 8).unwrap_or(identifier).to_string())
+            }
+            IdentifierKind::Bic => Err(format!("Invalid BIC {identifier}")),
+            IdentifierKind::Lei if is_valid_lei(identifier) => Ok(identifier.to_string()),
+            IdentifierKind::Lei => Err(format!("Invalid LEI {identifier}")),
+            IdentifierKind::Account => Ok(identifier.replace(' ', "").to_uppercase()),
+            IdentifierKind::FixAccount => Ok(identifier.to_string()),
+        }
+        .and_then(|id| if id.is_empty() { Err("Empty identifier".to_string()) } else { Ok(id) })
+    }
+}
+
+/// Adapter bound to the institution whose handle key signs its conversions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GatewayBinding {
+    pub adapter: Adapter,
+    pub institution: String, // @handle
+}
+
+/// External identifier → on-chain account, owned by the institution that may debit it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountMapping {
+    pub kind: IdentifierKind,
+    pub identifier: String,
+    pub institution: String, // @handle
+    pub target: String,      // address or @handle
+}
+
+/// Message a gateway signs: covers the envelope, the payload and the adapter,
+/// so a conversion cannot be relabeled or have its payload swapped
+pub fn conversion_signing_message(tx: &Transaction) -> Vec<u8> {
+    let mut message = INGESTION_SIGNING_TAG.to_vec();
+    let protocol = tx.bridge_protocol.as_deref().unwrap_or_default();
+    message.extend_from_slice(&(protocol.len() as u32).to_be_bytes());
+    message.extend_from_slice(protocol.as_bytes());
+    message.extend_from_slice(&tx.signing_message());
+    message.extend_from_slice(&Sha256::digest(&tx.payload));
+    message
+}
+
+/// Key an adapter signs its converted transactions with
+pub struct GatewaySigner {
+    pub adapter: Adapter,
+    signing_key: SigningKey,
+}
+
+impl GatewaySigner {
+    pub fn new(adapter: Adapter, signing_key: SigningKey) -> Self {
+        Self { adapter, signing_key }
+    }
+
+    /// Load a 32-byte secret key given as hex
+    pub fn from_hex(adapter: Adapter, secret_hex: &str) -> Result<Self, String> {
+        let bytes: [u8; 32] = hex::decode(secret_hex.trim())
+            .map_err(|e| format!("Invalid gateway key hex: {e}"))?
+            .try_into()
+            .map_err(|_| "Gateway key must be 32 bytes".to_string())?;
+        Ok(Self::new(adapter, SigningKey::from_bytes(&bytes)))
+    }
+
+    pub fn public_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Label the transaction with the adapter and sign it
+    pub fn sign(&self, tx: &mut Transaction) {
+        tx.bridge_protocol = Some(self.adapter.protocol().to_string());
+        tx.public_key = self.public_key();
+        tx.signature = self.signing_key.sign(&conversion_signing_message(tx));
+    }
+}
+
+/// Adapter bindings and the identifier mapping table used to ingest ISO 20022,
+/// SWIFT and FIX instructions. Institution keys come from the handle registry,
+/// so a key rotation applies to the gateway immediately.
+pub struct IngestionRegistry {
+    handles: Arc<Mutex<HandleRegistry>>,
+    bindings: Mutex<HashMap<Adapter, String>>,
+    mappings: Mutex<HashMap<(IdentifierKind, String), AccountMapping>>,
+    storage: Option<Arc<PersistentStorage>>,
+}
+
+impl IngestionRegistry {
+    pub fn new(handles: Arc<Mutex<HandleRegistry>>) -> Self {
+        Self {
+            handles,
+            bindings: Mutex::new(HashMap::new()),
+            mappings: Mutex::new(HashMap::new()),
+            storage: None,
+        }
+    }
+
+    /// Restore bindings and mappings from `storage`, and keep persisting changes there
+    pub fn load(handles: Arc<Mutex<HandleRegistry>>, storage: Arc<PersistentStorage>) -> Self {
+        let registry = Self::new(handles);
+        for binding in storage.load_gateway_bindings() {
+            registry.bindings.lock().unwrap().insert(binding.adapter, binding.institution);
+        }
+        for mapping in storage.load_account_mappings() {
+            registry.mappings.lock().unwrap().insert((mapping.kind, mapping.identifier.clone()), mapping);
+        }
+        Self { storage: Some(storage), ..registry }
+    }
+
+    /// Bind an adapter to a registered institution handle
+    pub fn bind(&self, adapter: Adapter, institution: &str) -> Result<GatewayBinding, String> {
+        self.handles.lock().unwrap().resolve_recipient(institution)?;
+        let binding = GatewayBinding { adapter, institution: institution.to_string() };
+        if let Some(storage) = &self.storage {
+            storage.store_gateway_binding(&binding).map_err(|e| format!("Failed to persist binding: {e}"))?;
+        }
+        self.bindings.lock().unwrap().insert(adapter, binding.institution.clone());
+        println!("[DEBUG] Ingestion: {} bound to {}", adapter.protocol(), institution);
+        Ok(binding)
+    }
+
+    pub fn bindings(&self) -> Vec<GatewayBinding> {
+        let mut bindings: Vec<GatewayBinding> = self.bindings.lock().unwrap().iter()
+            .map(|(adapter, institution)| GatewayBinding { adapter: *adapter, institution: institution.clone() })
+            .collect();
+        bindings.sort_by_key(|b| b.adapter.protocol());
+        bindings
+    }
+
+    /// Add or replace a mapping; the target must be a valid address or a live handle
+    pub fn map(&self, kind: IdentifierKind, identifier: &str, institution: &str, target: &str) -> Result<AccountMapping, String> {
+        let identifier = kind.normalize(identifier)?;
+        {
+            let handles = self.handles.lock().unwrap();
+            handles.resolve_recipient(institution)?;
+            if is_handle(target) {
+                handles.resolve_recipient(target)?;
+            } else {
+                Address::parse(target).map_err(|e| format!("Invalid target address: {e}"))?;
+            }
+        }
+        let mapping = AccountMapping {
+            kind,
+            identifier,
+            institution: institution.to_string(),
+            target: target.to_string(),
+        };
+        if let Some(storage) = &self.storage {
+            storage.store_account_mapping(&mapping).map_err(|e| format!("Failed to persist mapping: {e}"))?;
+        }
+        self.mappings.lock().unwrap().insert((kind, mapping.identifier.clone()), mapping.clone());
+        Ok(mapping)
+    }
+
+    pub fn unmap(&self, kind: IdentifierKind, identifier: &str) -> Result<AccountMapping, String> {
+        let identifier = kind.normalize(identifier)?;
+        let mapping = self.mappings.lock().unwrap().remove(&(kind, identifier.clone()))
+            .ok_or_else(|| format!("No {} mapping for {}", kind.name(), identifier))?;
+        if let Some(storage) = &self.storage {
+            storage.remove_account_mapping(kind.name(), &identifier).map_err(|e| format!("Failed to persist removal: {e}"))?;
+        }
+        Ok(mapping)
+    }
+
+    pub fn mappings(&self) -> Vec<AccountMapping> {
+        let mut mappings: Vec<AccountMapping> = self.mappings.lock().unwrap().values().cloned().collect();
+        mappings.sort_by(|a, b| (a.kind.name(), &a.identifier).cmp(&(b.kind.name(), &b.identifier)));
+        mappings
+    }
+
+    /// Institution handle and its current key for an adapter
+    pub fn gateway_key(&self, adapter: Adapter) -> Result<(String, VerifyingKey), String> {
+        let institution = self.bindings.lock().unwrap().get(&adapter).cloned()
+            .ok_or_else(|| format!("No institution bound to the {} adapter", adapter.protocol()))?;
+        let resolved = self.handles.lock().unwrap().resolve_recipient(&institution)?;
+        Ok((institution, resolved.public_key))
+    }
+
+    fn resolve_target(&self, target: &str) -> Result<Address, String> {
+        if is_handle(target) {
+            Ok(self.handles.lock().unwrap().resolve_recipient(target)?.address)
+        } else {
+            Address::parse(target).map_err(|e| e.to_string())
+        }
+    }
+
+    fn lookup(&self, kind: IdentifierKind, identifier: &str) -> Option<AccountMapping> {
+        let identifier = kind.normalize(identifier).ok()?;
+        self.mappings.lock().unwrap().get(&(kind, identifier)).cloned()
+    }
+
+    /// Account to debit: the first candidate mapped by the institution bound to `adapter`
+    pub fn debit_account(&self, adapter: Adapter, candidates: &[(IdentifierKind, &str)]) -> Result<Address, String> {
+        let (institution, _) = self.gateway_key(adapter)?;
+        for (kind, identifier) in candidates {
+            match self.lookup(*kind, identifier) {
+                Some(mapping) if mapping.institution == institution => return self.resolve_target(&mapping.target),
+                Some(mapping) => {
+                    return Err(format!("{} {} belongs to {}, not {}", kind.name(), identifier, mapping.institution, institution));
+                }
+                None => {}
+            }
+        }
+        Err(format!("No mapping for debtor {}", describe(candidates)))
+    }
+
+    /// Account to credit: the first mapped candidate, or a candidate that is already an address
+    pub fn credit_account(&self, candidates: &[(IdentifierKind, &str)]) -> Result<Address, String> {
+        for (kind, identifier) in candidates {
+            if let Some(mapping) = self.lookup(*kind, identifier) {
+                return self.resolve_target(&mapping.target);
+            }
+        }
+        candidates.iter()
+            .find_map(|(_, identifier)| Address::parse(identifier).ok())
+            .ok_or_else(|| format!("No mapping for creditor {}", describe(candidates)))
+    }
+
+    /// Admission check for converted transactions: None for other transactions,
+    /// otherwise the gateway signature and the debited account must match the binding
+    pub fn verify(&self, tx: &Transaction) -> Option<Result<(), String>> {
+        let adapter = Adapter::from_protocol(tx.bridge_protocol.as_deref()?)?;
+        Some(self.verify_conversion(adapter, tx))
+    }
+
+    fn verify_conversion(&self, adapter: Adapter, tx: &Transaction) -> Result<(), String> {
+        let (institution, key) = self.gateway_key(adapter)?;
+        if tx.public_key != key {
+            return Err(format!("{} conversion not signed by {}", adapter.protocol(), institution));
+        }
+        key.verify(&conversion_signing_message(tx), &tx.signature)
+            .map_err(|_| format!("Invalid {} gateway signature", adapter.protocol()))?;
+        let owned: Vec<String> = self.mappings.lock().unwrap().values()
+            .filter(|m| m.institution == institution)
+            .map(|m| m.target.clone())
+            .collect();
+        let debits_owned_account = owned.iter()
+            .any(|target| self.resolve_target(target).is_ok_and(|address| address == tx.from));
+        if !debits_owned_account {
+            return Err(format!("{} is not an account mapped by {}", tx.from.as_str(), institution));
+        }
+        Ok(())
+    }
+}
+
+fn describe(candidates: &[(IdentifierKind, &str)]) -> String {
+    candidates.iter()
+        .map(|(kind, identifier)| format!("{} {}", kind.name(), identifier))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Registry with `@bank` bound to every adapter and a few mapped accounts, for adapter tests
+#[cfg(test)]
+pub(crate) fn test_registry() -> (IngestionRegistry, SigningKey) {
+    let bank_key = SigningKey::from_bytes(&[7; 32]);
+    let mut handles = HandleRegistry::default();
+    handles.register_root("@bank", bank_key.verifying_key(), None, chrono::Utc::now()).unwrap();
+    let registry = IngestionRegistry::new(Arc::new(Mutex::new(handles)));
+    for adapter in Adapter::ALL {
+        registry.bind(adapter, "@bank").unwrap();
+    }
+    let alice = Address::from_verifying_key(&SigningKey::from_bytes(&[1; 32]).verifying_key());
+    let bob = Address::from_verifying_key(&SigningKey::from_bytes(&[2; 32]).verifying_key());
+    for (kind, identifier, target) in [
+        (IdentifierKind::Bic, "DEUTDEFF", &alice),
+        (IdentifierKind::Account, "FR1420041010050500013M02606", &alice),
+        (IdentifierKind::Account, "DE89370400440532013000", &bob),
+        (IdentifierKind::FixAccount, "ALICE_ACCOUNT", &alice),
+        (IdentifierKind::FixAccount, "BOB_ACCOUNT", &bob),
+    ] {
+        registry.map(kind, identifier, "@bank", target.as_str()).unwrap();
+    }
+    (registry, bank_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::ShardId;
+    use ed25519_dalek::Signature;
+
+    fn conversion(registry: &IngestionRegistry) -> Transaction {
+        Transaction {
+            from: registry.debit_account(Adapter::Swift, &[(IdentifierKind::Bic, "DEUTDEFFXXX")]).unwrap(),
+            to: registry.credit_account(&[(IdentifierKind::Account, "de89 3704 0044 0532 0130 00")]).unwrap(),
+            amount: 500,
+            payload: b"currency:EUR".to_vec(),
+            findag_time: 0,
+            hashtimer: [0; 32],
+            signature: Signature::from_bytes(&[0; 64]),
+            public_key: VerifyingKey::from_bytes(&[0; 32]).unwrap(),
+            shard_id: ShardId(0),
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: Some("SWIFT".to_string()),
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_gateway_signed_conversions() {
+        let (registry, bank_key) = test_registry();
+        let mut tx = conversion(&registry);
+        assert!(registry.verify(&tx).unwrap().unwrap_err().contains("not signed by @bank"));
+
+        GatewaySigner::new(Adapter::Swift, bank_key.clone()).sign(&mut tx);
+        assert_eq!(registry.verify(&tx), Some(Ok(())));
+
+        // Payload, adapter and debtor are all covered
+        let mut swapped = tx.clone();
+        swapped.payload = b"currency:USD".to_vec();
+        assert!(registry.verify(&swapped).unwrap().is_err());
+        let mut relabeled = tx.clone();
+        relabeled.bridge_protocol = Some("FIX".to_string());
+        assert!(registry.verify(&relabeled).unwrap().is_err());
+        let mut foreign = tx.clone();
+        foreign.from = Address::from_verifying_key(&SigningKey::from_bytes(&[9; 32]).verifying_key());
+        GatewaySigner::new(Adapter::Swift, bank_key).sign(&mut foreign);
+        assert!(registry.verify(&foreign).unwrap().unwrap_err().contains("not an account mapped"));
+
+        // Only the owning institution may debit a mapping; other transactions are not checked
+        let other = SigningKey::from_bytes(&[8; 32]);
+        registry.handles.lock().unwrap().register_root("@other", other.verifying_key(), None, chrono::Utc::now()).unwrap();
+        registry.bind(Adapter::Fix, "@other").unwrap();
+        assert!(registry.debit_account(Adapter::Fix, &[(IdentifierKind::FixAccount, "ALICE_ACCOUNT")]).unwrap_err().contains("belongs to @bank"));
+        tx.bridge_protocol = None;
+        assert!(registry.verify(&tx).is_none());
+    }
+}
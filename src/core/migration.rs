@@ -0,0 +1,142 @@
+use crate::core::address::Address;
+use crate::core::types::{ShardId, Transaction};
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Payload prefix identifying a legacy address migration carried in `Transaction.payload`
+pub const MIGRATION_PAYLOAD_TAG: &[u8] = b"FDG:MIGRATE:1";
+
+/// Shard migrations are executed on; balances move on every shard
+pub const MIGRATION_SHARD: ShardId = ShardId(0);
+
+/// Move every balance of a legacy `FD…` address to the canonical address of the same key.
+/// Signed by that key, which proves control of the legacy address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressMigrationTx {
+    pub legacy_address: String,
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+    pub public_key: VerifyingKey,
+    pub signature: Signature,
+}
+
+impl AddressMigrationTx {
+    /// Canonical message the key owner signs
+    pub fn signing_message(legacy_address: &str, findag_time: u64, shard_id: ShardId) -> Vec<u8> {
+        let mut message = MIGRATION_PAYLOAD_TAG.to_vec();
+        message.extend_from_slice(legacy_address.as_bytes());
+        message.extend_from_slice(&findag_time.to_be_bytes());
+        message.extend_from_slice(&shard_id.0.to_be_bytes());
+        message
+    }
+
+    pub fn sign(legacy_address: &str, findag_time: u64, signing_key: &SigningKey) -> Self {
+        let signature = signing_key.sign(&Self::signing_message(legacy_address, findag_time, MIGRATION_SHARD));
+        Self {
+            legacy_address: legacy_address.to_string(),
+            findag_time,
+            shard_id: MIGRATION_SHARD,
+            public_key: signing_key.verifying_key(),
+            signature,
+        }
+    }
+
+    /// Canonical address the balances move to
+    pub fn address(&self) -> Address {
+        Address::from_verifying_key(&self.public_key)
+    }
+
+    /// Check the signature and that the legacy address belongs to the signing key
+    pub fn verify(&self) -> Result<Address, String> {
+        let message = Self::signing_message(&self.legacy_address, self.findag_time, self.shard_id);
+        self.public_key.verify(&message, &self.signature)
+            .map_err(|_| "Invalid migration signature".to_string())?;
+        if self.shard_id != MIGRATION_SHARD {
+            return Err(format!("Address migrations must be on shard {}", MIGRATION_SHARD.0));
+        }
+        Address::migrate_legacy(&self.legacy_address, &self.public_key).map_err(|e| e.to_string())
+    }
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = MIGRATION_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&bincode::serialize(self).expect("migration tx serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a migration
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(MIGRATION_PAYLOAD_TAG)?;
+        Some(bincode::deserialize(body).map_err(|e| format!("Invalid migration payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the signed body
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|migration| {
+            if migration.shard_id != tx.shard_id
+                || migration.findag_time != tx.findag_time
+                || migration.public_key != tx.public_key
+                || migration.signature != tx.signature
+                || migration.address() != tx.from
+            {
+                return Err("Migration envelope does not match its payload".to_string());
+            }
+            Ok(migration)
+        }))
+    }
+
+    /// Wrap in a zero-amount `Transaction` from the canonical address to itself
+    pub fn to_transaction(&self) -> Transaction {
+        let address = self.address();
+        let payload = self.to_payload();
+        Transaction {
+            from: address.clone(),
+            to: address,
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature: self.signature,
+            public_key: self.public_key,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::executor;
+    use crate::storage::state::StateDB;
+
+    #[test]
+    fn test_migration_applied_by_executor() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::new(dir.path().to_str().unwrap());
+        let key = SigningKey::from_bytes(&[6u8; 32]);
+        let legacy = format!("FD{}", hex::encode(&key.verifying_key().to_bytes()[..8]));
+        state_db.set_balance(0, &legacy, "USD", 500).unwrap();
+        state_db.set_balance(1, &legacy, "EUR", 70).unwrap();
+
+        // Another key cannot claim the legacy address
+        let other = SigningKey::from_bytes(&[7u8; 32]);
+        let forged = AddressMigrationTx::sign(&legacy, 1, &other).to_transaction();
+        assert!(executor::apply_transaction(&state_db, &forged).is_err());
+
+        let tx = AddressMigrationTx::sign(&legacy, 2, &key).to_transaction();
+        AddressMigrationTx::from_transaction(&tx).unwrap().unwrap();
+        executor::apply_transaction(&state_db, &tx).unwrap();
+        let canonical = Address::from_signing_key(&key);
+        assert_eq!(state_db.get_balance(0, canonical.as_str(), "USD"), 500);
+        assert_eq!(state_db.get_balance(1, canonical.as_str(), "EUR"), 70);
+        assert_eq!(state_db.get_balance(0, &legacy, "USD"), 0);
+    }
+}
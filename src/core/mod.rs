@@ -3,10 +3,19 @@ pub mod block_producer;
 pub mod block_production_loop;
 pub mod bridge;
 pub mod confidential;
+pub mod dvp;
 pub mod dag_engine;
+pub mod executor;
+pub mod idempotency;
 pub mod identity;
+pub mod ingestion;
+pub mod migration;
+pub mod multi_leg;
+pub mod multisig;
+pub mod payload;
 pub mod round_checkpoint_loop;
 pub mod tx_pool;
+pub mod tx_status;
 pub mod types;
 pub mod wallet;
 pub mod handle_registry;
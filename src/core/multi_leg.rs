@@ -0,0 +1,268 @@
+use crate::core::address::Address;
+use crate::core::multisig::{authorize_party, MultisigAccount};
+use crate::core::types::{ShardId, Transaction, SUPPORTED_ASSETS};
+pub use crate::core::types::PartySignature;
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use ed25519_dalek::{Signer, SigningKey, Verifier};
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Payload prefix identifying a multi-leg transaction carried in `Transaction.payload`
+pub const MULTI_LEG_PAYLOAD_TAG: &[u8] = b"FDG:MULTILEG:1";
+/// Maximum number of legs in a single multi-leg transaction
+pub const MAX_LEGS: usize = 64;
+
+/// One transfer within a multi-leg transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferLeg {
+    pub from: Address,
+    pub to: Address,
+    pub asset: String,
+    pub amount: u64,
+}
+
+/// Atomic multi-leg transaction (e.g. DvP: securities leg + cash leg).
+/// Either every leg settles or none does.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiLegTransaction {
+    pub legs: Vec<TransferLeg>,
+    pub reference: String,          // Client trade/settlement reference
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+    pub valid_after: Option<u64>,
+    pub valid_until: Option<u64>,
+    pub signatures: Vec<PartySignature>,
+}
+
+impl MultiLegTransaction {
+    pub fn new(legs: Vec<TransferLeg>, reference: String, findag_time: u64, shard_id: ShardId) -> Self {
+        Self {
+            legs,
+            reference,
+            findag_time,
+            shard_id,
+            valid_after: None,
+            valid_until: None,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Canonical message every debited party signs (all fields except signatures)
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend_from_slice(MULTI_LEG_PAYLOAD_TAG);
+        message.extend_from_slice(&(self.legs.len() as u32).to_be_bytes());
+        for leg in &self.legs {
+            for field in [leg.from.as_str(), leg.to.as_str(), leg.asset.as_str()] {
+                message.extend_from_slice(&(field.len() as u32).to_be_bytes());
+                message.extend_from_slice(field.as_bytes());
+            }
+            message.extend_from_slice(&leg.amount.to_be_bytes());
+        }
+        message.extend_from_slice(&(self.reference.len() as u32).to_be_bytes());
+        message.extend_from_slice(self.reference.as_bytes());
+        message.extend_from_slice(&self.findag_time.to_be_bytes());
+        message.extend_from_slice(&self.shard_id.0.to_be_bytes());
+        message.extend_from_slice(&self.valid_after.unwrap_or(0).to_be_bytes());
+        message.extend_from_slice(&self.valid_until.unwrap_or(u64::MAX).to_be_bytes());
+        message
+    }
+
+    /// Hash of the signing message
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.signing_message()).into()
+    }
+
+    /// Add the signature of one debited party
+    pub fn sign(&mut self, signing_key: &SigningKey) {
+        let public_key = signing_key.verifying_key();
+        let signature = signing_key.sign(&self.signing_message());
+        self.signatures.retain(|s| s.public_key != public_key);
+        self.signatures.push(PartySignature { public_key, signature });
+    }
+
+    /// Distinct parties debited by at least one leg
+    pub fn debited_parties(&self) -> BTreeSet<String> {
+        self.legs.iter().map(|leg| leg.from.as_str().to_string()).collect()
+    }
+
+    /// Net total debited per (party, asset)
+    pub fn debits(&self) -> BTreeMap<(String, String), u64> {
+        let mut debits: BTreeMap<(String, String), u64> = BTreeMap::new();
+        for leg in &self.legs {
+            let entry = debits.entry((leg.from.as_str().to_string(), leg.asset.clone())).or_default();
+            *entry = entry.saturating_add(leg.amount);
+        }
+        debits
+    }
+
+    /// Structural checks plus authorization of each debited party: its own signature, or
+    /// threshold approval by the policy keys when `multisig_account` finds it is a multisig account
+    pub fn validate(&self, multisig_account: &dyn Fn(&str) -> Option<MultisigAccount>) -> Result<(), String> {
+        if self.legs.is_empty() {
+            return Err("Multi-leg transaction has no legs".to_string());
+        }
+        if self.legs.len() > MAX_LEGS {
+            return Err(format!("Multi-leg transaction exceeds {MAX_LEGS} legs"));
+        }
+        for (i, leg) in self.legs.iter().enumerate() {
+            leg.from.validate().map_err(|e| format!("Leg {i} sender: {e}"))?;
+            leg.to.validate().map_err(|e| format!("Leg {i} receiver: {e}"))?;
+            if leg.amount == 0 {
+                return Err(format!("Leg {i} has zero amount"));
+            }
+            if leg.from == leg.to {
+                return Err(format!("Leg {i} transfers to its own sender"));
+            }
+            if !SUPPORTED_ASSETS.contains(&leg.asset.as_str()) {
+                return Err(format!("Leg {i} uses unsupported asset '{}'", leg.asset));
+            }
+        }
+        if let (Some(after), Some(until)) = (self.valid_after, self.valid_until) {
+            if after > until {
+                return Err("valid_after is later than valid_until".to_string());
+            }
+        }
+
+        let message = self.signing_message();
+        for party_sig in &self.signatures {
+            party_sig.public_key.verify(&message, &party_sig.signature)
+                .map_err(|_| "Invalid party signature".to_string())?;
+        }
+        for party in self.debited_parties() {
+            authorize_party(&party, multisig_account, &message, &self.signatures)?;
+        }
+        Ok(())
+    }
+
+    /// Encode as a `Transaction.payload`
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = MULTI_LEG_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&bincode::serialize(self).expect("multi-leg serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a multi-leg transaction
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(MULTI_LEG_PAYLOAD_TAG)?;
+        Some(bincode::deserialize(body).map_err(|e| format!("Invalid multi-leg payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the signed body.
+    /// None if the transaction does not carry a multi-leg payload.
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|multi| {
+            if multi.shard_id != tx.shard_id
+                || multi.findag_time != tx.findag_time
+                || multi.valid_after != tx.valid_after
+                || multi.valid_until != tx.valid_until
+            {
+                return Err("Multi-leg envelope does not match its payload".to_string());
+            }
+            Ok(multi)
+        }))
+    }
+
+    /// Wrap in a `Transaction` so it flows through the pool, blocks and rounds.
+    /// The envelope carries the first leg and the first party signature; the
+    /// authoritative signatures are the ones inside the payload.
+    pub fn to_transaction(&self) -> Result<Transaction, String> {
+        let first_leg = self.legs.first().ok_or("Multi-leg transaction has no legs")?;
+        let first_sig = self.signatures.first().ok_or("Multi-leg transaction is unsigned")?;
+        let payload = self.to_payload();
+        Ok(Transaction {
+            from: first_leg.from.clone(),
+            to: first_leg.to.clone(),
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature: first_sig.signature,
+            public_key: first_sig.public_key,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: self.valid_after,
+            valid_until: self.valid_until,
+            multisig_signatures: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::multisig::{MultisigPolicy, MultisigSigner};
+
+    fn dvp(seller: &SigningKey, buyer: &SigningKey) -> MultiLegTransaction {
+        let seller_addr = Address::from_verifying_key(&seller.verifying_key());
+        let buyer_addr = Address::from_verifying_key(&buyer.verifying_key());
+        MultiLegTransaction::new(
+            vec![
+                TransferLeg { from: seller_addr.clone(), to: buyer_addr.clone(), asset: "BUND".to_string(), amount: 10 },
+                TransferLeg { from: buyer_addr, to: seller_addr, asset: "EUR".to_string(), amount: 1_000 },
+            ],
+            "TRADE-1".to_string(),
+            42,
+            ShardId(0),
+        )
+    }
+
+    #[test]
+    fn test_requires_every_debited_party() {
+        let seller = SigningKey::from_bytes(&[1u8; 32]);
+        let buyer = SigningKey::from_bytes(&[2u8; 32]);
+        let mut tx = dvp(&seller, &buyer);
+
+        tx.sign(&seller);
+        assert!(tx.validate(&|_| None).is_err());
+        tx.sign(&buyer);
+        assert!(tx.validate(&|_| None).is_ok());
+
+        // Any change to the legs invalidates the signatures
+        tx.legs[1].amount = 1;
+        assert!(tx.validate(&|_| None).is_err());
+    }
+
+    #[test]
+    fn test_multisig_party_authorized_by_policy() {
+        let (a, b, c) = (SigningKey::from_bytes(&[3u8; 32]), SigningKey::from_bytes(&[4u8; 32]), SigningKey::from_bytes(&[5u8; 32]));
+        let buyer = SigningKey::from_bytes(&[2u8; 32]);
+        let policy = MultisigPolicy {
+            signers: [&a, &b, &c].iter().map(|k| MultisigSigner { public_key: k.verifying_key(), weight: 1 }).collect(),
+            threshold: 2,
+        };
+        let treasury = MultisigAccount { address: policy.derive_address(b"treasury"), policy, nonce: 0 };
+        let lookup = |address: &str| (address == treasury.address.as_str()).then(|| treasury.clone());
+
+        let mut tx = dvp(&a, &buyer);
+        tx.legs[0].from = treasury.address.clone();
+        tx.legs[1].to = treasury.address.clone();
+        tx.sign(&buyer);
+        tx.sign(&a);
+        assert!(tx.validate(&lookup).is_err());
+        tx.sign(&b);
+        assert!(tx.validate(&lookup).is_ok());
+        // Without the on-chain policy the treasury address has no key of its own
+        assert!(tx.validate(&|_| None).is_err());
+    }
+
+    #[test]
+    fn test_payload_roundtrip() {
+        let seller = SigningKey::from_bytes(&[1u8; 32]);
+        let buyer = SigningKey::from_bytes(&[2u8; 32]);
+        let mut tx = dvp(&seller, &buyer);
+        tx.sign(&seller);
+        tx.sign(&buyer);
+
+        let envelope = tx.to_transaction().unwrap();
+        let decoded = MultiLegTransaction::from_payload(&envelope.payload).unwrap().unwrap();
+        assert_eq!(decoded.hash(), tx.hash());
+        assert!(decoded.validate(&|_| None).is_ok());
+        assert!(MultiLegTransaction::from_payload(b"plain payload").is_none());
+    }
+}
@@ -0,0 +1,337 @@
+use crate::core::address::Address;
+use crate::core::types::{PartySignature, ShardId, Transaction};
+use crate::dagtimer::hashtimer::compute_hashtimer;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+use std::collections::HashSet;
+
+/// Payload prefix identifying a multisig account operation carried in `Transaction.payload`
+pub const MULTISIG_PAYLOAD_TAG: &[u8] = b"FDG:MULTISIG:1";
+/// Maximum number of keys in a multisig policy
+pub const MAX_MULTISIG_SIGNERS: usize = 32;
+
+/// One key of a multisig account and its voting weight
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigSigner {
+    pub public_key: VerifyingKey,
+    pub weight: u32,
+}
+
+/// Key set, weights and the approval threshold of a multisig account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigPolicy {
+    pub signers: Vec<MultisigSigner>,
+    pub threshold: u64,
+}
+
+impl MultisigPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.signers.is_empty() {
+            return Err("Multisig policy has no signers".to_string());
+        }
+        if self.signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(format!("Multisig policy exceeds {MAX_MULTISIG_SIGNERS} signers"));
+        }
+        let mut seen = HashSet::new();
+        for signer in &self.signers {
+            if signer.weight == 0 {
+                return Err("Multisig signer weight must be positive".to_string());
+            }
+            if !seen.insert(signer.public_key.to_bytes()) {
+                return Err("Duplicate key in multisig policy".to_string());
+            }
+        }
+        if self.threshold == 0 || self.threshold > self.total_weight() {
+            return Err(format!("Threshold must be between 1 and {}", self.total_weight()));
+        }
+        Ok(())
+    }
+
+    pub fn total_weight(&self) -> u64 {
+        self.signers.iter().map(|s| s.weight as u64).sum()
+    }
+
+    /// Summed weight of the policy keys that validly signed `message` (each key counted once)
+    pub fn approved_weight(&self, message: &[u8], signatures: &[PartySignature]) -> u64 {
+        let mut counted = HashSet::new();
+        let mut weight = 0u64;
+        for sig in signatures {
+            let Some(signer) = self.signers.iter().find(|s| s.public_key == sig.public_key) else {
+                continue;
+            };
+            if counted.contains(&signer.public_key.to_bytes()) {
+                continue;
+            }
+            if signer.public_key.verify(message, &sig.signature).is_ok() {
+                counted.insert(signer.public_key.to_bytes());
+                weight += signer.weight as u64;
+            }
+        }
+        weight
+    }
+
+    /// Ok if the signatures over `message` reach the threshold
+    pub fn check_approval(&self, message: &[u8], signatures: &[PartySignature]) -> Result<(), String> {
+        let weight = self.approved_weight(message, signatures);
+        if weight < self.threshold {
+            return Err(format!("Multisig approval weight {weight} below threshold {}", self.threshold));
+        }
+        Ok(())
+    }
+
+    /// Deterministic account address for a policy at creation time
+    pub fn derive_address(&self, salt: &[u8]) -> Address {
+        let mut hasher = Sha256::new();
+        hasher.update(MULTISIG_PAYLOAD_TAG);
+        for signer in &self.signers {
+            hasher.update(signer.public_key.to_bytes());
+            hasher.update(signer.weight.to_be_bytes());
+        }
+        hasher.update(self.threshold.to_be_bytes());
+        hasher.update(salt);
+        Address::from_hash(&hasher.finalize().into())
+    }
+}
+
+/// On-chain multisig account. The address stays fixed when the key set changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigAccount {
+    pub address: Address,
+    pub policy: MultisigPolicy,
+    pub nonce: u64, // Number of applied spends and key-set changes (replay protection)
+}
+
+impl MultisigAccount {
+    /// Message the policy keys sign to approve `tx` as this account's next spend.
+    /// Binds the full transaction hash (payload, shard, validity window) and the current nonce.
+    pub fn spend_message(&self, tx: &Transaction) -> Vec<u8> {
+        let mut message = MULTISIG_PAYLOAD_TAG.to_vec();
+        message.extend_from_slice(b"spend");
+        message.extend_from_slice(self.address.as_str().as_bytes());
+        message.extend_from_slice(&self.nonce.to_be_bytes());
+        message.extend_from_slice(&tx.compute_hash());
+        message
+    }
+
+    /// Check that a transaction sent from this account carries threshold approval for the current nonce
+    pub fn authorize(&self, tx: &Transaction) -> Result<(), String> {
+        self.policy.check_approval(&self.spend_message(tx), &tx.multisig_signatures)
+    }
+
+    /// The account after an applied spend; approvals for the old nonce no longer verify
+    pub fn spent(&self) -> Self {
+        Self { nonce: self.nonce + 1, ..self.clone() }
+    }
+}
+
+/// Authorize a debit from `party` within a multi-party message (multi-leg legs, DvP instructions).
+/// A multisig account needs threshold approval under its policy; any other party needs a valid
+/// signature from its own key.
+pub fn authorize_party(
+    party: &str,
+    multisig_account: &dyn Fn(&str) -> Option<MultisigAccount>,
+    message: &[u8],
+    signatures: &[PartySignature],
+) -> Result<(), String> {
+    if let Some(account) = multisig_account(party) {
+        return account.policy.check_approval(message, signatures)
+            .map_err(|e| format!("Debited multisig account {party}: {e}"));
+    }
+    let signed = signatures.iter().any(|sig| {
+        Address::from_verifying_key(&sig.public_key).as_str() == party
+            && sig.public_key.verify(message, &sig.signature).is_ok()
+    });
+    if !signed {
+        return Err(format!("Missing signature from debited party {party}"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MultisigOpKind {
+    /// Register a new account; every key holder consents by signing up to the threshold
+    Create { policy: MultisigPolicy, salt: Vec<u8> },
+    /// Replace the key set; requires threshold approval under the current policy
+    UpdatePolicy { account: Address, new_policy: MultisigPolicy, nonce: u64 },
+}
+
+/// Signed multisig account operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigOp {
+    pub kind: MultisigOpKind,
+    pub findag_time: u64,
+    pub shard_id: ShardId,
+    pub approvals: Vec<PartySignature>,
+}
+
+impl MultisigOp {
+    pub fn new(kind: MultisigOpKind, findag_time: u64, shard_id: ShardId) -> Self {
+        Self { kind, findag_time, shard_id, approvals: Vec::new() }
+    }
+
+    /// Canonical message approvers sign (all fields except approvals)
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = MULTISIG_PAYLOAD_TAG.to_vec();
+        message.extend_from_slice(&bincode::serialize(&self.kind).expect("multisig op serialization"));
+        message.extend_from_slice(&self.findag_time.to_be_bytes());
+        message.extend_from_slice(&self.shard_id.0.to_be_bytes());
+        message
+    }
+
+    pub fn sign(&mut self, signing_key: &SigningKey) {
+        let public_key = signing_key.verifying_key();
+        let signature = signing_key.sign(&self.signing_message());
+        self.approvals.retain(|s| s.public_key != public_key);
+        self.approvals.push(PartySignature { public_key, signature });
+    }
+
+    /// Address of the account this operation creates or modifies
+    pub fn account_address(&self) -> Address {
+        match &self.kind {
+            MultisigOpKind::Create { policy, salt } => policy.derive_address(salt),
+            MultisigOpKind::UpdatePolicy { account, .. } => account.clone(),
+        }
+    }
+
+    /// Validate against the current on-chain account and return the resulting account
+    pub fn apply(&self, current: Option<&MultisigAccount>) -> Result<MultisigAccount, String> {
+        let message = self.signing_message();
+        match &self.kind {
+            MultisigOpKind::Create { policy, .. } => {
+                if current.is_some() {
+                    return Err("Multisig account already exists".to_string());
+                }
+                policy.validate()?;
+                policy.check_approval(&message, &self.approvals)?;
+                Ok(MultisigAccount { address: self.account_address(), policy: policy.clone(), nonce: 0 })
+            }
+            MultisigOpKind::UpdatePolicy { account, new_policy, nonce } => {
+                let current = current.ok_or_else(|| format!("Unknown multisig account {account}"))?;
+                if *nonce != current.nonce {
+                    return Err(format!("Stale multisig nonce {nonce}, expected {}", current.nonce));
+                }
+                new_policy.validate()?;
+                current.policy.check_approval(&message, &self.approvals)?;
+                Ok(MultisigAccount { address: current.address.clone(), policy: new_policy.clone(), nonce: current.nonce + 1 })
+            }
+        }
+    }
+
+    /// Check every approval signature, without reference to on-chain state
+    pub fn verify_signatures(&self) -> Result<(), String> {
+        if self.approvals.is_empty() {
+            return Err("Multisig operation has no approvals".to_string());
+        }
+        let message = self.signing_message();
+        for approval in &self.approvals {
+            approval.public_key.verify(&message, &approval.signature)
+                .map_err(|_| "Invalid multisig approval signature".to_string())?;
+        }
+        Ok(())
+    }
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut payload = MULTISIG_PAYLOAD_TAG.to_vec();
+        payload.extend_from_slice(&bincode::serialize(self).expect("multisig op serialization"));
+        payload
+    }
+
+    /// Decode from a `Transaction.payload`; None if the payload is not a multisig operation
+    pub fn from_payload(payload: &[u8]) -> Option<Result<Self, String>> {
+        let body = payload.strip_prefix(MULTISIG_PAYLOAD_TAG)?;
+        Some(bincode::deserialize(body).map_err(|e| format!("Invalid multisig payload: {e}")))
+    }
+
+    /// Decode from a `Transaction` envelope, checking the envelope matches the signed body
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        let decoded = Self::from_payload(&tx.payload)?;
+        Some(decoded.and_then(|op| {
+            if op.shard_id != tx.shard_id || op.findag_time != tx.findag_time || op.account_address() != tx.from {
+                return Err("Multisig envelope does not match its payload".to_string());
+            }
+            Ok(op)
+        }))
+    }
+
+    /// Wrap in a `Transaction` addressed from the account being created or changed
+    pub fn to_transaction(&self) -> Result<Transaction, String> {
+        let first = self.approvals.first().ok_or("Multisig operation has no approvals")?;
+        let address = self.account_address();
+        let payload = self.to_payload();
+        Ok(Transaction {
+            from: address.clone(),
+            to: address,
+            amount: 0,
+            hashtimer: compute_hashtimer(self.findag_time, &payload, 0),
+            payload,
+            findag_time: self.findag_time,
+            signature: first.signature,
+            public_key: first.public_key,
+            shard_id: self.shard_id,
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(keys: &[&SigningKey], threshold: u64) -> MultisigPolicy {
+        MultisigPolicy {
+            signers: keys.iter().map(|k| MultisigSigner { public_key: k.verifying_key(), weight: 1 }).collect(),
+            threshold,
+        }
+    }
+
+    #[test]
+    fn test_create_and_update_require_threshold() {
+        let a = SigningKey::from_bytes(&[1u8; 32]);
+        let b = SigningKey::from_bytes(&[2u8; 32]);
+        let c = SigningKey::from_bytes(&[3u8; 32]);
+
+        let mut create = MultisigOp::new(
+            MultisigOpKind::Create { policy: policy(&[&a, &b, &c], 2), salt: b"treasury".to_vec() },
+            1,
+            ShardId(0),
+        );
+        create.sign(&a);
+        assert!(create.apply(None).is_err());
+        create.sign(&b);
+        let account = create.apply(None).unwrap();
+        assert_eq!(account.address, policy(&[&a, &b, &c], 2).derive_address(b"treasury"));
+
+        // Rotating out `c` needs 2-of-3 under the current policy
+        let mut update = MultisigOp::new(
+            MultisigOpKind::UpdatePolicy { account: account.address.clone(), new_policy: policy(&[&a, &b], 2), nonce: 0 },
+            2,
+            ShardId(0),
+        );
+        update.sign(&c);
+        assert!(update.apply(Some(&account)).is_err());
+        update.sign(&a);
+        let updated = update.apply(Some(&account)).unwrap();
+        assert_eq!(updated.address, account.address);
+        assert_eq!(updated.nonce, 1);
+
+        // Replaying the same update against the new state fails on the nonce
+        assert!(update.apply(Some(&updated)).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_signatures_count_once() {
+        let a = SigningKey::from_bytes(&[1u8; 32]);
+        let b = SigningKey::from_bytes(&[2u8; 32]);
+        let p = policy(&[&a, &b], 2);
+        let message = b"spend";
+        let sig = PartySignature { public_key: a.verifying_key(), signature: a.sign(message) };
+        assert_eq!(p.approved_weight(message, &[sig.clone(), sig]), 1);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::consensus::governance_tx::GovernanceTx;
+use crate::consensus::slashing::SlashingTx;
+use crate::consensus::staking::StakingTx;
+use crate::consensus::validator_lifecycle::ValidatorTx;
+use crate::core::bridge::BridgeTx;
+use crate::core::dvp::DvpSettlement;
+use crate::core::handle_registry::HandleTx;
+use crate::core::migration::AddressMigrationTx;
+use crate::core::multisig::MultisigAccount;
+use crate::core::types::Transaction;
+
+/// Protocol operation carried in a `Transaction.payload` and signed inside the payload.
+/// Multisig operations and multi-leg transactions are authorized per party and handled separately.
+#[derive(Debug, Clone)]
+pub enum ProtocolPayload {
+    Governance(GovernanceTx),
+    Staking(StakingTx),
+    Slashing(SlashingTx),
+    Validator(ValidatorTx),
+    Handle(HandleTx),
+    Bridge(BridgeTx),
+    Dvp(DvpSettlement),
+    Migration(AddressMigrationTx),
+}
+
+impl ProtocolPayload {
+    /// Decode from a `Transaction` envelope; None if the payload is a plain transfer or another kind
+    pub fn from_transaction(tx: &Transaction) -> Option<Result<Self, String>> {
+        if let Some(decoded) = GovernanceTx::from_transaction(tx) {
+            return Some(decoded.map(Self::Governance));
+        }
+        if let Some(decoded) = StakingTx::from_transaction(tx) {
+            return Some(decoded.map(Self::Staking));
+        }
+        if let Some(decoded) = SlashingTx::from_transaction(tx) {
+            return Some(decoded.map(Self::Slashing));
+        }
+        if let Some(decoded) = ValidatorTx::from_transaction(tx) {
+            return Some(decoded.map(Self::Validator));
+        }
+        if let Some(decoded) = HandleTx::from_transaction(tx) {
+            return Some(decoded.map(Self::Handle));
+        }
+        if let Some(decoded) = BridgeTx::from_transaction(tx) {
+            return Some(decoded.map(Self::Bridge));
+        }
+        if let Some(decoded) = DvpSettlement::from_transaction(tx) {
+            return Some(decoded.map(Self::Dvp));
+        }
+        AddressMigrationTx::from_transaction(tx).map(|decoded| decoded.map(Self::Migration))
+    }
+
+    /// Check the signatures carried inside the payload. `multisig_account` resolves DvP
+    /// instructions from multisig accounts to their policy.
+    pub fn verify(&self, multisig_account: &dyn Fn(&str) -> Option<MultisigAccount>) -> Result<(), String> {
+        match self {
+            Self::Governance(gov_tx) => gov_tx.verify(),
+            Self::Staking(staking_tx) => staking_tx.verify(),
+            Self::Slashing(slashing_tx) => slashing_tx.verify(),
+            Self::Validator(validator_tx) => validator_tx.verify(),
+            Self::Handle(handle_tx) => handle_tx.verify(),
+            Self::Bridge(bridge_tx) => bridge_tx.verify(),
+            Self::Dvp(dvp) => dvp.verify(multisig_account).map(|_| ()),
+            Self::Migration(migration) => migration.verify().map(|_| ()),
+        }
+    }
+
+    /// Short name used in metrics labels and log lines
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Governance(_) => "governance",
+            Self::Staking(_) => "staking",
+            Self::Slashing(_) => "slashing",
+            Self::Validator(_) => "validator_tx",
+            Self::Handle(_) => "handle_tx",
+            Self::Bridge(_) => "bridge_tx",
+            Self::Dvp(_) => "dvp",
+            Self::Migration(_) => "address_migration",
+        }
+    }
+}
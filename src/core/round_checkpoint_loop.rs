@@ -2,17 +2,21 @@ use crate::core::dag_engine::DagEngine;
 use crate::core::types::Round;
 use crate::core::address::Address;
 use crate::dagtimer::findag_time_manager::FinDAGTimeManager;
-use ed25519_dalek::SigningKey;
+use ed25519_dalek::{Signature, SigningKey};
 use tokio::time::{sleep, Duration};
 use std::collections::HashSet;
 use crate::storage::persistent::PersistMsg;
 use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Mutex;
 use crate::consensus::roundchain::RoundChain;
+use crate::consensus::parameters::ROUND_INTERVAL_MS;
 
 /// Runs the round checkpointing loop at the given interval (ms)
-/// Uses simple linear RoundChain for deterministic finality
+/// Uses simple linear RoundChain for deterministic finality: each round is signed
+/// by this node if it sits on the round's committee, and its blocks become final
+/// once the signatures reach the committee quorum
 pub async fn run_round_checkpoint_loop(
-    dag: &mut DagEngine,
+    dag: &Mutex<DagEngine>,
     proposer: Address,
     keypair: &SigningKey,
     interval_ms: u64,
@@ -25,7 +29,7 @@ pub async fn run_round_checkpoint_loop(
     
     loop {
         // Collect all new blocks since last round
-        let all_blocks: Vec<_> = dag.get_all_blocks().await;
+        let all_blocks: Vec<_> = dag.lock().await.get_all_blocks().await;
         let mut new_blocks = Vec::new();
         for block in &all_blocks {
             if !last_block_set.contains(&block.block_id) {
@@ -49,6 +53,19 @@ pub async fn run_round_checkpoint_loop(
             
             // Add round to RoundChain
             roundchain.add_round(round.clone()).expect("Failed to add round to chain");
+
+            // The proposer signature covers the round content, so it doubles as our quorum vote
+            let committee = roundchain.validator_set.lock().unwrap().select_committee(round_number);
+            let signatures: Vec<(Address, Signature)> = if committee.validators.contains(&proposer) {
+                vec![(proposer.clone(), round.proposer_signature)]
+            } else {
+                Vec::new()
+            };
+            match roundchain.sign_round_with_quorum(round_number, &committee, &signatures) {
+                Ok(()) => println!("Round {round_number} finalized by quorum"),
+                Err(e) => println!("[DEBUG] Round {round_number} awaiting quorum: {e}"),
+            }
+            let round = roundchain.get_round(round_number).cloned().unwrap_or(round);
             
             // Convert RoundChain Round to core Round for compatibility
             let core_round = Round {
@@ -62,7 +79,7 @@ pub async fn run_round_checkpoint_loop(
                 proposer_signature: round.proposer_signature,
                 proposer_public_key: round.proposer_public_key,
             };
-            dag.add_round(core_round.clone()).await;
+            dag.lock().await.add_round(core_round.clone()).await;
             
             last_round_number = round_number;
             for block in &new_blocks {
@@ -75,6 +92,11 @@ pub async fn run_round_checkpoint_loop(
             let _ = persist_tx.send(PersistMsg::Round(core_round));
         }
         
+        // Governance sets the protocol round interval once an executor is attached
+        let interval_ms = match &roundchain.governance_executor {
+            Some(executor) => executor.control().param_u64(ROUND_INTERVAL_MS).map_or(interval_ms, |v| interval_ms.max(v)),
+            None => interval_ms,
+        };
         sleep(Duration::from_millis(interval_ms)).await;
     }
 }
@@ -83,8 +105,8 @@ pub async fn run_round_checkpoint_loop(
 //
 // #[tokio::main]
 // async fn main() {
-//     let mut dag = DagEngine::new();
+//     let dag = Mutex::new(DagEngine::new().await);
 //     let (keypair, address) = generate_address();
 //     let time_manager = FinDAGTimeManager::new();
-//     run_round_checkpoint_loop(&mut dag, address, &keypair, 200, &time_manager).await;
+//     run_round_checkpoint_loop(&dag, address, &keypair, 200, &time_manager, persist_tx, &mut roundchain).await;
 // } 
\ No newline at end of file
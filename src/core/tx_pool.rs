@@ -1,11 +1,25 @@
 use crate::core::types::{Transaction};
+use crate::core::tx_status::TxStatusRegistry;
+use crate::core::multi_leg::MultiLegTransaction;
+use crate::core::multisig::MultisigOp;
+use crate::core::handle_registry::HandleRegistry;
+use crate::core::ingestion::IngestionRegistry;
+use crate::core::dvp::DvpSettlement;
+use crate::core::bridge::BridgeAction;
+use crate::core::payload::ProtocolPayload;
+use crate::consensus::governance_executor::ChainControl;
+use crate::consensus::staking::{StakingAction, STAKING_ASSET};
+use crate::consensus::parameters::{FEE_MIN_TX_FEE, MEMPOOL_MAX_SIZE};
 use std::collections::{HashMap, BTreeMap};
 use std::sync::{Arc, Mutex};
 use crate::storage::state::StateDB;
+use crate::dagtimer::findag_time_manager::FinDAGTimeManager;
 use crate::metrics;
-use sha2::{Sha256, Digest};
 use hex;
 
+/// How far ahead of the local FinDAG Time a transaction's time or window may start (1 hour)
+pub const MAX_FUTURE_TX_TIME: u64 = 3600 << 24;
+
 /// Transaction Pool (Mempool) for FinDAG
 /// - Deduplicates by transaction hash
 /// - Prioritizes by FinDAG Time (oldest first)
@@ -16,8 +30,11 @@ pub struct TxPool {
     // FinDAG Time -> set of transaction hashes (for prioritization)
     pub time_index: BTreeMap<u64, Vec<[u8; 32]>>,
     pub max_size: usize,
+    pub min_tx_fee: u64, // Fee a plain transfer must cover beyond the amount; set from governance
     pub state_db: Arc<StateDB>,
     pub asset_whitelist: Arc<Mutex<Vec<String>>>,
+    pub status_registry: Arc<TxStatusRegistry>,
+    pub ingestion: Arc<IngestionRegistry>,
 }
 
 impl TxPool {
@@ -26,29 +43,33 @@ impl TxPool {
             transactions: HashMap::new(),
             time_index: BTreeMap::new(),
             max_size,
+            min_tx_fee: 0,
             state_db,
             asset_whitelist,
+            status_registry: Arc::new(TxStatusRegistry::default()),
+            // No adapter is bound until the node installs its registry
+            ingestion: Arc::new(IngestionRegistry::new(Arc::new(Mutex::new(HandleRegistry::default())))),
         }
     }
 
     /// Compute transaction hash from transaction data
     fn compute_tx_hash(&self, tx: &Transaction) -> [u8; 32] {
-        let mut hasher = Sha256::new();
-        hasher.update(tx.from.as_str().as_bytes());
-        hasher.update(tx.to.as_str().as_bytes());
-        hasher.update(tx.amount.to_le_bytes());
-        hasher.update(&tx.payload);
-        hasher.update(tx.findag_time.to_le_bytes());
-        hasher.update(tx.public_key.to_bytes());
-        hasher.update(tx.shard_id.0.to_le_bytes());
-        hasher.finalize().into()
+        tx.compute_hash()
     }
 
     /// Add a new transaction to the pool. Returns true if added.
     pub fn add_transaction(&mut self, tx: Transaction) -> bool {
+        let now = FinDAGTimeManager::new().get_findag_time();
+        self.add_transaction_at(tx, now)
+    }
+
+    /// Add a new transaction, checking its validity window against the given FinDAG Time
+    pub fn add_transaction_at(&mut self, tx: Transaction, now: u64) -> bool {
         println!("[DEBUG] TxPool: Attempting to add transaction: from={}, to={}, amount={}", 
                  tx.from.as_str(), tx.to.as_str(), tx.amount);
         
+        let tx_hash = self.compute_tx_hash(&tx);
+
         // Cross-shard transaction protocol (scaffold)
         if let (Some(source), Some(dest)) = (tx.source_shard, tx.dest_shard) {
             // TODO: Implement two-phase commit for cross-shard txs
@@ -57,10 +78,60 @@ impl TxPool {
             // Finalize and update state on both shards
             println!("[TxPool] Rejected cross-shard tx: {source:?} -> {dest:?}");
             // For now, reject or queue cross-shard txs
+            self.status_registry.mark_rejected(tx_hash, "cross-shard transactions not supported");
+            return false;
+        }
+
+        if let Err(reason) = Self::check_validity_window(&tx, now) {
+            println!("[DEBUG] TxPool: Rejected tx 0x{}: {}", hex::encode(tx_hash), reason);
+            metrics::ERROR_COUNT.with_label_values(&["invalid_validity_window"]).inc();
+            self.status_registry.mark_rejected(tx_hash, reason);
+            return false;
+        }
+
+        // ISO 20022 / SWIFT / FIX conversions must be signed by the institution bound to the adapter
+        if let Some(Err(reason)) = self.ingestion.verify(&tx) {
+            println!("[DEBUG] TxPool: Rejected adapter tx 0x{}: {}", hex::encode(tx_hash), reason);
+            metrics::ERROR_COUNT.with_label_values(&["invalid_ingestion"]).inc();
+            self.status_registry.mark_rejected(tx_hash, &reason);
+            return false;
+        }
+
+        // Multisig account operations and spends need threshold approval
+        let multisig_op = MultisigOp::from_transaction(&tx);
+        if let Err(reason) = self.check_multisig(&tx, multisig_op.as_ref()) {
+            println!("[DEBUG] TxPool: Rejected multisig tx 0x{}: {}", hex::encode(tx_hash), reason);
+            metrics::ERROR_COUNT.with_label_values(&["invalid_multisig"]).inc();
+            self.status_registry.mark_rejected(tx_hash, &reason);
             return false;
         }
+
+        // Multi-leg transactions are authorized and funded per leg, not by the envelope
+        let multi_leg = MultiLegTransaction::from_transaction(&tx);
+        if let Some(multi) = &multi_leg {
+            let check = match multi {
+                Ok(m) => self.check_multi_leg(m),
+                Err(e) => Err(e.clone()),
+            };
+            if let Err(reason) = check {
+                println!("[DEBUG] TxPool: Rejected multi-leg tx 0x{}: {}", hex::encode(tx_hash), reason);
+                metrics::ERROR_COUNT.with_label_values(&["invalid_multi_leg"]).inc();
+                self.status_registry.mark_rejected(tx_hash, &reason);
+                return false;
+            }
+        }
+
+        // Protocol payloads must carry valid signatures, and whatever they debit must be funded
+        if let Some(payload) = ProtocolPayload::from_transaction(&tx) {
+            let kind = payload.as_ref().map_or("payload", |p| p.kind());
+            if let Err(reason) = payload.and_then(|p| self.check_payload(&tx, &p)) {
+                println!("[DEBUG] TxPool: Rejected {} tx 0x{}: {}", kind, hex::encode(tx_hash), reason);
+                metrics::ERROR_COUNT.with_label_values(&[&format!("invalid_{kind}")]).inc();
+                self.status_registry.mark_rejected(tx_hash, &reason);
+                return false;
+            }
+        }
         
-        let tx_hash = self.compute_tx_hash(&tx);
         if self.transactions.contains_key(&tx_hash) {
             metrics::ERROR_COUNT.with_label_values(&["duplicate_tx"]).inc();
             println!("[DEBUG] TxPool: Rejected duplicate transaction with hash: 0x{}", 
@@ -73,6 +144,7 @@ impl TxPool {
             if let Some((&oldest_time, hashes)) = self.time_index.iter_mut().next() {
                 if let Some(evict_hash) = hashes.pop() {
                     self.transactions.remove(&evict_hash);
+                    self.status_registry.mark_rejected(evict_hash, "evicted from full pool");
                     println!("[DEBUG] TxPool: Evicted old transaction to make room");
                 }
                 if hashes.is_empty() {
@@ -84,14 +156,18 @@ impl TxPool {
         // For now, skip asset whitelist check since we're using a default asset
         // TODO: Add proper asset field to Transaction struct
         
-        // Check sender balance before adding (using USD as default asset)
+        // Check sender balance before adding (using USD as default asset), minimum fee included.
+        // Only plain transfers are charged the fee on execution; protocol payloads pay none,
+        // so governance can always resume a paused chain.
         let from = tx.from.as_str();
-        let amount = tx.amount;
+        let fee = if ProtocolPayload::from_transaction(&tx).is_some() { 0 } else { self.min_tx_fee };
+        let amount = tx.amount.saturating_add(fee);
         let bal = self.state_db.get_balance(tx.shard_id.0, from, "USD");
         println!("[DEBUG] TxPool: Balance check for {}: amount={}, balance={}, shard_id={}", from, amount, bal, tx.shard_id.0);
-        if bal < amount {
-            println!("[DEBUG] TxPool: Rejected tx: insufficient funds for {from} ({amount} USD, balance: {bal})");
+        if multi_leg.is_none() && multisig_op.is_none() && bal < amount {
+            println!("[DEBUG] TxPool: Rejected tx: insufficient funds for {from} ({amount} USD with fee {fee}, balance: {bal})");
             metrics::ERROR_COUNT.with_label_values(&["insufficient_funds"]).inc();
+            self.status_registry.mark_rejected(tx_hash, "insufficient funds");
             return false;
         }
         
@@ -99,6 +175,7 @@ impl TxPool {
         let added = self.transactions.insert(tx_hash, tx).is_none();
         if added {
             metrics::MEMPOOL_SIZE.set(self.transactions.len() as i64);
+            self.status_registry.mark_pending(tx_hash);
             println!("[DEBUG] TxPool: Successfully added transaction, pool size: {}", self.transactions.len());
         } else {
             println!("[DEBUG] TxPool: Failed to add transaction (insert returned Some)");
@@ -106,6 +183,109 @@ impl TxPool {
         added
     }
 
+    /// Admission checks for FinDAG Time and the validity window
+    pub fn check_validity_window(tx: &Transaction, now: u64) -> Result<(), &'static str> {
+        let horizon = now.saturating_add(MAX_FUTURE_TX_TIME);
+        if tx.findag_time > horizon {
+            return Err("findag_time too far in the future");
+        }
+        if let (Some(after), Some(until)) = (tx.valid_after, tx.valid_until) {
+            if after > until {
+                return Err("valid_after is later than valid_until");
+            }
+        }
+        if tx.is_expired_at(now) {
+            return Err("validity window already expired");
+        }
+        if tx.valid_after.is_some_and(|after| after > horizon) {
+            return Err("valid_after too far in the future");
+        }
+        Ok(())
+    }
+
+    /// Dry-run a multisig operation, or authorize a spend from a multisig account
+    fn check_multisig(&self, tx: &Transaction, op: Option<&Result<MultisigOp, String>>) -> Result<(), String> {
+        match op {
+            Some(Ok(op)) => {
+                let current = self.state_db.get_multisig_account(op.account_address().as_str());
+                op.apply(current.as_ref()).map(|_| ())
+            }
+            Some(Err(e)) => Err(e.clone()),
+            // Multi-leg debits are authorized per party in check_multi_leg
+            None if MultiLegTransaction::from_payload(&tx.payload).is_some() => Ok(()),
+            None => match self.state_db.get_multisig_account(tx.from.as_str()) {
+                Some(account) => account.authorize(tx),
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// Signatures and per-asset funding of every debited party
+    fn check_multi_leg(&self, multi: &MultiLegTransaction) -> Result<(), String> {
+        multi.validate(&|address| self.state_db.get_multisig_account(address))?;
+        for ((party, asset), amount) in multi.debits() {
+            let balance = self.state_db.get_balance(multi.shard_id.0, &party, &asset);
+            if balance < amount {
+                return Err(format!("insufficient {asset} funds for {party}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Payload signatures, plus funding of whatever the payload bonds, locks or settles
+    fn check_payload(&self, tx: &Transaction, payload: &ProtocolPayload) -> Result<(), String> {
+        match payload {
+            // DvP transfers verify both instructions before listing the legs
+            ProtocolPayload::Dvp(dvp) => return self.check_dvp(dvp),
+            other => other.verify(&|address| self.state_db.get_multisig_account(address))?,
+        }
+        match payload {
+            ProtocolPayload::Staking(s) => {
+                if let StakingAction::Delegate { amount, .. } = s.action {
+                    let balance = self.state_db.get_balance(s.shard_id.0, tx.from.as_str(), STAKING_ASSET);
+                    if balance < amount {
+                        return Err(format!("Insufficient {STAKING_ASSET} to bond {amount}"));
+                    }
+                }
+            }
+            ProtocolPayload::Bridge(b) => {
+                if let BridgeAction::Lock { asset, amount, .. } = &b.action {
+                    if self.state_db.get_balance(b.shard_id.0, tx.from.as_str(), asset) < *amount {
+                        return Err(format!("Insufficient {asset} to lock {amount}"));
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn check_dvp(&self, dvp: &DvpSettlement) -> Result<(), String> {
+        for (party, _, asset, amount) in dvp.transfers(&|address| self.state_db.get_multisig_account(address))? {
+            let balance = self.state_db.get_balance(dvp.shard_id.0, &party, &asset);
+            if balance < amount {
+                return Err(format!("insufficient {asset} funds for {party}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop transactions whose validity window has closed. Returns the number purged.
+    pub fn purge_expired(&mut self, now: u64) -> usize {
+        let expired: Vec<([u8; 32], u64)> = self.transactions.iter()
+            .filter(|(_, tx)| tx.is_expired_at(now))
+            .map(|(hash, tx)| (*hash, tx.findag_time))
+            .collect();
+        for (hash, findag_time) in &expired {
+            self.remove_transaction(hash, *findag_time);
+            self.status_registry.mark_expired(*hash);
+        }
+        if !expired.is_empty() {
+            println!("[DEBUG] TxPool: Purged {} expired transactions", expired.len());
+        }
+        expired.len()
+    }
+
     /// Remove a transaction (e.g., after block inclusion)
     pub fn remove_transaction(&mut self, tx_hash: &[u8; 32], findag_time: u64) {
         let removed = self.transactions.remove(tx_hash).is_some();
@@ -150,6 +330,11 @@ impl TxPool {
 pub struct ShardedTxPool {
     shards: Vec<Mutex<TxPool>>,
     shard_count: usize,
+    status_registry: Arc<TxStatusRegistry>,
+    state_db: Arc<StateDB>,
+    asset_whitelist: Arc<Mutex<Vec<String>>>,
+    chain_control: Arc<ChainControl>,
+    max_size_per_shard: usize, // Local cap; the governance mempool limit can lower it
 }
 
 impl ShardedTxPool {
@@ -160,19 +345,66 @@ impl ShardedTxPool {
     pub fn new_with_whitelist_per_shard_and_data_dir(max_size_per_shard: usize, asset_whitelist: Arc<Mutex<Vec<String>>>, shard_count: usize, data_dir: &str) -> Self {
         let mut shards = Vec::with_capacity(shard_count);
         let state_db = Arc::new(StateDB::new(data_dir));
+        let status_registry = Arc::new(TxStatusRegistry::default());
         for _ in 0..shard_count {
-            shards.push(Mutex::new(TxPool::new(max_size_per_shard, state_db.clone(), asset_whitelist.clone())));
+            let mut pool = TxPool::new(max_size_per_shard, state_db.clone(), asset_whitelist.clone());
+            pool.status_registry = status_registry.clone();
+            shards.push(Mutex::new(pool));
+        }
+        Self {
+            shards,
+            shard_count,
+            status_registry,
+            state_db,
+            asset_whitelist,
+            chain_control: Arc::new(ChainControl::new()),
+            max_size_per_shard,
+        }
+    }
+
+    /// State database shared by all shards
+    pub fn state_db(&self) -> Arc<StateDB> {
+        self.state_db.clone()
+    }
+
+    /// Shared transaction status registry fed by all shards
+    pub fn status_registry(&self) -> Arc<TxStatusRegistry> {
+        self.status_registry.clone()
+    }
+
+    /// Asset whitelist shared by all shards
+    pub fn asset_whitelist(&self) -> Arc<Mutex<Vec<String>>> {
+        self.asset_whitelist.clone()
+    }
+
+    /// Install the adapter bindings and mapping table that converted transactions are checked against
+    pub fn set_ingestion_registry(&self, registry: Arc<IngestionRegistry>) {
+        for shard in &self.shards {
+            shard.lock().unwrap().ingestion = registry.clone();
         }
-        Self { shards, shard_count }
+    }
+
+    /// Governance pause switch; while paused only governance transactions are admitted
+    pub fn chain_control(&self) -> Arc<ChainControl> {
+        self.chain_control.clone()
     }
     /// Route by tx.shard_id (single-shard mode: always 0)
     fn shard_for_id(&self, shard_id: u16) -> usize {
         (shard_id as usize) % self.shard_count
     }
     pub fn add_transaction(&self, tx: Transaction) -> bool {
+        if !self.chain_control.admits(&tx) {
+            self.status_registry.mark_rejected(tx.compute_hash(), "chain paused by governance");
+            return false;
+        }
         let shard = self.shard_for_id(tx.shard_id.0);
         println!("[DEBUG] ShardedTxPool: Adding transaction to shard {} (shard_id={})", shard, tx.shard_id.0);
-        let result = self.shards[shard].lock().unwrap().add_transaction(tx);
+        let max_size = self.chain_control.param_u64(MEMPOOL_MAX_SIZE)
+            .map_or(self.max_size_per_shard, |v| self.max_size_per_shard.min(v as usize));
+        let mut pool = self.shards[shard].lock().unwrap();
+        pool.max_size = max_size;
+        pool.min_tx_fee = self.chain_control.param_u64(FEE_MIN_TX_FEE).unwrap_or(0);
+        let result = pool.add_transaction(tx);
         println!("[DEBUG] ShardedTxPool: Transaction add result: {result}");
         result
     }
@@ -198,6 +430,10 @@ impl ShardedTxPool {
         println!("[DEBUG] ShardedTxPool: Returning {} transactions total", txs.len());
         txs
     }
+    /// Purge expired transactions from every shard. Returns the number purged.
+    pub fn purge_expired(&self, now: u64) -> usize {
+        self.shards.iter().map(|shard| shard.lock().unwrap().purge_expired(now)).sum()
+    }
     pub fn size(&self, shard_id: u16) -> usize {
         let shard = self.shard_for_id(shard_id);
         self.shards[shard].lock().unwrap().size()
@@ -207,4 +443,120 @@ impl ShardedTxPool {
         self.shards[shard].lock().unwrap().state_db.get_balance(shard_id, address, asset)
     }
     // For future: add multi-shard aggregation methods
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::address::Address;
+    use crate::core::tx_status::TxStatus;
+    use crate::consensus::governance_tx::{GovernanceAction, GovernanceTx};
+    use crate::consensus::parameters::{ParamValue, ParameterRegistry};
+    use crate::core::types::ShardId;
+    use ed25519_dalek::{Signature, SigningKey};
+
+    fn test_pool(dir: &tempfile::TempDir) -> TxPool {
+        let state_db = Arc::new(StateDB::new(dir.path().to_str().unwrap()));
+        state_db.set_balance(0, "fdg1sender", "USD", 1_000).unwrap();
+        TxPool::new(100, state_db, Arc::new(Mutex::new(vec!["USD".to_string()])))
+    }
+
+    fn test_tx(valid_after: Option<u64>, valid_until: Option<u64>) -> Transaction {
+        Transaction {
+            from: Address("fdg1sender".to_string()),
+            to: Address("fdg1receiver".to_string()),
+            amount: 10,
+            payload: vec![],
+            findag_time: 100,
+            hashtimer: [0u8; 32],
+            signature: Signature::from_bytes(&[0u8; 64]),
+            public_key: SigningKey::from_bytes(&[7u8; 32]).verifying_key(),
+            shard_id: ShardId(0),
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after,
+            valid_until,
+            multisig_signatures: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_admission_enforces_validity_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut pool = test_pool(&dir);
+
+        let expired = test_tx(None, Some(500));
+        let expired_hash = expired.compute_hash();
+        assert!(!pool.add_transaction_at(expired, 1_000));
+        assert!(matches!(pool.status_registry.status(&expired_hash), Some(TxStatus::Rejected { .. })));
+
+        assert!(!pool.add_transaction_at(test_tx(Some(900), Some(800)), 700));
+        assert!(pool.add_transaction_at(test_tx(Some(900), Some(2_000)), 1_000));
+    }
+
+    #[test]
+    fn test_pause_admits_governance_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let whitelist = Arc::new(Mutex::new(vec!["USD".to_string()]));
+        let pool = ShardedTxPool::new_with_whitelist_per_shard_and_data_dir(100, whitelist, 1, dir.path().to_str().unwrap());
+        pool.state_db().set_balance(0, "fdg1sender", "USD", 1_000).unwrap();
+        pool.chain_control().pause("incident");
+
+        assert!(!pool.add_transaction(test_tx(None, None)));
+        let vote = GovernanceTx::sign(
+            GovernanceAction::Vote { proposal_id: "proposal_1".to_string(), approve: true, reason: None },
+            7,
+            ShardId(0),
+            &SigningKey::from_bytes(&[5u8; 32]),
+        );
+        assert!(pool.add_transaction(vote.to_transaction()));
+
+        pool.chain_control().resume();
+        assert!(pool.add_transaction(test_tx(None, None)));
+    }
+
+    #[test]
+    fn test_min_fee_applied_at_admission() {
+        let dir = tempfile::tempdir().unwrap();
+        let whitelist = Arc::new(Mutex::new(vec!["USD".to_string()]));
+        let pool = ShardedTxPool::new_with_whitelist_per_shard_and_data_dir(100, whitelist, 1, dir.path().to_str().unwrap());
+        pool.state_db().set_balance(0, "fdg1sender", "USD", 1_000).unwrap();
+        let mut registry = ParameterRegistry::new();
+        registry.set(FEE_MIN_TX_FEE, ParamValue::U64(995), 0).unwrap();
+        pool.chain_control().set_parameters(registry.clone());
+
+        // 10 USD plus a 995 fee exceeds the balance
+        let tx = test_tx(None, None);
+        let tx_hash = tx.compute_hash();
+        assert!(!pool.add_transaction(tx));
+        assert!(matches!(pool.status_registry().status(&tx_hash), Some(TxStatus::Rejected { .. })));
+
+        // Votes from an unfunded validator are still admitted
+        let vote = GovernanceTx::sign(
+            GovernanceAction::Vote { proposal_id: "proposal_1".to_string(), approve: true, reason: None },
+            7,
+            ShardId(0),
+            &SigningKey::from_bytes(&[5u8; 32]),
+        );
+        assert!(pool.add_transaction(vote.to_transaction()));
+
+        registry.set(FEE_MIN_TX_FEE, ParamValue::U64(990), 0).unwrap();
+        pool.chain_control().set_parameters(registry);
+        assert!(pool.add_transaction(test_tx(None, None)));
+    }
+
+    #[test]
+    fn test_purge_expired_marks_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut pool = test_pool(&dir);
+
+        let tx = test_tx(None, Some(1_500));
+        let tx_hash = tx.compute_hash();
+        assert!(pool.add_transaction_at(tx, 1_000));
+        assert_eq!(pool.purge_expired(1_500), 0);
+        assert_eq!(pool.purge_expired(1_501), 1);
+        assert_eq!(pool.size(), 0);
+        assert_eq!(pool.status_registry.status(&tx_hash), Some(TxStatus::Expired));
+    }
+}
@@ -0,0 +1,325 @@
+use crate::core::handle_registry::ResolvedHandle;
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+/// Lifecycle state of a transaction as seen by this node
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TxStatus {
+    Pending,                                          // Admitted to the TxPool
+    Included { block_id: [u8; 32] },                  // Contained in a DAG block
+    Finalized { round_number: u64, block_id: [u8; 32] }, // Block finalized in a Round
+    Rejected { reason: String },                      // Refused or dropped by the node
+    Expired,                                          // Validity window passed before inclusion
+}
+
+impl TxStatus {
+    /// Short state name used by the API and websocket feeds
+    pub fn name(&self) -> &'static str {
+        match self {
+            TxStatus::Pending => "pending",
+            TxStatus::Included { .. } => "included",
+            TxStatus::Finalized { .. } => "finalized",
+            TxStatus::Rejected { .. } => "rejected",
+            TxStatus::Expired => "expired",
+        }
+    }
+
+    /// States after which the transaction leaves the node's pipeline
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, TxStatus::Finalized { .. } | TxStatus::Rejected { .. } | TxStatus::Expired)
+    }
+
+    /// JSON representation with hex-encoded hashes
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            TxStatus::Included { block_id } => serde_json::json!({
+                "state": self.name(),
+                "block_id": hex::encode(block_id),
+            }),
+            TxStatus::Finalized { round_number, block_id } => serde_json::json!({
+                "state": self.name(),
+                "round_number": round_number,
+                "block_id": hex::encode(block_id),
+            }),
+            TxStatus::Rejected { reason } => serde_json::json!({
+                "state": self.name(),
+                "reason": reason,
+            }),
+            _ => serde_json::json!({ "state": self.name() }),
+        }
+    }
+}
+
+/// A single status transition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxStatusTransition {
+    pub status: TxStatus,
+    pub timestamp: u64,
+}
+
+/// Status record for one transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxStatusRecord {
+    pub tx_hash: [u8; 32],
+    pub status: TxStatus,
+    pub updated_at: u64,
+    pub history: Vec<TxStatusTransition>,
+    #[serde(default)]
+    pub resolved_recipient: Option<ResolvedHandle>, // Set when the recipient was given as a handle
+}
+
+impl TxStatusRecord {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "tx_hash": hex::encode(self.tx_hash),
+            "status": self.status.to_json(),
+            "updated_at": self.updated_at,
+            "history": self.history.iter().map(|t| serde_json::json!({
+                "status": t.status.to_json(),
+                "timestamp": t.timestamp,
+            })).collect::<Vec<_>>(),
+        });
+        if let Some(resolved) = &self.resolved_recipient {
+            json["resolved_recipient"] = serde_json::json!({
+                "handle": resolved.handle,
+                "public_key": hex::encode(resolved.public_key.to_bytes()),
+                "address": resolved.address.as_str(),
+            });
+        }
+        json
+    }
+}
+
+/// Status change notification pushed to subscribers
+#[derive(Debug, Clone)]
+pub struct TxStatusUpdate {
+    pub tx_hash: [u8; 32],
+    pub status: TxStatus,
+    pub timestamp: u64,
+}
+
+/// Registry of transaction lifecycle states keyed by transaction hash.
+/// Fed by the TxPool (admission/rejection), the DagEngine (inclusion)
+/// and the RoundChain (finality).
+pub struct TxStatusRegistry {
+    records: Mutex<HashMap<[u8; 32], TxStatusRecord>>,
+    // Hashes in the order they were first recorded, oldest first, for eviction at the cap
+    insertion_order: Mutex<VecDeque<[u8; 32]>>,
+    // Block id -> transaction hashes, so rounds can finalize by block
+    block_index: Mutex<HashMap<[u8; 32], Vec<[u8; 32]>>>,
+    max_records: usize,
+    sender: broadcast::Sender<TxStatusUpdate>,
+}
+
+impl TxStatusRegistry {
+    pub fn new(max_records: usize) -> Self {
+        let (sender, _) = broadcast::channel(1000);
+        Self {
+            records: Mutex::new(HashMap::new()),
+            insertion_order: Mutex::new(VecDeque::new()),
+            block_index: Mutex::new(HashMap::new()),
+            max_records,
+            sender,
+        }
+    }
+
+    /// Subscribe to status change notifications
+    pub fn subscribe(&self) -> broadcast::Receiver<TxStatusUpdate> {
+        self.sender.subscribe()
+    }
+
+    /// Get the current record for a transaction
+    pub fn get(&self, tx_hash: &[u8; 32]) -> Option<TxStatusRecord> {
+        self.records.lock().unwrap().get(tx_hash).cloned()
+    }
+
+    /// Get the current status for a transaction
+    pub fn status(&self, tx_hash: &[u8; 32]) -> Option<TxStatus> {
+        self.records.lock().unwrap().get(tx_hash).map(|r| r.status.clone())
+    }
+
+    pub fn mark_pending(&self, tx_hash: [u8; 32]) {
+        self.update(tx_hash, TxStatus::Pending);
+    }
+
+    pub fn mark_rejected(&self, tx_hash: [u8; 32], reason: &str) {
+        self.update(tx_hash, TxStatus::Rejected { reason: reason.to_string() });
+    }
+
+    pub fn mark_expired(&self, tx_hash: [u8; 32]) {
+        self.update(tx_hash, TxStatus::Expired);
+    }
+
+    /// Record the handle a transaction's recipient was resolved from
+    pub fn record_resolved_recipient(&self, tx_hash: [u8; 32], resolved: ResolvedHandle) {
+        if let Some(record) = self.records.lock().unwrap().get_mut(&tx_hash) {
+            record.resolved_recipient = Some(resolved);
+        }
+    }
+
+    /// Record that the given transactions were included in a block
+    pub fn mark_included(&self, block_id: [u8; 32], tx_hashes: &[[u8; 32]]) {
+        self.block_index.lock().unwrap().insert(block_id, tx_hashes.to_vec());
+        for tx_hash in tx_hashes {
+            self.update(*tx_hash, TxStatus::Included { block_id });
+        }
+    }
+
+    /// Record that the given blocks were finalized in a round
+    pub fn mark_round_finalized(&self, round_number: u64, block_ids: &[[u8; 32]]) {
+        for block_id in block_ids {
+            let tx_hashes = self.block_index.lock().unwrap().remove(block_id).unwrap_or_default();
+            for tx_hash in tx_hashes {
+                self.update(tx_hash, TxStatus::Finalized { round_number, block_id: *block_id });
+            }
+        }
+    }
+
+    /// Number of tracked transactions
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn update(&self, tx_hash: [u8; 32], status: TxStatus) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        {
+            let mut records = self.records.lock().unwrap();
+            match records.get(&tx_hash) {
+                // Finality is sticky, and once included only finality moves a transaction on;
+                // a late duplicate or pool eviction must not reopen or reject it
+                Some(record) => {
+                    let superseded = match &record.status {
+                        TxStatus::Finalized { .. } => true,
+                        TxStatus::Included { .. } => !matches!(status, TxStatus::Finalized { .. } | TxStatus::Included { .. }),
+                        _ => false,
+                    };
+                    if superseded || record.status == status {
+                        return;
+                    }
+                }
+                None => {
+                    let mut order = self.insertion_order.lock().unwrap();
+                    while records.len() >= self.max_records {
+                        let Some(oldest) = order.pop_front() else { break };
+                        if let Some(evicted) = records.remove(&oldest) {
+                            self.unindex(&evicted);
+                        }
+                    }
+                    order.push_back(tx_hash);
+                }
+            }
+            let record = records.entry(tx_hash).or_insert_with(|| TxStatusRecord {
+                tx_hash,
+                status: status.clone(),
+                updated_at: now,
+                history: Vec::new(),
+                resolved_recipient: None,
+            });
+            record.status = status.clone();
+            record.updated_at = now;
+            record.history.push(TxStatusTransition { status: status.clone(), timestamp: now });
+        }
+        let _ = self.sender.send(TxStatusUpdate { tx_hash, status, timestamp: now });
+    }
+
+    /// Drop an evicted record from its block's entry, and the entry once it is empty
+    fn unindex(&self, record: &TxStatusRecord) {
+        let TxStatus::Included { block_id } = &record.status else { return };
+        let mut block_index = self.block_index.lock().unwrap();
+        if let Some(tx_hashes) = block_index.get_mut(block_id) {
+            tx_hashes.retain(|hash| *hash != record.tx_hash);
+            if tx_hashes.is_empty() {
+                block_index.remove(block_id);
+            }
+        }
+    }
+}
+
+impl Default for TxStatusRegistry {
+    fn default() -> Self {
+        Self::new(1_000_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifecycle_to_finalized() {
+        let registry = TxStatusRegistry::default();
+        let tx = [1u8; 32];
+        let block = [9u8; 32];
+
+        registry.mark_pending(tx);
+        assert_eq!(registry.status(&tx), Some(TxStatus::Pending));
+
+        registry.mark_included(block, &[tx]);
+        assert_eq!(registry.status(&tx), Some(TxStatus::Included { block_id: block }));
+
+        registry.mark_round_finalized(7, &[block]);
+        assert_eq!(registry.status(&tx), Some(TxStatus::Finalized { round_number: 7, block_id: block }));
+        assert_eq!(registry.get(&tx).unwrap().history.len(), 3);
+    }
+
+    #[test]
+    fn test_finalized_status_is_sticky() {
+        let registry = TxStatusRegistry::default();
+        let tx = [2u8; 32];
+        let block = [8u8; 32];
+
+        registry.mark_included(block, &[tx]);
+        registry.mark_round_finalized(1, &[block]);
+        registry.mark_rejected(tx, "duplicate");
+        assert_eq!(registry.status(&tx), Some(TxStatus::Finalized { round_number: 1, block_id: block }));
+    }
+
+    #[test]
+    fn test_included_status_is_not_rejected() {
+        let registry = TxStatusRegistry::default();
+        let tx = [4u8; 32];
+        let block = [7u8; 32];
+
+        registry.mark_included(block, &[tx]);
+        registry.mark_rejected(tx, "evicted from full pool");
+        assert_eq!(registry.status(&tx), Some(TxStatus::Included { block_id: block }));
+    }
+
+    #[test]
+    fn test_cap_evicts_oldest_and_prunes_block_index() {
+        let registry = TxStatusRegistry::new(2);
+        let block = [6u8; 32];
+        registry.mark_included(block, &[[1u8; 32]]);
+        registry.mark_pending([2u8; 32]);
+        registry.mark_pending([3u8; 32]);
+
+        // The oldest record gives way even though it is not terminal, and its block goes with it
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.status(&[1u8; 32]), None);
+        assert!(registry.block_index.lock().unwrap().is_empty());
+        registry.mark_pending([4u8; 32]);
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.status(&[2u8; 32]), None);
+        assert_eq!(registry.status(&[4u8; 32]), Some(TxStatus::Pending));
+    }
+
+    #[test]
+    fn test_subscribers_receive_updates() {
+        let registry = TxStatusRegistry::default();
+        let mut rx = registry.subscribe();
+        registry.mark_pending([3u8; 32]);
+        let update = rx.try_recv().unwrap();
+        assert_eq!(update.tx_hash, [3u8; 32]);
+        assert_eq!(update.status, TxStatus::Pending);
+    }
+}
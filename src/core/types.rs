@@ -7,6 +7,13 @@ use serde::{Serialize, Deserialize};
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ShardId(pub u16); // Up to 65536 shards
 
+/// A public key and its signature, used where more than one party signs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartySignature {
+    pub public_key: VerifyingKey,
+    pub signature: Signature,
+}
+
 /// Represents a FinDAG transaction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
@@ -25,6 +32,14 @@ pub struct Transaction {
     // Cross-chain transaction support
     pub target_chain: Option<String>, // Target chain ID for cross-chain txs
     pub bridge_protocol: Option<String>, // Bridge protocol (e.g., IBC, custom)
+    // Validity window (FinDAG Time, inclusive bounds)
+    #[serde(default)]
+    pub valid_after: Option<u64>,  // Not includable before this time
+    #[serde(default)]
+    pub valid_until: Option<u64>,  // Expires after this time
+    // Approvals for transactions sent from a multisig account
+    #[serde(default)]
+    pub multisig_signatures: Vec<PartySignature>,
 }
 
 /// Serializable version of Transaction for network transmission
@@ -43,6 +58,12 @@ pub struct SerializableTransaction {
     pub dest_shard: Option<u16>,
     pub target_chain: Option<String>,
     pub bridge_protocol: Option<String>,
+    #[serde(default)]
+    pub valid_after: Option<u64>,
+    #[serde(default)]
+    pub valid_until: Option<u64>,
+    #[serde(default)]
+    pub multisig_signatures: Vec<PartySignature>,
 }
 
 impl From<Transaction> for SerializableTransaction {
@@ -61,6 +82,9 @@ impl From<Transaction> for SerializableTransaction {
             dest_shard: tx.dest_shard,
             target_chain: tx.target_chain,
             bridge_protocol: tx.bridge_protocol,
+            valid_after: tx.valid_after,
+            valid_until: tx.valid_until,
+            multisig_signatures: tx.multisig_signatures,
         }
     }
 }
@@ -86,6 +110,9 @@ impl TryFrom<SerializableTransaction> for Transaction {
             dest_shard: stx.dest_shard,
             target_chain: stx.target_chain,
             bridge_protocol: stx.bridge_protocol,
+            valid_after: stx.valid_after,
+            valid_until: stx.valid_until,
+            multisig_signatures: stx.multisig_signatures,
         })
     }
 }
@@ -250,13 +277,15 @@ pub const SUPPORTED_ASSETS: &[&str] = &[
 
 impl Block {
     /// Validates that the Merkle root matches the transactions in this block
+    /// Merkle leaves of the block: the hex hash of each transaction, in block order
+    pub fn merkle_leaves(&self) -> Vec<String> {
+        self.transactions.iter().map(|tx| hex::encode(tx.compute_hash())).collect()
+    }
+
     pub fn validate_merkle_root(&self) -> bool {
         use crate::core::bridge::merkle_root;
         if let Some(expected_root) = self.merkle_root {
-            let tx_hashes: Vec<String> = self.transactions.iter().map(|tx| {
-                hex::encode(tx.hashtimer)
-            }).collect();
-            let computed_hex = merkle_root(&tx_hashes);
+            let computed_hex = merkle_root(&self.merkle_leaves());
             let mut computed = [0u8; 32];
             if hex::decode_to_slice(&computed_hex, &mut computed as &mut [u8]).is_ok() {
                 computed == expected_root
@@ -268,9 +297,74 @@ impl Block {
             true
         }
     }
+
+    /// Validates that every transaction's validity window contains the block's FinDAG Time
+    pub fn validate_tx_validity_windows(&self) -> bool {
+        self.transactions.iter().all(|tx| tx.is_valid_at(self.findag_time))
+    }
+}
+
+/// Returns true if `findag_time` lies within the optional inclusive bounds
+pub fn within_validity_window(valid_after: Option<u64>, valid_until: Option<u64>, findag_time: u64) -> bool {
+    valid_after.is_none_or(|after| findag_time >= after)
+        && valid_until.is_none_or(|until| findag_time <= until)
+}
+
+/// Bytes appended to the signed transaction message when a validity window is set,
+/// so the bounds cannot be stripped or widened in transit
+pub fn validity_window_signing_bytes(valid_after: Option<u64>, valid_until: Option<u64>) -> Vec<u8> {
+    if valid_after.is_none() && valid_until.is_none() {
+        return Vec::new();
+    }
+    let mut bytes = Vec::with_capacity(16);
+    bytes.extend_from_slice(&valid_after.unwrap_or(0).to_be_bytes());
+    bytes.extend_from_slice(&valid_until.unwrap_or(u64::MAX).to_be_bytes());
+    bytes
 }
 
 impl Transaction {
+    /// Canonical transaction hash used for deduplication and status tracking
+    pub fn compute_hash(&self) -> [u8; 32] {
+        use sha2::{Sha256, Digest};
+        let mut hasher = Sha256::new();
+        hasher.update(self.from.as_str().as_bytes());
+        hasher.update(self.to.as_str().as_bytes());
+        hasher.update(self.amount.to_le_bytes());
+        hasher.update(&self.payload);
+        hasher.update(self.findag_time.to_le_bytes());
+        hasher.update(self.public_key.to_bytes());
+        hasher.update(self.shard_id.0.to_le_bytes());
+        hasher.update(self.valid_after.unwrap_or(0).to_le_bytes());
+        hasher.update(self.valid_until.unwrap_or(u64::MAX).to_le_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Message signed by the sender (and by each approver of a multisig account)
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend_from_slice(self.from.as_str().as_bytes());
+        message.extend_from_slice(self.to.as_str().as_bytes());
+        message.extend_from_slice(&self.amount.to_be_bytes());
+        message.extend_from_slice(&self.findag_time.to_be_bytes());
+        message.extend_from_slice(&self.hashtimer);
+        // Payload and shard are hashed into the tx id, so they must be signed too
+        message.extend_from_slice(&(self.payload.len() as u32).to_be_bytes());
+        message.extend_from_slice(&self.payload);
+        message.extend_from_slice(&self.shard_id.0.to_be_bytes());
+        message.extend_from_slice(&validity_window_signing_bytes(self.valid_after, self.valid_until));
+        message
+    }
+
+    /// True if the transaction may be included at the given FinDAG Time
+    pub fn is_valid_at(&self, findag_time: u64) -> bool {
+        within_validity_window(self.valid_after, self.valid_until, findag_time)
+    }
+
+    /// True if the validity window has closed at the given FinDAG Time
+    pub fn is_expired_at(&self, findag_time: u64) -> bool {
+        self.valid_until.is_some_and(|until| findag_time > until)
+    }
+
     #[allow(dead_code)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Transaction {{ from: {}, to: {}, amount: {}, hashtimer: {} }}", 
@@ -299,4 +393,43 @@ pub struct AssetHistory {
     pub from: Option<String>,
     pub to: Option<String>,
     pub action: String,        // "load", "transfer", "unload", "update"
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey, Verifier};
+
+    #[test]
+    fn test_signature_covers_payload_and_shard() {
+        let key = SigningKey::from_bytes(&[11u8; 32]);
+        let mut tx = Transaction {
+            from: Address::from_signing_key(&key),
+            to: Address::from_signing_key(&SigningKey::from_bytes(&[12u8; 32])),
+            amount: 100,
+            payload: b"invoice 42".to_vec(),
+            findag_time: 1,
+            hashtimer: [0u8; 32],
+            signature: key.sign(b""),
+            public_key: key.verifying_key(),
+            shard_id: ShardId(0),
+            source_shard: None,
+            dest_shard: None,
+            target_chain: None,
+            bridge_protocol: None,
+            valid_after: None,
+            valid_until: None,
+            multisig_signatures: Vec::new(),
+        };
+        tx.signature = key.sign(&tx.signing_message());
+        assert!(tx.public_key.verify(&tx.signing_message(), &tx.signature).is_ok());
+
+        let mut repayloaded = tx.clone();
+        repayloaded.payload = b"invoice 43".to_vec();
+        assert_ne!(repayloaded.compute_hash(), tx.compute_hash());
+        assert!(repayloaded.public_key.verify(&repayloaded.signing_message(), &repayloaded.signature).is_err());
+
+        let mut moved = tx.clone();
+        moved.shard_id = ShardId(1);
+        assert!(moved.public_key.verify(&moved.signing_message(), &moved.signature).is_err());
+    }
+}
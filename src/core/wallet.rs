@@ -67,12 +67,7 @@ impl WalletAccount {
 
     pub fn sign_transaction(&self, transaction: &mut Transaction) -> Result<(), String> {
         // Create message to sign
-        let mut message = Vec::new();
-        message.extend_from_slice(transaction.from.as_str().as_bytes());
-        message.extend_from_slice(transaction.to.as_str().as_bytes());
-        message.extend_from_slice(&transaction.amount.to_be_bytes());
-        message.extend_from_slice(&transaction.findag_time.to_be_bytes());
-        message.extend_from_slice(&transaction.hashtimer);
+        let message = transaction.signing_message();
         
         // Sign the message
         let signature = self.signing_key.sign(&message);
@@ -78,6 +78,11 @@ impl FinDAGTimeManager {
     }
 }
 
+/// Unix seconds of a FinDAG Time value (seconds in the high bits, 100ns slots in the low 24)
+pub fn findag_time_to_unix_secs(findag_time: u64) -> u64 {
+    findag_time >> 24
+}
+
 impl Default for FinDAGTimeManager {
     fn default() -> Self {
         Self::new()
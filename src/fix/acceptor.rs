@@ -0,0 +1,151 @@
+use super::message::{next_frame, tags, FixMessage};
+use super::session::{FixApplication, Session, SessionConfig};
+use super::store::MessageStore;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+/// Largest buffered partial message before the connection is dropped
+const MAX_FRAME_BYTES: usize = 1 << 20;
+
+/// TCP acceptor running one FIX session per connection
+#[derive(Clone)]
+pub struct FixAcceptor {
+    config: Arc<SessionConfig>,
+    store: Arc<MessageStore>,
+    application: Arc<dyn FixApplication>,
+    sessions: Arc<Mutex<HashMap<String, mpsc::UnboundedSender<FixMessage>>>>,
+}
+
+impl FixAcceptor {
+    pub fn new(config: SessionConfig, store: Arc<MessageStore>, application: Arc<dyn FixApplication>) -> Self {
+        FixAcceptor {
+            config: Arc::new(config),
+            store,
+            application,
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Accept connections until the listener fails
+    pub async fn run(self, listener: TcpListener) {
+        println!("[DEBUG] FIX: Acceptor listening on {:?}", listener.local_addr());
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    println!("[DEBUG] FIX: Connection from {}", peer);
+                    let acceptor = self.clone();
+                    tokio::spawn(async move { acceptor.handle_connection(stream).await });
+                }
+                Err(e) => {
+                    println!("[DEBUG] FIX: Accept failed: {}", e);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Queue an application message on a logged-on session
+    pub fn send(&self, session: &str, message: FixMessage) -> bool {
+        self.sessions.lock().unwrap()
+            .get(session)
+            .is_some_and(|tx| tx.send(message).is_ok())
+    }
+
+    pub fn logged_on_sessions(&self) -> Vec<String> {
+        self.sessions.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Whether a Logon names a session that is already connected
+    fn is_duplicate_logon(&self, frame: &[u8]) -> bool {
+        let Ok((begin_string, message)) = FixMessage::decode(frame) else {
+            return false;
+        };
+        let id = format!(
+            "{}:{}:{}",
+            begin_string,
+            self.config.sender_comp_id,
+            message.get(tags::SENDER_COMP_ID).unwrap_or_default()
+        );
+        let duplicate = message.msg_type == "A" && self.sessions.lock().unwrap().contains_key(&id);
+        if duplicate {
+            println!("[DEBUG] FIX: Duplicate logon for {}", id);
+        }
+        duplicate
+    }
+
+    async fn handle_connection(&self, mut stream: TcpStream) {
+        let mut session = Session::new(self.config.clone(), self.store.clone(), Instant::now());
+        let (outbound_tx, mut outbound_rx) = mpsc::unbounded_channel();
+        let mut registered: Option<String> = None;
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let mut ticker = tokio::time::interval(Duration::from_secs(1));
+
+        'connection: loop {
+            let output = tokio::select! {
+                read = stream.read(&mut chunk) => match read {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => {
+                        buffer.extend_from_slice(&chunk[..n]);
+                        let mut output = Vec::new();
+                        loop {
+                            match next_frame(&mut buffer) {
+                                Ok(Some(frame)) => {
+                                    if !session.is_logged_on() && self.is_duplicate_logon(&frame) {
+                                        // The counterparty is already connected on this session
+                                        break 'connection;
+                                    }
+                                    output.extend(session.on_raw(&frame, self.application.as_ref(), Instant::now()));
+                                    if registered.is_none() && session.is_logged_on() {
+                                        let id = session.id().unwrap_or_default().to_string();
+                                        self.sessions.lock().unwrap().insert(id.clone(), outbound_tx.clone());
+                                        registered = Some(id);
+                                    }
+                                    if session.should_disconnect() {
+                                        break;
+                                    }
+                                }
+                                Ok(None) => break,
+                                Err(e) => {
+                                    println!("[DEBUG] FIX: Framing error: {}", e);
+                                    output.extend(session.logout(&e.to_string(), Instant::now()));
+                                    break;
+                                }
+                            }
+                        }
+                        if buffer.len() > MAX_FRAME_BYTES {
+                            output.extend(session.logout("Message too large", Instant::now()));
+                        }
+                        output
+                    }
+                },
+                Some(message) = outbound_rx.recv() => {
+                    if session.is_logged_on() {
+                        vec![session.send(message, Instant::now())]
+                    } else {
+                        Vec::new()
+                    }
+                }
+                _ = ticker.tick() => session.on_timer(Instant::now()),
+            };
+            for raw in output {
+                if stream.write_all(raw.as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+            if session.should_disconnect() {
+                break;
+            }
+        }
+
+        if let Some(id) = registered {
+            self.sessions.lock().unwrap().remove(&id);
+            println!("[DEBUG] FIX: Session {} disconnected", id);
+        }
+        let _ = stream.shutdown().await;
+    }
+}
@@ -0,0 +1,104 @@
+use super::acceptor::FixAcceptor;
+use super::message::FixMessage;
+use super::orders::{OrderManager, Outbound, Trade};
+use super::schemas::msg_types;
+use super::session::{FixApplication, RejectApplication};
+use crate::core::idempotency::IdempotencyStore;
+use crate::core::tx_status::TxStatusRegistry;
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::{broadcast, mpsc};
+
+/// FIX application handling the order lifecycle. Matched trades are handed out for
+/// settlement on FinDAG; execution reports follow the settlement transactions' status.
+pub struct FixOrderGateway {
+    orders: Mutex<OrderManager>,
+    acceptor: OnceLock<FixAcceptor>,
+    trades: mpsc::UnboundedSender<Trade>,
+}
+
+impl FixOrderGateway {
+    /// Returns the gateway and the stream of trades awaiting a settlement transaction
+    pub fn new(id_prefix: &str) -> (Arc<Self>, mpsc::UnboundedReceiver<Trade>) {
+        let (trades, receiver) = mpsc::unbounded_channel();
+        let gateway = FixOrderGateway {
+            orders: Mutex::new(OrderManager::new(id_prefix)),
+            acceptor: OnceLock::new(),
+            trades,
+        };
+        (Arc::new(gateway), receiver)
+    }
+
+    /// Acceptor used to reach sessions other than the one a message arrived on
+    pub fn attach(&self, acceptor: FixAcceptor) {
+        let _ = self.acceptor.set(acceptor);
+    }
+
+    /// Refuse ClOrdIDs already claimed in `store`, including before a restart
+    pub fn use_idempotency(&self, store: Arc<IdempotencyStore>) {
+        self.orders.lock().unwrap().set_idempotency(store);
+    }
+
+    pub fn settlement_submitted(&self, match_id: &str, tx_hash: [u8; 32]) {
+        self.orders.lock().unwrap().settlement_submitted(match_id, tx_hash);
+    }
+
+    pub fn settlement_failed(&self, match_id: &str, reason: &str) {
+        let reports = self.orders.lock().unwrap().settlement_failed(match_id, reason);
+        self.dispatch(reports);
+    }
+
+    /// Follow settlement transactions until the registry's channel closes
+    pub fn spawn_status_listener(self: &Arc<Self>, registry: Arc<TxStatusRegistry>) {
+        let gateway = self.clone();
+        let mut updates = registry.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(update) => {
+                        let reports = gateway.orders.lock().unwrap().on_tx_status(&update.tx_hash, &update.status);
+                        gateway.dispatch(reports);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        println!("[DEBUG] FIX: Status listener skipped {} updates", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        });
+    }
+
+    fn dispatch(&self, reports: Outbound) {
+        for (session, message) in reports {
+            let sent = self.acceptor.get().is_some_and(|acceptor| acceptor.send(&session, message));
+            if !sent {
+                println!("[DEBUG] FIX: Session {} is not logged on, report dropped", session);
+            }
+        }
+    }
+
+    fn queue_trades(&self, trades: Vec<Trade>) {
+        for trade in trades {
+            let _ = self.trades.send(trade);
+        }
+    }
+}
+
+impl FixApplication for FixOrderGateway {
+    fn on_message(&self, session: &str, message: &FixMessage) -> Vec<FixMessage> {
+        let (reports, trades) = {
+            let mut orders = self.orders.lock().unwrap();
+            match message.msg_type.as_str() {
+                msg_types::ORDER_SINGLE => orders.new_order(session, message),
+                msg_types::ORDER_CANCEL_REPLACE_REQUEST => orders.replace(session, message),
+                msg_types::ORDER_CANCEL_REQUEST => (orders.cancel(session, message), Vec::new()),
+                msg_types::ORDER_STATUS_REQUEST => (orders.status(session, message), Vec::new()),
+                _ => return RejectApplication.on_message(session, message),
+            }
+        };
+        self.queue_trades(trades);
+        // Replies to this session go back in order; counterparties are reached through the acceptor
+        let (own, others): (Outbound, Outbound) = reports.into_iter().partition(|(s, _)| s == session);
+        self.dispatch(others);
+        own.into_iter().map(|(_, message)| message).collect()
+    }
+}
@@ -0,0 +1,234 @@
+use anyhow::{anyhow, Result};
+
+pub const SOH: u8 = 0x01;
+pub const FIX_4_4: &str = "FIX.4.4";
+
+/// Tags used by the session engine and the order gateway
+pub mod tags {
+    pub const ACCOUNT: u32 = 1;
+    pub const AVG_PX: u32 = 6;
+    pub const BEGIN_SEQ_NO: u32 = 7;
+    pub const BEGIN_STRING: u32 = 8;
+    pub const BODY_LENGTH: u32 = 9;
+    pub const CHECKSUM: u32 = 10;
+    pub const CL_ORD_ID: u32 = 11;
+    pub const CUM_QTY: u32 = 14;
+    pub const CURRENCY: u32 = 15;
+    pub const END_SEQ_NO: u32 = 16;
+    pub const EXEC_ID: u32 = 17;
+    pub const LAST_PX: u32 = 31;
+    pub const LAST_QTY: u32 = 32;
+    pub const MSG_SEQ_NUM: u32 = 34;
+    pub const MSG_TYPE: u32 = 35;
+    pub const NEW_SEQ_NO: u32 = 36;
+    pub const ORDER_ID: u32 = 37;
+    pub const ORDER_QTY: u32 = 38;
+    pub const ORD_STATUS: u32 = 39;
+    pub const ORD_TYPE: u32 = 40;
+    pub const ORIG_CL_ORD_ID: u32 = 41;
+    pub const POSS_DUP_FLAG: u32 = 43;
+    pub const PRICE: u32 = 44;
+    pub const REF_SEQ_NUM: u32 = 45;
+    pub const SENDER_COMP_ID: u32 = 49;
+    pub const SENDING_TIME: u32 = 52;
+    pub const SIDE: u32 = 54;
+    pub const SYMBOL: u32 = 55;
+    pub const TARGET_COMP_ID: u32 = 56;
+    pub const TEXT: u32 = 58;
+    pub const ENCRYPT_METHOD: u32 = 98;
+    pub const CXL_REJ_REASON: u32 = 102;
+    pub const ORD_REJ_REASON: u32 = 103;
+    pub const HEART_BT_INT: u32 = 108;
+    pub const TEST_REQ_ID: u32 = 112;
+    pub const ORIG_SENDING_TIME: u32 = 122;
+    pub const GAP_FILL_FLAG: u32 = 123;
+    pub const RESET_SEQ_NUM_FLAG: u32 = 141;
+    pub const EXEC_TYPE: u32 = 150;
+    pub const LEAVES_QTY: u32 = 151;
+    pub const REF_TAG_ID: u32 = 371;
+    pub const REF_MSG_TYPE: u32 = 372;
+    pub const SESSION_REJECT_REASON: u32 = 373;
+    pub const BUSINESS_REJECT_REASON: u32 = 380;
+    pub const CXL_REJ_RESPONSE_TO: u32 = 434;
+    pub const TRD_MATCH_ID: u32 = 880;
+}
+
+/// Standard header fields the encoder writes itself
+const HEADER_TAGS: [u32; 3] = [tags::BEGIN_STRING, tags::BODY_LENGTH, tags::MSG_TYPE];
+
+/// A FIX message as ordered tag/value pairs, without BeginString, BodyLength and CheckSum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixMessage {
+    pub msg_type: String,
+    pub fields: Vec<(u32, String)>,
+}
+
+impl FixMessage {
+    pub fn new(msg_type: &str) -> Self {
+        FixMessage { msg_type: msg_type.to_string(), fields: Vec::new() }
+    }
+
+    pub fn with(mut self, tag: u32, value: impl ToString) -> Self {
+        self.set(tag, value);
+        self
+    }
+
+    /// Set a field, replacing its first occurrence
+    pub fn set(&mut self, tag: u32, value: impl ToString) {
+        let value = value.to_string();
+        match self.fields.iter_mut().find(|(t, _)| *t == tag) {
+            Some(field) => field.1 = value,
+            None => self.fields.push((tag, value)),
+        }
+    }
+
+    pub fn remove(&mut self, tag: u32) {
+        self.fields.retain(|(t, _)| *t != tag);
+    }
+
+    pub fn get(&self, tag: u32) -> Option<&str> {
+        self.fields.iter().find(|(t, _)| *t == tag).map(|(_, v)| v.as_str())
+    }
+
+    pub fn get_u64(&self, tag: u32) -> Option<u64> {
+        self.get(tag).and_then(|v| v.parse().ok())
+    }
+
+    pub fn seq_num(&self) -> Option<u64> {
+        self.get_u64(tags::MSG_SEQ_NUM)
+    }
+
+    pub fn flag(&self, tag: u32) -> bool {
+        self.get(tag) == Some("Y")
+    }
+
+    /// Session-level (admin) message types
+    pub fn is_admin(&self) -> bool {
+        matches!(self.msg_type.as_str(), "0" | "1" | "2" | "3" | "4" | "5" | "A")
+    }
+
+    /// Encode with BodyLength and CheckSum computed over the SOH-delimited bytes
+    pub fn encode(&self, begin_string: &str) -> String {
+        let mut body = format!("35={}\x01", self.msg_type);
+        for (tag, value) in &self.fields {
+            body.push_str(&format!("{}={}\x01", tag, value));
+        }
+        let mut message = format!("8={}\x019={}\x01{}", begin_string, body.len(), body);
+        let checksum = checksum(message.as_bytes());
+        message.push_str(&format!("10={:03}\x01", checksum));
+        message
+    }
+
+    /// Decode one complete message, validating BeginString, BodyLength and CheckSum
+    pub fn decode(raw: &[u8]) -> Result<(String, FixMessage)> {
+        let text = std::str::from_utf8(raw).map_err(|_| anyhow!("FIX message is not valid text"))?;
+        let trailer_at = text.rfind("\x0110=").ok_or_else(|| anyhow!("Missing CheckSum(10)"))? + 1;
+        let declared = text[trailer_at + 3..]
+            .strip_suffix('\x01')
+            .filter(|v| v.len() == 3)
+            .and_then(|v| v.parse::<u8>().ok())
+            .ok_or_else(|| anyhow!("Invalid CheckSum(10) field"))?;
+        let actual = checksum(&raw[..trailer_at]);
+        if declared != actual {
+            return Err(anyhow!("CheckSum mismatch: declared {:03}, computed {:03}", declared, actual));
+        }
+
+        let mut pairs = text[..trailer_at].split_terminator('\x01').map(|pair| {
+            let (tag, value) = pair.split_once('=').ok_or_else(|| anyhow!("Malformed field '{}'", pair))?;
+            let tag = tag.parse::<u32>().map_err(|_| anyhow!("Malformed tag '{}'", tag))?;
+            Ok::<_, anyhow::Error>((tag, value))
+        });
+        let begin_string = match pairs.next().transpose()? {
+            Some((tags::BEGIN_STRING, v)) => v.to_string(),
+            _ => return Err(anyhow!("BeginString(8) must be the first field")),
+        };
+        let (body_length, body_start) = match pairs.next().transpose()? {
+            Some((tags::BODY_LENGTH, v)) => (
+                v.parse::<usize>().map_err(|_| anyhow!("Invalid BodyLength(9) '{}'", v))?,
+                // "8=" BeginString SOH "9=" BodyLength SOH
+                begin_string.len() + v.len() + 6,
+            ),
+            _ => return Err(anyhow!("BodyLength(9) must be the second field")),
+        };
+        if trailer_at.checked_sub(body_start) != Some(body_length) {
+            return Err(anyhow!("BodyLength mismatch: declared {}", body_length));
+        }
+        let msg_type = match pairs.next().transpose()? {
+            Some((tags::MSG_TYPE, v)) => v.to_string(),
+            _ => return Err(anyhow!("MsgType(35) must be the third field")),
+        };
+        let mut message = FixMessage::new(&msg_type);
+        for pair in pairs {
+            let (tag, value) = pair?;
+            if HEADER_TAGS.contains(&tag) {
+                return Err(anyhow!("Tag {} appears out of order", tag));
+            }
+            message.fields.push((tag, value.to_string()));
+        }
+        Ok((begin_string, message))
+    }
+}
+
+/// Sum of all bytes modulo 256
+pub fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b))
+}
+
+/// Split the next complete message off the front of a stream buffer using BodyLength.
+/// Returns None until enough bytes have arrived.
+pub fn next_frame(buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>> {
+    if buffer.is_empty() {
+        return Ok(None);
+    }
+    if !buffer.starts_with(b"8=") && !b"8=".starts_with(buffer.as_slice()) {
+        return Err(anyhow!("Stream is not positioned at BeginString(8)"));
+    }
+    let Some(first_soh) = buffer.iter().position(|b| *b == SOH) else {
+        return Ok(None);
+    };
+    let rest = &buffer[first_soh + 1..];
+    let Some(second_soh) = rest.iter().position(|b| *b == SOH) else {
+        return Ok(None);
+    };
+    let length_field = std::str::from_utf8(&rest[..second_soh]).unwrap_or_default();
+    let body_length = length_field
+        .strip_prefix("9=")
+        .and_then(|v| v.parse::<usize>().ok())
+        .ok_or_else(|| anyhow!("BodyLength(9) must follow BeginString(8)"))?;
+    // Header, body, then "10=NNN<SOH>"
+    let total = first_soh + 1 + second_soh + 1 + body_length + 7;
+    if buffer.len() < total {
+        return Ok(None);
+    }
+    Ok(Some(buffer.drain(..total).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_and_framing() {
+        let message = FixMessage::new("0")
+            .with(tags::SENDER_COMP_ID, "FINDAG")
+            .with(tags::TARGET_COMP_ID, "CLIENT1")
+            .with(tags::MSG_SEQ_NUM, 2);
+        let encoded = message.encode(FIX_4_4);
+        assert!(encoded.starts_with("8=FIX.4.4\x019=31\x0135=0\x01"));
+        let (begin_string, decoded) = FixMessage::decode(encoded.as_bytes()).unwrap();
+        assert_eq!(begin_string, FIX_4_4);
+        assert_eq!(decoded, message);
+
+        let corrupted = encoded.replace("CLIENT1", "CLIENT2");
+        assert!(FixMessage::decode(corrupted.as_bytes()).unwrap_err().to_string().contains("CheckSum"));
+        let wrong_length = encoded.replace("9=31", "9=30");
+        assert!(FixMessage::decode(wrong_length.as_bytes()).is_err());
+
+        // Two messages and a partial third arrive in one read
+        let mut buffer = format!("{encoded}{encoded}{}", &encoded[..10]).into_bytes();
+        assert_eq!(next_frame(&mut buffer).unwrap().unwrap(), encoded.as_bytes());
+        assert_eq!(next_frame(&mut buffer).unwrap().unwrap(), encoded.as_bytes());
+        assert!(next_frame(&mut buffer).unwrap().is_none());
+        assert!(next_frame(&mut b"garbage".to_vec()).is_err());
+    }
+}
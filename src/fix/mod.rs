@@ -1,12 +1,19 @@
 // src/fix/mod.rs
 
+pub mod acceptor;
+pub mod gateway;
+pub mod message;
+pub mod orders;
 pub mod schemas;
+pub mod session;
+pub mod store;
 
 use anyhow::{Result, anyhow};
 use crate::core::types::Transaction;
 use crate::core::address::Address;
+use crate::core::ingestion::{Adapter, GatewaySigner, IdentifierKind, IngestionRegistry};
 use sha2::{Sha256, Digest};
-use ed25519_dalek::{Signature, VerifyingKey};
+use ed25519_dalek::Signature;
 
 /// Minimal representation of a FIX Order Single (MsgType = D)
 #[derive(Debug, Clone)]
@@ -71,8 +78,9 @@ pub fn parse_order_single(raw: &str) -> Result<FixOrderSingle> {
 }
 
 /// Convert FIX Order Single → FinDAG Transaction
-pub fn fix_order_to_findag_tx(fix: &FixOrderSingle) -> Transaction {
-    let from = Address::new(fix.account.clone());
+pub fn fix_order_to_findag_tx(fix: &FixOrderSingle, registry: &IngestionRegistry, signer: &GatewaySigner) -> Result<Transaction> {
+    check_fix_signer(signer)?;
+    let from = registry.debit_account(Adapter::Fix, &[(IdentifierKind::FixAccount, &fix.account)]).map_err(|e| anyhow!(e))?;
     let to = Address::new(format!("FX::{}", fix.symbol)); // symbolic — adjust for your settlement logic
     let amount = fix.order_qty;
     
@@ -93,59 +101,87 @@ pub fn fix_order_to_findag_tx(fix: &FixOrderSingle) -> Transaction {
     let mut hashtimer_array = [0u8; 32];
     hashtimer_array.copy_from_slice(&hashtimer);
 
-    // Create a dummy signature and public key for now
-    // In a real implementation, these would be properly signed by the FIX client
-    let dummy_signature = Signature::from_bytes(&[0u8; 64]);
-    let dummy_public_key = VerifyingKey::from_bytes(&[0u8; 32]).unwrap();
-
-    Transaction {
+    let mut tx = Transaction {
         from,
         to,
         amount,
         payload,
         findag_time: 0, // Will be set by the system
         hashtimer: hashtimer_array,
-        signature: dummy_signature,
-        public_key: dummy_public_key,
+        signature: Signature::from_bytes(&[0u8; 64]), // Set with bridge_protocol by the gateway signer
+        public_key: signer.public_key(),
         shard_id: crate::core::types::ShardId(0),
         source_shard: None,
         dest_shard: None,
         target_chain: None,
-        bridge_protocol: Some("FIX".to_string()),
-    }
+        bridge_protocol: None,
+        valid_after: None,
+        valid_until: None,
+        multisig_signatures: Vec::new(),
+    };
+    signer.sign(&mut tx);
+    Ok(tx)
 }
 
-/// Validate FIX message checksum (simple implementation)
-pub fn validate_fix_checksum(raw: &str) -> Result<bool> {
-    let parts: Vec<&str> = raw.split('\x01').collect();
-    if parts.is_empty() {
-        return Err(anyhow!("Empty FIX message"));
-    }
+/// Convert a matched FIX trade → FinDAG settlement Transaction (seller delivers to buyer)
+pub fn fix_trade_to_findag_tx(trade: &orders::Trade, registry: &IngestionRegistry, signer: &GatewaySigner) -> Result<Transaction> {
+    check_fix_signer(signer)?;
+    let from = registry.debit_account(Adapter::Fix, &[(IdentifierKind::FixAccount, &trade.seller_account)]).map_err(|e| anyhow!(e))?;
+    let to = registry.credit_account(&[(IdentifierKind::FixAccount, &trade.buyer_account)]).map_err(|e| anyhow!(e))?;
+    let payload = format!(
+        "fix_trade:{}:{}:{}:{}:{}:{}",
+        trade.match_id,
+        trade.symbol,
+        trade.buy_order,
+        trade.sell_order,
+        trade.qty,
+        orders::format_px(trade.price)
+    ).into_bytes();
 
-    // Find the checksum field (10=xxx)
-    let mut calculated_checksum = 0u8;
-    let mut found_checksum = false;
-    let mut checksum_value = 0u8;
-
-    for part in &parts {
-        if part.starts_with("10=") {
-            found_checksum = true;
-            let checksum_str = &part[3..];
-            checksum_value = checksum_str.parse().unwrap_or(0);
-            break;
-        }
-    }
+    let mut hasher = Sha256::new();
+    hasher.update(trade.match_id.as_bytes());
+    let hashtimer: [u8; 32] = hasher.finalize().into();
 
-    // Calculate checksum (sum of all ASCII values mod 256)
-    for part in &parts {
-        if !part.starts_with("10=") { // Exclude the checksum field itself
-            for byte in part.as_bytes() {
-                calculated_checksum = calculated_checksum.wrapping_add(*byte);
-            }
-        }
+    let mut tx = Transaction {
+        from,
+        to,
+        amount: trade.qty,
+        payload,
+        findag_time: 0,
+        hashtimer,
+        signature: Signature::from_bytes(&[0u8; 64]),
+        public_key: signer.public_key(),
+        shard_id: crate::core::types::ShardId(0),
+        source_shard: None,
+        dest_shard: None,
+        target_chain: None,
+        bridge_protocol: None,
+        valid_after: None,
+        valid_until: None,
+        multisig_signatures: Vec::new(),
+    };
+    signer.sign(&mut tx);
+    Ok(tx)
+}
+
+fn check_fix_signer(signer: &GatewaySigner) -> Result<()> {
+    if signer.adapter != Adapter::Fix {
+        return Err(anyhow!("{} gateway key cannot sign FIX conversions", signer.adapter.protocol()));
     }
+    Ok(())
+}
 
-    Ok(found_checksum && calculated_checksum == checksum_value)
+/// Validate the FIX CheckSum(10): the sum of every byte before the trailer,
+/// including the SOH delimiters, modulo 256
+pub fn validate_fix_checksum(raw: &str) -> Result<bool> {
+    if raw.is_empty() {
+        return Err(anyhow!("Empty FIX message"));
+    }
+    let Some(trailer_at) = raw.rfind("\x0110=").map(|i| i + 1).or_else(|| raw.starts_with("10=").then_some(0)) else {
+        return Ok(false);
+    };
+    let declared = raw[trailer_at + 3..].trim_end_matches('\x01').parse::<u8>();
+    Ok(declared == Ok(message::checksum(&raw.as_bytes()[..trailer_at])))
 }
 
 #[cfg(test)]
@@ -167,13 +203,16 @@ mod tests {
 
     #[test]
     fn test_fix_to_findag_tx() {
+        let (registry, key) = crate::core::ingestion::test_registry();
         let order = parse_order_single(EXAMPLE_FIX_ORDER).unwrap();
-        let tx = fix_order_to_findag_tx(&order);
+        let tx = fix_order_to_findag_tx(&order, &registry, &GatewaySigner::new(Adapter::Fix, key.clone())).unwrap();
 
-        assert_eq!(tx.from.as_str(), "ALICE_ACCOUNT");
+        assert_eq!(tx.from, registry.debit_account(Adapter::Fix, &[(IdentifierKind::FixAccount, "ALICE_ACCOUNT")]).unwrap());
         assert_eq!(tx.to.as_str(), "FX::EUR/USD");
         assert_eq!(tx.amount, 1_000_000);
         assert_eq!(tx.bridge_protocol, Some("FIX".to_string()));
+        assert_eq!(registry.verify(&tx), Some(Ok(())));
+        assert!(fix_order_to_findag_tx(&order, &registry, &GatewaySigner::new(Adapter::Swift, key)).is_err());
         
         // Check payload contains order details
         let payload_str = String::from_utf8_lossy(&tx.payload);
@@ -197,4 +236,12 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Missing required FIX fields"));
     }
+
+    #[test]
+    fn test_validate_fix_checksum_counts_soh() {
+        let raw = message::FixMessage::new("0").with(message::tags::MSG_SEQ_NUM, 1).encode(message::FIX_4_4);
+        assert!(validate_fix_checksum(&raw).unwrap());
+        assert!(!validate_fix_checksum(&raw.replace("34=1", "34=2")).unwrap());
+        assert!(!validate_fix_checksum("8=FIX.4.4\x0135=0\x01").unwrap());
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,681 @@
+use super::message::{tags, FixMessage};
+use super::schemas::{exec_types, msg_types, order_status};
+use crate::core::idempotency::{Claim, IdempotencyStore};
+use crate::core::ingestion::Adapter;
+use crate::core::tx_status::TxStatus;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Messages addressed to FIX sessions, as (session id, message)
+pub type Outbound = Vec<(String, FixMessage)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "1" => Some(Side::Buy),
+            "2" => Some(Side::Sell),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Side::Buy => "1",
+            Side::Sell => "2",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrdStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    Rejected,
+}
+
+impl OrdStatus {
+    pub fn code(&self) -> &'static str {
+        match self {
+            OrdStatus::New => order_status::NEW,
+            OrdStatus::PartiallyFilled => order_status::PARTIALLY_FILLED,
+            OrdStatus::Filled => order_status::FILLED,
+            OrdStatus::Canceled => order_status::CANCELED,
+            OrdStatus::Rejected => order_status::REJECTED,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, OrdStatus::Filled | OrdStatus::Canceled | OrdStatus::Rejected)
+    }
+}
+
+/// A FIX order and its execution state. Matched quantity stays pending until
+/// the trade settles on FinDAG; only then is it counted in `cum_qty`.
+#[derive(Debug, Clone)]
+pub struct Order {
+    pub order_id: String,
+    pub session: String,
+    pub cl_ord_id: String,
+    pub orig_cl_ord_id: Option<String>,
+    pub account: String,
+    pub symbol: String,
+    pub currency: String,
+    pub side: Side,
+    pub order_qty: u64,
+    pub price: Option<f64>, // None: market order
+    pub cum_qty: u64,
+    pub avg_px: f64,
+    pub pending_qty: u64,
+    pub canceled: bool,
+    priority: u64,
+}
+
+impl Order {
+    pub fn status(&self) -> OrdStatus {
+        if self.cum_qty == self.order_qty {
+            OrdStatus::Filled
+        } else if self.canceled && self.pending_qty == 0 {
+            OrdStatus::Canceled
+        } else if self.cum_qty > 0 {
+            OrdStatus::PartiallyFilled
+        } else {
+            OrdStatus::New
+        }
+    }
+
+    /// Quantity still available for matching
+    pub fn open_qty(&self) -> u64 {
+        if self.canceled {
+            0
+        } else {
+            self.order_qty - self.cum_qty - self.pending_qty
+        }
+    }
+
+    pub fn leaves_qty(&self) -> u64 {
+        match self.status() {
+            status if status.is_terminal() => 0,
+            _ if self.canceled => self.pending_qty,
+            _ => self.order_qty - self.cum_qty,
+        }
+    }
+
+    fn crosses(&self, resting: &Order) -> bool {
+        match (self.price, resting.price) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(limit), Some(price)) => match self.side {
+                Side::Buy => price <= limit,
+                Side::Sell => price >= limit,
+            },
+        }
+    }
+}
+
+/// A match between a buy and a sell order, settled on FinDAG as one transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trade {
+    pub match_id: String,
+    pub symbol: String,
+    pub currency: String,
+    pub buy_order: String,
+    pub sell_order: String,
+    pub buyer_account: String,
+    pub seller_account: String,
+    pub qty: u64,
+    pub price: f64,
+}
+
+/// Fields of a NewOrderSingle or OrderCancelReplaceRequest
+struct OrderFields {
+    account: String,
+    symbol: String,
+    currency: String,
+    side: Side,
+    order_qty: u64,
+    price: Option<f64>,
+}
+
+impl OrderFields {
+    fn parse(message: &FixMessage) -> Result<Self, String> {
+        let required = |tag: u32, name: &str| {
+            message.get(tag)
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| format!("Missing {}({})", name, tag))
+        };
+        let side = Side::parse(required(tags::SIDE, "Side")?).ok_or("Unsupported Side(54)")?;
+        let order_qty = required(tags::ORDER_QTY, "OrderQty")?.parse::<u64>()
+            .ok()
+            .filter(|q| *q > 0)
+            .ok_or("OrderQty(38) must be a positive integer")?;
+        let price = message.get(tags::PRICE)
+            .map(|p| p.parse::<f64>().ok().filter(|p| p.is_finite() && *p > 0.0).ok_or("Invalid Price(44)"))
+            .transpose()?;
+        match (message.get(tags::ORD_TYPE), price) {
+            (Some("1"), Some(_)) => return Err("Market orders must not carry a Price(44)".to_string()),
+            (Some("2"), None) => return Err("Limit orders require a Price(44)".to_string()),
+            (Some("1") | Some("2") | None, _) => {}
+            (Some(other), _) => return Err(format!("Unsupported OrdType(40) {}", other)),
+        }
+        Ok(OrderFields {
+            account: required(tags::ACCOUNT, "Account")?.to_string(),
+            symbol: required(tags::SYMBOL, "Symbol")?.to_string(),
+            currency: message.get(tags::CURRENCY).unwrap_or_default().to_string(),
+            side,
+            order_qty,
+            price,
+        })
+    }
+}
+
+/// Order state of the FIX gateway: crosses orders per symbol by price-time
+/// priority and reports executions as their settlement transactions finalize
+pub struct OrderManager {
+    orders: HashMap<String, Order>,
+    cl_ord_ids: HashMap<(String, String), String>, // (session, ClOrdID) -> OrderID
+    trades: HashMap<String, Trade>,                // match id -> trade awaiting settlement
+    settlements: HashMap<[u8; 32], String>,        // settlement tx hash -> match id
+    id_prefix: String,
+    next_id: u64,
+    idempotency: Option<Arc<IdempotencyStore>>, // ClOrdIDs seen before a restart
+}
+
+impl OrderManager {
+    /// `id_prefix` keeps OrderIDs and ExecIDs unique across restarts
+    pub fn new(id_prefix: &str) -> Self {
+        OrderManager {
+            orders: HashMap::new(),
+            cl_ord_ids: HashMap::new(),
+            trades: HashMap::new(),
+            settlements: HashMap::new(),
+            id_prefix: id_prefix.to_string(),
+            next_id: 1,
+            idempotency: None,
+        }
+    }
+
+    /// Persist ClOrdIDs so a resent order is refused across restarts too
+    pub fn set_idempotency(&mut self, store: Arc<IdempotencyStore>) {
+        self.idempotency = Some(store);
+    }
+
+    /// Claim a new ClOrdID for the session's counterparty. Returns the reject text for a replay.
+    fn claim_cl_ord_id(&self, session: &str, cl_ord_id: &str) -> Option<String> {
+        let store = self.idempotency.as_ref()?;
+        let sender = session.rsplit(':').next().unwrap_or(session);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        match store.claim(sender, Adapter::Fix, cl_ord_id, now) {
+            Ok(Claim::New) => None,
+            Ok(Claim::Replay(record)) => Some(format!("Duplicate ClOrdID(11), first received at {}", record.first_seen)),
+            Err(e) => Some(e),
+        }
+    }
+
+    pub fn order(&self, order_id: &str) -> Option<&Order> {
+        self.orders.get(order_id)
+    }
+
+    pub fn trade(&self, match_id: &str) -> Option<&Trade> {
+        self.trades.get(match_id)
+    }
+
+    fn next_id(&mut self, kind: &str) -> String {
+        let id = format!("{}-{}-{}", self.id_prefix, kind, self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    /// NewOrderSingle (35=D): acknowledge and match. Returns the reports and the
+    /// trades that now need a settlement transaction.
+    pub fn new_order(&mut self, session: &str, message: &FixMessage) -> (Outbound, Vec<Trade>) {
+        let cl_ord_id = message.get(tags::CL_ORD_ID).unwrap_or_default().to_string();
+        let fields = match OrderFields::parse(message) {
+            Ok(_) if cl_ord_id.is_empty() => Err("Missing ClOrdID(11)".to_string()),
+            other => other,
+        };
+        let fields = match fields {
+            Ok(fields) => fields,
+            Err(e) => return (vec![self.order_reject(session, message, 0, &e)], Vec::new()),
+        };
+        if self.cl_ord_ids.contains_key(&(session.to_string(), cl_ord_id.clone())) {
+            return (vec![self.order_reject(session, message, 6, "Duplicate ClOrdID(11)")], Vec::new());
+        }
+        if let Some(reason) = self.claim_cl_ord_id(session, &cl_ord_id) {
+            return (vec![self.order_reject(session, message, 6, &reason)], Vec::new());
+        }
+
+        let order_id = self.next_id("ORD");
+        let priority = self.next_id;
+        self.cl_ord_ids.insert((session.to_string(), cl_ord_id.clone()), order_id.clone());
+        self.orders.insert(order_id.clone(), Order {
+            order_id: order_id.clone(),
+            session: session.to_string(),
+            cl_ord_id,
+            orig_cl_ord_id: None,
+            account: fields.account,
+            symbol: fields.symbol,
+            currency: fields.currency,
+            side: fields.side,
+            order_qty: fields.order_qty,
+            price: fields.price,
+            cum_qty: 0,
+            avg_px: 0.0,
+            pending_qty: 0,
+            canceled: false,
+            priority,
+        });
+        println!("[DEBUG] FIX: Accepted order {} from {}", order_id, session);
+
+        let mut reports = vec![self.execution_report(&order_id, exec_types::NEW, None, None)];
+        let trades = self.match_order(&order_id);
+        // Market orders never rest; whatever did not match (or fails to settle) is canceled
+        if self.orders[&order_id].price.is_none() {
+            self.orders.get_mut(&order_id).unwrap().canceled = true;
+            if trades.is_empty() {
+                reports.push(self.execution_report(&order_id, exec_types::CANCELED, Some("No matching liquidity"), None));
+            }
+        }
+        (reports, trades)
+    }
+
+    /// OrderCancelRequest (35=F)
+    pub fn cancel(&mut self, session: &str, message: &FixMessage) -> Outbound {
+        let order_id = match self.amendable(session, message, "1") {
+            Ok(order_id) => order_id,
+            Err(reject) => return vec![reject],
+        };
+        self.rename(&order_id, message);
+        self.orders.get_mut(&order_id).unwrap().canceled = true;
+        println!("[DEBUG] FIX: Canceled order {}", order_id);
+        vec![self.execution_report(&order_id, exec_types::CANCELED, None, None)]
+    }
+
+    /// OrderCancelReplaceRequest (35=G): change quantity or price; the order loses time priority
+    pub fn replace(&mut self, session: &str, message: &FixMessage) -> (Outbound, Vec<Trade>) {
+        let order_id = match self.amendable(session, message, "2") {
+            Ok(order_id) => order_id,
+            Err(reject) => return (vec![reject], Vec::new()),
+        };
+        let order = &self.orders[&order_id];
+        let check = OrderFields::parse(message).and_then(|fields| {
+            if fields.side != order.side || fields.symbol != order.symbol || fields.account != order.account {
+                return Err("Side, Symbol and Account cannot be replaced".to_string());
+            }
+            if fields.price.is_none() {
+                return Err("A resting order can only be replaced by a limit order".to_string());
+            }
+            if fields.order_qty <= order.cum_qty {
+                return Err("OrderQty(38) must exceed CumQty".to_string());
+            }
+            Ok(fields)
+        });
+        let fields = match check {
+            Ok(fields) => fields,
+            Err(e) => return (vec![self.cancel_reject(session, message, "2", 2, &e)], Vec::new()),
+        };
+        self.rename(&order_id, message);
+        let priority = self.next_id;
+        self.next_id += 1;
+        let order = self.orders.get_mut(&order_id).unwrap();
+        order.order_qty = fields.order_qty;
+        order.price = fields.price;
+        order.priority = priority;
+        println!("[DEBUG] FIX: Replaced order {}", order_id);
+        let reports = vec![self.execution_report(&order_id, exec_types::REPLACE, None, None)];
+        (reports, self.match_order(&order_id))
+    }
+
+    /// OrderStatusRequest (35=H), by ClOrdID(11) or OrderID(37)
+    pub fn status(&mut self, session: &str, message: &FixMessage) -> Outbound {
+        let order_id = message.get(tags::CL_ORD_ID)
+            .and_then(|cl_ord_id| self.cl_ord_ids.get(&(session.to_string(), cl_ord_id.to_string())).cloned())
+            .or_else(|| message.get(tags::ORDER_ID).map(str::to_string))
+            .filter(|order_id| self.orders.get(order_id).is_some_and(|o| o.session == session));
+        match order_id {
+            Some(order_id) => vec![self.execution_report(&order_id, exec_types::ORDER_STATUS, None, None)],
+            None => {
+                let mut report = self.order_reject(session, message, 5, "Unknown order");
+                report.1.set(tags::EXEC_TYPE, exec_types::ORDER_STATUS);
+                vec![report]
+            }
+        }
+    }
+
+    /// Link a submitted settlement transaction to its trade
+    pub fn settlement_submitted(&mut self, match_id: &str, tx_hash: [u8; 32]) {
+        if self.trades.contains_key(match_id) {
+            self.settlements.insert(tx_hash, match_id.to_string());
+        }
+    }
+
+    /// The settlement transaction was refused or expired: release the matched quantity
+    pub fn settlement_failed(&mut self, match_id: &str, reason: &str) -> Outbound {
+        let Some(trade) = self.trades.remove(match_id) else {
+            return Vec::new();
+        };
+        self.settlements.retain(|_, id| id != match_id);
+        println!("[DEBUG] FIX: Settlement of {} failed: {}", match_id, reason);
+        let mut reports = Vec::new();
+        for order_id in [&trade.buy_order, &trade.sell_order] {
+            let order = self.orders.get_mut(order_id).unwrap();
+            order.pending_qty -= trade.qty;
+            if order.status() == OrdStatus::Canceled {
+                reports.push(self.execution_report(order_id, exec_types::CANCELED, Some(reason), None));
+            }
+        }
+        reports
+    }
+
+    /// Apply a status change of a settlement transaction; finality produces the fills
+    pub fn on_tx_status(&mut self, tx_hash: &[u8; 32], status: &TxStatus) -> Outbound {
+        let Some(match_id) = self.settlements.get(tx_hash).cloned() else {
+            return Vec::new();
+        };
+        match status {
+            TxStatus::Finalized { .. } => {
+                self.settlements.remove(tx_hash);
+                let trade = self.trades.remove(&match_id).unwrap();
+                let mut reports = Vec::new();
+                for order_id in [&trade.buy_order, &trade.sell_order] {
+                    let order = self.orders.get_mut(order_id).unwrap();
+                    order.avg_px = (order.avg_px * order.cum_qty as f64 + trade.price * trade.qty as f64)
+                        / (order.cum_qty + trade.qty) as f64;
+                    order.cum_qty += trade.qty;
+                    order.pending_qty -= trade.qty;
+                    let exec_type = if order.cum_qty == order.order_qty { exec_types::FILL } else { exec_types::PARTIAL_FILL };
+                    let done = order.status() == OrdStatus::Canceled;
+                    let mut report = self.execution_report(order_id, exec_type, None, Some((trade.qty, trade.price)));
+                    report.1.set(tags::TRD_MATCH_ID, &trade.match_id);
+                    reports.push(report);
+                    if done {
+                        reports.push(self.execution_report(order_id, exec_types::CANCELED, Some("Remaining quantity canceled"), None));
+                    }
+                }
+                println!("[DEBUG] FIX: Trade {} settled", match_id);
+                reports
+            }
+            TxStatus::Rejected { reason } => self.settlement_failed(&match_id, reason),
+            TxStatus::Expired => self.settlement_failed(&match_id, "Settlement transaction expired"),
+            TxStatus::Pending | TxStatus::Included { .. } => Vec::new(),
+        }
+    }
+
+    /// Cross an order against the resting opposite side, best price then time first
+    fn match_order(&mut self, order_id: &str) -> Vec<Trade> {
+        let incoming = self.orders[order_id].clone();
+        let mut candidates: Vec<&Order> = self.orders.values()
+            .filter(|o| o.symbol == incoming.symbol && o.side != incoming.side && o.account != incoming.account)
+            .filter(|o| o.open_qty() > 0 && incoming.crosses(o))
+            .collect();
+        candidates.sort_by(|a, b| {
+            let (pa, pb) = (a.price.unwrap_or_default(), b.price.unwrap_or_default());
+            let by_price = match incoming.side {
+                Side::Buy => pa.total_cmp(&pb),
+                Side::Sell => pb.total_cmp(&pa),
+            };
+            by_price.then(a.priority.cmp(&b.priority))
+        });
+        let candidates: Vec<(String, u64, f64)> = candidates.iter()
+            .map(|o| (o.order_id.clone(), o.open_qty(), o.price.unwrap_or_default()))
+            .collect();
+
+        let mut remaining = incoming.open_qty();
+        let mut trades = Vec::new();
+        for (resting_id, open, price) in candidates {
+            if remaining == 0 {
+                break;
+            }
+            let qty = remaining.min(open);
+            remaining -= qty;
+            let (buy, sell) = match incoming.side {
+                Side::Buy => (order_id.to_string(), resting_id),
+                Side::Sell => (resting_id, order_id.to_string()),
+            };
+            for id in [&buy, &sell] {
+                self.orders.get_mut(id).unwrap().pending_qty += qty;
+            }
+            let trade = Trade {
+                match_id: self.next_id("TRD"),
+                symbol: incoming.symbol.clone(),
+                currency: incoming.currency.clone(),
+                buyer_account: self.orders[&buy].account.clone(),
+                seller_account: self.orders[&sell].account.clone(),
+                buy_order: buy,
+                sell_order: sell,
+                qty,
+                price,
+            };
+            println!("[DEBUG] FIX: Matched {} {} @ {} ({})", trade.qty, trade.symbol, trade.price, trade.match_id);
+            self.trades.insert(trade.match_id.clone(), trade.clone());
+            trades.push(trade);
+        }
+        trades
+    }
+
+    /// Resolve the OrigClOrdID(41) of a cancel or replace to an order that can still change
+    fn amendable(&mut self, session: &str, message: &FixMessage, response_to: &str) -> Result<String, (String, FixMessage)> {
+        let orig = message.get(tags::ORIG_CL_ORD_ID).unwrap_or_default();
+        let cl_ord_id = message.get(tags::CL_ORD_ID).unwrap_or_default();
+        if cl_ord_id.is_empty() {
+            return Err(self.cancel_reject(session, message, response_to, 99, "Missing ClOrdID(11)"));
+        }
+        if self.cl_ord_ids.contains_key(&(session.to_string(), cl_ord_id.to_string())) {
+            return Err(self.cancel_reject(session, message, response_to, 6, "Duplicate ClOrdID(11)"));
+        }
+        let Some(order_id) = self.cl_ord_ids.get(&(session.to_string(), orig.to_string())).cloned() else {
+            return Err(self.cancel_reject(session, message, response_to, 1, "Unknown order"));
+        };
+        let order = &self.orders[&order_id];
+        if order.status().is_terminal() || order.canceled {
+            return Err(self.cancel_reject(session, message, response_to, 0, "Too late: order is done"));
+        }
+        if order.pending_qty > 0 {
+            return Err(self.cancel_reject(session, message, response_to, 2, "Order has executions pending settlement"));
+        }
+        if let Some(reason) = self.claim_cl_ord_id(session, cl_ord_id) {
+            return Err(self.cancel_reject(session, message, response_to, 6, &reason));
+        }
+        Ok(order_id)
+    }
+
+    /// Make the request's ClOrdID current, keeping the previous one as OrigClOrdID
+    fn rename(&mut self, order_id: &str, message: &FixMessage) {
+        let cl_ord_id = message.get(tags::CL_ORD_ID).unwrap_or_default().to_string();
+        let order = self.orders.get_mut(order_id).unwrap();
+        order.orig_cl_ord_id = Some(std::mem::replace(&mut order.cl_ord_id, cl_ord_id.clone()));
+        self.cl_ord_ids.insert((order.session.clone(), cl_ord_id), order_id.to_string());
+    }
+
+    fn execution_report(&mut self, order_id: &str, exec_type: &str, text: Option<&str>, last: Option<(u64, f64)>) -> (String, FixMessage) {
+        let exec_id = self.next_id("EXE");
+        let order = &self.orders[order_id];
+        let mut report = FixMessage::new(msg_types::EXECUTION_REPORT)
+            .with(tags::ORDER_ID, &order.order_id)
+            .with(tags::CL_ORD_ID, &order.cl_ord_id);
+        if let Some(orig) = &order.orig_cl_ord_id {
+            report.set(tags::ORIG_CL_ORD_ID, orig);
+        }
+        report.set(tags::EXEC_ID, exec_id);
+        report.set(tags::EXEC_TYPE, exec_type);
+        report.set(tags::ORD_STATUS, order.status().code());
+        report.set(tags::ACCOUNT, &order.account);
+        report.set(tags::SYMBOL, &order.symbol);
+        report.set(tags::SIDE, order.side.code());
+        report.set(tags::ORDER_QTY, order.order_qty);
+        if let Some(price) = order.price {
+            report.set(tags::PRICE, format_px(price));
+        }
+        if !order.currency.is_empty() {
+            report.set(tags::CURRENCY, &order.currency);
+        }
+        if let Some((qty, px)) = last {
+            report.set(tags::LAST_QTY, qty);
+            report.set(tags::LAST_PX, format_px(px));
+        }
+        report.set(tags::LEAVES_QTY, order.leaves_qty());
+        report.set(tags::CUM_QTY, order.cum_qty);
+        report.set(tags::AVG_PX, format_px(order.avg_px));
+        if let Some(text) = text {
+            report.set(tags::TEXT, text);
+        }
+        (order.session.clone(), report)
+    }
+
+    /// ExecutionReport rejecting an order that was never accepted
+    fn order_reject(&mut self, session: &str, message: &FixMessage, reason: u32, text: &str) -> (String, FixMessage) {
+        println!("[DEBUG] FIX: Rejecting order from {}: {}", session, text);
+        let mut report = FixMessage::new(msg_types::EXECUTION_REPORT)
+            .with(tags::ORDER_ID, "NONE")
+            .with(tags::CL_ORD_ID, message.get(tags::CL_ORD_ID).unwrap_or_default())
+            .with(tags::EXEC_ID, self.next_id("EXE"))
+            .with(tags::EXEC_TYPE, exec_types::REJECTED)
+            .with(tags::ORD_STATUS, order_status::REJECTED);
+        for tag in [tags::ACCOUNT, tags::SYMBOL, tags::SIDE, tags::ORDER_QTY] {
+            if let Some(value) = message.get(tag) {
+                report.set(tag, value);
+            }
+        }
+        report.set(tags::LEAVES_QTY, 0);
+        report.set(tags::CUM_QTY, 0);
+        report.set(tags::AVG_PX, 0);
+        report.set(tags::ORD_REJ_REASON, reason);
+        report.set(tags::TEXT, text);
+        (session.to_string(), report)
+    }
+
+    /// OrderCancelReject (35=9); `response_to` is 1 for a cancel and 2 for a replace
+    fn cancel_reject(&self, session: &str, message: &FixMessage, response_to: &str, reason: u32, text: &str) -> (String, FixMessage) {
+        println!("[DEBUG] FIX: Rejecting cancel/replace from {}: {}", session, text);
+        let order = message.get(tags::ORIG_CL_ORD_ID)
+            .and_then(|orig| self.cl_ord_ids.get(&(session.to_string(), orig.to_string())))
+            .and_then(|order_id| self.orders.get(order_id));
+        let mut reject = FixMessage::new(msg_types::ORDER_CANCEL_REJECT)
+            .with(tags::ORDER_ID, order.map(|o| o.order_id.as_str()).unwrap_or("NONE"))
+            .with(tags::CL_ORD_ID, message.get(tags::CL_ORD_ID).unwrap_or_default())
+            .with(tags::ORIG_CL_ORD_ID, message.get(tags::ORIG_CL_ORD_ID).unwrap_or_default())
+            .with(tags::ORD_STATUS, order.map(|o| o.status()).unwrap_or(OrdStatus::Rejected).code())
+            .with(tags::CXL_REJ_RESPONSE_TO, response_to)
+            .with(tags::CXL_REJ_REASON, reason);
+        reject.set(tags::TEXT, text);
+        (session.to_string(), reject)
+    }
+}
+
+/// Price without trailing zeros, e.g. 1.1 or 1.08345
+pub fn format_px(price: f64) -> String {
+    let formatted = format!("{:.8}", price);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(cl_ord_id: &str, account: &str, side: &str, qty: u64, price: Option<f64>) -> FixMessage {
+        let mut message = FixMessage::new("D")
+            .with(tags::CL_ORD_ID, cl_ord_id)
+            .with(tags::ACCOUNT, account)
+            .with(tags::SYMBOL, "EUR/USD")
+            .with(tags::SIDE, side)
+            .with(tags::ORDER_QTY, qty)
+            .with(tags::CURRENCY, "EUR");
+        if let Some(price) = price {
+            message.set(tags::PRICE, price);
+        }
+        message
+    }
+
+    fn exec(report: &(String, FixMessage)) -> (&str, &str, &str, &str) {
+        let m = &report.1;
+        (report.0.as_str(), m.get(tags::EXEC_TYPE).unwrap(), m.get(tags::ORD_STATUS).unwrap(), m.get(tags::LEAVES_QTY).unwrap())
+    }
+
+    fn finalized() -> TxStatus {
+        TxStatus::Finalized { round_number: 7, block_id: [0; 32] }
+    }
+
+    #[test]
+    fn test_partial_fill_then_fill_on_settlement() {
+        let mut book = OrderManager::new("T");
+        let (reports, trades) = book.new_order("S1", &order("B1", "ALICE", "1", 1_000, Some(1.10)));
+        assert_eq!(exec(&reports[0]), ("S1", "0", "0", "1000"));
+        assert!(trades.is_empty());
+
+        // A cheaper sell crosses at the resting price for part of the quantity
+        let (_, trades) = book.new_order("S2", &order("S1", "BOB", "2", 400, Some(1.05)));
+        assert_eq!((trades[0].qty, trades[0].price), (400, 1.10));
+        assert_eq!((trades[0].buyer_account.as_str(), trades[0].seller_account.as_str()), ("ALICE", "BOB"));
+        book.settlement_submitted(&trades[0].match_id, [1; 32]);
+
+        // Nothing is filled until the settlement transaction is final
+        assert!(book.on_tx_status(&[1; 32], &TxStatus::Pending).is_empty());
+        let fills = book.on_tx_status(&[1; 32], &finalized());
+        assert_eq!(exec(&fills[0]), ("S1", "1", "1", "600"));
+        assert_eq!(exec(&fills[1]), ("S2", "2", "2", "0"));
+        assert_eq!(fills[0].1.get(tags::LAST_PX), Some("1.1"));
+
+        // A failed settlement releases the quantity instead of filling it
+        let (_, trades) = book.new_order("S2", &order("S2", "BOB", "2", 600, None));
+        book.settlement_submitted(&trades[0].match_id, [2; 32]);
+        assert!(book.on_tx_status(&[2; 32], &TxStatus::Rejected { reason: "insufficient funds".to_string() })
+            .iter().any(|r| exec(r) == ("S2", "4", "4", "0")));
+        let buy_id = fills[0].1.get(tags::ORDER_ID).unwrap().to_string();
+        assert_eq!(book.order(&buy_id).unwrap().open_qty(), 600);
+
+        let (_, trades) = book.new_order("S2", &order("S3", "BOB", "2", 600, Some(1.10)));
+        book.settlement_submitted(&trades[0].match_id, [3; 32]);
+        let fills = book.on_tx_status(&[3; 32], &finalized());
+        assert_eq!(exec(&fills[0]), ("S1", "2", "2", "0"));
+        assert_eq!(fills[0].1.get(tags::CUM_QTY), Some("1000"));
+    }
+
+    #[test]
+    fn test_cancel_replace_and_status() {
+        let mut book = OrderManager::new("T");
+        book.new_order("S1", &order("B1", "ALICE", "1", 500, Some(1.00)));
+        let (rejected, _) = book.new_order("S1", &order("B1", "ALICE", "1", 500, Some(1.00)));
+        assert_eq!(rejected[0].1.get(tags::ORD_REJ_REASON), Some("6"));
+
+        let replace = order("B2", "ALICE", "1", 800, Some(1.20)).with(tags::ORIG_CL_ORD_ID, "B1");
+        let (replaced, _) = book.replace("S1", &replace.clone());
+        assert_eq!(exec(&replaced[0]), ("S1", "5", "0", "800"));
+        assert_eq!(replaced[0].1.get(tags::ORIG_CL_ORD_ID), Some("B1"));
+
+        // The old ClOrdID no longer names a live request; another session cannot see the order
+        let status = book.status("S1", &FixMessage::new("H").with(tags::CL_ORD_ID, "B2"));
+        assert_eq!(exec(&status[0]), ("S1", "I", "0", "800"));
+        let unknown = book.status("S2", &FixMessage::new("H").with(tags::CL_ORD_ID, "B2"));
+        assert_eq!(unknown[0].1.get(tags::ORD_STATUS), Some("8"));
+
+        let cancel = FixMessage::new("F").with(tags::CL_ORD_ID, "B3").with(tags::ORIG_CL_ORD_ID, "B2");
+        assert_eq!(exec(&book.cancel("S1", &cancel)[0]), ("S1", "4", "4", "0"));
+        let again = book.cancel("S1", &FixMessage::new("F").with(tags::CL_ORD_ID, "B4").with(tags::ORIG_CL_ORD_ID, "B3"));
+        assert_eq!((again[0].1.msg_type.as_str(), again[0].1.get(tags::CXL_REJ_REASON)), ("9", Some("0")));
+    }
+
+    #[test]
+    fn test_cl_ord_id_replay_after_restart() {
+        let store = Arc::new(IdempotencyStore::new(3_600));
+        let mut book = OrderManager::new("A");
+        book.set_idempotency(store.clone());
+        let (reports, _) = book.new_order("FIX.4.4:FINDAG:CLIENT1", &order("B1", "ALICE", "1", 100, Some(1.10)));
+        assert_eq!(exec(&reports[0]).1, "0");
+
+        // A restarted gateway forgets its orders but not the ClOrdIDs it accepted
+        let mut restarted = OrderManager::new("B");
+        restarted.set_idempotency(store);
+        let (reports, _) = restarted.new_order("FIX.4.4:FINDAG:CLIENT1", &order("B1", "ALICE", "1", 100, Some(1.10)));
+        assert_eq!((exec(&reports[0]).1, reports[0].1.get(tags::ORD_REJ_REASON)), ("8", Some("6")));
+        let (reports, _) = restarted.new_order("FIX.4.4:FINDAG:CLIENT2", &order("B1", "BOB", "1", 100, Some(1.10)));
+        assert_eq!(exec(&reports[0]).1, "0");
+    }
+}
@@ -41,8 +41,10 @@ pub mod msg_types {
     pub const ORDER_SINGLE: &str = "D";
     pub const ORDER_CANCEL_REQUEST: &str = "F";
     pub const ORDER_CANCEL_REPLACE_REQUEST: &str = "G";
+    pub const ORDER_STATUS_REQUEST: &str = "H";
     pub const EXECUTION_REPORT: &str = "8";
     pub const ORDER_CANCEL_REJECT: &str = "9";
+    pub const BUSINESS_MESSAGE_REJECT: &str = "j";
     pub const REJECT: &str = "3";
     pub const HEARTBEAT: &str = "0";
     pub const TEST_REQUEST: &str = "1";
@@ -81,6 +83,7 @@ pub mod exec_types {
     pub const EXPIRED: &str = "C";
     pub const RESTATED: &str = "D";
     pub const PENDING_REPLACE: &str = "E";
+    pub const ORDER_STATUS: &str = "I";
 }
 
 /// FIX order status values
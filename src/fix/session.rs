@@ -0,0 +1,510 @@
+use super::message::{tags, FixMessage};
+use super::store::MessageStore;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Time a new connection has to send its Logon
+pub const LOGON_TIMEOUT: Duration = Duration::from_secs(10);
+/// Largest HeartBtInt (seconds) a counterparty may request
+pub const MAX_HEARTBEAT_SECONDS: u64 = 300;
+
+/// Acceptor-side session settings
+#[derive(Debug, Clone)]
+pub struct SessionConfig {
+    pub begin_string: String,
+    pub sender_comp_id: String,
+    pub allowed_targets: Vec<String>, // Empty: any TargetCompID may log on
+}
+
+/// Receives the application (non-session) messages of logged-on sessions
+pub trait FixApplication: Send + Sync {
+    fn on_logon(&self, _session: &str) {}
+    fn on_logout(&self, _session: &str) {}
+    /// Handle an application message, returning the immediate replies
+    fn on_message(&self, session: &str, message: &FixMessage) -> Vec<FixMessage>;
+}
+
+/// Answers every application message with a BusinessMessageReject (unsupported message type)
+pub struct RejectApplication;
+
+impl FixApplication for RejectApplication {
+    fn on_message(&self, _session: &str, message: &FixMessage) -> Vec<FixMessage> {
+        vec![FixMessage::new("j")
+            .with(tags::REF_SEQ_NUM, message.seq_num().unwrap_or(0))
+            .with(tags::REF_MSG_TYPE, &message.msg_type)
+            .with(tags::BUSINESS_REJECT_REASON, 3)]
+    }
+}
+
+/// FIX session state for one connection: logon, heartbeats, sequence numbers and resends.
+/// Each call returns the encoded messages to write to the counterparty.
+pub struct Session {
+    config: Arc<SessionConfig>,
+    store: Arc<MessageStore>,
+    id: Option<String>,
+    target_comp_id: String,
+    next_sender_seq: u64,
+    next_target_seq: u64,
+    heartbeat: Duration,
+    connected_at: Instant,
+    last_sent: Instant,
+    last_received: Instant,
+    test_request: Option<(String, Instant)>,
+    resend_requested: Option<u64>,
+    logout_sent: bool,
+    disconnect: bool,
+}
+
+impl Session {
+    pub fn new(config: Arc<SessionConfig>, store: Arc<MessageStore>, now: Instant) -> Self {
+        Session {
+            config,
+            store,
+            id: None,
+            target_comp_id: String::new(),
+            next_sender_seq: 1,
+            next_target_seq: 1,
+            heartbeat: Duration::from_secs(30),
+            connected_at: now,
+            last_sent: now,
+            last_received: now,
+            test_request: None,
+            resend_requested: None,
+            logout_sent: false,
+            disconnect: false,
+        }
+    }
+
+    /// `BeginString:SenderCompID:TargetCompID` once logged on
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn is_logged_on(&self) -> bool {
+        self.id.is_some() && !self.disconnect
+    }
+
+    pub fn should_disconnect(&self) -> bool {
+        self.disconnect
+    }
+
+    pub fn sequence_numbers(&self) -> (u64, u64) {
+        (self.next_sender_seq, self.next_target_seq)
+    }
+
+    /// Handle raw bytes of one framed message
+    pub fn on_raw(&mut self, raw: &[u8], app: &dyn FixApplication, now: Instant) -> Vec<String> {
+        match FixMessage::decode(raw) {
+            Ok((begin_string, _)) if begin_string != self.config.begin_string => {
+                println!("[DEBUG] FIX: Unsupported BeginString {}", begin_string);
+                self.logout(&format!("Unsupported BeginString {}", begin_string), now)
+            }
+            Ok((_, message)) => self.on_message(message, app, now),
+            Err(e) => {
+                // Garbled messages are ignored without consuming a sequence number
+                println!("[DEBUG] FIX: Ignoring garbled message: {}", e);
+                if self.id.is_none() {
+                    self.disconnect = true;
+                }
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn on_message(&mut self, message: FixMessage, app: &dyn FixApplication, now: Instant) -> Vec<String> {
+        self.last_received = now;
+        self.test_request = None;
+        if self.id.is_none() {
+            if message.msg_type != "A" {
+                println!("[DEBUG] FIX: First message was {} instead of Logon", message.msg_type);
+                self.disconnect = true;
+                return Vec::new();
+            }
+            return self.on_logon(&message, app, now);
+        }
+        if message.get(tags::SENDER_COMP_ID) != Some(self.target_comp_id.as_str())
+            || message.get(tags::TARGET_COMP_ID) != Some(self.config.sender_comp_id.as_str())
+        {
+            let mut out = self.reject(&message, 9, "CompID problem", now);
+            out.extend(self.logout("CompID problem", now));
+            return out;
+        }
+        let Some(seq) = message.seq_num() else {
+            return self.logout("MsgSeqNum(34) missing", now);
+        };
+
+        // SequenceReset in reset mode moves the expected number regardless of MsgSeqNum
+        if message.msg_type == "4" && !message.flag(tags::GAP_FILL_FLAG) {
+            return self.on_sequence_reset(&message, now);
+        }
+        let mut out = Vec::new();
+        if seq > self.next_target_seq {
+            // Their ResendRequest and Logout are still answered; everything else is resent
+            if message.msg_type == "2" {
+                out.extend(self.on_resend_request(&message, now));
+            }
+            if message.msg_type == "5" {
+                out.extend(self.on_logout(app, now));
+                return out;
+            }
+            out.extend(self.request_resend(now));
+            return out;
+        }
+        if seq < self.next_target_seq {
+            if message.flag(tags::POSS_DUP_FLAG) {
+                return out;
+            }
+            return self.logout(
+                &format!("MsgSeqNum too low, expecting {} but received {}", self.next_target_seq, seq),
+                now,
+            );
+        }
+        self.next_target_seq += 1;
+        if !message.flag(tags::POSS_DUP_FLAG) {
+            self.resend_requested = None;
+        }
+        self.persist();
+
+        match message.msg_type.as_str() {
+            "0" | "3" => {}
+            "1" => {
+                let mut heartbeat = FixMessage::new("0");
+                if let Some(id) = message.get(tags::TEST_REQ_ID) {
+                    heartbeat.set(tags::TEST_REQ_ID, id);
+                }
+                out.push(self.send(heartbeat, now));
+            }
+            "2" => out.extend(self.on_resend_request(&message, now)),
+            "4" => {
+                if let Some(new_seq) = message.get_u64(tags::NEW_SEQ_NO) {
+                    if new_seq > self.next_target_seq {
+                        self.next_target_seq = new_seq;
+                        self.persist();
+                    }
+                }
+            }
+            "5" => out.extend(self.on_logout(app, now)),
+            "A" => out.extend(self.reject(&message, 11, "Already logged on", now)),
+            _ => {
+                let id = self.id.clone().unwrap_or_default();
+                for reply in app.on_message(&id, &message) {
+                    out.push(self.send(reply, now));
+                }
+            }
+        }
+        out
+    }
+
+    fn on_logon(&mut self, message: &FixMessage, app: &dyn FixApplication, now: Instant) -> Vec<String> {
+        let target = message.get(tags::SENDER_COMP_ID).unwrap_or_default().to_string();
+        let heartbeat = message.get_u64(tags::HEART_BT_INT).unwrap_or(0);
+        let refuse = |reason: &str| {
+            println!("[DEBUG] FIX: Refusing logon from '{}': {}", target, reason);
+        };
+        if target.is_empty()
+            || (!self.config.allowed_targets.is_empty() && !self.config.allowed_targets.contains(&target))
+            || message.get(tags::TARGET_COMP_ID) != Some(self.config.sender_comp_id.as_str())
+        {
+            refuse("unknown CompID");
+            self.disconnect = true;
+            return Vec::new();
+        }
+        let Some(seq) = message.seq_num() else {
+            refuse("MsgSeqNum(34) missing");
+            self.disconnect = true;
+            return Vec::new();
+        };
+        self.target_comp_id = target.clone();
+        let id = format!("{}:{}:{}", self.config.begin_string, self.config.sender_comp_id, target);
+        let reset = message.flag(tags::RESET_SEQ_NUM_FLAG);
+        if reset {
+            if let Err(e) = self.store.reset(&id) {
+                println!("[DEBUG] FIX: Failed to reset session {}: {}", id, e);
+            }
+        }
+        (self.next_sender_seq, self.next_target_seq) = self.store.sequence_numbers(&id);
+        self.id = Some(id.clone());
+        if heartbeat == 0 || heartbeat > MAX_HEARTBEAT_SECONDS || message.get(tags::ENCRYPT_METHOD).is_some_and(|m| m != "0") {
+            return self.logout("Invalid HeartBtInt(108) or EncryptMethod(98)", now);
+        }
+        if seq < self.next_target_seq {
+            return self.logout(
+                &format!("MsgSeqNum too low, expecting {} but received {}", self.next_target_seq, seq),
+                now,
+            );
+        }
+        self.heartbeat = Duration::from_secs(heartbeat);
+        println!("[DEBUG] FIX: Session {} logged on (in {}, out {})", id, seq, self.next_sender_seq);
+
+        let mut logon = FixMessage::new("A")
+            .with(tags::ENCRYPT_METHOD, 0)
+            .with(tags::HEART_BT_INT, heartbeat);
+        if reset {
+            logon.set(tags::RESET_SEQ_NUM_FLAG, "Y");
+        }
+        let mut out = vec![self.send(logon, now)];
+        if seq > self.next_target_seq {
+            out.extend(self.request_resend(now));
+        } else {
+            self.next_target_seq += 1;
+            self.persist();
+        }
+        app.on_logon(&id);
+        out
+    }
+
+    fn on_sequence_reset(&mut self, message: &FixMessage, now: Instant) -> Vec<String> {
+        match message.get_u64(tags::NEW_SEQ_NO) {
+            Some(new_seq) if new_seq >= self.next_target_seq => {
+                self.next_target_seq = new_seq;
+                self.resend_requested = None;
+                self.persist();
+                Vec::new()
+            }
+            _ => self.reject(message, 5, "NewSeqNo(36) lower than expected MsgSeqNum", now),
+        }
+    }
+
+    /// Replay stored messages; session-level ones are replaced by SequenceReset-GapFill
+    fn on_resend_request(&mut self, message: &FixMessage, now: Instant) -> Vec<String> {
+        let id = self.id.clone().unwrap_or_default();
+        let last_sent = self.next_sender_seq - 1;
+        let begin = message.get_u64(tags::BEGIN_SEQ_NO).unwrap_or(1).max(1);
+        let end = match message.get_u64(tags::END_SEQ_NO) {
+            Some(0) | None => last_sent,
+            Some(end) => end.min(last_sent),
+        };
+        if begin > end {
+            return Vec::new();
+        }
+        println!("[DEBUG] FIX: Session {} resending {}..={}", id, begin, end);
+        let stored: std::collections::BTreeMap<u64, String> = self.store.outgoing(&id, begin, end).into_iter().collect();
+        let mut out = Vec::new();
+        let mut gap_start = None;
+        for seq in begin..=end {
+            let original = stored.get(&seq)
+                .and_then(|raw| FixMessage::decode(raw.as_bytes()).ok())
+                .map(|(_, m)| m)
+                .filter(|m| !m.is_admin());
+            match original {
+                Some(mut resent) => {
+                    if let Some(start) = gap_start.take() {
+                        out.push(self.gap_fill(start, seq, now));
+                    }
+                    let sending_time = resent.get(tags::SENDING_TIME).unwrap_or_default().to_string();
+                    resent.set(tags::POSS_DUP_FLAG, "Y");
+                    resent.set(tags::SENDING_TIME, sending_time_now());
+                    resent.set(tags::ORIG_SENDING_TIME, sending_time);
+                    out.push(resent.encode(&self.config.begin_string));
+                }
+                None => {
+                    gap_start.get_or_insert(seq);
+                }
+            }
+        }
+        if let Some(start) = gap_start {
+            out.push(self.gap_fill(start, end + 1, now));
+        }
+        self.last_sent = now;
+        out
+    }
+
+    fn gap_fill(&self, seq: u64, new_seq: u64, _now: Instant) -> String {
+        FixMessage::new("4")
+            .with(tags::SENDER_COMP_ID, &self.config.sender_comp_id)
+            .with(tags::TARGET_COMP_ID, &self.target_comp_id)
+            .with(tags::MSG_SEQ_NUM, seq)
+            .with(tags::POSS_DUP_FLAG, "Y")
+            .with(tags::SENDING_TIME, sending_time_now())
+            .with(tags::GAP_FILL_FLAG, "Y")
+            .with(tags::NEW_SEQ_NO, new_seq)
+            .encode(&self.config.begin_string)
+    }
+
+    fn request_resend(&mut self, now: Instant) -> Vec<String> {
+        if self.resend_requested == Some(self.next_target_seq) {
+            return Vec::new();
+        }
+        self.resend_requested = Some(self.next_target_seq);
+        let request = FixMessage::new("2")
+            .with(tags::BEGIN_SEQ_NO, self.next_target_seq)
+            .with(tags::END_SEQ_NO, 0);
+        vec![self.send(request, now)]
+    }
+
+    fn on_logout(&mut self, app: &dyn FixApplication, now: Instant) -> Vec<String> {
+        let mut out = Vec::new();
+        if !self.logout_sent {
+            out.push(self.send(FixMessage::new("5"), now));
+        }
+        if let Some(id) = &self.id {
+            println!("[DEBUG] FIX: Session {} logged out", id);
+            app.on_logout(id);
+        }
+        self.disconnect = true;
+        out
+    }
+
+    /// Session-level Reject of a received message
+    fn reject(&mut self, message: &FixMessage, reason: u32, text: &str, now: Instant) -> Vec<String> {
+        let mut reject = FixMessage::new("3")
+            .with(tags::REF_SEQ_NUM, message.seq_num().unwrap_or(0))
+            .with(tags::REF_MSG_TYPE, &message.msg_type)
+            .with(tags::SESSION_REJECT_REASON, reason);
+        reject.set(tags::TEXT, text);
+        vec![self.send(reject, now)]
+    }
+
+    /// Send a Logout and close the connection
+    pub fn logout(&mut self, text: &str, now: Instant) -> Vec<String> {
+        println!("[DEBUG] FIX: Logout: {}", text);
+        let out = if self.id.is_some() && !self.logout_sent {
+            self.logout_sent = true;
+            vec![self.send(FixMessage::new("5").with(tags::TEXT, text), now)]
+        } else {
+            Vec::new()
+        };
+        self.disconnect = true;
+        out
+    }
+
+    /// Heartbeats, TestRequests and timeouts
+    pub fn on_timer(&mut self, now: Instant) -> Vec<String> {
+        if self.id.is_none() {
+            if now.duration_since(self.connected_at) >= LOGON_TIMEOUT {
+                println!("[DEBUG] FIX: No Logon received, closing connection");
+                self.disconnect = true;
+            }
+            return Vec::new();
+        }
+        if self.disconnect {
+            return Vec::new();
+        }
+        if let Some((_, sent_at)) = &self.test_request {
+            if now.duration_since(*sent_at) >= self.heartbeat {
+                return self.logout("Heartbeat timeout", now);
+            }
+        } else if now.duration_since(self.last_received) >= self.heartbeat + self.heartbeat / 5 {
+            let id = format!("TEST-{}", self.next_sender_seq);
+            self.test_request = Some((id.clone(), now));
+            return vec![self.send(FixMessage::new("1").with(tags::TEST_REQ_ID, id), now)];
+        }
+        if now.duration_since(self.last_sent) >= self.heartbeat {
+            return vec![self.send(FixMessage::new("0"), now)];
+        }
+        Vec::new()
+    }
+
+    /// Number, stamp, store and encode an outgoing message
+    pub fn send(&mut self, message: FixMessage, now: Instant) -> String {
+        let seq = self.next_sender_seq;
+        let mut fields = vec![
+            (tags::SENDER_COMP_ID, self.config.sender_comp_id.clone()),
+            (tags::TARGET_COMP_ID, self.target_comp_id.clone()),
+            (tags::MSG_SEQ_NUM, seq.to_string()),
+            (tags::SENDING_TIME, sending_time_now()),
+        ];
+        fields.extend(message.fields.into_iter().filter(|(t, _)| {
+            ![tags::SENDER_COMP_ID, tags::TARGET_COMP_ID, tags::MSG_SEQ_NUM, tags::SENDING_TIME].contains(t)
+        }));
+        let raw = FixMessage { msg_type: message.msg_type, fields }.encode(&self.config.begin_string);
+        self.next_sender_seq += 1;
+        if let Some(id) = &self.id {
+            if let Err(e) = self.store.save_outgoing(id, seq, &raw) {
+                println!("[DEBUG] FIX: Failed to store message {} of {}: {}", seq, id, e);
+            }
+        }
+        self.persist();
+        self.last_sent = now;
+        raw
+    }
+
+    fn persist(&self) {
+        if let Some(id) = &self.id {
+            if let Err(e) = self.store.set_sequence_numbers(id, self.next_sender_seq, self.next_target_seq) {
+                println!("[DEBUG] FIX: Failed to persist sequence numbers of {}: {}", id, e);
+            }
+        }
+    }
+}
+
+/// UTCTimestamp with milliseconds, e.g. 20250703-14:00:00.000
+pub fn sending_time_now() -> String {
+    chrono::Utc::now().format("%Y%m%d-%H:%M:%S%.3f").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+
+    impl FixApplication for Echo {
+        fn on_message(&self, _session: &str, message: &FixMessage) -> Vec<FixMessage> {
+            vec![FixMessage::new("j").with(tags::REF_SEQ_NUM, message.seq_num().unwrap_or(0))]
+        }
+    }
+
+    fn inbound(msg_type: &str, seq: u64) -> FixMessage {
+        FixMessage::new(msg_type)
+            .with(tags::SENDER_COMP_ID, "CLIENT1")
+            .with(tags::TARGET_COMP_ID, "FINDAG")
+            .with(tags::MSG_SEQ_NUM, seq)
+            .with(tags::SENDING_TIME, sending_time_now())
+    }
+
+    fn decode(raw: &str) -> FixMessage {
+        FixMessage::decode(raw.as_bytes()).unwrap().1
+    }
+
+    #[test]
+    fn test_session_timers_gaps_and_resend() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(MessageStore::open(dir.path()).unwrap());
+        let config = Arc::new(SessionConfig {
+            begin_string: "FIX.4.4".to_string(),
+            sender_comp_id: "FINDAG".to_string(),
+            allowed_targets: vec!["CLIENT1".to_string()],
+        });
+        let start = Instant::now();
+        let mut session = Session::new(config.clone(), store.clone(), start);
+
+        let logon = session.on_message(inbound("A", 1).with(tags::ENCRYPT_METHOD, 0).with(tags::HEART_BT_INT, 30), &Echo, start);
+        assert_eq!(decode(&logon[0]).msg_type, "A");
+        assert_eq!(session.sequence_numbers(), (2, 2));
+
+        // Application message answered, then silence triggers Heartbeat, TestRequest and Logout
+        let reply = session.on_message(inbound("D", 2), &Echo, start);
+        assert_eq!(decode(&reply[0]).msg_type, "j");
+        assert_eq!(decode(&session.on_timer(start + Duration::from_secs(31))[0]).msg_type, "0");
+        let test_request = decode(&session.on_timer(start + Duration::from_secs(37))[0]);
+        assert_eq!(test_request.msg_type, "1");
+
+        // A gap triggers one ResendRequest; the PossDup replay fills it
+        let resend = session.on_message(inbound("D", 5), &Echo, start + Duration::from_secs(38));
+        let resend = decode(&resend[0]);
+        assert_eq!((resend.msg_type.as_str(), resend.get_u64(tags::BEGIN_SEQ_NO)), ("2", Some(3)));
+        assert!(session.on_message(inbound("D", 6), &Echo, start).is_empty());
+        let gap_fill = inbound("4", 3).with(tags::GAP_FILL_FLAG, "Y").with(tags::NEW_SEQ_NO, 5).with(tags::POSS_DUP_FLAG, "Y");
+        assert!(session.on_message(gap_fill, &Echo, start).is_empty());
+        assert_eq!(session.sequence_numbers().1, 5);
+
+        // Their ResendRequest replays the application reply and gap-fills the admin messages
+        let replay: Vec<FixMessage> = session.on_message(inbound("2", 5).with(tags::BEGIN_SEQ_NO, 1).with(tags::END_SEQ_NO, 0), &Echo, start)
+            .iter().map(|raw| decode(raw)).collect();
+        assert_eq!(replay[0].msg_type, "4");
+        assert_eq!(replay[0].get_u64(tags::NEW_SEQ_NO), Some(2));
+        assert_eq!(replay[1].msg_type, "j");
+        assert!(replay[1].flag(tags::POSS_DUP_FLAG) && replay[1].get(tags::ORIG_SENDING_TIME).is_some());
+        assert_eq!(replay[2].get_u64(tags::NEW_SEQ_NO), Some(6));
+
+        // Too low without PossDupFlag ends the session; numbering survives a restart
+        let logout = session.on_message(inbound("D", 2), &Echo, start);
+        assert_eq!(decode(&logout[0]).msg_type, "5");
+        assert!(session.should_disconnect());
+        let (sent, expected) = session.sequence_numbers();
+        let mut resumed = Session::new(config, store, start);
+        resumed.on_message(inbound("A", 6).with(tags::HEART_BT_INT, 30), &Echo, start);
+        assert_eq!(resumed.sequence_numbers(), (sent + 1, expected + 1));
+    }
+}
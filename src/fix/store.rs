@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Result};
+use sled::Db;
+use std::path::Path;
+
+/// Persisted sequence numbers and sent messages of FIX sessions, so a session
+/// resumes its numbering after a restart and can answer ResendRequests
+pub struct MessageStore {
+    db: Db,
+}
+
+impl MessageStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let db = sled::open(path).map_err(|e| anyhow!("Failed to open FIX message store: {}", e))?;
+        Ok(MessageStore { db })
+    }
+
+    fn seq_key(session: &str) -> String {
+        format!("seq:{}", session)
+    }
+
+    fn message_key(session: &str, seq: u64) -> String {
+        format!("msg:{}:{:020}", session, seq)
+    }
+
+    /// Next outgoing and next expected incoming MsgSeqNum (both 1 for a new session)
+    pub fn sequence_numbers(&self, session: &str) -> (u64, u64) {
+        match self.db.get(Self::seq_key(session)) {
+            Ok(Some(bytes)) if bytes.len() == 16 => (
+                u64::from_be_bytes(bytes[..8].try_into().unwrap()),
+                u64::from_be_bytes(bytes[8..].try_into().unwrap()),
+            ),
+            _ => (1, 1),
+        }
+    }
+
+    pub fn set_sequence_numbers(&self, session: &str, next_sender: u64, next_target: u64) -> Result<()> {
+        let mut bytes = next_sender.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&next_target.to_be_bytes());
+        self.db.insert(Self::seq_key(session), bytes)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
+    /// Keep a sent message for resending
+    pub fn save_outgoing(&self, session: &str, seq: u64, raw: &str) -> Result<()> {
+        self.db.insert(Self::message_key(session, seq), raw.as_bytes())?;
+        Ok(())
+    }
+
+    /// Sent messages with `begin <= seq <= end`, in sequence order
+    pub fn outgoing(&self, session: &str, begin: u64, end: u64) -> Vec<(u64, String)> {
+        self.db
+            .range(Self::message_key(session, begin)..=Self::message_key(session, end))
+            .filter_map(|entry| entry.ok())
+            .filter_map(|(key, value)| {
+                let seq = std::str::from_utf8(&key).ok()?.rsplit(':').next()?.parse().ok()?;
+                Some((seq, String::from_utf8(value.to_vec()).ok()?))
+            })
+            .collect()
+    }
+
+    /// Drop the session's messages and restart numbering at 1
+    pub fn reset(&self, session: &str) -> Result<()> {
+        let prefix = format!("msg:{}:", session);
+        for key in self.db.scan_prefix(prefix.as_bytes()).keys() {
+            self.db.remove(key?)?;
+        }
+        self.set_sequence_numbers(session, 1, 1)
+    }
+}
@@ -0,0 +1,171 @@
+use crate::core::executor::DEFAULT_TRANSFER_ASSET;
+use crate::core::tx_status::TxStatusRegistry;
+use crate::iso20022::reporting::{self, AccountReport};
+use crate::storage::state::StateDB;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Writes end-of-day camt.053 statements and per-round camt.054 notifications to a directory
+pub struct ReportExporter {
+    state_db: Arc<StateDB>,
+    tx_status: Arc<TxStatusRegistry>,
+    output_dir: PathBuf,
+    currencies: Vec<String>,
+    last_statement_day: Option<u64>,
+    last_notified_round: u64,
+    sequence: u64,
+}
+
+impl ReportExporter {
+    pub fn new(state_db: Arc<StateDB>, tx_status: Arc<TxStatusRegistry>, output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            state_db,
+            tx_status,
+            output_dir: output_dir.into(),
+            currencies: vec![DEFAULT_TRANSFER_ASSET.to_string()],
+            last_statement_day: None,
+            last_notified_round: 0,
+            sequence: 0,
+        }
+    }
+
+    /// Currencies to report on (assets that are ISO 4217 codes)
+    pub fn with_currencies(mut self, currencies: Vec<String>) -> Self {
+        self.currencies = currencies;
+        self
+    }
+
+    /// Run one export pass at `now` (Unix seconds), returning the files written.
+    /// Statements cover every completed day not yet exported (on the first pass, yesterday);
+    /// notifications cover rounds finalized since the previous pass.
+    pub fn run_once(&mut self, now: u64) -> Result<Vec<PathBuf>, String> {
+        std::fs::create_dir_all(&self.output_dir)
+            .map_err(|e| format!("Failed to create export directory: {e}"))?;
+        let today = reporting::day_of(now);
+        let mut written = Vec::new();
+
+        let first_day = self.last_statement_day.map(|d| d + 1).unwrap_or(today.saturating_sub(1));
+        for day in first_day..today {
+            for account in self.state_db.booked_accounts(day) {
+                for currency in &self.currencies {
+                    let report = AccountReport::for_day(&self.state_db, &self.tx_status, &account, currency, day);
+                    if report.entries.is_empty() {
+                        continue;
+                    }
+                    self.sequence += 1;
+                    let message_id = reporting::report_message_id("CAMT053", now, self.sequence);
+                    let xml = reporting::camt053_statement(&message_id, now, day + 1, &report)
+                        .map_err(|e| e.to_string())?;
+                    let date = chrono::DateTime::from_timestamp((day * 86_400) as i64, 0)
+                        .unwrap_or_default()
+                        .format("%Y-%m-%d");
+                    written.push(self.write(&format!("camt053_{account}_{currency}_{date}.xml"), &xml)?);
+                }
+            }
+            self.last_statement_day = Some(day);
+        }
+
+        // A round's entries were booked shortly before it finalized, so today and yesterday suffice
+        let mut pending: BTreeSet<(u64, String, String)> = BTreeSet::new();
+        for day in today.saturating_sub(1)..=today {
+            for account in self.state_db.booked_accounts(day) {
+                for currency in &self.currencies {
+                    let report = AccountReport::for_day(&self.state_db, &self.tx_status, &account, currency, day);
+                    for round in report.entries.iter().filter_map(|e| e.finalized_round()) {
+                        if round > self.last_notified_round {
+                            pending.insert((round, account.clone(), currency.clone()));
+                        }
+                    }
+                }
+            }
+        }
+        let from = today.saturating_sub(1) * 86_400;
+        for (round, account, currency) in &pending {
+            let report = AccountReport::build(&self.state_db, &self.tx_status, account, currency, from, now + 1)
+                .finalized_in(*round);
+            self.sequence += 1;
+            let message_id = reporting::report_message_id("CAMT054", now, self.sequence);
+            let xml = reporting::camt054_notification(&message_id, now, &format!("round-{round}"), &report)
+                .map_err(|e| e.to_string())?;
+            written.push(self.write(&format!("camt054_{account}_{currency}_{round}.xml"), &xml)?);
+        }
+        if let Some((round, _, _)) = pending.last() {
+            self.last_notified_round = *round;
+        }
+
+        Ok(written)
+    }
+
+    /// Run export passes on a fixed interval in the background
+    pub fn spawn(mut self, interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                match self.run_once(now) {
+                    Ok(files) if !files.is_empty() => {
+                        println!("[DEBUG] ISO 20022 export wrote {} report(s) to {}", files.len(), self.output_dir.display());
+                    }
+                    Ok(_) => {}
+                    Err(e) => println!("[DEBUG] ISO 20022 export failed: {e}"),
+                }
+            }
+        });
+    }
+
+    fn write(&self, file_name: &str, xml: &str) -> Result<PathBuf, String> {
+        let path = self.output_dir.join(file_name);
+        std::fs::write(&path, xml)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::state::{AccountEntry, EntryDirection};
+
+    #[test]
+    fn test_exports_statements_once_and_notifications_per_round() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = Arc::new(StateDB::new(dir.path().join("state").to_str().unwrap()));
+        let tx_status = Arc::new(TxStatusRegistry::default());
+        let day = 20_000;
+        let tx_hash = [4u8; 32];
+        state_db.record_account_entries(&[AccountEntry {
+            tx_hash,
+            leg: 0,
+            shard_id: 0,
+            address: "fdg1qalice".to_string(),
+            counterparty: "fdg1qbob".to_string(),
+            asset: "USD".to_string(),
+            amount: 500,
+            direction: EntryDirection::Credit,
+            balance_after: 500,
+            booked_at: day * 86_400 + 60,
+        }]).unwrap();
+        tx_status.mark_included([5u8; 32], &[tx_hash]);
+        tx_status.mark_round_finalized(11, &[[5u8; 32]]);
+
+        let mut exporter = ReportExporter::new(state_db, tx_status, dir.path().join("out"));
+        let files = exporter.run_once((day + 1) * 86_400 + 10).unwrap();
+        let names: Vec<String> = files.iter()
+            .map(|f| f.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec![
+            "camt053_fdg1qalice_USD_2024-10-04.xml".to_string(),
+            "camt054_fdg1qalice_USD_11.xml".to_string(),
+        ]);
+        assert!(std::fs::read_to_string(&files[0]).unwrap().contains("<Cd>CLBD</Cd>"));
+
+        // Nothing new since the last pass
+        assert!(exporter.run_once((day + 1) * 86_400 + 70).unwrap().is_empty());
+    }
+}
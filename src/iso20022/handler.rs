@@ -2,14 +2,21 @@ use crate::iso20022::ISO20022Transaction;
 use crate::core::types::Transaction;
 use sha2::{Sha256, Digest};
 
-use crate::core::address::Address;
+use crate::core::ingestion::{Adapter, GatewaySigner, IdentifierKind, IngestionRegistry};
 use ed25519_dalek::{Signature, VerifyingKey};
 
-/// Map ISO20022Transaction into your internal Transaction format
-pub fn iso20022_to_findag_tx(iso_tx: &ISO20022Transaction) -> Transaction {
-    // Create addresses from the debtor and creditor strings
-    let from = Address::new(iso_tx.debtor.clone());
-    let to = Address::new(iso_tx.creditor.clone());
+/// Map ISO20022Transaction into a Transaction signed by the ISO 20022 gateway.
+/// Debtor and creditor (BIC, LEI or account) are resolved through the mapping table.
+pub fn iso20022_to_findag_tx(
+    iso_tx: &ISO20022Transaction,
+    registry: &IngestionRegistry,
+    signer: &GatewaySigner,
+) -> Result<Transaction, String> {
+    if signer.adapter != Adapter::Iso20022 {
+        return Err(format!("{} gateway key cannot sign ISO 20022 conversions", signer.adapter.protocol()));
+    }
+    let from = registry.debit_account(Adapter::Iso20022, &[(IdentifierKind::classify(&iso_tx.debtor), &iso_tx.debtor)])?;
+    let to = registry.credit_account(&[(IdentifierKind::classify(&iso_tx.creditor), &iso_tx.creditor)])?;
     let amount = iso_tx.amount;
     
     // Create payload with currency information
@@ -22,24 +29,24 @@ pub fn iso20022_to_findag_tx(iso_tx: &ISO20022Transaction) -> Transaction {
     let mut hashtimer_array = [0u8; 32];
     hashtimer_array.copy_from_slice(&hashtimer);
 
-    // Create a dummy signature and public key for now
-    // In a real implementation, these would be properly signed
-    let dummy_signature = Signature::from_bytes(&[0u8; 64]);
-    let dummy_public_key = VerifyingKey::from_bytes(&[0u8; 32]).unwrap();
-
-    Transaction {
+    let mut tx = Transaction {
         from,
         to,
         amount,
         payload,
         findag_time: 0, // Will be set by the system
         hashtimer: hashtimer_array,
-        signature: dummy_signature,
-        public_key: dummy_public_key,
+        signature: Signature::from_bytes(&[0u8; 64]), // Set with bridge_protocol by the gateway signer
+        public_key: VerifyingKey::from_bytes(&[0u8; 32]).unwrap(),
         shard_id: crate::core::types::ShardId(0),
         source_shard: None,
         dest_shard: None,
         target_chain: None,
         bridge_protocol: None,
-    }
-} 
\ No newline at end of file
+        valid_after: None,
+        valid_until: None,
+        multisig_signatures: Vec::new(),
+    };
+    signer.sign(&mut tx);
+    Ok(tx)
+}
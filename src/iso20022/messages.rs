@@ -0,0 +1,699 @@
+use crate::iso20022::ISO20022Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use xmltree::Element;
+
+/// Common prefix of every ISO 20022 message namespace
+pub const ISO20022_NAMESPACE_PREFIX: &str = "urn:iso:std:iso:20022:tech:xsd:";
+
+/// Largest number of fraction digits allowed by ActiveCurrencyAndAmount
+const MAX_FRACTION_DIGITS: u32 = 5;
+/// Largest number of total digits allowed by ActiveCurrencyAndAmount
+const MAX_TOTAL_DIGITS: usize = 18;
+
+/// Supported ISO 20022 credit transfer message definitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageKind {
+    /// FI to FI customer credit transfer
+    Pacs008,
+    /// Financial institution credit transfer
+    Pacs009,
+    /// Customer credit transfer initiation
+    Pain001,
+}
+
+impl MessageKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            MessageKind::Pacs008 => "pacs.008",
+            MessageKind::Pacs009 => "pacs.009",
+            MessageKind::Pain001 => "pain.001",
+        }
+    }
+
+    /// Message root element directly below `Document`
+    pub fn root_element(&self) -> &'static str {
+        match self {
+            MessageKind::Pacs008 => "FIToFICstmrCdtTrf",
+            MessageKind::Pacs009 => "FICdtTrf",
+            MessageKind::Pain001 => "CstmrCdtTrfInitn",
+        }
+    }
+
+    /// Resolve a namespace such as `urn:iso:std:iso:20022:tech:xsd:pacs.008.001.08`
+    /// into the message kind and its full identifier (`pacs.008.001.08`)
+    pub fn from_namespace(namespace: &str) -> Result<(Self, String), ISO20022Error> {
+        let identifier = namespace
+            .strip_prefix(ISO20022_NAMESPACE_PREFIX)
+            .ok_or_else(|| ISO20022Error::UnsupportedMessage(namespace.to_string()))?;
+        let parts: Vec<&str> = identifier.split('.').collect();
+        if parts.len() != 4 || parts[2..].iter().any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+            return Err(ISO20022Error::UnsupportedMessage(namespace.to_string()));
+        }
+        let kind = match (parts[0], parts[1]) {
+            ("pacs", "008") => MessageKind::Pacs008,
+            ("pacs", "009") => MessageKind::Pacs009,
+            ("pain", "001") => MessageKind::Pain001,
+            _ => return Err(ISO20022Error::UnsupportedMessage(namespace.to_string())),
+        };
+        Ok((kind, identifier.to_string()))
+    }
+}
+
+/// Decimal amount with its ISO 4217 currency, kept exact as mantissa and scale
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Amount {
+    pub mantissa: u64,
+    pub scale: u32,
+    pub currency: String,
+}
+
+impl Amount {
+    /// Parse an `xs:decimal` amount (e.g. `1234.50`) with its `Ccy` attribute
+    pub fn parse(value: &str, currency: &str, field: &str) -> Result<Self, ISO20022Error> {
+        validate_currency(currency)?;
+        let value = value.trim();
+        let invalid = || ISO20022Error::InvalidAmount(format!("{}: {}", field, value));
+        let (int_part, frac_part) = match value.split_once('.') {
+            Some((i, f)) => (i, f),
+            None => (value, ""),
+        };
+        if int_part.is_empty()
+            || !int_part.chars().all(|c| c.is_ascii_digit())
+            || !frac_part.chars().all(|c| c.is_ascii_digit())
+            || (value.contains('.') && frac_part.is_empty())
+        {
+            return Err(invalid());
+        }
+        let frac_part = frac_part.trim_end_matches('0');
+        if frac_part.len() as u32 > MAX_FRACTION_DIGITS
+            || int_part.trim_start_matches('0').len() + frac_part.len() > MAX_TOTAL_DIGITS
+        {
+            return Err(invalid());
+        }
+        let mantissa = format!("{}{}", int_part, frac_part)
+            .parse::<u64>()
+            .map_err(|_| invalid())?;
+        Ok(Amount {
+            mantissa,
+            scale: frac_part.len() as u32,
+            currency: currency.to_string(),
+        })
+    }
+
+    /// Build an amount from minor units of its currency
+    pub fn from_minor_units(minor_units: u64, currency: &str) -> Result<Self, ISO20022Error> {
+        validate_currency(currency)?;
+        Ok(Amount {
+            mantissa: minor_units,
+            scale: currency_exponent(currency),
+            currency: currency.to_string(),
+        })
+    }
+
+    /// Convert to minor units of the currency, rejecting sub-minor-unit precision
+    pub fn to_minor_units(&self) -> Result<u64, ISO20022Error> {
+        let exponent = currency_exponent(&self.currency);
+        if self.scale > exponent && !self.mantissa.is_multiple_of(10u64.pow(self.scale - exponent)) {
+            return Err(ISO20022Error::InvalidAmount(format!(
+                "{} {} has more than {} decimal places",
+                self, self.currency, exponent
+            )));
+        }
+        if self.scale > exponent {
+            return Ok(self.mantissa / 10u64.pow(self.scale - exponent));
+        }
+        self.mantissa
+            .checked_mul(10u64.pow(exponent - self.scale))
+            .ok_or_else(|| ISO20022Error::InvalidAmount(format!("{} {} overflows", self, self.currency)))
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.scale == 0 {
+            return write!(f, "{}", self.mantissa);
+        }
+        let digits = format!("{:0>width$}", self.mantissa, width = self.scale as usize + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - self.scale as usize);
+        write!(f, "{}.{}", int_part, frac_part)
+    }
+}
+
+/// ISO 4217 minor unit exponent for a currency code
+pub fn currency_exponent(currency: &str) -> u32 {
+    match currency {
+        "JPY" | "KRW" | "CLP" | "ISK" | "VND" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        _ => 2,
+    }
+}
+
+fn validate_currency(currency: &str) -> Result<(), ISO20022Error> {
+    if currency.len() == 3 && currency.chars().all(|c| c.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(ISO20022Error::InvalidCurrency(currency.to_string()))
+    }
+}
+
+/// Check a BIC (ISO 9362): 4 letter institution, 2 letter country, 2 location, optional 3 branch
+pub fn is_valid_bic(bic: &str) -> bool {
+    let bytes = bic.as_bytes();
+    (bytes.len() == 8 || bytes.len() == 11)
+        && bytes[..6].iter().all(|b| b.is_ascii_uppercase())
+        && bytes[6..].iter().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Check an LEI (ISO 17442): 20 alphanumerics with ISO 7064 MOD 97-10 check digits
+pub fn is_valid_lei(lei: &str) -> bool {
+    if lei.len() != 20 || !lei.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+        return false;
+    }
+    let remainder = lei.chars().fold(0u32, |acc, c| {
+        let value = c.to_digit(36).unwrap_or(0);
+        if value >= 10 {
+            (acc * 100 + value) % 97
+        } else {
+            (acc * 10 + value) % 97
+        }
+    });
+    remainder == 1
+}
+
+/// Party or agent identification (name, BIC, LEI and account)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Party {
+    pub name: Option<String>,
+    pub bic: Option<String>,
+    pub lei: Option<String>,
+    pub account: Option<String>,
+}
+
+impl Party {
+    /// Preferred identifier: BIC, then LEI, then name
+    pub fn identifier(&self) -> Option<&str> {
+        self.bic
+            .as_deref()
+            .or(self.lei.as_deref())
+            .or(self.name.as_deref())
+    }
+}
+
+/// Group header shared by all supported messages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupHeader {
+    pub message_id: String,
+    pub creation_date_time: String,
+    pub number_of_transactions: usize,
+    pub control_sum: Option<String>,
+    pub settlement_method: Option<String>,
+    pub initiating_party: Option<Party>,
+}
+
+/// One credit transfer (`CdtTrfTxInf`), with pain.001 payment information inlined
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreditTransferTx {
+    pub payment_information_id: Option<String>,
+    pub instruction_id: Option<String>,
+    pub end_to_end_id: String,
+    pub transaction_id: Option<String>,
+    pub uetr: Option<String>,
+    pub amount: Amount,
+    pub settlement_date: Option<String>,
+    pub debtor: Party,
+    pub debtor_agent: Option<Party>,
+    pub creditor: Party,
+    pub creditor_agent: Option<Party>,
+    pub remittance_info: Option<String>,
+}
+
+impl CreditTransferTx {
+    /// Most specific reference carried by the transfer: UETR, TxId, then EndToEndId
+    pub fn reference(&self) -> &str {
+        self.uetr
+            .as_deref()
+            .or(self.transaction_id.as_deref())
+            .unwrap_or(&self.end_to_end_id)
+    }
+}
+
+/// A parsed pacs.008, pacs.009 or pain.001 document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Iso20022Message {
+    pub kind: MessageKind,
+    /// Full message identifier, e.g. `pacs.008.001.08`
+    pub message_identifier: String,
+    pub group_header: GroupHeader,
+    pub transactions: Vec<CreditTransferTx>,
+}
+
+/// Parse a namespaced ISO 20022 `Document` into a typed message
+pub fn parse_message(xml_content: &str) -> Result<Iso20022Message, ISO20022Error> {
+    let document = Element::parse(xml_content.as_bytes())
+        .map_err(|e| ISO20022Error::XmlParseError(e.to_string()))?;
+    if document.name != "Document" {
+        return Err(ISO20022Error::UnsupportedMessage(format!("root element <{}>", document.name)));
+    }
+    let namespace = document
+        .namespace
+        .clone()
+        .ok_or_else(|| ISO20022Error::UnsupportedMessage("Document without namespace".to_string()))?;
+    let (kind, message_identifier) = MessageKind::from_namespace(&namespace)?;
+    let reader = Reader { ns: &namespace };
+
+    let root = reader
+        .child(&document, kind.root_element())
+        .ok_or_else(|| ISO20022Error::MissingField(kind.root_element().to_string()))?;
+    let group_header = reader.group_header(root)?;
+
+    let transactions = match kind {
+        MessageKind::Pacs008 | MessageKind::Pacs009 => reader
+            .children(root, "CdtTrfTxInf")
+            .enumerate()
+            .map(|(i, tx)| reader.interbank_transfer(kind, tx, &format!("CdtTrfTxInf[{}]", i)))
+            .collect::<Result<Vec<_>, _>>()?,
+        MessageKind::Pain001 => {
+            let mut transactions = Vec::new();
+            for (i, info) in reader.children(root, "PmtInf").enumerate() {
+                transactions.extend(reader.payment_information(info, &format!("PmtInf[{}]", i))?);
+            }
+            transactions
+        }
+    };
+    if transactions.is_empty() {
+        return Err(ISO20022Error::MissingField(match kind {
+            MessageKind::Pain001 => "PmtInf/CdtTrfTxInf".to_string(),
+            _ => "CdtTrfTxInf".to_string(),
+        }));
+    }
+    if transactions.len() != group_header.number_of_transactions {
+        return Err(ISO20022Error::TransactionCountMismatch {
+            declared: group_header.number_of_transactions,
+            actual: transactions.len(),
+        });
+    }
+
+    Ok(Iso20022Message {
+        kind,
+        message_identifier,
+        group_header,
+        transactions,
+    })
+}
+
+/// Element lookups restricted to the document's namespace
+pub(crate) struct Reader<'a> {
+    pub(crate) ns: &'a str,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn child<'e>(&self, element: &'e Element, name: &str) -> Option<&'e Element> {
+        element.get_child((name, self.ns))
+    }
+
+    pub(crate) fn children<'e>(&self, element: &'e Element, name: &'e str) -> impl Iterator<Item = &'e Element> + 'e {
+        let ns = self.ns.to_string();
+        element
+            .children
+            .iter()
+            .filter_map(|node| node.as_element())
+            .filter(move |e| e.name == name && e.namespace.as_deref() == Some(ns.as_str()))
+    }
+
+    pub(crate) fn path<'e>(&self, element: &'e Element, path: &[&str]) -> Option<&'e Element> {
+        path.iter().try_fold(element, |current, name| self.child(current, name))
+    }
+
+    pub(crate) fn text(&self, element: &Element, path: &[&str]) -> Option<String> {
+        self.path(element, path)
+            .and_then(|e| e.get_text())
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    }
+
+    pub(crate) fn required_text(&self, element: &Element, path: &[&str], context: &str) -> Result<String, ISO20022Error> {
+        self.text(element, path)
+            .ok_or_else(|| ISO20022Error::MissingField(format!("{}/{}", context, path.join("/"))))
+    }
+
+    fn group_header(&self, root: &Element) -> Result<GroupHeader, ISO20022Error> {
+        let header = self
+            .child(root, "GrpHdr")
+            .ok_or_else(|| ISO20022Error::MissingField("GrpHdr".to_string()))?;
+        let message_id = self.required_text(header, &["MsgId"], "GrpHdr")?;
+        let creation_date_time = self.required_text(header, &["CreDtTm"], "GrpHdr")?;
+        validate_date_time(&creation_date_time)?;
+        let count = self.required_text(header, &["NbOfTxs"], "GrpHdr")?;
+        let number_of_transactions = count
+            .parse::<usize>()
+            .map_err(|_| ISO20022Error::InvalidAmount(format!("GrpHdr/NbOfTxs: {}", count)))?;
+        let initiating_party = match self.child(header, "InitgPty") {
+            Some(party) => Some(self.party(party, None, "GrpHdr/InitgPty")?),
+            None => None,
+        };
+        Ok(GroupHeader {
+            message_id,
+            creation_date_time,
+            number_of_transactions,
+            control_sum: self.text(header, &["CtrlSum"]),
+            settlement_method: self.text(header, &["SttlmInf", "SttlmMtd"]),
+            initiating_party,
+        })
+    }
+
+    fn interbank_transfer(&self, kind: MessageKind, tx: &Element, context: &str) -> Result<CreditTransferTx, ISO20022Error> {
+        let amount = self.amount(tx, "IntrBkSttlmAmt", context)?;
+        let settlement_date = self.text(tx, &["IntrBkSttlmDt"]);
+        if let Some(date) = &settlement_date {
+            validate_date(date)?;
+        }
+        // pacs.009 debtor and creditor are financial institutions themselves
+        let (debtor, creditor) = match kind {
+            MessageKind::Pacs009 => (
+                self.required_agent(tx, "Dbtr", context)?,
+                self.required_agent(tx, "Cdtr", context)?,
+            ),
+            _ => (
+                self.required_party(tx, "Dbtr", "DbtrAcct", context)?,
+                self.required_party(tx, "Cdtr", "CdtrAcct", context)?,
+            ),
+        };
+        Ok(CreditTransferTx {
+            payment_information_id: None,
+            instruction_id: self.text(tx, &["PmtId", "InstrId"]),
+            end_to_end_id: self.required_text(tx, &["PmtId", "EndToEndId"], context)?,
+            transaction_id: self.text(tx, &["PmtId", "TxId"]),
+            uetr: self.text(tx, &["PmtId", "UETR"]),
+            amount,
+            settlement_date,
+            debtor,
+            debtor_agent: self.agent(tx, "DbtrAgt", context)?,
+            creditor,
+            creditor_agent: self.agent(tx, "CdtrAgt", context)?,
+            remittance_info: self.text(tx, &["RmtInf", "Ustrd"]),
+        })
+    }
+
+    fn payment_information(&self, info: &Element, context: &str) -> Result<Vec<CreditTransferTx>, ISO20022Error> {
+        let payment_information_id = self.required_text(info, &["PmtInfId"], context)?;
+        let execution_date = self
+            .text(info, &["ReqdExctnDt", "Dt"])
+            .or_else(|| self.text(info, &["ReqdExctnDt", "DtTm"]).map(|t| t.chars().take(10).collect()))
+            .or_else(|| self.text(info, &["ReqdExctnDt"]));
+        if let Some(date) = &execution_date {
+            validate_date(date)?;
+        }
+        let debtor = self.required_party(info, "Dbtr", "DbtrAcct", context)?;
+        let debtor_agent = self.agent(info, "DbtrAgt", context)?;
+
+        self.children(info, "CdtTrfTxInf")
+            .enumerate()
+            .map(|(i, tx)| {
+                let tx_context = format!("{}/CdtTrfTxInf[{}]", context, i);
+                let amount = self.amount(tx, "Amt/InstdAmt", &tx_context)?;
+                Ok(CreditTransferTx {
+                    payment_information_id: Some(payment_information_id.clone()),
+                    instruction_id: self.text(tx, &["PmtId", "InstrId"]),
+                    end_to_end_id: self.required_text(tx, &["PmtId", "EndToEndId"], &tx_context)?,
+                    transaction_id: None,
+                    uetr: self.text(tx, &["PmtId", "UETR"]),
+                    amount,
+                    settlement_date: execution_date.clone(),
+                    debtor: debtor.clone(),
+                    debtor_agent: debtor_agent.clone(),
+                    creditor: self.required_party(tx, "Cdtr", "CdtrAcct", &tx_context)?,
+                    creditor_agent: self.agent(tx, "CdtrAgt", &tx_context)?,
+                    remittance_info: self.text(tx, &["RmtInf", "Ustrd"]),
+                })
+            })
+            .collect()
+    }
+
+    /// Amount element at a `/`-separated path, with its mandatory `Ccy` attribute
+    pub(crate) fn amount(&self, element: &Element, path: &str, context: &str) -> Result<Amount, ISO20022Error> {
+        let field = format!("{}/{}", context, path);
+        let segments: Vec<&str> = path.split('/').collect();
+        let amount = self
+            .path(element, &segments)
+            .ok_or_else(|| ISO20022Error::MissingField(field.clone()))?;
+        let currency = amount
+            .attributes
+            .get("Ccy")
+            .ok_or_else(|| ISO20022Error::MissingField(format!("{}@Ccy", field)))?;
+        let value = amount
+            .get_text()
+            .ok_or_else(|| ISO20022Error::MissingField(field.clone()))?;
+        Amount::parse(&value, currency, &field)
+    }
+
+    fn required_party(&self, element: &Element, name: &str, account: &str, context: &str) -> Result<Party, ISO20022Error> {
+        let party = self
+            .child(element, name)
+            .ok_or_else(|| ISO20022Error::MissingField(format!("{}/{}", context, name)))?;
+        self.party(party, self.child(element, account), &format!("{}/{}", context, name))
+    }
+
+    fn required_agent(&self, element: &Element, name: &str, context: &str) -> Result<Party, ISO20022Error> {
+        self.agent(element, name, context)?
+            .ok_or_else(|| ISO20022Error::MissingField(format!("{}/{}", context, name)))
+    }
+
+    /// Financial institution identified under `FinInstnId`
+    fn agent(&self, element: &Element, name: &str, context: &str) -> Result<Option<Party>, ISO20022Error> {
+        let Some(agent) = self.child(element, name) else {
+            return Ok(None);
+        };
+        let field = format!("{}/{}", context, name);
+        let institution = self
+            .child(agent, "FinInstnId")
+            .ok_or_else(|| ISO20022Error::MissingField(format!("{}/FinInstnId", field)))?;
+        let party = Party {
+            name: self.text(institution, &["Nm"]),
+            bic: self.identifier(institution, &["BICFI"], &field, is_valid_bic)?,
+            lei: self.identifier(institution, &["LEI"], &field, is_valid_lei)?,
+            account: None,
+        };
+        if party.identifier().is_none() {
+            return Err(ISO20022Error::MissingField(format!("{}/FinInstnId/BICFI", field)));
+        }
+        Ok(Some(party))
+    }
+
+    fn party(&self, party: &Element, account: Option<&Element>, field: &str) -> Result<Party, ISO20022Error> {
+        let bic = match self.identifier(party, &["Id", "OrgId", "AnyBIC"], field, is_valid_bic)? {
+            Some(bic) => Some(bic),
+            // pacs.008.001.02 and pain.001.001.03 still use BICOrBEI
+            None => self.identifier(party, &["Id", "OrgId", "BICOrBEI"], field, is_valid_bic)?,
+        };
+        let party = Party {
+            name: self.text(party, &["Nm"]),
+            bic,
+            lei: self.identifier(party, &["Id", "OrgId", "LEI"], field, is_valid_lei)?,
+            account: account.and_then(|a| {
+                self.text(a, &["Id", "IBAN"])
+                    .or_else(|| self.text(a, &["Id", "Othr", "Id"]))
+            }),
+        };
+        if party.identifier().is_none() {
+            return Err(ISO20022Error::MissingField(format!("{}/Nm", field)));
+        }
+        Ok(party)
+    }
+
+    fn identifier(
+        &self,
+        element: &Element,
+        path: &[&str],
+        field: &str,
+        is_valid: fn(&str) -> bool,
+    ) -> Result<Option<String>, ISO20022Error> {
+        match self.text(element, path) {
+            Some(value) if !is_valid(&value) => Err(ISO20022Error::InvalidIdentifier {
+                field: format!("{}/{}", field, path.join("/")),
+                value,
+            }),
+            value => Ok(value),
+        }
+    }
+}
+
+fn validate_date_time(value: &str) -> Result<(), ISO20022Error> {
+    let valid = chrono::DateTime::parse_from_rfc3339(value).is_ok()
+        || chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").is_ok();
+    if valid {
+        Ok(())
+    } else {
+        Err(ISO20022Error::InvalidDate(value.to_string()))
+    }
+}
+
+pub(crate) fn validate_date(value: &str) -> Result<(), ISO20022Error> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|_| ())
+        .map_err(|_| ISO20022Error::InvalidDate(value.to_string()))
+}
+
+/// Escape text content for inclusion in generated XML
+pub fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::address::Address;
+    use crate::core::ingestion::{test_registry, Adapter, GatewaySigner, IdentifierKind, IngestionRegistry};
+    use crate::core::types::Transaction;
+    use crate::iso20022::handler::iso20022_to_findag_tx;
+    use crate::iso20022::ISO20022Transaction;
+    use ed25519_dalek::SigningKey;
+
+    const PACS_008: &str = include_str!("../../tests/fixtures/iso20022/pacs.008.001.08.xml");
+    const PACS_009: &str = include_str!("../../tests/fixtures/iso20022/pacs.009.001.08.xml");
+    const PAIN_001: &str = include_str!("../../tests/fixtures/iso20022/pain.001.001.09.xml");
+
+    /// Parse a fixture and convert every transfer into a gateway-signed FinDAG transaction
+    fn convert(xml: &str, mappings: &[(IdentifierKind, &str, u8)]) -> (Iso20022Message, Vec<Transaction>, IngestionRegistry) {
+        let (registry, bank_key) = test_registry();
+        for (kind, identifier, seed) in mappings {
+            registry.map(*kind, identifier, "@bank", address(*seed).as_str()).unwrap();
+        }
+        let signer = GatewaySigner::new(Adapter::Iso20022, bank_key);
+        let message = parse_message(xml).unwrap();
+        let txs = message.transactions.iter()
+            .map(|tx| {
+                let iso_tx = ISO20022Transaction::from_credit_transfer(&message, tx).unwrap();
+                iso20022_to_findag_tx(&iso_tx, &registry, &signer).unwrap()
+            })
+            .collect();
+        (message, txs, registry)
+    }
+
+    fn address(seed: u8) -> Address {
+        Address::from_signing_key(&SigningKey::from_bytes(&[seed; 32]))
+    }
+
+    #[test]
+    fn test_pacs008_fixture_parse_and_convert() {
+        let (message, txs, registry) = convert(PACS_008, &[
+            (IdentifierKind::Lei, "529900T8BM49AURSDO55", 1),
+            (IdentifierKind::Bic, "BENEFRPP", 2),
+            (IdentifierKind::Account, "Client Holdings SA", 1),
+            (IdentifierKind::Account, "Supplier & Sons", 3),
+        ]);
+        assert_eq!(message.kind, MessageKind::Pacs008);
+        assert_eq!(message.group_header.number_of_transactions, 2);
+        assert_eq!(message.transactions[0].uetr.as_deref(), Some("8a562c67-ca16-48ba-b074-65581be6f011"));
+        assert_eq!(message.transactions[0].settlement_date.as_deref(), Some("2025-07-03"));
+
+        assert_eq!((txs[0].from.clone(), txs[0].to.clone(), txs[0].amount), (address(1), address(2), 123_456));
+        assert_eq!((txs[1].to.clone(), txs[1].amount), (address(3), 25_000_000));
+        assert_eq!(txs[0].payload, b"currency:EUR".to_vec());
+        assert_eq!(txs[0].bridge_protocol.as_deref(), Some("ISO20022"));
+        assert!(txs.iter().all(|tx| registry.verify(tx) == Some(Ok(()))));
+    }
+
+    #[test]
+    fn test_pacs009_fixture_parse_and_convert() {
+        let (message, txs, registry) = convert(PACS_009, &[
+            (IdentifierKind::Bic, "BOTKJPJT", 1),
+            (IdentifierKind::Bic, "CHASUS33XXX", 2),
+        ]);
+        assert_eq!(message.kind, MessageKind::Pacs009);
+        assert_eq!(message.message_identifier, "pacs.009.001.08");
+        assert_eq!(message.group_header.settlement_method.as_deref(), Some("INDA"));
+        let transfer = &message.transactions[0];
+        assert_eq!(transfer.instruction_id.as_deref(), Some("FI-INSTR-7"));
+        assert_eq!(transfer.amount.to_string(), "500000000");
+        assert_eq!(transfer.creditor.bic.as_deref(), Some("CHASUS33XXX"));
+
+        // JPY has no minor unit
+        assert_eq!((txs[0].from.clone(), txs[0].to.clone(), txs[0].amount), (address(1), address(2), 500_000_000));
+        assert_eq!(txs[0].payload, b"currency:JPY".to_vec());
+        assert_eq!(registry.verify(&txs[0]), Some(Ok(())));
+    }
+
+    #[test]
+    fn test_pain001_fixture_parse_and_convert() {
+        let (message, txs, registry) = convert(PAIN_001, &[
+            (IdentifierKind::Account, "Treasury Desk", 1),
+            (IdentifierKind::Account, "Vendor One Ltd", 2),
+            (IdentifierKind::Account, "Vendor Two Ltd", 3),
+            (IdentifierKind::Account, "Gulf Trading", 4),
+        ]);
+        assert_eq!(message.kind, MessageKind::Pain001);
+        assert_eq!(message.group_header.control_sum.as_deref(), Some("1500.75"));
+        assert_eq!(
+            message.transactions.iter().map(|t| t.payment_information_id.as_deref()).collect::<Vec<_>>(),
+            vec![Some("BATCH-A"), Some("BATCH-A"), Some("BATCH-B")]
+        );
+        assert_eq!(message.transactions[2].creditor_agent.as_ref().unwrap().bic.as_deref(), Some("NBOKKWKW"));
+
+        // Debtor is shared by each payment block; KWD has three decimals
+        assert!(txs.iter().all(|tx| tx.from == address(1)));
+        assert_eq!(txs.iter().map(|tx| (tx.to.clone(), tx.amount)).collect::<Vec<_>>(), vec![
+            (address(2), 100_050),
+            (address(3), 30_025),
+            (address(4), 200_000),
+        ]);
+        assert_eq!(txs[2].payload, b"currency:KWD".to_vec());
+        assert!(txs.iter().all(|tx| registry.verify(tx) == Some(Ok(()))));
+    }
+
+    #[test]
+    fn test_malformed_fixtures() {
+        let decimal_comma = include_str!("../../tests/fixtures/iso20022/malformed/pacs.008-decimal-comma.xml");
+        assert!(matches!(
+            parse_message(decimal_comma),
+            Err(ISO20022Error::InvalidAmount(f)) if f.starts_with("CdtTrfTxInf[0]/IntrBkSttlmAmt")
+        ));
+        let no_institution = include_str!("../../tests/fixtures/iso20022/malformed/pacs.009-missing-fininstnid.xml");
+        assert!(matches!(
+            parse_message(no_institution),
+            Err(ISO20022Error::MissingField(f)) if f == "CdtTrfTxInf[0]/Cdtr/FinInstnId"
+        ));
+        let bad_date = include_str!("../../tests/fixtures/iso20022/malformed/pain.001-invalid-execution-date.xml");
+        assert!(matches!(parse_message(bad_date), Err(ISO20022Error::InvalidDate(d)) if d == "04.07.2025"));
+    }
+
+    #[test]
+    fn test_malformed_fields_in_fixtures() {
+        // pacs.009: institutions are identified by a valid BICFI or LEI
+        let bad_bicfi = PACS_009.replace("<pacs:BICFI>BOTKJPJT</pacs:BICFI>", "<pacs:BICFI>BOTK JPJT</pacs:BICFI>");
+        assert!(matches!(
+            parse_message(&bad_bicfi),
+            Err(ISO20022Error::InvalidIdentifier { field, value }) if field == "CdtTrfTxInf[0]/Dbtr/BICFI" && value == "BOTK JPJT"
+        ));
+        let no_debtor = PACS_009.replace("<pacs:BICFI>BOTKJPJT</pacs:BICFI>", "");
+        assert!(matches!(
+            parse_message(&no_debtor),
+            Err(ISO20022Error::MissingField(f)) if f == "CdtTrfTxInf[0]/Dbtr/FinInstnId/BICFI"
+        ));
+        let bad_ccy = PACS_009.replace("Ccy=\"JPY\"", "Ccy=\"JP\"");
+        assert!(matches!(parse_message(&bad_ccy), Err(ISO20022Error::InvalidCurrency(_))));
+
+        // pain.001: amounts, payment block ids and creditors are mandatory
+        let no_ccy = PAIN_001.replace("<InstdAmt Ccy=\"GBP\">300.25", "<InstdAmt>300.25");
+        assert!(matches!(
+            parse_message(&no_ccy),
+            Err(ISO20022Error::MissingField(f)) if f == "PmtInf[0]/CdtTrfTxInf[1]/Amt/InstdAmt@Ccy"
+        ));
+        let no_block_id = PAIN_001.replace("<PmtInfId>BATCH-B</PmtInfId>", "");
+        assert!(matches!(parse_message(&no_block_id), Err(ISO20022Error::MissingField(f)) if f == "PmtInf[1]/PmtInfId"));
+        let no_creditor = PAIN_001.replace("<Nm>Gulf Trading</Nm>", "");
+        assert!(matches!(parse_message(&no_creditor), Err(ISO20022Error::MissingField(f)) if f == "PmtInf[1]/CdtTrfTxInf[0]/Cdtr/Nm"));
+        let fils = PAIN_001.replace("200.000", "200.0005");
+        let message = parse_message(&fils).unwrap();
+        assert!(matches!(message.transactions[2].amount.to_minor_units(), Err(ISO20022Error::InvalidAmount(_))));
+
+        // pacs.008: a debtor must be identifiable
+        let anonymous = PACS_008.replace("<Nm>Client Holdings SA</Nm>\n            </Dbtr>", "</Dbtr>");
+        assert!(matches!(parse_message(&anonymous), Err(ISO20022Error::MissingField(f)) if f == "CdtTrfTxInf[1]/Dbtr/Nm"));
+    }
+}
@@ -1,8 +1,15 @@
 pub mod schemas;
 pub mod handler;
+pub mod messages;
+pub mod reporting;
+pub mod export;
+pub mod settlement;
 
 use serde::{Deserialize, Serialize};
 
+pub use messages::{parse_message, Amount, CreditTransferTx, GroupHeader, Iso20022Message, MessageKind, Party};
+
+/// Flattened view of one credit transfer, amount in minor units of the currency
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ISO20022Transaction {
     pub message_id: String,
@@ -19,172 +26,236 @@ pub struct ISO20022Transaction {
 pub enum ISO20022Error {
     #[error("XML parsing error: {0}")]
     XmlParseError(String),
+    #[error("Unsupported message: {0}")]
+    UnsupportedMessage(String),
     #[error("Missing required field: {0}")]
     MissingField(String),
     #[error("Invalid amount format: {0}")]
     InvalidAmount(String),
+    #[error("Invalid currency code: {0}")]
+    InvalidCurrency(String),
     #[error("Invalid date format: {0}")]
     InvalidDate(String),
+    #[error("Invalid identifier in {field}: {value}")]
+    InvalidIdentifier { field: String, value: String },
+    #[error("NbOfTxs declares {declared} transactions but message contains {actual}")]
+    TransactionCountMismatch { declared: usize, actual: usize },
 }
 
-/// Parse an ISO20022 XML message into a structured transaction
-pub fn parse_iso20022(xml_content: &str) -> Result<ISO20022Transaction, ISO20022Error> {
-    // Simple XML parsing using string operations for now
-    // In a production environment, you'd use a proper XML parser
-    
-    let extract_field = |content: &str, field_name: &str| -> Result<String, ISO20022Error> {
-        let start_tag = format!("<{}>", field_name);
-        let end_tag = format!("</{}>", field_name);
-        
-        if let Some(start) = content.find(&start_tag) {
-            if let Some(end) = content.find(&end_tag) {
-                let start_pos = start + start_tag.len();
-                if start_pos < end {
-                    return Ok(content[start_pos..end].to_string());
-                }
-            }
-        }
-        Err(ISO20022Error::MissingField(field_name.to_string()))
-    };
-
-    let message_id = extract_field(xml_content, "message_id")?;
-    let creation_date_time = extract_field(xml_content, "creation_date_time")?;
-    let transaction_type = extract_field(xml_content, "transaction_type")?;
-    let debtor = extract_field(xml_content, "debtor")?;
-    let creditor = extract_field(xml_content, "creditor")?;
-    let amount_str = extract_field(xml_content, "amount")?;
-    let currency = extract_field(xml_content, "currency")?;
-    let findag_tx_id = extract_field(xml_content, "findag_tx_id")?;
-
-    let amount = amount_str.parse::<u64>()
-        .map_err(|_| ISO20022Error::InvalidAmount(amount_str))?;
-
-    Ok(ISO20022Transaction {
-        message_id,
-        creation_date_time,
-        transaction_type,
-        debtor,
-        creditor,
-        amount,
-        currency,
-        findag_tx_id,
-    })
+/// Parse a pacs.008, pacs.009 or pain.001 document into one transaction per credit transfer
+pub fn parse_iso20022(xml_content: &str) -> Result<Vec<ISO20022Transaction>, ISO20022Error> {
+    let message = parse_message(xml_content)?;
+    message
+        .transactions
+        .iter()
+        .map(|tx| ISO20022Transaction::from_credit_transfer(&message, tx))
+        .collect()
 }
 
-/// Export an ISO20022 transaction to XML format
+impl ISO20022Transaction {
+    /// Flatten one credit transfer of a parsed message
+    pub fn from_credit_transfer(message: &Iso20022Message, tx: &CreditTransferTx) -> Result<Self, ISO20022Error> {
+        Ok(ISO20022Transaction {
+            message_id: message.group_header.message_id.clone(),
+            creation_date_time: message.group_header.creation_date_time.clone(),
+            transaction_type: message.kind.code().to_string(),
+            debtor: tx.debtor.identifier().unwrap_or_default().to_string(),
+            creditor: tx.creditor.identifier().unwrap_or_default().to_string(),
+            amount: tx.amount.to_minor_units()?,
+            currency: tx.amount.currency.clone(),
+            findag_tx_id: tx.reference().to_string(),
+        })
+    }
+}
+
+/// Export an ISO20022 transaction as a pacs.008.001.08 document
 pub fn export_iso20022(transaction: &ISO20022Transaction) -> Result<String, ISO20022Error> {
+    let amount = Amount::from_minor_units(transaction.amount, &transaction.currency)?;
     let xml = format!(
-        r#"<ISO20022Transaction>
-    <message_id>{}</message_id>
-    <creation_date_time>{}</creation_date_time>
-    <transaction_type>{}</transaction_type>
-    <debtor>{}</debtor>
-    <creditor>{}</creditor>
-    <amount>{}</amount>
-    <currency>{}</currency>
-    <findag_tx_id>{}</findag_tx_id>
-</ISO20022Transaction>"#,
-        transaction.message_id,
-        transaction.creation_date_time,
-        transaction.transaction_type,
-        transaction.debtor,
-        transaction.creditor,
-        transaction.amount,
-        transaction.currency,
-        transaction.findag_tx_id
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:pacs.008.001.08">
+    <FIToFICstmrCdtTrf>
+        <GrpHdr>
+            <MsgId>{}</MsgId>
+            <CreDtTm>{}</CreDtTm>
+            <NbOfTxs>1</NbOfTxs>
+            <SttlmInf>
+                <SttlmMtd>CLRG</SttlmMtd>
+            </SttlmInf>
+        </GrpHdr>
+        <CdtTrfTxInf>
+            <PmtId>
+                <EndToEndId>{}</EndToEndId>
+            </PmtId>
+            <IntrBkSttlmAmt Ccy="{}">{}</IntrBkSttlmAmt>
+            <ChrgBr>SLEV</ChrgBr>
+            <Dbtr>
+                {}
+            </Dbtr>
+            <Cdtr>
+                {}
+            </Cdtr>
+        </CdtTrfTxInf>
+    </FIToFICstmrCdtTrf>
+</Document>"#,
+        messages::escape_xml(&transaction.message_id),
+        messages::escape_xml(&transaction.creation_date_time),
+        messages::escape_xml(&transaction.findag_tx_id),
+        amount.currency,
+        amount,
+        party_xml(&transaction.debtor),
+        party_xml(&transaction.creditor),
     );
-    
+
     Ok(xml)
 }
 
+/// Party element content: BIC and LEI go under OrgId, anything else is a name
+fn party_xml(identifier: &str) -> String {
+    if messages::is_valid_bic(identifier) {
+        format!("<Id><OrgId><AnyBIC>{}</AnyBIC></OrgId></Id>", identifier)
+    } else if messages::is_valid_lei(identifier) {
+        format!("<Id><OrgId><LEI>{}</LEI></OrgId></Id>", identifier)
+    } else {
+        format!("<Nm>{}</Nm>", messages::escape_xml(identifier))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::iso20022::schemas;
 
     #[test]
-    fn test_parse_valid_settlement() {
-        let tx = parse_iso20022(schemas::SETTLEMENT_TRANSACTION)
-            .expect("Should parse valid settlement transaction");
-        assert_eq!(tx.transaction_type, "Settlement");
-        assert_eq!(tx.amount, 50000000);
-        assert_eq!(tx.currency, "EUR");
-    }
+    fn test_parse_pacs008() {
+        let message = parse_message(schemas::PACS_008).expect("Should parse pacs.008");
+        assert_eq!(message.kind, MessageKind::Pacs008);
+        assert_eq!(message.message_identifier, "pacs.008.001.08");
+        assert_eq!(message.group_header.message_id, "PACS008-20250703-0001");
+        assert_eq!(message.group_header.settlement_method.as_deref(), Some("CLRG"));
+        assert_eq!(message.transactions.len(), 2);
 
-    #[test]
-    fn test_parse_valid_credit_transfer() {
-        let tx = parse_iso20022(schemas::CREDIT_TRANSFER)
-            .expect("Should parse valid credit transfer transaction");
-        assert_eq!(tx.transaction_type, "CreditTransfer");
-        assert_eq!(tx.amount, 250000);
-        assert_eq!(tx.currency, "USD");
+        let first = &message.transactions[0];
+        assert_eq!(first.amount.to_string(), "1234.56");
+        assert_eq!(first.amount.currency, "EUR");
+        assert_eq!(first.debtor.lei.as_deref(), Some("529900T8BM49AURSDO55"));
+        assert_eq!(first.debtor.account.as_deref(), Some("DE89370400440532013000"));
+        assert_eq!(first.creditor.bic.as_deref(), Some("BENEFRPP"));
+        assert_eq!(first.debtor_agent.as_ref().unwrap().bic.as_deref(), Some("DEUTDEFFXXX"));
+        assert_eq!(first.reference(), "8a562c67-ca16-48ba-b074-65581be6f011");
+        assert_eq!(first.remittance_info.as_deref(), Some("Invoice 2025-117"));
+
+        let second = &message.transactions[1];
+        assert_eq!(second.creditor.name.as_deref(), Some("Supplier & Sons"));
+        assert_eq!(second.creditor_agent.as_ref().unwrap().lei.as_deref(), Some("7LTWFZYICNSX8D621K86"));
+
+        let txs = parse_iso20022(schemas::PACS_008).unwrap();
+        assert_eq!(txs[0].amount, 123456);
+        assert_eq!(txs[0].debtor, "529900T8BM49AURSDO55");
+        assert_eq!(txs[0].creditor, "BENEFRPP");
+        assert_eq!(txs[1].amount, 25000000);
+        assert_eq!(txs[1].findag_tx_id, "E2E-002");
     }
 
     #[test]
-    fn test_serialize_roundtrip() {
-        let tx = parse_iso20022(schemas::SETTLEMENT_TRANSACTION).unwrap();
-        let xml_back = export_iso20022(&tx).unwrap();
-        assert!(xml_back.contains("<ISO20022Transaction>"));
-        assert!(xml_back.contains("EuroclearBank"));
+    fn test_parse_prefixed_pacs009() {
+        let message = parse_message(schemas::PACS_009).expect("Should parse pacs.009");
+        assert_eq!(message.kind, MessageKind::Pacs009);
+        let tx = &message.transactions[0];
+        assert_eq!(tx.debtor.bic.as_deref(), Some("BOTKJPJT"));
+        assert_eq!(tx.creditor.lei.as_deref(), Some("7LTWFZYICNSX8D621K86"));
+
+        let txs = parse_iso20022(schemas::PACS_009).unwrap();
+        assert_eq!(txs[0].transaction_type, "pacs.009");
+        assert_eq!(txs[0].amount, 500000000);
+        assert_eq!(txs[0].currency, "JPY");
     }
 
     #[test]
-    fn test_parse_invalid_message() {
-        let result = parse_iso20022(schemas::INVALID_MESSAGE);
-        assert!(result.is_err(), "Invalid message should fail parsing");
+    fn test_parse_pain001_payment_blocks() {
+        let message = parse_message(schemas::PAIN_001).expect("Should parse pain.001");
+        assert_eq!(message.kind, MessageKind::Pain001);
+        assert_eq!(message.transactions.len(), 3);
+        assert_eq!(
+            message.group_header.initiating_party.as_ref().unwrap().lei.as_deref(),
+            Some("5493001KJTIIGC8Y1R12")
+        );
+
+        let first = &message.transactions[0];
+        assert_eq!(first.payment_information_id.as_deref(), Some("BATCH-A"));
+        assert_eq!(first.settlement_date.as_deref(), Some("2025-07-04"));
+        assert_eq!(first.creditor.account.as_deref(), Some("12345678"));
+        assert_eq!(first.debtor_agent.as_ref().unwrap().bic.as_deref(), Some("NWBKGB2L"));
+
+        let txs = parse_iso20022(schemas::PAIN_001).unwrap();
+        assert_eq!(txs.iter().map(|t| t.amount).collect::<Vec<_>>(), vec![100050, 30025, 200000]);
+        assert_eq!(txs[2].currency, "KWD");
     }
 
     #[test]
-    fn test_parse_missing_fields() {
-        let invalid_xml = r#"
-<ISO20022Transaction>
-    <message_id>msg-123</message_id>
-    <!-- Missing required fields -->
-</ISO20022Transaction>
-"#;
-        let result = parse_iso20022(invalid_xml);
-        assert!(result.is_err());
+    fn test_rejects_unsupported_messages() {
+        assert!(matches!(parse_iso20022(schemas::LEGACY_MESSAGE), Err(ISO20022Error::UnsupportedMessage(_))));
+        let camt = schemas::PACS_008.replace("pacs.008.001.08", "camt.053.001.08");
+        assert!(matches!(parse_iso20022(&camt), Err(ISO20022Error::UnsupportedMessage(_))));
+        // Elements from a foreign namespace are not picked up
+        let foreign = schemas::PACS_008.replace("<FIToFICstmrCdtTrf>", "<FIToFICstmrCdtTrf xmlns=\"urn:example\">");
+        assert!(matches!(parse_iso20022(&foreign), Err(ISO20022Error::MissingField(f)) if f == "FIToFICstmrCdtTrf"));
+        assert!(matches!(parse_iso20022("<Document"), Err(ISO20022Error::XmlParseError(_))));
     }
 
     #[test]
-    fn test_parse_invalid_amount() {
-        let invalid_xml = r#"
-<ISO20022Transaction>
-    <message_id>msg-123</message_id>
-    <creation_date_time>2025-07-03T12:30:00Z</creation_date_time>
-    <transaction_type>Settlement</transaction_type>
-    <debtor>BankA</debtor>
-    <creditor>BankB</creditor>
-    <amount>invalid</amount>
-    <currency>EUR</currency>
-    <findag_tx_id>fdg1qhash</findag_tx_id>
-</ISO20022Transaction>
-"#;
-        let result = parse_iso20022(invalid_xml);
-        assert!(result.is_err());
+    fn test_structured_field_errors() {
+        assert!(matches!(parse_iso20022(schemas::MISSING_MSG_ID), Err(ISO20022Error::MissingField(f)) if f == "GrpHdr/MsgId"));
+
+        let no_e2e = schemas::PACS_008.replace("<EndToEndId>E2E-002</EndToEndId>", "");
+        assert!(matches!(parse_iso20022(&no_e2e), Err(ISO20022Error::MissingField(f)) if f == "CdtTrfTxInf[1]/PmtId/EndToEndId"));
+
+        let no_ccy = schemas::PACS_008.replace("<IntrBkSttlmAmt Ccy=\"EUR\">250000", "<IntrBkSttlmAmt>250000");
+        assert!(matches!(parse_iso20022(&no_ccy), Err(ISO20022Error::MissingField(f)) if f == "CdtTrfTxInf[1]/IntrBkSttlmAmt@Ccy"));
+
+        let bad_bic = schemas::PACS_008.replace("BENEFRPP", "BENE-FR");
+        assert!(matches!(parse_iso20022(&bad_bic), Err(ISO20022Error::InvalidIdentifier { field, .. }) if field == "CdtTrfTxInf[0]/Cdtr/Id/OrgId/AnyBIC"));
+
+        let bad_lei = schemas::PACS_008.replace("529900T8BM49AURSDO55", "529900T8BM49AURSDO56");
+        assert!(matches!(parse_iso20022(&bad_lei), Err(ISO20022Error::InvalidIdentifier { .. })));
+
+        let bad_count = schemas::PACS_008.replace("<NbOfTxs>2</NbOfTxs>", "<NbOfTxs>3</NbOfTxs>");
+        assert!(matches!(
+            parse_iso20022(&bad_count),
+            Err(ISO20022Error::TransactionCountMismatch { declared: 3, actual: 2 })
+        ));
+
+        let bad_date = schemas::PACS_008.replace("2025-07-03T14:00:00Z", "03/07/2025");
+        assert!(matches!(parse_iso20022(&bad_date), Err(ISO20022Error::InvalidDate(_))));
     }
 
     #[test]
-    fn test_roundtrip_credit_transfer() {
-        let original_tx = parse_iso20022(schemas::CREDIT_TRANSFER).unwrap();
-        let xml = export_iso20022(&original_tx).unwrap();
-        let parsed_tx = parse_iso20022(&xml).unwrap();
-        
-        assert_eq!(original_tx, parsed_tx);
+    fn test_decimal_amounts() {
+        assert_eq!(Amount::parse("0001234.5000", "EUR", "f").unwrap().to_string(), "1234.5");
+        assert_eq!(Amount::parse("42", "USD", "f").unwrap().to_minor_units().unwrap(), 4200);
+        assert!(matches!(Amount::parse("-5", "EUR", "f"), Err(ISO20022Error::InvalidAmount(_))));
+        assert!(matches!(Amount::parse("1.", "EUR", "f"), Err(ISO20022Error::InvalidAmount(_))));
+        assert!(matches!(Amount::parse("1.123456", "EUR", "f"), Err(ISO20022Error::InvalidAmount(_))));
+        assert!(matches!(Amount::parse("1e5", "EUR", "f"), Err(ISO20022Error::InvalidAmount(_))));
+        assert!(matches!(Amount::parse("10", "eur", "f"), Err(ISO20022Error::InvalidCurrency(_))));
+        // Sub-minor-unit precision cannot be represented on chain
+        let yen = Amount::parse("100.5", "JPY", "f").unwrap();
+        assert!(matches!(yen.to_minor_units(), Err(ISO20022Error::InvalidAmount(_))));
+        assert_eq!(Amount::from_minor_units(5, "EUR").unwrap().to_string(), "0.05");
     }
 
     #[test]
-    fn test_iso20022_schemas_accessible() {
-        // Test that all schema constants are accessible
-        assert!(!schemas::SETTLEMENT_TRANSACTION.is_empty());
-        assert!(!schemas::CREDIT_TRANSFER.is_empty());
-        assert!(!schemas::INVALID_MESSAGE.is_empty());
-        
-        // Test that they contain expected content
-        assert!(schemas::SETTLEMENT_TRANSACTION.contains("Settlement"));
-        assert!(schemas::CREDIT_TRANSFER.contains("CreditTransfer"));
-        assert!(schemas::INVALID_MESSAGE.contains("msg-bad"));
-        
-        println!("ISO20022 schemas integration test passed!");
+    fn test_export_roundtrip() {
+        for tx in parse_iso20022(schemas::PACS_008).unwrap() {
+            let xml = export_iso20022(&tx).unwrap();
+            assert!(xml.contains("urn:iso:std:iso:20022:tech:xsd:pacs.008.001.08"));
+            let mut parsed = parse_iso20022(&xml).unwrap();
+            assert_eq!(parsed.len(), 1);
+            let parsed = parsed.remove(0);
+            assert_eq!(parsed.amount, tx.amount);
+            assert_eq!(parsed.debtor, tx.debtor);
+            assert_eq!(parsed.creditor, tx.creditor);
+            assert_eq!(parsed.findag_tx_id, tx.findag_tx_id);
+        }
     }
 }
@@ -0,0 +1,473 @@
+use crate::core::tx_status::{TxStatus, TxStatusRegistry};
+use crate::iso20022::messages::escape_xml;
+use crate::iso20022::{Amount, ISO20022Error};
+use crate::storage::state::{AccountEntry, EntryDirection, StateDB};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const PACS_002_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:pacs.002.001.10";
+pub const CAMT_053_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:camt.053.001.08";
+pub const CAMT_054_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:camt.054.001.08";
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// An inbound payment whose FinDAG settlement is reported back in a pacs.002
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentStatus {
+    pub end_to_end_id: String,
+    #[serde(default)]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    pub uetr: Option<String>,
+    pub tx_hash: [u8; 32],
+    #[serde(default)]
+    pub status: Option<TxStatus>,
+}
+
+/// ISO 20022 transaction status code and optional reason text for a FinDAG status
+pub fn iso_status_code(status: Option<&TxStatus>) -> (&'static str, Option<String>) {
+    match status {
+        Some(TxStatus::Pending) => ("PDNG", None),
+        Some(TxStatus::Included { .. }) => ("ACSP", None),
+        Some(TxStatus::Finalized { .. }) => ("ACSC", None),
+        Some(TxStatus::Rejected { reason }) => ("RJCT", Some(reason.clone())),
+        Some(TxStatus::Expired) => ("RJCT", Some("validity window expired before inclusion".to_string())),
+        None => ("RJCT", Some("transaction unknown to this node".to_string())),
+    }
+}
+
+/// Group status: the common code when all payments agree, otherwise partially accepted
+fn group_status(codes: &[&'static str]) -> &'static str {
+    match codes.first() {
+        Some(first) if codes.iter().all(|c| c == first) => first,
+        _ => "PART",
+    }
+}
+
+/// Build a pacs.002 FI to FI payment status report for an original message
+pub fn pacs002_status_report(
+    message_id: &str,
+    created_at: u64,
+    original_message_id: &str,
+    original_message_type: &str,
+    payments: &[PaymentStatus],
+) -> String {
+    let codes: Vec<&'static str> = payments.iter().map(|p| iso_status_code(p.status.as_ref()).0).collect();
+    let mut transactions = String::new();
+    for (i, payment) in payments.iter().enumerate() {
+        let (code, reason) = iso_status_code(payment.status.as_ref());
+        transactions.push_str(&format!(
+            "\n        <TxInfAndSts>\n            <StsId>{}-{}</StsId>\n            <OrgnlEndToEndId>{}</OrgnlEndToEndId>",
+            escape_xml(message_id), i + 1, escape_xml(&payment.end_to_end_id)
+        ));
+        if let Some(tx_id) = &payment.transaction_id {
+            transactions.push_str(&format!("\n            <OrgnlTxId>{}</OrgnlTxId>", escape_xml(tx_id)));
+        }
+        if let Some(uetr) = &payment.uetr {
+            transactions.push_str(&format!("\n            <OrgnlUETR>{}</OrgnlUETR>", escape_xml(uetr)));
+        }
+        transactions.push_str(&format!("\n            <TxSts>{}</TxSts>", code));
+        if let Some(reason) = reason {
+            transactions.push_str(&format!(
+                "\n            <StsRsnInf>\n                <Rsn>\n                    <Cd>NARR</Cd>\n                </Rsn>\n                <AddtlInf>{}</AddtlInf>\n            </StsRsnInf>",
+                escape_xml(&reason)
+            ));
+        }
+        if let Some(TxStatus::Finalized { round_number, .. }) = &payment.status {
+            transactions.push_str(&format!("\n            <AccptncDtTm>{}</AccptncDtTm>", format_date_time(created_at)));
+            transactions.push_str(&format!("\n            <ClrSysRef>round-{}</ClrSysRef>", round_number));
+        }
+        transactions.push_str(&format!("\n            <AcctSvcrRef>{}</AcctSvcrRef>\n        </TxInfAndSts>", hex::encode(payment.tx_hash)));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="{}">
+    <FIToFIPmtStsRpt>
+        <GrpHdr>
+            <MsgId>{}</MsgId>
+            <CreDtTm>{}</CreDtTm>
+        </GrpHdr>
+        <OrgnlGrpInfAndSts>
+            <OrgnlMsgId>{}</OrgnlMsgId>
+            <OrgnlMsgNmId>{}</OrgnlMsgNmId>
+            <OrgnlNbOfTxs>{}</OrgnlNbOfTxs>
+            <GrpSts>{}</GrpSts>
+        </OrgnlGrpInfAndSts>{}
+    </FIToFIPmtStsRpt>
+</Document>"#,
+        PACS_002_NAMESPACE,
+        escape_xml(message_id),
+        format_date_time(created_at),
+        escape_xml(original_message_id),
+        escape_xml(original_message_type),
+        payments.len(),
+        group_status(&codes),
+        transactions,
+    )
+}
+
+/// An account booking together with its current lifecycle status
+#[derive(Debug, Clone)]
+pub struct ReportEntry {
+    pub entry: AccountEntry,
+    pub status: Option<TxStatus>,
+}
+
+impl ReportEntry {
+    /// Round the entry's transaction was finalized in, if any
+    pub fn finalized_round(&self) -> Option<u64> {
+        match self.status {
+            Some(TxStatus::Finalized { round_number, .. }) => Some(round_number),
+            _ => None,
+        }
+    }
+}
+
+/// Bookings of one account in one currency over `[from, to)`, with the balances around them
+#[derive(Debug, Clone)]
+pub struct AccountReport {
+    pub account: String,
+    pub currency: String,
+    pub from: u64,
+    pub to: u64,
+    pub opening_balance: u64,
+    pub closing_balance: u64,
+    pub entries: Vec<ReportEntry>,
+}
+
+impl AccountReport {
+    /// Collect the account history of `account` in `currency` over `[from, to)`
+    pub fn build(state_db: &StateDB, tx_status: &TxStatusRegistry, account: &str, currency: &str, from: u64, to: u64) -> Self {
+        let entries: Vec<ReportEntry> = state_db.account_entries(account, from, to)
+            .into_iter()
+            .filter(|entry| entry.asset == currency)
+            .map(|entry| ReportEntry { status: tx_status.status(&entry.tx_hash), entry })
+            .collect();
+        let opening_balance = state_db.last_account_entry_before(account, currency, from)
+            .map(|entry| entry.balance_after)
+            .or_else(|| entries.first().map(|first| match first.entry.direction {
+                EntryDirection::Credit => first.entry.balance_after - first.entry.amount,
+                EntryDirection::Debit => first.entry.balance_after + first.entry.amount,
+            }))
+            // Never booked: the balance has not moved since it was set
+            .unwrap_or_else(|| state_db.get_balance(0, account, currency));
+        let closing_balance = entries.last()
+            .map(|last| last.entry.balance_after)
+            .unwrap_or(opening_balance);
+        AccountReport {
+            account: account.to_string(),
+            currency: currency.to_string(),
+            from,
+            to,
+            opening_balance,
+            closing_balance,
+            entries,
+        }
+    }
+
+    /// Report covering one UTC day (days since the Unix epoch)
+    pub fn for_day(state_db: &StateDB, tx_status: &TxStatusRegistry, account: &str, currency: &str, day: u64) -> Self {
+        Self::build(state_db, tx_status, account, currency, day * SECONDS_PER_DAY, (day + 1) * SECONDS_PER_DAY)
+    }
+
+    /// Keep only the entries finalized in `round`
+    pub fn finalized_in(mut self, round: u64) -> Self {
+        self.entries.retain(|e| e.finalized_round() == Some(round));
+        self
+    }
+}
+
+/// Build a camt.053 end-of-day statement; balances are the booked positions around the period
+pub fn camt053_statement(message_id: &str, created_at: u64, sequence_number: u64, report: &AccountReport) -> Result<String, ISO20022Error> {
+    let opening = Amount::from_minor_units(report.opening_balance, &report.currency)?;
+    let closing = Amount::from_minor_units(report.closing_balance, &report.currency)?;
+    let mut credits = (0usize, 0u64);
+    let mut debits = (0usize, 0u64);
+    for e in &report.entries {
+        let total = match e.entry.direction {
+            EntryDirection::Credit => &mut credits,
+            EntryDirection::Debit => &mut debits,
+        };
+        total.0 += 1;
+        total.1 += e.entry.amount;
+    }
+    let balance = |code: &str, amount: &Amount, at: u64| format!(
+        "\n            <Bal>\n                <Tp>\n                    <CdOrPrtry>\n                        <Cd>{}</Cd>\n                    </CdOrPrtry>\n                </Tp>\n                <Amt Ccy=\"{}\">{}</Amt>\n                <CdtDbtInd>CRDT</CdtDbtInd>\n                <Dt>\n                    <Dt>{}</Dt>\n                </Dt>\n            </Bal>",
+        code, amount.currency, amount, format_date(at)
+    );
+    let summary = |count: usize, sum: u64| -> Result<String, ISO20022Error> {
+        Ok(format!(
+            "<NbOfNtries>{}</NbOfNtries>\n                    <Sum>{}</Sum>",
+            count, Amount::from_minor_units(sum, &report.currency)?
+        ))
+    };
+
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="{}">
+    <BkToCstmrStmt>
+        <GrpHdr>
+            <MsgId>{}</MsgId>
+            <CreDtTm>{}</CreDtTm>
+        </GrpHdr>
+        <Stmt>
+            <Id>{}</Id>
+            <ElctrncSeqNb>{}</ElctrncSeqNb>
+            <CreDtTm>{}</CreDtTm>
+            <FrToDt>
+                <FrDtTm>{}</FrDtTm>
+                <ToDtTm>{}</ToDtTm>
+            </FrToDt>{}{}{}
+            <TxsSummry>
+                <TtlNtries>
+                    <NbOfNtries>{}</NbOfNtries>
+                </TtlNtries>
+                <TtlCdtNtries>
+                    {}
+                </TtlCdtNtries>
+                <TtlDbtNtries>
+                    {}
+                </TtlDbtNtries>
+            </TxsSummry>{}
+        </Stmt>
+    </BkToCstmrStmt>
+</Document>"#,
+        CAMT_053_NAMESPACE,
+        escape_xml(message_id),
+        format_date_time(created_at),
+        escape_xml(message_id),
+        sequence_number,
+        format_date_time(created_at),
+        format_date_time(report.from),
+        format_date_time(report.to),
+        account_xml(report),
+        balance("OPBD", &opening, report.from),
+        balance("CLBD", &closing, report.to.saturating_sub(1)),
+        report.entries.len(),
+        summary(credits.0, credits.1)?,
+        summary(debits.0, debits.1)?,
+        entries_xml(report)?,
+    ))
+}
+
+/// Build a camt.054 debit/credit notification for the entries of a report
+pub fn camt054_notification(message_id: &str, created_at: u64, notification_id: &str, report: &AccountReport) -> Result<String, ISO20022Error> {
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="{}">
+    <BkToCstmrDbtCdtNtfctn>
+        <GrpHdr>
+            <MsgId>{}</MsgId>
+            <CreDtTm>{}</CreDtTm>
+        </GrpHdr>
+        <Ntfctn>
+            <Id>{}</Id>
+            <CreDtTm>{}</CreDtTm>{}{}
+        </Ntfctn>
+    </BkToCstmrDbtCdtNtfctn>
+</Document>"#,
+        CAMT_054_NAMESPACE,
+        escape_xml(message_id),
+        format_date_time(created_at),
+        escape_xml(notification_id),
+        format_date_time(created_at),
+        account_xml(report),
+        entries_xml(report)?,
+    ))
+}
+
+fn account_xml(report: &AccountReport) -> String {
+    format!(
+        "\n            <Acct>\n                <Id>\n                    <Othr>\n                        <Id>{}</Id>\n                    </Othr>\n                </Id>\n                <Ccy>{}</Ccy>\n            </Acct>",
+        escape_xml(&report.account), escape_xml(&report.currency)
+    )
+}
+
+fn entries_xml(report: &AccountReport) -> Result<String, ISO20022Error> {
+    let mut xml = String::new();
+    for e in &report.entries {
+        let entry = &e.entry;
+        let amount = Amount::from_minor_units(entry.amount, &entry.asset)?;
+        let (indicator, counterparty_role) = match entry.direction {
+            EntryDirection::Credit => ("CRDT", "Dbtr"),
+            EntryDirection::Debit => ("DBIT", "Cdtr"),
+        };
+        // Only finalized transactions are booked; the rest are reported as pending
+        let status = if e.finalized_round().is_some() { "BOOK" } else { "PDNG" };
+        let tx_hash = hex::encode(entry.tx_hash);
+        xml.push_str(&format!(
+            r#"
+            <Ntry>
+                <NtryRef>{tx_hash}:{}</NtryRef>
+                <Amt Ccy="{}">{}</Amt>
+                <CdtDbtInd>{indicator}</CdtDbtInd>
+                <Sts>
+                    <Cd>{status}</Cd>
+                </Sts>
+                <BookgDt>
+                    <DtTm>{}</DtTm>
+                </BookgDt>
+                <ValDt>
+                    <Dt>{}</Dt>
+                </ValDt>
+                <AcctSvcrRef>{tx_hash}</AcctSvcrRef>
+                <BkTxCd>
+                    <Prtry>
+                        <Cd>FINDAG</Cd>
+                    </Prtry>
+                </BkTxCd>
+                <NtryDtls>
+                    <TxDtls>
+                        <Refs>
+                            <AcctSvcrRef>{tx_hash}</AcctSvcrRef>
+                        </Refs>
+                        <RltdPties>
+                            <{counterparty_role}>
+                                <Pty>
+                                    <Nm>{}</Nm>
+                                </Pty>
+                            </{counterparty_role}>
+                        </RltdPties>
+                    </TxDtls>
+                </NtryDtls>
+            </Ntry>"#,
+            entry.leg,
+            amount.currency,
+            amount,
+            format_date_time(entry.booked_at),
+            format_date(entry.booked_at),
+            escape_xml(&entry.counterparty),
+        ));
+    }
+    Ok(xml)
+}
+
+pub(crate) fn format_date_time(unix_seconds: u64) -> String {
+    DateTime::<Utc>::from_timestamp(unix_seconds as i64, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+pub(crate) fn format_date(unix_seconds: u64) -> String {
+    DateTime::<Utc>::from_timestamp(unix_seconds as i64, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Message id for generated reports, e.g. `CAMT053-1751500800-7`
+pub fn report_message_id(prefix: &str, created_at: u64, sequence: u64) -> String {
+    format!("{prefix}-{created_at}-{sequence}")
+}
+
+/// Days since the Unix epoch of a Unix timestamp
+pub fn day_of(unix_seconds: u64) -> u64 {
+    unix_seconds / SECONDS_PER_DAY
+}
+
+/// Parse a `YYYY-MM-DD` date into days since the Unix epoch
+pub fn parse_day(date: &str) -> Result<u64, ISO20022Error> {
+    let date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| ISO20022Error::InvalidDate(date.to_string()))?;
+    let seconds = date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp();
+    u64::try_from(seconds)
+        .map(day_of)
+        .map_err(|_| ISO20022Error::InvalidDate(date.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::address::Address;
+    use crate::core::executor::apply_transaction;
+    use crate::core::multi_leg::{MultiLegTransaction, TransferLeg};
+    use crate::core::types::ShardId;
+    use ed25519_dalek::SigningKey;
+    use xmltree::Element;
+
+    fn text(element: &Element, path: &[&str]) -> String {
+        path.iter()
+            .try_fold(element, |e, name| e.get_child(*name))
+            .and_then(|e| e.get_text())
+            .map(|t| t.to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_statements_and_status_reports_from_account_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::new(dir.path().to_str().unwrap());
+        let tx_status = TxStatusRegistry::default();
+        let alice = SigningKey::from_bytes(&[1u8; 32]);
+        let bob = SigningKey::from_bytes(&[2u8; 32]);
+        let alice_addr = Address::from_verifying_key(&alice.verifying_key());
+        let bob_addr = Address::from_verifying_key(&bob.verifying_key());
+        state_db.set_balance(0, alice_addr.as_str(), "USD", 10_000).unwrap();
+
+        // Alice pays 12.34 and gets 1.00 back within the same transaction
+        let mut multi = MultiLegTransaction::new(
+            vec![
+                TransferLeg { from: alice_addr.clone(), to: bob_addr.clone(), asset: "USD".to_string(), amount: 1_234 },
+                TransferLeg { from: bob_addr.clone(), to: alice_addr.clone(), asset: "USD".to_string(), amount: 100 },
+            ],
+            "INV-1".to_string(),
+            1_700_000_000 << 24,
+            ShardId(0),
+        );
+        multi.sign(&alice);
+        multi.sign(&bob);
+        let tx = multi.to_transaction().unwrap();
+        let tx_hash = tx.compute_hash();
+        apply_transaction(&state_db, &tx).unwrap();
+        tx_status.mark_included([7u8; 32], &[tx_hash]);
+
+        // Booked on the day of the transaction's FinDAG Time, whenever the node replays it
+        let today = day_of(1_700_000_000);
+        let report = AccountReport::for_day(&state_db, &tx_status, alice_addr.as_str(), "USD", today);
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.opening_balance, 10_000);
+        assert_eq!(report.closing_balance, 8_866);
+        assert_eq!(report.entries[0].entry.balance_after, 8_766);
+
+        let statement = camt053_statement("CAMT053-1", 0, 1, &report).unwrap();
+        let document = Element::parse(statement.as_bytes()).unwrap();
+        assert_eq!(document.namespace.as_deref(), Some(CAMT_053_NAMESPACE));
+        let stmt = document.get_child("BkToCstmrStmt").unwrap().get_child("Stmt").unwrap();
+        let balances: Vec<String> = stmt.children.iter()
+            .filter_map(|n| n.as_element())
+            .filter(|e| e.name == "Bal")
+            .map(|e| text(e, &["Amt"]))
+            .collect();
+        assert_eq!(balances, vec!["100.00", "88.66"]);
+        assert_eq!(text(stmt, &["TxsSummry", "TtlDbtNtries", "Sum"]), "12.34");
+        assert_eq!(text(stmt, &["Ntry", "Sts", "Cd"]), "PDNG");
+
+        // Only finalized entries show up in the round's notification, as booked
+        assert!(report.clone().finalized_in(3).entries.is_empty());
+        tx_status.mark_round_finalized(3, &[[7u8; 32]]);
+        let report = AccountReport::for_day(&state_db, &tx_status, bob_addr.as_str(), "USD", today).finalized_in(3);
+        let notification = camt054_notification("CAMT054-1", 0, "round-3", &report).unwrap();
+        let document = Element::parse(notification.as_bytes()).unwrap();
+        let ntfctn = document.get_child("BkToCstmrDbtCdtNtfctn").unwrap().get_child("Ntfctn").unwrap();
+        assert_eq!(text(ntfctn, &["Ntry", "Sts", "Cd"]), "BOOK");
+        assert_eq!(text(ntfctn, &["Ntry", "CdtDbtInd"]), "CRDT");
+        assert_eq!(text(ntfctn, &["Ntry", "NtryDtls", "TxDtls", "RltdPties", "Dbtr", "Pty", "Nm"]), alice_addr.as_str());
+
+        let payments = vec![
+            PaymentStatus { end_to_end_id: "E2E-1".to_string(), transaction_id: None, uetr: None, tx_hash, status: tx_status.status(&tx_hash) },
+            PaymentStatus { end_to_end_id: "E2E-2".to_string(), transaction_id: None, uetr: None, tx_hash: [9u8; 32], status: None },
+        ];
+        let report = pacs002_status_report("PACS002-1", 0, "PACS008-1", "pacs.008.001.08", &payments);
+        let document = Element::parse(report.as_bytes()).unwrap();
+        let root = document.get_child("FIToFIPmtStsRpt").unwrap();
+        assert_eq!(text(root, &["OrgnlGrpInfAndSts", "GrpSts"]), "PART");
+        let statuses: Vec<String> = root.children.iter()
+            .filter_map(|n| n.as_element())
+            .filter(|e| e.name == "TxInfAndSts")
+            .map(|e| text(e, &["TxSts"]))
+            .collect();
+        assert_eq!(statuses, vec!["ACSC", "RJCT"]);
+    }
+}
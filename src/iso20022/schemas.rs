@@ -1,5 +1,120 @@
-/// Example ISO 20022 Settlement Transaction (semt.017)
-pub const SETTLEMENT_TRANSACTION: &str = r#"
+/// Example pacs.008.001.08 FI to FI customer credit transfer with two transactions
+pub const PACS_008: &str = include_str!("../../tests/fixtures/iso20022/pacs.008.001.08.xml");
+
+/// Example pacs.009.001.08 financial institution credit transfer, using a namespace prefix
+pub const PACS_009: &str = include_str!("../../tests/fixtures/iso20022/pacs.009.001.08.xml");
+
+/// Example pain.001.001.09 customer credit transfer initiation with two payment blocks
+pub const PAIN_001: &str = include_str!("../../tests/fixtures/iso20022/pain.001.001.09.xml");
+
+/// Example sese.023.001.09 delivery against payment of 10 Bunds for EUR 1000.00 (seller's side)
+pub const SESE_023_DELIVER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:sese.023.001.09">
+    <SctiesSttlmTxInstr>
+        <TxId>SELL-0001</TxId>
+        <SttlmTpAndAddtlParams>
+            <SctiesMvmntTp>DELI</SctiesMvmntTp>
+            <Pmt>APMT</Pmt>
+            <CmonId>TRADE-2025-0703-17</CmonId>
+        </SttlmTpAndAddtlParams>
+        <TradDtls>
+            <TradDt>
+                <Dt>
+                    <Dt>2025-07-03</Dt>
+                </Dt>
+            </TradDt>
+            <SttlmDt>
+                <Dt>
+                    <Dt>2025-07-07</Dt>
+                </Dt>
+            </SttlmDt>
+        </TradDtls>
+        <FinInstrmId>
+            <ISIN>DE0001135275</ISIN>
+        </FinInstrmId>
+        <QtyAndAcctDtls>
+            <SttlmQty>
+                <Qty>
+                    <FaceAmt>10</FaceAmt>
+                </Qty>
+            </SttlmQty>
+            <SfkpgAcct>
+                <Id>fdg1x36slx9at870e9rd53d2405n80s4ff94p98pcj4lg2rx2p0ne9lq9sdxq0</Id>
+            </SfkpgAcct>
+        </QtyAndAcctDtls>
+        <RcvgSttlmPties>
+            <Pty1>
+                <Id>
+                    <AnyBIC>BNPAFRPP</AnyBIC>
+                </Id>
+                <SfkpgAcct>
+                    <Id>fdg1dguq840stxgz58rd477fhfrjjgf00j4vpp35esaww6e8220s8qns4sj3yv</Id>
+                </SfkpgAcct>
+            </Pty1>
+        </RcvgSttlmPties>
+        <SttlmAmt>
+            <Amt Ccy="EUR">1000.00</Amt>
+            <CdtDbtInd>CRDT</CdtDbtInd>
+        </SttlmAmt>
+    </SctiesSttlmTxInstr>
+</Document>
+"#;
+
+/// Counterparty's matching receive-against-payment instruction
+pub const SESE_023_RECEIVE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:sese.023.001.09">
+    <SctiesSttlmTxInstr>
+        <TxId>BUY-0042</TxId>
+        <SttlmTpAndAddtlParams>
+            <SctiesMvmntTp>RECE</SctiesMvmntTp>
+            <Pmt>APMT</Pmt>
+            <CmonId>TRADE-2025-0703-17</CmonId>
+        </SttlmTpAndAddtlParams>
+        <TradDtls>
+            <TradDt>
+                <Dt>
+                    <Dt>2025-07-03</Dt>
+                </Dt>
+            </TradDt>
+            <SttlmDt>
+                <Dt>
+                    <Dt>2025-07-07</Dt>
+                </Dt>
+            </SttlmDt>
+        </TradDtls>
+        <FinInstrmId>
+            <ISIN>DE0001135275</ISIN>
+        </FinInstrmId>
+        <QtyAndAcctDtls>
+            <SttlmQty>
+                <Qty>
+                    <FaceAmt>10</FaceAmt>
+                </Qty>
+            </SttlmQty>
+            <SfkpgAcct>
+                <Id>fdg1dguq840stxgz58rd477fhfrjjgf00j4vpp35esaww6e8220s8qns4sj3yv</Id>
+            </SfkpgAcct>
+        </QtyAndAcctDtls>
+        <DlvrgSttlmPties>
+            <Pty1>
+                <Id>
+                    <AnyBIC>DEUTDEFFXXX</AnyBIC>
+                </Id>
+                <SfkpgAcct>
+                    <Id>fdg1x36slx9at870e9rd53d2405n80s4ff94p98pcj4lg2rx2p0ne9lq9sdxq0</Id>
+                </SfkpgAcct>
+            </Pty1>
+        </DlvrgSttlmPties>
+        <SttlmAmt>
+            <Amt Ccy="EUR">1000.00</Amt>
+            <CdtDbtInd>DBIT</CdtDbtInd>
+        </SttlmAmt>
+    </SctiesSttlmTxInstr>
+</Document>
+"#;
+
+/// Legacy homegrown format, no longer accepted (for negative test)
+pub const LEGACY_MESSAGE: &str = r#"
 <ISO20022Transaction>
     <message_id>msg-456</message_id>
     <creation_date_time>2025-07-03T12:30:00Z</creation_date_time>
@@ -12,24 +127,14 @@ pub const SETTLEMENT_TRANSACTION: &str = r#"
 </ISO20022Transaction>
 "#;
 
-/// Example ISO 20022 Funds Transfer (pacs.008)
-pub const CREDIT_TRANSFER: &str = r#"
-<ISO20022Transaction>
-    <message_id>msg-789</message_id>
-    <creation_date_time>2025-07-03T14:00:00Z</creation_date_time>
-    <transaction_type>CreditTransfer</transaction_type>
-    <debtor>ClientBank</debtor>
-    <creditor>BeneficiaryBank</creditor>
-    <amount>250000</amount>
-    <currency>USD</currency>
-    <findag_tx_id>fdg1qcredittransferhash</findag_tx_id>
-</ISO20022Transaction>
+/// pacs.008 missing its group header message id (for negative test)
+pub const MISSING_MSG_ID: &str = r#"
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:pacs.008.001.08">
+    <FIToFICstmrCdtTrf>
+        <GrpHdr>
+            <CreDtTm>2025-07-03T14:00:00Z</CreDtTm>
+            <NbOfTxs>1</NbOfTxs>
+        </GrpHdr>
+    </FIToFICstmrCdtTrf>
+</Document>
 "#;
-
-/// Example invalid message (for negative test)
-pub const INVALID_MESSAGE: &str = r#"
-<ISO20022Transaction>
-    <message_id>msg-bad</message_id>
-    <!-- Missing required fields -->
-</ISO20022Transaction>
-"#; 
\ No newline at end of file
@@ -0,0 +1,614 @@
+use crate::core::dvp::{DvpSettlement, SignedInstruction};
+use crate::core::multisig::MultisigAccount;
+use crate::core::tx_status::{TxStatus, TxStatusRegistry};
+use crate::core::types::{ShardId, SUPPORTED_ASSETS};
+use crate::iso20022::messages::{escape_xml, validate_date, Reader, ISO20022_NAMESPACE_PREFIX};
+use crate::iso20022::reporting::format_date_time;
+use crate::iso20022::{Amount, ISO20022Error};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use xmltree::Element;
+
+pub const SESE_024_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:sese.024.001.10";
+pub const SESE_025_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:sese.025.001.09";
+const ROOT: &str = "SctiesSttlmTxInstr";
+
+/// Direction of the securities movement from the instructing party's side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MovementType {
+    Deliver, // DELI
+    Receive, // RECE
+}
+
+impl MovementType {
+    pub fn code(&self) -> &'static str {
+        match self {
+            MovementType::Deliver => "DELI",
+            MovementType::Receive => "RECE",
+        }
+    }
+}
+
+/// Whether cash moves against the securities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentType {
+    AgainstPayment, // APMT
+    FreeOfPayment,  // FREE
+}
+
+impl PaymentType {
+    pub fn code(&self) -> &'static str {
+        match self {
+            PaymentType::AgainstPayment => "APMT",
+            PaymentType::FreeOfPayment => "FREE",
+        }
+    }
+}
+
+/// A parsed sese.023 securities settlement instruction. Safekeeping account ids are
+/// FinDAG addresses; the same address settles the cash leg.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementInstruction {
+    pub transaction_id: String,
+    pub common_id: Option<String>,
+    pub movement: MovementType,
+    pub payment: PaymentType,
+    pub trade_date: Option<String>,
+    pub settlement_date: String,
+    pub asset: String,              // ISIN or FinDAG instrument code (e.g. BUND)
+    pub quantity: u64,
+    pub deliverer_account: String,
+    pub receiver_account: String,
+    pub deliverer_bic: Option<String>,
+    pub receiver_bic: Option<String>,
+    pub settlement_amount: Option<Amount>,
+}
+
+impl SettlementInstruction {
+    /// Account of the instructing party
+    pub fn owner_account(&self) -> &str {
+        match self.movement {
+            MovementType::Deliver => &self.deliverer_account,
+            MovementType::Receive => &self.receiver_account,
+        }
+    }
+
+    /// Check this instruction and the counterparty's describe the same settlement.
+    /// Returns the first mismatching field.
+    pub fn matches(&self, other: &SettlementInstruction) -> Result<(), String> {
+        if self.movement == other.movement {
+            return Err("both instructions have the same movement type".to_string());
+        }
+        let mismatch = |field: &str| Err(format!("{field} does not match"));
+        if self.asset != other.asset {
+            return mismatch("financial instrument");
+        }
+        if self.quantity != other.quantity {
+            return mismatch("settlement quantity");
+        }
+        if self.settlement_date != other.settlement_date {
+            return mismatch("settlement date");
+        }
+        if self.payment != other.payment {
+            return mismatch("payment type");
+        }
+        if self.settlement_amount != other.settlement_amount {
+            return mismatch("settlement amount");
+        }
+        if self.deliverer_account != other.deliverer_account {
+            return mismatch("delivering account");
+        }
+        if self.receiver_account != other.receiver_account {
+            return mismatch("receiving account");
+        }
+        // Optional fields only need to agree when both sides supply them
+        if let (Some(a), Some(b)) = (&self.trade_date, &other.trade_date) {
+            if a != b {
+                return mismatch("trade date");
+            }
+        }
+        if let (Some(a), Some(b)) = (&self.common_id, &other.common_id) {
+            if a != b {
+                return mismatch("common id");
+            }
+        }
+        Ok(())
+    }
+
+    /// Transfers settling the instruction: securities to the receiver, cash back when against payment
+    pub fn transfers(&self) -> Result<Vec<(String, String, String, u64)>, String> {
+        let mut transfers = vec![(
+            self.deliverer_account.clone(),
+            self.receiver_account.clone(),
+            self.asset.clone(),
+            self.quantity,
+        )];
+        if let Some(amount) = &self.settlement_amount {
+            let cash = amount.to_minor_units().map_err(|e| e.to_string())?;
+            if cash > 0 {
+                transfers.push((self.receiver_account.clone(), self.deliverer_account.clone(), amount.currency.clone(), cash));
+            }
+        }
+        Ok(transfers)
+    }
+}
+
+/// Parse a namespaced sese.023 `SctiesSttlmTxInstr` document
+pub fn parse_sese023(xml_content: &str) -> Result<SettlementInstruction, ISO20022Error> {
+    let document = Element::parse(xml_content.as_bytes())
+        .map_err(|e| ISO20022Error::XmlParseError(e.to_string()))?;
+    let namespace = document.namespace.clone().unwrap_or_default();
+    let is_sese023 = namespace
+        .strip_prefix(ISO20022_NAMESPACE_PREFIX)
+        .is_some_and(|id| id.starts_with("sese.023.001."));
+    if document.name != "Document" || !is_sese023 {
+        return Err(ISO20022Error::UnsupportedMessage(format!("<{}> in namespace '{}'", document.name, namespace)));
+    }
+    let reader = Reader { ns: &namespace };
+    let root = reader
+        .child(&document, "SctiesSttlmTxInstr")
+        .ok_or_else(|| ISO20022Error::MissingField(ROOT.to_string()))?;
+
+    let transaction_id = reader
+        .text(root, &["TxId", "AcctOwnrTxId"])
+        .or_else(|| reader.text(root, &["TxId"]))
+        .ok_or_else(|| ISO20022Error::MissingField(format!("{ROOT}/TxId")))?;
+    let movement = match reader.required_text(root, &["SttlmTpAndAddtlParams", "SctiesMvmntTp"], ROOT)?.as_str() {
+        "DELI" => MovementType::Deliver,
+        "RECE" => MovementType::Receive,
+        other => return Err(invalid("SttlmTpAndAddtlParams/SctiesMvmntTp", other)),
+    };
+    let payment = match reader.required_text(root, &["SttlmTpAndAddtlParams", "Pmt"], ROOT)?.as_str() {
+        "APMT" => PaymentType::AgainstPayment,
+        "FREE" => PaymentType::FreeOfPayment,
+        other => return Err(invalid("SttlmTpAndAddtlParams/Pmt", other)),
+    };
+    let trade_date = reader.text(root, &["TradDtls", "TradDt", "Dt", "Dt"]);
+    if let Some(date) = &trade_date {
+        validate_date(date)?;
+    }
+    let settlement_date = reader.required_text(root, &["TradDtls", "SttlmDt", "Dt", "Dt"], ROOT)?;
+    validate_date(&settlement_date)?;
+
+    let asset = match reader.text(root, &["FinInstrmId", "ISIN"]) {
+        Some(isin) if !is_isin(&isin) => return Err(invalid("FinInstrmId/ISIN", &isin)),
+        Some(isin) => isin,
+        None => reader.required_text(root, &["FinInstrmId", "OthrId", "Id"], ROOT)?,
+    };
+    if !SUPPORTED_ASSETS.contains(&asset.as_str()) {
+        return Err(invalid("FinInstrmId", &asset));
+    }
+    let quantity = quantity(&reader, root)?;
+
+    let own_account = reader.required_text(root, &["QtyAndAcctDtls", "SfkpgAcct", "Id"], ROOT)?;
+    let counterparty_side = match movement {
+        MovementType::Deliver => "RcvgSttlmPties",
+        MovementType::Receive => "DlvrgSttlmPties",
+    };
+    let counterparty_account = reader.text(root, &[counterparty_side, "Pty1", "SfkpgAcct", "Id"])
+        .ok_or_else(|| ISO20022Error::MissingField(format!("{ROOT}/{counterparty_side}/Pty1/SfkpgAcct/Id")))?;
+    let (deliverer_account, receiver_account) = match movement {
+        MovementType::Deliver => (own_account, counterparty_account),
+        MovementType::Receive => (counterparty_account, own_account),
+    };
+
+    let settlement_amount = match reader.path(root, &["SttlmAmt", "Amt"]) {
+        Some(_) => Some(reader.amount(root, "SttlmAmt/Amt", ROOT)?),
+        None => None,
+    };
+    if payment == PaymentType::AgainstPayment && settlement_amount.is_none() {
+        return Err(ISO20022Error::MissingField(format!("{ROOT}/SttlmAmt/Amt")));
+    }
+
+    Ok(SettlementInstruction {
+        transaction_id,
+        common_id: reader.text(root, &["SttlmTpAndAddtlParams", "CmonId"]),
+        movement,
+        payment,
+        trade_date,
+        settlement_date,
+        asset,
+        quantity,
+        deliverer_account,
+        receiver_account,
+        deliverer_bic: reader.text(root, &["DlvrgSttlmPties", "Pty1", "Id", "AnyBIC"]),
+        receiver_bic: reader.text(root, &["RcvgSttlmPties", "Pty1", "Id", "AnyBIC"]),
+        settlement_amount,
+    })
+}
+
+/// Settlement quantity in units or face amount; FinDAG balances are whole units
+fn quantity(reader: &Reader, root: &Element) -> Result<u64, ISO20022Error> {
+    let value = reader.text(root, &["QtyAndAcctDtls", "SttlmQty", "Qty", "Unit"])
+        .or_else(|| reader.text(root, &["QtyAndAcctDtls", "SttlmQty", "Qty", "FaceAmt"]))
+        .ok_or_else(|| ISO20022Error::MissingField(format!("{ROOT}/QtyAndAcctDtls/SttlmQty/Qty")))?;
+    let whole = value.split_once('.')
+        .filter(|(_, frac)| frac.chars().all(|c| c == '0'))
+        .map(|(int, _)| int)
+        .unwrap_or(&value);
+    match whole.parse::<u64>() {
+        Ok(quantity) if quantity > 0 => Ok(quantity),
+        _ => Err(ISO20022Error::InvalidAmount(format!("QtyAndAcctDtls/SttlmQty/Qty: {value}"))),
+    }
+}
+
+fn invalid(field: &str, value: &str) -> ISO20022Error {
+    ISO20022Error::InvalidIdentifier { field: field.to_string(), value: value.to_string() }
+}
+
+/// ISIN shape: country code, 9 alphanumerics, check digit
+fn is_isin(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 12
+        && bytes[..2].iter().all(|b| b.is_ascii_uppercase())
+        && bytes[2..11].iter().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        && bytes[11].is_ascii_digit()
+}
+
+/// Where an instruction stands, as reported in sese.024 and sese.025
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettlementProgress {
+    Unmatched,
+    Matched,                    // Settlement transaction pending on chain
+    Settled { round: u64 },
+    Failed { reason: String },  // Matched but the settlement transaction was refused
+}
+
+/// Matcher-side state of a received instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionState {
+    Unmatched,
+    Matched { tx_hash: [u8; 32] },
+    Rejected { reason: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct InstructionRecord {
+    pub signed: SignedInstruction,
+    pub instruction: SettlementInstruction,
+    pub state: InstructionState,
+}
+
+impl InstructionRecord {
+    /// Combine the matcher state with the settlement transaction's lifecycle
+    pub fn progress(&self, tx_status: &TxStatusRegistry) -> SettlementProgress {
+        match &self.state {
+            InstructionState::Unmatched => SettlementProgress::Unmatched,
+            InstructionState::Rejected { reason } => SettlementProgress::Failed { reason: reason.clone() },
+            InstructionState::Matched { tx_hash } => match tx_status.status(tx_hash) {
+                Some(TxStatus::Finalized { round_number, .. }) => SettlementProgress::Settled { round: round_number },
+                Some(TxStatus::Rejected { reason }) => SettlementProgress::Failed { reason },
+                Some(TxStatus::Expired) => SettlementProgress::Failed { reason: "settlement transaction expired".to_string() },
+                _ => SettlementProgress::Matched,
+            },
+        }
+    }
+
+    pub fn tx_hash(&self) -> Option<[u8; 32]> {
+        match self.state {
+            InstructionState::Matched { tx_hash } => Some(tx_hash),
+            _ => None,
+        }
+    }
+}
+
+/// Holds received sese.023 instructions until the counterparty's matching instruction arrives
+#[derive(Default)]
+pub struct SettlementMatcher {
+    // "{owner account}:{transaction id}" -> record
+    records: Mutex<HashMap<String, InstructionRecord>>,
+}
+
+impl SettlementMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(account: &str, transaction_id: &str) -> String {
+        format!("{account}:{transaction_id}")
+    }
+
+    /// Accept a signed instruction and look for its counterpart. When one is found, returns
+    /// the DvP settlement to submit; call `record_settlement` once it is in the pool.
+    pub fn submit(
+        &self,
+        signed: SignedInstruction,
+        findag_time: u64,
+        shard_id: ShardId,
+        multisig_account: &dyn Fn(&str) -> Option<MultisigAccount>,
+    ) -> Result<(SettlementInstruction, Option<DvpSettlement>), String> {
+        let instruction = signed.verify(multisig_account)?;
+        let key = Self::key(instruction.owner_account(), &instruction.transaction_id);
+        let mut records = self.records.lock().unwrap();
+        if records.contains_key(&key) {
+            return Err(format!("Instruction {} already received", instruction.transaction_id));
+        }
+        let counterpart = records.values()
+            .find(|r| r.state == InstructionState::Unmatched && r.instruction.matches(&instruction).is_ok())
+            .map(|r| r.signed.clone());
+        records.insert(key, InstructionRecord {
+            signed: signed.clone(),
+            instruction: instruction.clone(),
+            state: InstructionState::Unmatched,
+        });
+        let settlement = counterpart.map(|other| match instruction.movement {
+            MovementType::Deliver => DvpSettlement::new(signed, other, findag_time, shard_id),
+            MovementType::Receive => DvpSettlement::new(other, signed, findag_time, shard_id),
+        });
+        Ok((instruction, settlement))
+    }
+
+    /// Record the outcome of submitting a matched pair's settlement transaction
+    pub fn record_settlement(&self, settlement: &DvpSettlement, outcome: Result<[u8; 32], String>) {
+        let state = match outcome {
+            Ok(tx_hash) => InstructionState::Matched { tx_hash },
+            Err(reason) => InstructionState::Rejected { reason },
+        };
+        let mut records = self.records.lock().unwrap();
+        for signed in [&settlement.deliver, &settlement.receive] {
+            if let Ok(instruction) = signed.parse() {
+                if let Some(record) = records.get_mut(&Self::key(instruction.owner_account(), &instruction.transaction_id)) {
+                    record.state = state.clone();
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, account: &str, transaction_id: &str) -> Option<InstructionRecord> {
+        self.records.lock().unwrap().get(&Self::key(account, transaction_id)).cloned()
+    }
+}
+
+fn instrument_xml(asset: &str) -> String {
+    if is_isin(asset) {
+        format!("<ISIN>{}</ISIN>", escape_xml(asset))
+    } else {
+        format!("<OthrId><Id>{}</Id><Tp><Prtry>FINDAG</Prtry></Tp></OthrId>", escape_xml(asset))
+    }
+}
+
+/// Cash side from the instructing party's point of view: the deliverer is credited
+fn settlement_amount_xml(instruction: &SettlementInstruction, element: &str) -> String {
+    // Render in the currency's minor unit precision, e.g. 1000.00
+    let amount = instruction.settlement_amount.as_ref().map(|a| {
+        a.to_minor_units()
+            .and_then(|minor| Amount::from_minor_units(minor, &a.currency))
+            .unwrap_or_else(|_| a.clone())
+    });
+    match amount {
+        Some(amount) => format!(
+            "\n        <{element}>\n            <Amt Ccy=\"{}\">{}</Amt>\n            <CdtDbtInd>{}</CdtDbtInd>\n        </{element}>",
+            amount.currency,
+            amount,
+            match instruction.movement {
+                MovementType::Deliver => "CRDT",
+                MovementType::Receive => "DBIT",
+            }
+        ),
+        None => String::new(),
+    }
+}
+
+/// Build a sese.024 status advice for an instruction
+pub fn sese024_status_advice(message_id: &str, created_at: u64, instruction: &SettlementInstruction, progress: &SettlementProgress, tx_hash: Option<[u8; 32]>) -> String {
+    const NO_REASON: &str = "<NoSpcfdRsn>NORE</NoSpcfdRsn>";
+    let processing = match progress {
+        SettlementProgress::Failed { reason } => format!(
+            "<Rjctd><Rsn><Cd><Prtry><Id>FINDAG</Id><Issr>FINDAG</Issr></Prtry></Cd><AddtlRsnInf>{}</AddtlRsnInf></Rsn></Rjctd>",
+            escape_xml(reason)
+        ),
+        _ => format!("<AckdAccptd>{NO_REASON}</AckdAccptd>"),
+    };
+    let matching = match progress {
+        SettlementProgress::Unmatched => format!("<Umtchd>{NO_REASON}</Umtchd>"),
+        _ => format!("<Mtchd>{NO_REASON}</Mtchd>"),
+    };
+    let settlement = match progress {
+        SettlementProgress::Settled { .. } => String::new(),
+        SettlementProgress::Failed { .. } => format!("\n        <SttlmSts><Flng>{NO_REASON}</Flng></SttlmSts>"),
+        _ => format!("\n        <SttlmSts><Pdg>{NO_REASON}</Pdg></SttlmSts>"),
+    };
+    let servicer_ref = tx_hash
+        .map(|h| format!("\n            <AcctSvcrTxId>{}</AcctSvcrTxId>", hex::encode(h)))
+        .unwrap_or_default();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="{}">
+    <SctiesSttlmTxStsAdvc>
+        <TxId>
+            <AcctOwnrTxId>{}</AcctOwnrTxId>{}
+        </TxId>
+        <PrcgSts>{}</PrcgSts>
+        <MtchgSts>{}</MtchgSts>{}
+        <TxDtls>
+            <FinInstrmId>{}</FinInstrmId>
+            <SctiesMvmntTp>{}</SctiesMvmntTp>
+            <Pmt>{}</Pmt>
+            <SttlmQty><Qty><Unit>{}</Unit></Qty></SttlmQty>
+            <SfkpgAcct><Id>{}</Id></SfkpgAcct>
+            <SttlmDt><Dt><Dt>{}</Dt></Dt></SttlmDt>
+        </TxDtls>{}
+        <AddtlInf>{}</AddtlInf>
+    </SctiesSttlmTxStsAdvc>
+</Document>"#,
+        SESE_024_NAMESPACE,
+        escape_xml(&instruction.transaction_id),
+        servicer_ref,
+        processing,
+        matching,
+        settlement,
+        instrument_xml(&instruction.asset),
+        instruction.movement.code(),
+        instruction.payment.code(),
+        instruction.quantity,
+        escape_xml(instruction.owner_account()),
+        escape_xml(&instruction.settlement_date),
+        settlement_amount_xml(instruction, "SttlmAmt"),
+        escape_xml(&format!("{message_id} {}", format_date_time(created_at))),
+    )
+}
+
+/// Build a sese.025 settlement confirmation once the settlement transaction is finalized
+pub fn sese025_confirmation(message_id: &str, created_at: u64, instruction: &SettlementInstruction, tx_hash: [u8; 32], round: u64) -> String {
+    let party = |element: &str, account: &str, bic: &Option<String>| format!(
+        "\n        <{element}>\n            <Pty1>{}\n                <SfkpgAcct><Id>{}</Id></SfkpgAcct>\n            </Pty1>\n        </{element}>",
+        bic.as_ref().map(|b| format!("\n                <Id><AnyBIC>{}</AnyBIC></Id>", escape_xml(b))).unwrap_or_default(),
+        escape_xml(account),
+    );
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="{}">
+    <SctiesSttlmTxConf>
+        <TxIdDtls>
+            <AcctOwnrTxId>{}</AcctOwnrTxId>
+            <AcctSvcrTxId>{}</AcctSvcrTxId>
+            <SctiesMvmntTp>{}</SctiesMvmntTp>
+            <Pmt>{}</Pmt>
+        </TxIdDtls>
+        <TradDtls>
+            <SttlmDt><Dt><Dt>{}</Dt></Dt></SttlmDt>
+            <FctvSttlmDt><Dt><DtTm>{}</DtTm></Dt></FctvSttlmDt>
+        </TradDtls>
+        <FinInstrmId>{}</FinInstrmId>
+        <QtyAndAcctDtls>
+            <SttldQty><Qty><Unit>{}</Unit></Qty></SttldQty>
+            <SfkpgAcct><Id>{}</Id></SfkpgAcct>
+        </QtyAndAcctDtls>{}{}{}
+        <AddtlInf>{}</AddtlInf>
+    </SctiesSttlmTxConf>
+</Document>"#,
+        SESE_025_NAMESPACE,
+        escape_xml(&instruction.transaction_id),
+        hex::encode(tx_hash),
+        instruction.movement.code(),
+        instruction.payment.code(),
+        escape_xml(&instruction.settlement_date),
+        format_date_time(created_at),
+        instrument_xml(&instruction.asset),
+        instruction.quantity,
+        escape_xml(instruction.owner_account()),
+        party("DlvrgSttlmPties", &instruction.deliverer_account, &instruction.deliverer_bic),
+        party("RcvgSttlmPties", &instruction.receiver_account, &instruction.receiver_bic),
+        settlement_amount_xml(instruction, "SttldAmt"),
+        escape_xml(&format!("{message_id} settled in round {round}")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dvp::instruction_signing_message;
+    use crate::core::executor::apply_transaction;
+    use crate::iso20022::schemas::{SESE_023_DELIVER, SESE_023_RECEIVE};
+    use crate::storage::state::StateDB;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn sign(xml: &str, key: &SigningKey) -> SignedInstruction {
+        SignedInstruction {
+            xml: xml.to_string(),
+            public_key: key.verifying_key(),
+            signature: key.sign(&instruction_signing_message(xml)),
+            approvals: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_and_match_sese023() {
+        let deliver = parse_sese023(SESE_023_DELIVER).unwrap();
+        let receive = parse_sese023(SESE_023_RECEIVE).unwrap();
+        assert_eq!(deliver.movement, MovementType::Deliver);
+        assert_eq!(deliver.payment, PaymentType::AgainstPayment);
+        assert_eq!(deliver.asset, "DE0001135275");
+        assert_eq!(deliver.quantity, 10);
+        assert_eq!(deliver.receiver_bic.as_deref(), Some("BNPAFRPP"));
+        assert_eq!(receive.owner_account(), deliver.receiver_account);
+        assert!(deliver.matches(&receive).is_ok());
+        assert_eq!(
+            deliver.transfers().unwrap()[1],
+            (deliver.receiver_account.clone(), deliver.deliverer_account.clone(), "EUR".to_string(), 100_000)
+        );
+
+        let other_amount = parse_sese023(&SESE_023_RECEIVE.replace("1000.00", "999.99")).unwrap();
+        assert_eq!(deliver.matches(&other_amount), Err("settlement amount does not match".to_string()));
+        assert!(matches!(
+            parse_sese023(&SESE_023_DELIVER.replace("DE0001135275", "DE0001135276")),
+            Err(ISO20022Error::InvalidIdentifier { .. })
+        ));
+        assert!(matches!(
+            parse_sese023(&SESE_023_DELIVER.replace("sese.023.001.09", "sese.024.001.10")),
+            Err(ISO20022Error::UnsupportedMessage(_))
+        ));
+    }
+
+    #[test]
+    fn test_multisig_account_instruction_needs_policy_approval() {
+        use crate::core::address::Address;
+        use crate::core::multisig::{MultisigPolicy, MultisigSigner};
+        use crate::core::types::PartySignature;
+
+        let (a, b) = (SigningKey::from_bytes(&[3u8; 32]), SigningKey::from_bytes(&[4u8; 32]));
+        let policy = MultisigPolicy {
+            signers: [&a, &b].iter().map(|k| MultisigSigner { public_key: k.verifying_key(), weight: 1 }).collect(),
+            threshold: 2,
+        };
+        let treasury = MultisigAccount { address: policy.derive_address(b"custody"), policy, nonce: 0 };
+        let lookup = |address: &str| (address == treasury.address.as_str()).then(|| treasury.clone());
+        let seller = Address::from_signing_key(&SigningKey::from_bytes(&[1u8; 32]));
+        let xml = SESE_023_DELIVER.replace(seller.as_str(), treasury.address.as_str());
+
+        let mut signed = sign(&xml, &a);
+        assert!(signed.verify(&lookup).is_err());
+        signed.approvals.push(PartySignature { public_key: b.verifying_key(), signature: b.sign(&instruction_signing_message(&xml)) });
+        assert_eq!(signed.verify(&lookup).unwrap().owner_account(), treasury.address.as_str());
+        assert!(signed.verify(&|_| None).is_err());
+    }
+
+    #[test]
+    fn test_matched_instructions_settle_atomically_and_confirm() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_db = StateDB::new(dir.path().to_str().unwrap());
+        let seller = SigningKey::from_bytes(&[1u8; 32]);
+        let buyer = SigningKey::from_bytes(&[2u8; 32]);
+        let matcher = SettlementMatcher::new();
+
+        // The buyer cannot submit the seller's instruction
+        assert!(matcher.submit(sign(SESE_023_DELIVER, &buyer), 7, ShardId(0), &|_| None).is_err());
+        let (deliver, none) = matcher.submit(sign(SESE_023_DELIVER, &seller), 7, ShardId(0), &|_| None).unwrap();
+        assert!(none.is_none());
+        assert!(matcher.submit(sign(SESE_023_DELIVER, &seller), 7, ShardId(0), &|_| None).is_err());
+        let (_, settlement) = matcher.submit(sign(SESE_023_RECEIVE, &buyer), 7, ShardId(0), &|_| None).unwrap();
+        let settlement = settlement.expect("instructions should match");
+        let tx = settlement.to_transaction(&|_| None).unwrap();
+
+        // Underfunded cash leg: neither leg settles
+        let (seller_addr, buyer_addr) = (&deliver.deliverer_account, &deliver.receiver_account);
+        state_db.set_balance(0, seller_addr, "DE0001135275", 10).unwrap();
+        state_db.set_balance(0, buyer_addr, "EUR", 50_000).unwrap();
+        assert!(apply_transaction(&state_db, &tx).is_err());
+        assert_eq!(state_db.get_balance(0, seller_addr, "DE0001135275"), 10);
+
+        state_db.set_balance(0, buyer_addr, "EUR", 100_000).unwrap();
+        apply_transaction(&state_db, &tx).unwrap();
+        assert_eq!(state_db.get_balance(0, buyer_addr, "DE0001135275"), 10);
+        assert_eq!(state_db.get_balance(0, seller_addr, "EUR"), 100_000);
+
+        let tx_status = TxStatusRegistry::default();
+        let tx_hash = tx.compute_hash();
+        matcher.record_settlement(&settlement, Ok(tx_hash));
+        let record = matcher.get(seller_addr, "SELL-0001").unwrap();
+        assert_eq!(record.progress(&tx_status), SettlementProgress::Matched);
+        let advice = sese024_status_advice("SESE024-1", 1_751_500_000, &record.instruction, &record.progress(&tx_status), record.tx_hash());
+        assert!(advice.contains("<Mtchd>") && advice.contains("<Pdg>"));
+
+        tx_status.mark_included([9u8; 32], &[tx_hash]);
+        tx_status.mark_round_finalized(12, &[[9u8; 32]]);
+        let record = matcher.get(buyer_addr, "BUY-0042").unwrap();
+        assert_eq!(record.progress(&tx_status), SettlementProgress::Settled { round: 12 });
+        let confirmation = sese025_confirmation("SESE025-1", 1_751_500_000, &record.instruction, tx_hash, 12);
+        assert!(confirmation.contains(&hex::encode(tx_hash)));
+        assert!(confirmation.contains("<SctiesMvmntTp>RECE</SctiesMvmntTp>"));
+        assert!(confirmation.contains("<Amt Ccy=\"EUR\">1000.00</Amt>"));
+    }
+}
@@ -11,6 +11,9 @@ use serde::{Serialize, Deserialize};
 use clap::Parser;
 
 use findag::core::dag_engine::DagEngine;
+use findag::core::block_production_loop::{run_block_production_loop, BlockProductionConfig};
+use findag::core::round_checkpoint_loop::run_round_checkpoint_loop;
+use findag::consensus::roundchain::RoundChain;
 use findag::core::tx_pool::ShardedTxPool;
 use findag::core::address::Address;
 use findag::core::types::{Transaction, Block, ShardId};
@@ -19,7 +22,8 @@ use findag::dagtimer::hashtimer::compute_hashtimer;
 use findag::network::propagation::NetworkPropagator;
 use findag::network::consensus_integration::ConsensusIntegration;
 use findag::network::encryption::P2PEncryption;
-use findag::consensus::validator_set::ValidatorSet;
+use findag::api::http_server::NodeServices;
+use findag::storage::persistent::PersistentStorage;
 use serde_json::json;
 
 #[derive(Parser, Debug)]
@@ -108,10 +112,6 @@ async fn submit_transaction(State(state): State<AppState>, Json(req): Json<Trans
     let from_address = Address(req.from.clone());
     let to_address = Address(req.to.clone());
     
-    // Verify signature
-    let message = format!("{}{}{}", req.from, req.to, req.amount);
-    println!("[DEBUG] HTTP API: Verifying signature for message: '{message}'");
-    
     // Convert Vec<u8> to [u8; 64] for signature
     let signature_bytes: [u8; 64] = match req.signature.clone().try_into() {
         Ok(bytes) => bytes,
@@ -146,16 +146,6 @@ async fn submit_transaction(State(state): State<AppState>, Json(req): Json<Trans
         }
     };
     
-    match public_key.verify(message.as_bytes(), &signature) {
-        Ok(_) => println!("[DEBUG] HTTP API: Signature verification passed"),
-        Err(e) => {
-            println!("[DEBUG] HTTP API: Signature verification failed: {e:?}");
-            return Err((StatusCode::BAD_REQUEST, Json(json!({
-                "error": "Signature verification failed"
-            }))));
-        }
-    }
-    
     // Create transaction
     let transaction = Transaction {
         from: from_address.clone(),
@@ -179,8 +169,22 @@ async fn submit_transaction(State(state): State<AppState>, Json(req): Json<Trans
         dest_shard: None,
         target_chain: None,
         bridge_protocol: None,
+        valid_after: None,
+        valid_until: None,
+        multisig_signatures: Vec::new(),
     };
     
+    // Verify signature over the canonical transaction message
+    match public_key.verify(&transaction.signing_message(), &signature) {
+        Ok(_) => println!("[DEBUG] HTTP API: Signature verification passed"),
+        Err(e) => {
+            println!("[DEBUG] HTTP API: Signature verification failed: {e:?}");
+            return Err((StatusCode::BAD_REQUEST, Json(json!({
+                "error": "Signature verification failed"
+            }))));
+        }
+    }
+    
     println!("[DEBUG] HTTP API: Created transaction, adding to tx_pool");
     // Add to transaction pool
     let added = state.tx_pool.add_transaction(transaction);
@@ -362,17 +366,43 @@ async fn main() {
         &args.data_dir
     ));
 
-    // Initialize DAG engine
-    let dag = Arc::new(Mutex::new(DagEngine::new().await));
-    
-    // Initialize validator set
-    let validator_set = Arc::new(Mutex::new(ValidatorSet::new()));
+    // Protocol handlers shared by the DAG engine and the HTTP API
+    // Blocks, rounds and protocol state live next to the state DB, under the node's data directory
+    let storage_path = std::path::Path::new(&args.data_dir).join("node_storage");
+    let storage = Arc::new(PersistentStorage::new(&storage_path.to_string_lossy()).expect("Failed to open node storage"));
+    // Generate local node address and keypair; the epoch manager co-signs transitions with it
+    let (local_keypair, local_address) = findag::core::address::generate_address();
+    let services = NodeServices::open(storage, tx_pool.clone(), local_keypair.clone());
+
+    // Initialize DAG engine, executing blocks against the state and reporting inclusion
+    let mut dag_engine = DagEngine::new().await;
+    dag_engine.set_status_registry(tx_pool.status_registry());
+    dag_engine.set_state_db(tx_pool.state_db());
+    dag_engine.set_governance_executor(services.governance_executor.clone());
+    dag_engine.set_staking_ledger(services.staking_ledger.clone());
+    dag_engine.set_slashing_engine(services.slashing_engine.clone());
+    dag_engine.set_validator_lifecycle(services.validator_lifecycle.clone());
+    dag_engine.set_handle_registry(services.handle_registry.clone());
+    dag_engine.set_bridge(services.bridge.clone());
+    let dag = Arc::new(Mutex::new(dag_engine));
+
+    // Round chain over the node's shared validator set; finality is reported once a round reaches quorum
+    let mut roundchain = RoundChain::new(services.validator_set.clone());
+    roundchain.set_status_registry(tx_pool.status_registry());
+    roundchain.set_governance_executor(services.governance_executor.clone());
+    roundchain.set_staking_ledger(services.staking_ledger.clone());
+    roundchain.set_reward_distributor(services.reward_distributor.clone());
+    roundchain.set_validator_lifecycle(services.validator_lifecycle.clone());
+    roundchain.set_epoch_manager(services.epoch_manager.clone());
+    roundchain.set_bridge(services.bridge.clone());
+
+    // Produced blocks and rounds are persisted in the background
+    let (persist_tx, persist_rx) = tokio::sync::mpsc::unbounded_channel();
+    services.storage.clone().spawn_background_writer(persist_rx);
     
     // Initialize time manager
-    let _time_manager = Arc::new(FinDAGTimeManager::new());
-    
-    // Generate local node address and keypair
-    let (local_keypair, local_address) = findag::core::address::generate_address();
+    let time_manager = FinDAGTimeManager::new();
+
     
     // Initialize encryption layer
     let encryption = Arc::new(P2PEncryption::new_from_ed25519(&local_keypair));
@@ -387,20 +417,47 @@ async fn main() {
     // Initialize consensus integration
     let consensus_integration = ConsensusIntegration::new(
         propagator.clone(),
-        validator_set.clone(),
+        services.validator_set.clone(),
         dag.clone(),
         tx_pool.clone(),
         local_address.clone(),
-        Some(local_keypair),
+        Some(local_keypair.clone()),
     );
     
     // Start consensus integration
     consensus_integration.start().await;
 
+    // Block production and round checkpointing run alongside the HTTP server; the
+    // local limits are floors and caps that governance parameters can only tighten
+    let block_production = run_block_production_loop(
+        &dag,
+        &tx_pool,
+        local_address.clone(),
+        &local_keypair,
+        BlockProductionConfig { max_block_txs: 100_000, interval_ms: 10, shard_id: 0 },
+        &time_manager,
+        persist_tx.clone(),
+    );
+    let round_checkpoints = run_round_checkpoint_loop(
+        &dag,
+        local_address.clone(),
+        &local_keypair,
+        50,
+        &time_manager,
+        persist_tx,
+        &mut roundchain,
+    );
+
     // Start HTTP server
-    if let Err(e) = findag::api::http_server::start(args.port, tx_pool.clone()).await {
-        eprintln!("Failed to start HTTP server: {e}");
-        return;
+    tokio::select! {
+        result = findag::api::http_server::start(args.port, services.clone(), propagator.clone()) => {
+            if let Err(e) = result {
+                eprintln!("Failed to start HTTP server: {e}");
+                return;
+            }
+        }
+        _ = block_production => {}
+        _ = round_checkpoints => {}
     }
 
     // Start P2P network
@@ -1,9 +1,13 @@
 use crate::network::propagation::{NetworkPropagator, GossipMsg};
 use crate::consensus::validator_set::{ValidatorSet, ValidatorReputation};
-use crate::core::types::{SerializableTransaction, SerializableBlock, SerializableRound, Transaction, Block, Round};
+use crate::core::types::{SerializableTransaction, SerializableBlock, SerializableRound, Transaction, Block, Round, within_validity_window};
 use crate::core::dag_engine::DagEngine;
-use crate::core::tx_pool::ShardedTxPool;
+use crate::core::tx_pool::{ShardedTxPool, TxPool};
+use crate::core::multi_leg::MultiLegTransaction;
+use crate::core::multisig::MultisigOp;
+use crate::core::payload::ProtocolPayload;
 use crate::core::address::Address;
+use crate::dagtimer::findag_time_manager::FinDAGTimeManager;
 use ed25519_dalek::{SigningKey, VerifyingKey, Verifier};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -58,7 +62,7 @@ impl Default for RateLimitConfig {
 /// Consensus integration manager
 pub struct ConsensusIntegration {
     propagator: Arc<NetworkPropagator>,
-    validator_set: Arc<Mutex<ValidatorSet>>,
+    validator_set: Arc<std::sync::Mutex<ValidatorSet>>, // Node's shared validator set
     dag: Arc<Mutex<DagEngine>>,
     tx_pool: Arc<ShardedTxPool>,
     peer_scores: Arc<Mutex<HashMap<Address, PeerScore>>>,
@@ -73,7 +77,7 @@ pub struct ConsensusIntegration {
 impl ConsensusIntegration {
     pub fn new(
         propagator: Arc<NetworkPropagator>,
-        validator_set: Arc<Mutex<ValidatorSet>>,
+        validator_set: Arc<std::sync::Mutex<ValidatorSet>>,
         dag: Arc<Mutex<DagEngine>>,
         tx_pool: Arc<ShardedTxPool>,
         local_address: Address,
@@ -217,19 +221,57 @@ impl ConsensusIntegration {
 
     /// Validate transaction message
     async fn validate_transaction(&self, tx: &SerializableTransaction) -> MessageValidationResult {
-        // Basic validation
-        if tx.amount == 0 {
+        // Envelope checks apply to every transaction, whatever its payload
+        let transaction = match Transaction::try_from(tx.clone()) {
+            Ok(transaction) => transaction,
+            Err(_) => return MessageValidationResult {
+                is_valid: false,
+                reason: "Invalid transaction encoding".to_string(),
+            },
+        };
+        if !self.is_valid_address(&tx.from) || !self.is_valid_address(&tx.to) {
             return MessageValidationResult {
                 is_valid: false,
-                reason: "Transaction amount cannot be zero".to_string(),
+                reason: "Invalid address format".to_string(),
             };
         }
+        if let Err(reason) = TxPool::check_validity_window(&transaction, FinDAGTimeManager::new().get_findag_time()) {
+            return MessageValidationResult { is_valid: false, reason: reason.to_string() };
+        }
 
-        // Validate addresses
-        if !self.is_valid_address(&tx.from) || !self.is_valid_address(&tx.to) {
+        // Multi-leg transactions carry their own per-party signatures
+        if MultiLegTransaction::from_payload(&tx.payload).is_some() {
+            return match self.validate_multi_leg(tx) {
+                Ok(()) => MessageValidationResult { is_valid: true, reason: "Valid".to_string() },
+                Err(reason) => MessageValidationResult { is_valid: false, reason },
+            };
+        }
+
+        // Multisig account operations are checked against state when admitted to the pool
+        if let Some(op) = MultisigOp::from_payload(&tx.payload) {
+            return match op.and_then(|op| {
+                op.account_address().validate().map_err(|e| e.to_string())?;
+                op.verify_signatures()
+            }) {
+                Ok(()) => MessageValidationResult { is_valid: true, reason: "Valid".to_string() },
+                Err(reason) => MessageValidationResult { is_valid: false, reason },
+            };
+        }
+
+        // Governance, staking, slashing, validator, handle, bridge and DvP payloads carry their own signatures
+        if let Some(payload) = ProtocolPayload::from_transaction(&transaction) {
+            let state_db = self.tx_pool.state_db();
+            return match payload.and_then(|p| p.verify(&|address| state_db.get_multisig_account(address))) {
+                Ok(()) => MessageValidationResult { is_valid: true, reason: "Valid".to_string() },
+                Err(reason) => MessageValidationResult { is_valid: false, reason },
+            };
+        }
+
+        // Basic validation
+        if tx.amount == 0 {
             return MessageValidationResult {
                 is_valid: false,
-                reason: "Invalid address format".to_string(),
+                reason: "Transaction amount cannot be zero".to_string(),
             };
         }
 
@@ -257,6 +299,14 @@ impl ConsensusIntegration {
             };
         }
 
+        // Every transaction must be valid at the block's FinDAG Time
+        if !block.transactions.iter().all(|tx| within_validity_window(tx.valid_after, tx.valid_until, block.findag_time)) {
+            return MessageValidationResult {
+                is_valid: false,
+                reason: "Block contains transactions outside their validity window".to_string(),
+            };
+        }
+
         // Validate proposer signature
         if let Err(_) = self.verify_block_signature(block).await {
             return MessageValidationResult {
@@ -418,33 +468,34 @@ impl ConsensusIntegration {
 
     /// Check if address is valid
     fn is_valid_address(&self, address: &Address) -> bool {
-        address.as_str().starts_with("fdg1") && address.as_str().len() >= 10
+        address.is_valid()
     }
 
     /// Verify transaction signature
     async fn verify_transaction_signature(&self, tx: &SerializableTransaction) -> Result<(), String> {
-        // Convert signature bytes to signature
-        let signature = ed25519_dalek::Signature::from_bytes(&tx.signature_bytes.clone().try_into().unwrap());
+        let transaction: Transaction = tx.clone().try_into()
+            .map_err(|_| "Invalid signature or public key format".to_string())?;
         
-        // Convert public key bytes to public key
-        let public_key = VerifyingKey::from_bytes(&tx.public_key_bytes.clone().try_into().unwrap())
-            .map_err(|_| "Invalid public key format".to_string())?;
-        
-        // Create message to verify (transaction content)
-        let mut message = Vec::new();
-        message.extend_from_slice(tx.from.as_str().as_bytes());
-        message.extend_from_slice(tx.to.as_str().as_bytes());
-        message.extend_from_slice(&tx.amount.to_be_bytes());
-        message.extend_from_slice(&tx.findag_time.to_be_bytes());
-        message.extend_from_slice(&tx.hashtimer);
-        
-        // Verify signature
-        public_key.verify(&message, &signature)
+        // Same message the wallet and the HTTP API sign and verify
+        transaction.public_key.verify(&transaction.signing_message(), &transaction.signature)
             .map_err(|_| "Transaction signature verification failed".to_string())?;
         
         Ok(())
     }
 
+    /// Validate a multi-leg transaction envelope and its party signatures
+    fn validate_multi_leg(&self, tx: &SerializableTransaction) -> Result<(), String> {
+        let transaction: Transaction = tx.clone().try_into()
+            .map_err(|_| "Invalid transaction encoding".to_string())?;
+        match MultiLegTransaction::from_transaction(&transaction) {
+            Some(multi) => {
+                let state_db = self.tx_pool.state_db();
+                multi?.validate(&|address| state_db.get_multisig_account(address))
+            }
+            None => Err("Invalid multi-leg payload".to_string()),
+        }
+    }
+
     /// Verify block signature
     async fn verify_block_signature(&self, block: &SerializableBlock) -> Result<(), String> {
         // Convert signature bytes to signature
@@ -531,7 +582,7 @@ impl ConsensusIntegration {
 
     /// Get validator set statistics
     pub async fn get_validator_stats(&self) -> HashMap<Address, ValidatorReputation> {
-        let validator_set = self.validator_set.lock().await;
+        let validator_set = self.validator_set.lock().unwrap();
         validator_set.get_validator_stats()
     }
 }
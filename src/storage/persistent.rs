@@ -12,6 +12,8 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use crate::consensus::validator_set::ValidatorSet;
 use crate::core::handle_registry::HandleRecord;
+use crate::core::ingestion::{AccountMapping, GatewayBinding};
+use crate::core::idempotency::IdempotencyRecord;
 
 #[derive(Debug)]
 pub struct PersistentStorage {
@@ -19,8 +21,8 @@ pub struct PersistentStorage {
 }
 
 impl PersistentStorage {
-    pub fn new(_path: &str) -> Result<Self, sled::Error> {
-        let config = Self::create_optimized_config();
+    pub fn new(path: &str) -> Result<Self, sled::Error> {
+        let config = Self::create_optimized_config().path(path);
         let db = config.open()?;
         Ok(Self { db })
     }
@@ -39,8 +41,9 @@ impl PersistentStorage {
     }
 
     /// Create configuration optimized for high-frequency financial data
-    pub fn new_high_frequency(_path: &str) -> Result<Self, sled::Error> {
+    pub fn new_high_frequency(path: &str) -> Result<Self, sled::Error> {
         let config = sled::Config::default()
+            .path(path)
             // Optimized for high-frequency trading scenarios
             .cache_capacity(2048 * 1024 * 1024) // 2GB cache for ultra-low latency
             .use_compression(false) // Disable compression for speed
@@ -52,8 +55,9 @@ impl PersistentStorage {
     }
 
     /// Create configuration optimized for storage efficiency
-    pub fn new_storage_efficient(_path: &str) -> Result<Self, sled::Error> {
+    pub fn new_storage_efficient(path: &str) -> Result<Self, sled::Error> {
         let config = sled::Config::default()
+            .path(path)
             // Optimized for storage efficiency
             .cache_capacity(512 * 1024 * 1024) // 512MB cache
             .use_compression(true)
@@ -131,6 +135,24 @@ impl PersistentStorage {
         }
     }
 
+    pub fn store_parameter_registry(&self, registry: &crate::consensus::parameters::ParameterRegistry) -> Result<(), Box<dyn std::error::Error>> {
+        let key = b"parameter_registry";
+        let value = bincode::serialize(registry)?;
+        self.db.insert(key, value)?;
+        Ok(())
+    }
+
+    pub fn load_parameter_registry(&self) -> Result<Option<crate::consensus::parameters::ParameterRegistry>, Box<dyn std::error::Error>> {
+        let key = b"parameter_registry";
+        match self.db.get(key)? {
+            Some(ivec) => {
+                let registry: crate::consensus::parameters::ParameterRegistry = bincode::deserialize(&ivec)?;
+                Ok(Some(registry))
+            }
+            None => Ok(None),
+        }
+    }
+
     pub fn store_parameter(&self, key: &str, value: &str) -> Result<(), sled::Error> {
         let key_bytes = key.as_bytes();
         let value_bytes = value.as_bytes();
@@ -248,10 +270,10 @@ impl PersistentStorage {
         })
     }
 
-    // Store a handle record
+    // Store a handle record (JSON, since handle metadata is free-form JSON)
     pub fn store_handle(&self, handle: &HandleRecord) -> Result<(), sled::Error> {
         let key = format!("handle:{}", handle.handle);
-        let value = bincode::serialize(handle).unwrap();
+        let value = serde_json::to_vec(handle).unwrap();
         self.db.insert(key.as_bytes(), value)?;
         Ok(())
     }
@@ -259,9 +281,71 @@ impl PersistentStorage {
     // Load a handle record
     pub fn load_handle(&self, handle: &str) -> Option<HandleRecord> {
         let key = format!("handle:{handle}");
-        self.db.get(key.as_bytes()).unwrap().map(|ivec| {
-            bincode::deserialize(&ivec).unwrap()
-        })
+        self.db.get(key.as_bytes()).ok().flatten()
+            .and_then(|ivec| serde_json::from_slice(&ivec).ok())
+    }
+
+    // Load every handle record
+    pub fn load_handles(&self) -> Vec<HandleRecord> {
+        self.db.scan_prefix(b"handle:")
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, ivec)| serde_json::from_slice(&ivec).ok())
+            .collect()
+    }
+
+    // Store the institution bound to an ingestion adapter
+    pub fn store_gateway_binding(&self, binding: &GatewayBinding) -> Result<(), sled::Error> {
+        let key = format!("gateway_binding:{}", binding.adapter.protocol());
+        self.db.insert(key.as_bytes(), serde_json::to_vec(binding).unwrap())?;
+        Ok(())
+    }
+
+    // Load every adapter binding
+    pub fn load_gateway_bindings(&self) -> Vec<GatewayBinding> {
+        self.db.scan_prefix(b"gateway_binding:")
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, ivec)| serde_json::from_slice(&ivec).ok())
+            .collect()
+    }
+
+    // Store an identifier mapping of the ingestion table
+    pub fn store_account_mapping(&self, mapping: &AccountMapping) -> Result<(), sled::Error> {
+        let key = format!("account_mapping:{}:{}", mapping.kind.name(), mapping.identifier);
+        self.db.insert(key.as_bytes(), serde_json::to_vec(mapping).unwrap())?;
+        Ok(())
+    }
+
+    pub fn remove_account_mapping(&self, kind: &str, identifier: &str) -> Result<(), sled::Error> {
+        self.db.remove(format!("account_mapping:{kind}:{identifier}").as_bytes())?;
+        Ok(())
+    }
+
+    // Load every identifier mapping
+    pub fn load_account_mappings(&self) -> Vec<AccountMapping> {
+        self.db.scan_prefix(b"account_mapping:")
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, ivec)| serde_json::from_slice(&ivec).ok())
+            .collect()
+    }
+
+    // Store an idempotency record of an ingested instruction
+    pub fn store_idempotency_record(&self, record: &IdempotencyRecord) -> Result<(), sled::Error> {
+        let key = format!("idempotency:{}", record.storage_key());
+        self.db.insert(key.as_bytes(), serde_json::to_vec(record).unwrap())?;
+        Ok(())
+    }
+
+    pub fn remove_idempotency_record(&self, storage_key: &str) -> Result<(), sled::Error> {
+        self.db.remove(format!("idempotency:{storage_key}").as_bytes())?;
+        Ok(())
+    }
+
+    // Load every idempotency record
+    pub fn load_idempotency_records(&self) -> Vec<IdempotencyRecord> {
+        self.db.scan_prefix(b"idempotency:")
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, ivec)| serde_json::from_slice(&ivec).ok())
+            .collect()
     }
 }
 
@@ -1,6 +1,11 @@
 use sled::Db;
+use crate::consensus::epoch::EpochTransition;
+use crate::consensus::rewards::RewardEntry;
+use crate::consensus::slashing::SlashRecord;
+use crate::core::bridge::BridgeTransfer;
+use crate::core::multisig::MultisigAccount;
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
@@ -9,6 +14,38 @@ pub struct Account {
     pub nonce: u64,
 }
 
+/// Side of an account booking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntryDirection {
+    Credit,
+    Debit,
+}
+
+/// One booking on an account, kept for statements and debit/credit notifications
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountEntry {
+    pub tx_hash: [u8; 32],
+    pub leg: u32,                 // Leg index within a multi-leg transaction
+    pub shard_id: u16,
+    pub address: String,
+    pub counterparty: String,
+    pub asset: String,
+    pub amount: u64,
+    pub direction: EntryDirection,
+    pub balance_after: u64,
+    pub booked_at: u64,           // Unix seconds of the transaction's FinDAG Time
+}
+
+impl AccountEntry {
+    fn key(&self) -> String {
+        let side = match self.direction {
+            EntryDirection::Credit => 'C',
+            EntryDirection::Debit => 'D',
+        };
+        format!("account_entry:{}:{:020}:{}:{:04}:{side}", self.address, self.booked_at, hex::encode(self.tx_hash), self.leg)
+    }
+}
+
 pub struct StateManager {
     accounts: HashMap<String, Account>,
     shard_states: HashMap<u16, HashMap<String, u64>>,
@@ -72,6 +109,53 @@ pub struct StateDB {
     db: Db,
 }
 
+/// Balance and account changes staged against a StateDB and written atomically on commit
+pub struct StateBatch<'a> {
+    state_db: &'a StateDB,
+    balances: BTreeMap<(u16, String, String), u64>,
+    batch: sled::Batch,
+}
+
+impl StateBatch<'_> {
+    /// Stage a transfer of `amount` from (shard, address) to (shard, address)
+    pub fn transfer(&mut self, from: (u16, &str), to: (u16, &str), asset: &str, amount: u64) -> Result<(), String> {
+        let from_balance = self.balance(from.0, from.1, asset);
+        if from_balance < amount {
+            return Err(format!("Insufficient {asset} funds for {}", from.1));
+        }
+        self.balances.insert((from.0, from.1.to_string(), asset.to_string()), from_balance - amount);
+
+        let to_balance = self.balance(to.0, to.1, asset).checked_add(amount)
+            .ok_or_else(|| format!("{asset} balance overflow for {}", to.1))?;
+        self.balances.insert((to.0, to.1.to_string(), asset.to_string()), to_balance);
+        Ok(())
+    }
+
+    /// Stage a multisig account update, such as a spent nonce, alongside the balances
+    pub fn put_multisig_account(&mut self, account: &MultisigAccount) -> Result<(), String> {
+        let value = serde_json::to_vec(account)
+            .map_err(|e| format!("Failed to encode multisig account: {e}"))?;
+        self.batch.insert(format!("multisig:{}", account.address.as_str()).as_bytes(), value);
+        Ok(())
+    }
+
+    /// Write every staged change, or none of them
+    pub fn commit(mut self) -> Result<(), String> {
+        for ((shard_id, address, asset), balance) in &self.balances {
+            let key = format!("state:{shard_id}:{address}:{asset}");
+            self.batch.insert(key.as_bytes(), balance.to_string().as_bytes());
+        }
+        self.state_db.db.apply_batch(self.batch)
+            .map_err(|e| format!("Failed to apply transfer batch: {e}"))
+    }
+
+    fn balance(&self, shard_id: u16, address: &str, asset: &str) -> u64 {
+        self.balances.get(&(shard_id, address.to_string(), asset.to_string()))
+            .copied()
+            .unwrap_or_else(|| self.state_db.get_balance(shard_id, address, asset))
+    }
+}
+
 impl StateDB {
     pub fn new(path: &str) -> Self {
         let db = sled::open(path).expect("Failed to open sled state DB");
@@ -116,6 +200,21 @@ impl StateDB {
         Ok(())
     }
 
+    /// Apply a sequence of (from, to, asset, amount) transfers atomically on one shard.
+    /// Either every transfer is applied or none is.
+    pub fn transfer_batch(&self, shard_id: u16, transfers: &[(&str, &str, &str, u64)]) -> Result<(), String> {
+        let mut batch = self.batch();
+        for (from, to, asset, amount) in transfers {
+            batch.transfer((shard_id, from), (shard_id, to), asset, *amount)?;
+        }
+        batch.commit()
+    }
+
+    /// Start staging writes that are committed together in one sled batch
+    pub fn batch(&self) -> StateBatch<'_> {
+        StateBatch { state_db: self, balances: BTreeMap::new(), batch: sled::Batch::default() }
+    }
+
     /// Cross-shard transfer (two-phase commit)
     pub fn cross_shard_transfer(&self, source: u16, dest: u16, from: &str, to: &str, amount: u64, asset: &str) -> Result<(), String> {
         println!("[StateDB] Cross-shard transfer: {from} -> {to} ({source} -> {dest})");
@@ -136,6 +235,361 @@ impl StateDB {
         Ok(())
     }
 
+    /// Move every balance held under a legacy address to its canonical address.
+    /// Returns the number of (shard, asset) balances moved.
+    pub fn migrate_account(&self, legacy: &str, canonical: &str) -> Result<usize, String> {
+        let mut batch = sled::Batch::default();
+        let mut moved = 0;
+        for result in self.db.scan_prefix(b"state:") {
+            let (key, value) = result.map_err(|e| format!("Failed to scan state: {e}"))?;
+            let key_str = String::from_utf8(key.to_vec()).unwrap_or_default();
+            let parts: Vec<&str> = key_str.splitn(4, ':').collect();
+            if parts.len() != 4 || parts[2] != legacy {
+                continue;
+            }
+            let (shard_id, asset) = (parts[1].parse::<u16>().unwrap_or(0), parts[3]);
+            let amount = String::from_utf8(value.to_vec()).ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0);
+            let balance = self.get_balance(shard_id, canonical, asset).checked_add(amount)
+                .ok_or_else(|| format!("{asset} balance overflow for {canonical}"))?;
+            batch.remove(key);
+            batch.insert(format!("state:{shard_id}:{canonical}:{asset}").as_bytes(), balance.to_string().as_bytes());
+            moved += 1;
+        }
+        self.db.apply_batch(batch)
+            .map_err(|e| format!("Failed to migrate account: {e}"))?;
+        Ok(moved)
+    }
+
+    /// Get a registered multisig account
+    pub fn get_multisig_account(&self, address: &str) -> Option<MultisigAccount> {
+        let key = format!("multisig:{address}");
+        self.db.get(key).ok().flatten()
+            .and_then(|value| serde_json::from_slice(&value).ok())
+    }
+
+    /// Store a multisig account
+    pub fn put_multisig_account(&self, account: &MultisigAccount) -> Result<(), String> {
+        let key = format!("multisig:{}", account.address.as_str());
+        let value = serde_json::to_vec(account)
+            .map_err(|e| format!("Failed to encode multisig account: {e}"))?;
+        self.db.insert(key, value)
+            .map_err(|e| format!("Failed to store multisig account: {e}"))?;
+        Ok(())
+    }
+
+    /// Amount `delegator` has bonded to `validator`
+    pub fn get_delegation(&self, delegator: &str, validator: &str) -> u64 {
+        let key = format!("delegation:{delegator}:{validator}");
+        self.db.get(key).ok().flatten()
+            .and_then(|value| String::from_utf8(value.to_vec()).ok())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Set a delegation amount; zero removes the entry
+    pub fn set_delegation(&self, delegator: &str, validator: &str, amount: u64) -> Result<(), String> {
+        let key = format!("delegation:{delegator}:{validator}");
+        let result = if amount == 0 {
+            self.db.remove(key).map(|_| ())
+        } else {
+            self.db.insert(key, amount.to_string().as_bytes()).map(|_| ())
+        };
+        result.map_err(|e| format!("Failed to store delegation: {e}"))
+    }
+
+    /// Every (validator, amount) delegation held by `delegator`
+    pub fn delegations_of(&self, delegator: &str) -> Vec<(String, u64)> {
+        let prefix = format!("delegation:{delegator}:");
+        self.db.scan_prefix(prefix.as_bytes())
+            .filter_map(|result| result.ok())
+            .filter_map(|(key, value)| {
+                let key = String::from_utf8(key.to_vec()).ok()?;
+                let amount = String::from_utf8(value.to_vec()).ok()?.parse().ok()?;
+                Some((key.strip_prefix(&prefix)?.to_string(), amount))
+            })
+            .collect()
+    }
+
+    /// Queue unbonded stake for release back to `delegator` at `release_round`
+    pub fn add_unbonding(&self, release_round: u64, delegator: &str, validator: &str, amount: u64) -> Result<(), String> {
+        let key = format!("unbonding:{release_round:020}:{delegator}:{validator}");
+        let queued = self.db.get(&key).ok().flatten()
+            .and_then(|value| String::from_utf8(value.to_vec()).ok())
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(0);
+        self.db.insert(key, (queued + amount).to_string().as_bytes())
+            .map_err(|e| format!("Failed to queue unbonding: {e}"))?;
+        Ok(())
+    }
+
+    /// Pending unbondings as (release_round, delegator, validator, amount), earliest first
+    pub fn unbondings(&self) -> Vec<(u64, String, String, u64)> {
+        self.db.scan_prefix(b"unbonding:")
+            .filter_map(|result| result.ok())
+            .filter_map(|(key, value)| {
+                let key = String::from_utf8(key.to_vec()).ok()?;
+                let parts: Vec<&str> = key.splitn(4, ':').collect();
+                if parts.len() != 4 {
+                    return None;
+                }
+                let amount = String::from_utf8(value.to_vec()).ok()?.parse().ok()?;
+                Some((parts[1].parse().ok()?, parts[2].to_string(), parts[3].to_string(), amount))
+            })
+            .collect()
+    }
+
+    /// Delegations bonded to `validator` as (delegator, amount)
+    pub fn delegations_to(&self, validator: &str) -> Vec<(String, u64)> {
+        self.db.scan_prefix(b"delegation:")
+            .filter_map(|result| result.ok())
+            .filter_map(|(key, value)| {
+                let key = String::from_utf8(key.to_vec()).ok()?;
+                let (delegator, target) = key.strip_prefix("delegation:")?.split_once(':')?;
+                if target != validator {
+                    return None;
+                }
+                let amount = String::from_utf8(value.to_vec()).ok()?.parse().ok()?;
+                Some((delegator.to_string(), amount))
+            })
+            .collect()
+    }
+
+    /// Remove an unbonding entry once it has been released
+    pub fn remove_unbonding(&self, release_round: u64, delegator: &str, validator: &str) -> Result<(), String> {
+        let key = format!("unbonding:{release_round:020}:{delegator}:{validator}");
+        self.db.remove(key)
+            .map_err(|e| format!("Failed to remove unbonding: {e}"))?;
+        Ok(())
+    }
+
+    /// Whether evidence with this id has already been slashed for
+    pub fn has_slash_record(&self, evidence_id: &[u8; 32]) -> bool {
+        self.db.contains_key(format!("slash_evidence:{}", hex::encode(evidence_id))).unwrap_or(false)
+    }
+
+    /// Store a slash record, indexed by validator and by evidence id
+    pub fn put_slash_record(&self, record: &SlashRecord) -> Result<(), String> {
+        let value = serde_json::to_vec(record)
+            .map_err(|e| format!("Failed to encode slash record: {e}"))?;
+        let evidence = hex::encode(record.evidence_id);
+        let mut batch = sled::Batch::default();
+        batch.insert(format!("slash_record:{}:{:020}:{evidence}", record.validator, record.round).as_bytes(), value);
+        batch.insert(format!("slash_evidence:{evidence}").as_bytes(), record.validator.as_bytes());
+        self.db.apply_batch(batch)
+            .map_err(|e| format!("Failed to store slash record: {e}"))
+    }
+
+    /// Slash records of a validator, oldest first
+    pub fn slash_records_of(&self, validator: &str) -> Vec<SlashRecord> {
+        self.db.scan_prefix(format!("slash_record:{validator}:").as_bytes())
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, value)| serde_json::from_slice(&value).ok())
+            .collect()
+    }
+
+    /// Rewards credited to `address` and not yet claimed
+    pub fn get_claimable_reward(&self, address: &str) -> u64 {
+        self.db.get(format!("reward:{address}")).ok().flatten()
+            .and_then(|value| String::from_utf8(value.to_vec()).ok())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    }
+
+    pub fn set_claimable_reward(&self, address: &str, amount: u64) -> Result<(), String> {
+        self.db.insert(format!("reward:{address}"), amount.to_string().as_bytes())
+            .map_err(|e| format!("Failed to store claimable reward: {e}"))?;
+        Ok(())
+    }
+
+    /// Credit one round's rewards: claimable balances, history entries and the
+    /// round marker are written in a single batch
+    pub fn credit_round_rewards(&self, round: u64, entries: &[RewardEntry]) -> Result<(), String> {
+        let mut claimable: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut batch = sled::Batch::default();
+        for (i, entry) in entries.iter().enumerate() {
+            let balance = claimable.entry(entry.recipient.as_str())
+                .or_insert_with(|| self.get_claimable_reward(&entry.recipient));
+            *balance += entry.amount;
+            let value = serde_json::to_vec(entry)
+                .map_err(|e| format!("Failed to encode reward entry: {e}"))?;
+            batch.insert(format!("reward_history:{}:{round:020}:{i:06}", entry.recipient).as_bytes(), value);
+        }
+        for (address, balance) in claimable {
+            batch.insert(format!("reward:{address}").as_bytes(), balance.to_string().as_bytes());
+        }
+        batch.insert(format!("rewarded_round:{round:020}").as_bytes(), b"1".as_slice());
+        self.db.apply_batch(batch)
+            .map_err(|e| format!("Failed to credit rewards: {e}"))
+    }
+
+    /// Whether rewards for `round` have already been distributed
+    pub fn is_round_rewarded(&self, round: u64) -> bool {
+        self.db.contains_key(format!("rewarded_round:{round:020}")).unwrap_or(false)
+    }
+
+    /// Reward history of `address`, oldest first
+    pub fn reward_history(&self, address: &str) -> Vec<RewardEntry> {
+        self.db.scan_prefix(format!("reward_history:{address}:").as_bytes())
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, value)| serde_json::from_slice(&value).ok())
+            .collect()
+    }
+
+    /// Store an epoch transition record, replacing any earlier copy of the same epoch
+    pub fn put_epoch_transition(&self, transition: &EpochTransition) -> Result<(), String> {
+        let value = serde_json::to_vec(transition)
+            .map_err(|e| format!("Failed to encode epoch transition: {e}"))?;
+        self.db.insert(format!("epoch_transition:{:020}", transition.epoch), value)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to store epoch transition: {e}"))
+    }
+
+    pub fn get_epoch_transition(&self, epoch: u64) -> Option<EpochTransition> {
+        self.db.get(format!("epoch_transition:{epoch:020}")).ok().flatten()
+            .and_then(|value| serde_json::from_slice(&value).ok())
+    }
+
+    pub fn latest_epoch_transition(&self) -> Option<EpochTransition> {
+        self.db.scan_prefix(b"epoch_transition:").next_back()
+            .and_then(|result| result.ok())
+            .and_then(|(_, value)| serde_json::from_slice(&value).ok())
+    }
+
+    /// Up to `limit` epoch transitions starting at `from_epoch`, oldest first
+    pub fn epoch_transitions(&self, from_epoch: u64, limit: usize) -> Vec<EpochTransition> {
+        self.db.range(format!("epoch_transition:{from_epoch:020}").into_bytes()..b"epoch_transition;".to_vec())
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, value)| serde_json::from_slice(&value).ok())
+            .take(limit)
+            .collect()
+    }
+
+    /// Record account bookings, indexing each account under its booking day
+    pub fn record_account_entries(&self, entries: &[AccountEntry]) -> Result<(), String> {
+        let mut batch = sled::Batch::default();
+        for entry in entries {
+            let value = serde_json::to_vec(entry)
+                .map_err(|e| format!("Failed to encode account entry: {e}"))?;
+            batch.insert(entry.key().as_bytes(), value);
+            batch.insert(format!("booking_day:{:010}:{}", entry.booked_at / 86_400, entry.address).as_bytes(), b"1".as_slice());
+        }
+        self.db.apply_batch(batch)
+            .map_err(|e| format!("Failed to record account entries: {e}"))
+    }
+
+    /// Bookings of `address` with `from <= booked_at < to`, oldest first
+    pub fn account_entries(&self, address: &str, from: u64, to: u64) -> Vec<AccountEntry> {
+        let start = format!("account_entry:{address}:{from:020}");
+        let end = format!("account_entry:{address}:{to:020}");
+        self.db.range(start.into_bytes()..end.into_bytes())
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, value)| serde_json::from_slice(&value).ok())
+            .collect()
+    }
+
+    /// Latest booking of `address` in `asset` strictly before `before`
+    pub fn last_account_entry_before(&self, address: &str, asset: &str, before: u64) -> Option<AccountEntry> {
+        let start = format!("account_entry:{address}:");
+        let end = format!("account_entry:{address}:{before:020}");
+        self.db.range(start.into_bytes()..end.into_bytes())
+            .rev()
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, value)| serde_json::from_slice::<AccountEntry>(&value).ok())
+            .find(|entry| entry.asset == asset)
+    }
+
+    /// Accounts with at least one booking on the given day (days since the Unix epoch)
+    pub fn booked_accounts(&self, day: u64) -> Vec<String> {
+        let prefix = format!("booking_day:{day:010}:");
+        self.db.scan_prefix(prefix.as_bytes())
+            .filter_map(|result| result.ok())
+            .filter_map(|(key, _)| String::from_utf8(key.to_vec()).ok())
+            .filter_map(|key| key.strip_prefix(&prefix).map(str::to_string))
+            .collect()
+    }
+
+    /// Bridge transfer by FinDAG transaction id (hex)
+    pub fn get_bridge_transfer(&self, id: &str) -> Option<BridgeTransfer> {
+        self.db.get(format!("bridge_transfer:{id}")).ok().flatten()
+            .and_then(|value| serde_json::from_slice(&value).ok())
+    }
+
+    /// Store a bridge transfer. An inbound transfer also claims its source-chain
+    /// transaction id in the same batch, so a source transaction releases once.
+    pub fn put_bridge_transfer(&self, transfer: &BridgeTransfer) -> Result<(), String> {
+        let value = serde_json::to_vec(transfer)
+            .map_err(|e| format!("Failed to encode bridge transfer: {e}"))?;
+        let mut batch = sled::Batch::default();
+        batch.insert(format!("bridge_transfer:{}", transfer.id).as_bytes(), value);
+        if let Some(source_tx_id) = &transfer.source_tx_id {
+            batch.insert(format!("bridge_source:{}:{source_tx_id}", transfer.chain).as_bytes(), transfer.id.as_bytes());
+        }
+        self.db.apply_batch(batch)
+            .map_err(|e| format!("Failed to store bridge transfer: {e}"))
+    }
+
+    /// FinDAG transaction that released a source-chain transaction, if any
+    pub fn bridge_source_release(&self, chain: &str, source_tx_id: &str) -> Option<String> {
+        self.db.get(format!("bridge_source:{chain}:{source_tx_id}")).ok().flatten()
+            .and_then(|value| String::from_utf8(value.to_vec()).ok())
+    }
+
+    /// Amount of `asset` held in bridge escrow for transfers to `chain`
+    pub fn bridge_locked(&self, chain: &str, asset: &str) -> u64 {
+        self.get_u64(&format!("bridge_locked:{chain}:{asset}"))
+    }
+
+    pub fn set_bridge_locked(&self, chain: &str, asset: &str, amount: u64) -> Result<(), String> {
+        self.set_u64(&format!("bridge_locked:{chain}:{asset}"), amount)
+    }
+
+    /// Amount of `asset` bridged in either direction during a rate-limit window
+    pub fn bridge_volume(&self, asset: &str, window: u64) -> u64 {
+        self.get_u64(&format!("bridge_volume:{asset}:{window:020}"))
+    }
+
+    pub fn set_bridge_volume(&self, asset: &str, window: u64, amount: u64) -> Result<(), String> {
+        self.set_u64(&format!("bridge_volume:{asset}:{window:020}"), amount)
+    }
+
+    /// Mark an outbound transfer as included in `block_id` and awaiting finality
+    pub fn add_bridge_inflight(&self, block_id: &[u8; 32], id: &str) -> Result<(), String> {
+        self.db.insert(format!("bridge_inflight:{}:{id}", hex::encode(block_id)), b"1".as_slice())
+            .map(|_| ())
+            .map_err(|e| format!("Failed to store in-flight bridge transfer: {e}"))
+    }
+
+    /// Outbound transfers included in `block_id` and not yet finalized
+    pub fn bridge_inflight(&self, block_id: &[u8; 32]) -> Vec<String> {
+        let prefix = format!("bridge_inflight:{}:", hex::encode(block_id));
+        self.db.scan_prefix(prefix.as_bytes())
+            .filter_map(|result| result.ok())
+            .filter_map(|(key, _)| String::from_utf8(key.to_vec()).ok())
+            .filter_map(|key| key.strip_prefix(&prefix).map(str::to_string))
+            .collect()
+    }
+
+    pub fn remove_bridge_inflight(&self, block_id: &[u8; 32], id: &str) -> Result<(), String> {
+        self.db.remove(format!("bridge_inflight:{}:{id}", hex::encode(block_id)))
+            .map(|_| ())
+            .map_err(|e| format!("Failed to remove in-flight bridge transfer: {e}"))
+    }
+
+    fn get_u64(&self, key: &str) -> u64 {
+        self.db.get(key).ok().flatten()
+            .and_then(|value| String::from_utf8(value.to_vec()).ok())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    }
+
+    fn set_u64(&self, key: &str, value: u64) -> Result<(), String> {
+        self.db.insert(key, value.to_string().as_bytes())
+            .map(|_| ())
+            .map_err(|e| format!("Failed to store {key}: {e}"))
+    }
+
     /// Get all accounts on a shard
     pub fn get_accounts(&self, shard_id: u16) -> Vec<String> {
         let mut accounts = Vec::new();
@@ -0,0 +1,249 @@
+use anyhow::{anyhow, Result};
+use crate::iso20022::messages::{currency_exponent, is_valid_bic};
+
+/// Maximum length of a SWIFT amount, decimal comma included
+const MAX_AMOUNT_LEN: usize = 15;
+/// Line width of name, address and narrative fields
+const LINE_WIDTH: usize = 35;
+
+/// Parse a `YYMMDD` date, returning it unchanged
+pub fn parse_date(value: &str) -> Result<String> {
+    chrono::NaiveDate::parse_from_str(value, "%y%m%d")
+        .map_err(|_| anyhow!("Invalid date '{}', expected YYMMDD", value))?;
+    Ok(value.to_string())
+}
+
+fn parse_currency(value: &str) -> Result<String> {
+    if value.len() != 3 || !value.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(anyhow!("Invalid currency code '{}'", value));
+    }
+    Ok(value.to_string())
+}
+
+/// Parse a SWIFT decimal amount (`1234,56`, `123456,`) into minor units of the currency
+pub fn parse_amount(value: &str, currency: &str) -> Result<u64> {
+    let invalid = || anyhow!("Invalid {} amount '{}'", currency, value);
+    if value.len() > MAX_AMOUNT_LEN {
+        return Err(invalid());
+    }
+    let (int_part, frac_part) = value.split_once(',').ok_or_else(invalid)?;
+    if int_part.is_empty()
+        || !int_part.chars().all(|c| c.is_ascii_digit())
+        || !frac_part.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let exponent = currency_exponent(currency) as usize;
+    let frac_part = frac_part.trim_end_matches('0');
+    if frac_part.len() > exponent {
+        return Err(anyhow!("{} amount '{}' has more than {} decimal places", currency, value, exponent));
+    }
+    let minor = format!("{}{:0<width$}", int_part, frac_part, width = exponent);
+    minor.parse::<u64>().map_err(|_| invalid())
+}
+
+/// Format minor units of a currency as a SWIFT amount, always with a decimal comma
+pub fn format_amount(minor_units: u64, currency: &str) -> String {
+    let exponent = currency_exponent(currency) as usize;
+    if exponent == 0 {
+        return format!("{},", minor_units);
+    }
+    let digits = format!("{:0>width$}", minor_units, width = exponent + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - exponent);
+    format!("{},{}", int_part, frac_part.trim_end_matches('0'))
+}
+
+/// Field 32A: value date, currency and settled amount
+pub fn parse_date_currency_amount(value: &str) -> Result<(String, String, u64)> {
+    if value.len() < 10 || !value.is_char_boundary(9) {
+        return Err(anyhow!("Invalid :32A: value '{}'", value));
+    }
+    let date = parse_date(&value[..6])?;
+    let currency = parse_currency(&value[6..9])?;
+    let amount = parse_amount(&value[9..], &currency)?;
+    Ok((date, currency, amount))
+}
+
+/// Fields 32B/33B: currency and amount
+pub fn parse_currency_amount(value: &str) -> Result<(String, u64)> {
+    if value.len() < 4 || !value.is_char_boundary(3) {
+        return Err(anyhow!("Invalid currency/amount '{}'", value));
+    }
+    let currency = parse_currency(&value[..3])?;
+    let amount = parse_amount(&value[3..], &currency)?;
+    Ok((currency, amount))
+}
+
+/// Narrative fields such as 70 and 72: up to `max_lines` lines of 35 characters
+pub fn parse_narrative(tag: &str, value: &str, max_lines: usize) -> Result<String> {
+    check_lines(tag, &value.lines().collect::<Vec<_>>(), max_lines)?;
+    Ok(value.to_string())
+}
+
+fn check_lines(tag: &str, lines: &[&str], max_lines: usize) -> Result<()> {
+    if lines.len() > max_lines {
+        return Err(anyhow!(":{}: has more than {} lines", tag, max_lines));
+    }
+    if let Some(line) = lines.iter().find(|l| l.chars().count() > LINE_WIDTH) {
+        return Err(anyhow!(":{}: line longer than {} characters: '{}'", tag, LINE_WIDTH, line));
+    }
+    Ok(())
+}
+
+/// Field 71A details of charges
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChargeBearer {
+    Beneficiary, // BEN
+    Ours,        // OUR
+    Shared,      // SHA
+}
+
+impl ChargeBearer {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "BEN" => Ok(ChargeBearer::Beneficiary),
+            "OUR" => Ok(ChargeBearer::Ours),
+            "SHA" => Ok(ChargeBearer::Shared),
+            other => Err(anyhow!("Invalid :71A: code '{}'", other)),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ChargeBearer::Beneficiary => "BEN",
+            ChargeBearer::Ours => "OUR",
+            ChargeBearer::Shared => "SHA",
+        }
+    }
+}
+
+/// Party field in any of its letter options:
+/// - A: optional `/account` line and a BIC
+/// - B: optional `/account` line and a location
+/// - D, K and no letter: optional `/account` line, name and address lines
+/// - F: `/account` or coded party identifier line, then numbered `1/` name and `2/`..`8/` lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Party {
+    pub option: Option<char>,
+    pub account: Option<String>,
+    pub party_identifier: Option<String>, // Option F code such as CUST/DE/REGISTRY/12345
+    pub bic: Option<String>,
+    pub name: Option<String>,
+    pub address: Vec<String>,
+}
+
+impl Party {
+    /// BIC-identified party (option A)
+    pub fn from_bic(bic: &str, account: Option<String>) -> Self {
+        Party { option: Some('A'), account, party_identifier: None, bic: Some(bic.to_string()), name: None, address: Vec::new() }
+    }
+
+    /// Parse the value of a party field; `tag` is the full tag such as `50K` or `59`
+    pub fn parse(tag: &str, value: &str) -> Result<Self> {
+        let option = tag.chars().nth(2);
+        let mut lines: Vec<&str> = value.lines().collect();
+        if lines.is_empty() || lines.iter().all(|l| l.trim().is_empty()) {
+            return Err(anyhow!(":{}: is empty", tag));
+        }
+        let mut party_identifier = None;
+        let account = match option {
+            _ if lines[0].starts_with('/') => Some(party_account(lines.remove(0))),
+            // 50F/59F without an account open with a coded party identifier
+            Some('F') => {
+                party_identifier = Some(lines.remove(0).to_string());
+                None
+            }
+            _ => None,
+        };
+        let mut party = Party { option, account, party_identifier, bic: None, name: None, address: Vec::new() };
+        match option {
+            Some('A') => {
+                let [bic] = lines[..] else {
+                    return Err(anyhow!(":{}: must hold one BIC line after the account", tag));
+                };
+                if !is_valid_bic(bic) {
+                    return Err(anyhow!("Invalid BIC '{}' in :{}:", bic, tag));
+                }
+                party.bic = Some(bic.to_string());
+            }
+            Some('B') => {
+                check_lines(tag, &lines, 1)?;
+                party.address = lines.iter().map(|l| l.to_string()).collect();
+            }
+            Some('F') => {
+                check_lines(tag, &lines, 4)?;
+                let mut name = Vec::new();
+                for line in &lines {
+                    match line.split_once('/') {
+                        Some(("1", rest)) => name.push(rest),
+                        // Kept with their number, which says what the line holds
+                        Some(("2" | "3" | "4" | "5" | "6" | "7" | "8", _)) => party.address.push(line.to_string()),
+                        _ => return Err(anyhow!("Invalid :{}: line '{}', expected a numbered line", tag, line)),
+                    }
+                }
+                if name.is_empty() {
+                    return Err(anyhow!(":{}: has no 1/ name line", tag));
+                }
+                party.name = Some(name.join(" "));
+            }
+            Some('D') | Some('K') | None => {
+                check_lines(tag, &lines, 4)?;
+                let mut lines = lines.into_iter();
+                party.name = lines.next().map(str::to_string);
+                party.address = lines.map(str::to_string).collect();
+            }
+            Some(other) => return Err(anyhow!("Unsupported option {} for :{}:", other, tag)),
+        }
+        Ok(party)
+    }
+
+    /// Tag letter this party is written with (e.g. `K` for `:50K:`)
+    pub fn option_suffix(&self) -> String {
+        self.option.map(String::from).unwrap_or_default()
+    }
+
+    /// Field value in the party's option format
+    pub fn to_field_value(&self) -> String {
+        let mut lines = Vec::new();
+        match self.option {
+            Some('F') => {
+                match (&self.account, &self.party_identifier) {
+                    (Some(account), _) => lines.push(format!("/{}", account)),
+                    (None, Some(identifier)) => lines.push(identifier.clone()),
+                    (None, None) => {}
+                }
+                if let Some(name) = &self.name {
+                    lines.push(format!("1/{}", name));
+                }
+                lines.extend(self.address.iter().cloned());
+            }
+            _ => {
+                if let Some(account) = &self.account {
+                    lines.push(format!("/{}", account));
+                }
+                lines.extend(self.bic.iter().cloned());
+                lines.extend(self.name.iter().cloned());
+                lines.extend(self.address.iter().cloned());
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Best single identifier: account, then BIC, then name
+    pub fn identifier(&self) -> &str {
+        self.account.as_deref()
+            .or(self.party_identifier.as_deref())
+            .or(self.bic.as_deref())
+            .or(self.name.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// Strip the leading slash and any `/D/` or `/C/` debit/credit mark from an account line
+fn party_account(line: &str) -> String {
+    let account = line.trim_start_matches('/');
+    match account.split_once('/') {
+        Some(("C" | "D", rest)) => rest.to_string(),
+        _ => account.to_string(),
+    }
+}
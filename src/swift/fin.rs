@@ -0,0 +1,306 @@
+use anyhow::{anyhow, Result};
+use crate::iso20022::messages::is_valid_bic;
+
+/// Block 1: application id, service id and the session of the sending (input)
+/// or receiving (output) logical terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicHeader {
+    pub application_id: char,    // F = FIN
+    pub service_id: String,      // 01 = user-to-user
+    pub logical_terminal: String, // BIC8 + terminal code + branch
+    pub session_number: String,
+    pub sequence_number: String,
+}
+
+/// Block 2 direction: `I` as sent to SWIFT, `O` as delivered by SWIFT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Input,
+    Output,
+}
+
+/// Block 2: message type and the other party's logical terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplicationHeader {
+    pub direction: Direction,
+    pub message_type: String,
+    pub counterparty: String, // Receiver on input, sender (from the MIR) on output
+    pub priority: Option<char>,
+}
+
+/// One `:tag:value` field of the text block; continuation lines are joined with `\n`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub tag: String,
+    pub value: String,
+}
+
+/// A FIN message split into its blocks. Messages without an envelope carry only `fields`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FinMessage {
+    pub basic_header: Option<BasicHeader>,
+    pub application_header: Option<ApplicationHeader>,
+    pub user_header: Vec<(String, String)>, // e.g. 108 MUR, 119 COV, 121 UETR
+    pub fields: Vec<Field>,
+    pub trailer: Vec<(String, String)>,
+}
+
+impl FinMessage {
+    /// New input message from `sender` to `receiver` (BIC8 or BIC11)
+    pub fn input(message_type: &str, sender: &str, receiver: &str) -> Result<Self> {
+        Ok(FinMessage {
+            basic_header: Some(BasicHeader {
+                application_id: 'F',
+                service_id: "01".to_string(),
+                logical_terminal: logical_terminal(sender)?,
+                session_number: "0000".to_string(),
+                sequence_number: "000000".to_string(),
+            }),
+            application_header: Some(ApplicationHeader {
+                direction: Direction::Input,
+                message_type: message_type.to_string(),
+                counterparty: logical_terminal(receiver)?,
+                priority: Some('N'),
+            }),
+            ..Default::default()
+        })
+    }
+
+    pub fn message_type(&self) -> Option<&str> {
+        self.application_header.as_ref().map(|h| h.message_type.as_str())
+    }
+
+    /// BIC of the sending institution, if the message has an envelope
+    pub fn sender_bic(&self) -> Option<String> {
+        let terminal = match self.application_header.as_ref()?.direction {
+            Direction::Input => &self.basic_header.as_ref()?.logical_terminal,
+            Direction::Output => &self.application_header.as_ref()?.counterparty,
+        };
+        Some(bic_of_terminal(terminal))
+    }
+
+    /// BIC of the receiving institution, if the message has an envelope
+    pub fn receiver_bic(&self) -> Option<String> {
+        let terminal = match self.application_header.as_ref()?.direction {
+            Direction::Input => &self.application_header.as_ref()?.counterparty,
+            Direction::Output => &self.basic_header.as_ref()?.logical_terminal,
+        };
+        Some(bic_of_terminal(terminal))
+    }
+
+    pub fn user_header_value(&self, tag: &str) -> Option<&str> {
+        self.user_header.iter().find(|(t, _)| t == tag).map(|(_, v)| v.as_str())
+    }
+
+    /// First field with exactly this tag
+    pub fn field(&self, tag: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.tag == tag)
+    }
+
+    pub fn push_field(&mut self, tag: &str, value: impl Into<String>) {
+        self.fields.push(Field { tag: tag.to_string(), value: value.into() });
+    }
+
+    /// Serialize with the `{1:}{2:}{3:}{4:}` blocks; the trailer is left to the network
+    pub fn to_fin(&self) -> String {
+        let mut out = String::new();
+        if let Some(b1) = &self.basic_header {
+            out.push_str(&format!(
+                "{{1:{}{}{}{}{}}}",
+                b1.application_id, b1.service_id, b1.logical_terminal, b1.session_number, b1.sequence_number
+            ));
+        }
+        if let Some(b2) = &self.application_header {
+            let direction = match b2.direction {
+                Direction::Input => 'I',
+                Direction::Output => 'O',
+            };
+            out.push_str(&format!(
+                "{{2:{}{}{}{}}}",
+                direction,
+                b2.message_type,
+                b2.counterparty,
+                b2.priority.map(String::from).unwrap_or_default()
+            ));
+        }
+        if !self.user_header.is_empty() {
+            let tags: String = self.user_header.iter().map(|(t, v)| format!("{{{}:{}}}", t, v)).collect();
+            out.push_str(&format!("{{3:{}}}", tags));
+        }
+        out.push_str("{4:\r\n");
+        for field in &self.fields {
+            out.push_str(&format!(":{}:{}\r\n", field.tag, field.value.replace('\n', "\r\n")));
+        }
+        out.push_str("-}");
+        out
+    }
+}
+
+/// 12-character logical terminal address of a BIC (terminal code `A`)
+fn logical_terminal(bic: &str) -> Result<String> {
+    if !is_valid_bic(bic) {
+        return Err(anyhow!("Invalid BIC '{}'", bic));
+    }
+    let branch = bic.get(8..).filter(|b| !b.is_empty()).unwrap_or("XXX");
+    Ok(format!("{}A{}", &bic[..8], branch))
+}
+
+/// BIC of a logical terminal: BIC8, plus the branch unless it is the primary office
+fn bic_of_terminal(terminal: &str) -> String {
+    match (terminal.get(..8), terminal.get(9..12)) {
+        (Some(bic8), Some(branch)) if branch != "XXX" => format!("{}{}", bic8, branch),
+        (Some(bic8), _) => bic8.to_string(),
+        _ => terminal.to_string(),
+    }
+}
+
+/// Parse a FIN message. Input without a `{1:` envelope is read as a bare text block.
+pub fn parse_fin(raw: &str) -> Result<FinMessage> {
+    let raw = raw.trim();
+    if !raw.starts_with('{') {
+        return Ok(FinMessage { fields: parse_text_block(raw)?, ..Default::default() });
+    }
+    let mut message = FinMessage::default();
+    let mut text_block = None;
+    for (id, content) in split_blocks(raw)? {
+        match id.as_str() {
+            "1" => message.basic_header = Some(parse_basic_header(&content)?),
+            "2" => message.application_header = Some(parse_application_header(&content)?),
+            "3" => message.user_header = split_blocks(&content)?,
+            "4" => text_block = Some(content),
+            "5" => message.trailer = split_blocks(&content)?,
+            other => return Err(anyhow!("Unknown FIN block {{{}:", other)),
+        }
+    }
+    if message.basic_header.is_none() || message.application_header.is_none() {
+        return Err(anyhow!("FIN message is missing its basic or application header"));
+    }
+    let text_block = text_block.ok_or_else(|| anyhow!("FIN message has no text block {{4:"))?;
+    message.fields = parse_text_block(&text_block)?;
+    Ok(message)
+}
+
+/// Split `{id:content}{id:content}...` at the top level, honouring nested braces
+fn split_blocks(raw: &str) -> Result<Vec<(String, String)>> {
+    let mut blocks = Vec::new();
+    let mut rest = raw.trim();
+    while !rest.is_empty() {
+        let body = rest.strip_prefix('{').ok_or_else(|| anyhow!("Expected '{{' at '{}'", truncate(rest)))?;
+        let (id, body) = body.split_once(':').ok_or_else(|| anyhow!("Block without an identifier at '{}'", truncate(rest)))?;
+        let mut depth = 1;
+        let end = body.char_indices()
+            .find(|(_, c)| {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            })
+            .map(|(i, _)| i)
+            .ok_or_else(|| anyhow!("Unterminated block {{{}:", id))?;
+        blocks.push((id.to_string(), body[..end].to_string()));
+        rest = body[end + 1..].trim_start();
+    }
+    Ok(blocks)
+}
+
+fn truncate(value: &str) -> &str {
+    value.char_indices().nth(20).map(|(i, _)| &value[..i]).unwrap_or(value)
+}
+
+fn parse_basic_header(content: &str) -> Result<BasicHeader> {
+    if content.len() != 25 || !content.is_ascii() {
+        return Err(anyhow!("Invalid basic header '{}'", content));
+    }
+    Ok(BasicHeader {
+        application_id: content.chars().next().unwrap(),
+        service_id: content[1..3].to_string(),
+        logical_terminal: content[3..15].to_string(),
+        session_number: content[15..19].to_string(),
+        sequence_number: content[19..25].to_string(),
+    })
+}
+
+fn parse_application_header(content: &str) -> Result<ApplicationHeader> {
+    let invalid = || anyhow!("Invalid application header '{}'", content);
+    if !content.is_ascii() || content.len() < 4 {
+        return Err(invalid());
+    }
+    let message_type = content[1..4].to_string();
+    if !message_type.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    match &content[..1] {
+        // I + MT + receiver LT + optional priority, delivery monitoring, obsolescence period
+        "I" => Ok(ApplicationHeader {
+            direction: Direction::Input,
+            message_type,
+            counterparty: content.get(4..16).ok_or_else(invalid)?.to_string(),
+            priority: content[16..].chars().next(),
+        }),
+        // O + MT + input time + MIR (date, sender LT, session, sequence) + output date/time + priority
+        "O" => Ok(ApplicationHeader {
+            direction: Direction::Output,
+            message_type,
+            counterparty: content.get(14..26).ok_or_else(invalid)?.to_string(),
+            priority: content.get(46..).and_then(|p| p.chars().next()),
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+/// Split the text block body into fields. A line opens a new field when it starts with
+/// `:NN:` or `:NNa:`; any other line continues the previous field.
+fn parse_text_block(body: &str) -> Result<Vec<Field>> {
+    let mut fields: Vec<Field> = Vec::new();
+    for line in body.lines().map(|l| l.trim_end_matches('\r')) {
+        if line == "-" {
+            break;
+        }
+        if let Some((tag, value)) = field_start(line) {
+            fields.push(Field { tag: tag.to_string(), value: value.to_string() });
+        } else if let Some(field) = fields.last_mut() {
+            field.value.push('\n');
+            field.value.push_str(line);
+        } else if !line.trim().is_empty() {
+            return Err(anyhow!("Text block line outside any field: '{}'", line));
+        }
+    }
+    Ok(fields)
+}
+
+fn field_start(line: &str) -> Option<(&str, &str)> {
+    let (tag, value) = line.strip_prefix(':')?.split_once(':')?;
+    let bytes = tag.as_bytes();
+    let valid = (tag.len() == 2 || tag.len() == 3)
+        && bytes[..2].iter().all(u8::is_ascii_digit)
+        && bytes.get(2).is_none_or(u8::is_ascii_uppercase);
+    valid.then_some((tag, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fin_blocks() {
+        let raw = "{1:F01DEUTDEFFAXXX0123456789}{2:O1031200250703BNPAFRPPAXXX12345678902507031201N}\
+            {3:{108:MUR-7}{121:8a562c67-ca16-48ba-b074-65581be6f011}}{4:\r\n:20:REF-1\r\n:70:LINE ONE\r\nLINE TWO\r\n-}{5:{CHK:1A2B3C4D5E6F}}";
+        let message = parse_fin(raw).unwrap();
+        assert_eq!(message.message_type(), Some("103"));
+        assert_eq!(message.sender_bic().as_deref(), Some("BNPAFRPP"));
+        assert_eq!(message.receiver_bic().as_deref(), Some("DEUTDEFF"));
+        assert_eq!(message.user_header_value("121"), Some("8a562c67-ca16-48ba-b074-65581be6f011"));
+        assert_eq!(message.trailer, vec![("CHK".to_string(), "1A2B3C4D5E6F".to_string())]);
+        assert_eq!(message.field("70").unwrap().value, "LINE ONE\nLINE TWO");
+
+        let mut out = FinMessage::input("910", "DEUTDEFF", "BNPAFRPPXXX").unwrap();
+        out.push_field("20", "CONF-1");
+        let reparsed = parse_fin(&out.to_fin()).unwrap();
+        assert_eq!(reparsed, out);
+        assert_eq!(reparsed.receiver_bic().as_deref(), Some("BNPAFRPP"));
+
+        assert!(parse_fin("{1:F01DEUTDEFFAXXX0123456789}{4:\r\n:20:X\r\n-").is_err());
+    }
+}
@@ -1,65 +1,159 @@
 use anyhow::{Result, anyhow};
 use crate::core::types::Transaction;
-use crate::core::address::Address;
+use crate::core::ingestion::{Adapter, GatewaySigner, IdentifierKind, IngestionRegistry};
+use crate::iso20022::messages::is_valid_lei;
 use sha2::{Sha256, Digest};
-use ed25519_dalek::{Signature, VerifyingKey};
+use ed25519_dalek::Signature;
 
+pub mod fields;
+pub mod fin;
+pub mod mt202;
+pub mod mt910;
+
+pub use fields::{ChargeBearer, Party};
+pub use fin::{parse_fin, FinMessage};
+pub use mt202::{parse_mt202, MT202Message};
+pub use mt910::MT910Confirmation;
+
+/// MT103 single customer credit transfer. Amounts are in minor units of the currency.
 #[derive(Debug, Clone, PartialEq)]
 pub struct MT103Message {
-    pub reference: String,
-    pub value_date: String,
+    pub sender: Option<String>,   // BIC from the FIN envelope
+    pub receiver: Option<String>, // BIC from the FIN envelope
+    pub uetr: Option<String>,     // {121:} in the user header
+    pub reference: String,        // :20:
+    pub bank_operation_code: Option<String>, // :23B:
+    pub value_date: String,       // :32A: YYMMDD
     pub currency: String,
     pub amount: u64,
-    pub debtor: String,
-    pub creditor: String,
+    pub instructed_amount: Option<(String, u64)>, // :33B:
+    pub ordering_customer: Party,                 // :50A:, :50F: or :50K:
+    pub ordering_institution: Option<Party>,      // :52A:
+    pub account_with_institution: Option<Party>,  // :57A:
+    pub beneficiary: Party,                       // :59:, :59A: or :59F:
+    pub remittance_info: Option<String>,          // :70:
+    pub charges: Option<ChargeBearer>,            // :71A:
+}
+
+/// Find the single field of a party with one of the allowed options (e.g. `50` with `AFK`)
+pub(crate) fn party_field(fields: &[fin::Field], base: &str, options: &[Option<char>]) -> Result<Option<Party>> {
+    let mut found = fields.iter().filter(|f| f.tag.starts_with(base) && f.tag.len() <= base.len() + 1);
+    let Some(field) = found.next() else {
+        return Ok(None);
+    };
+    if found.next().is_some() {
+        return Err(anyhow!("Field :{}a: appears more than once", base));
+    }
+    let option = field.tag.chars().nth(base.len());
+    if !options.contains(&option) {
+        return Err(anyhow!("Option :{}: is not allowed here", field.tag));
+    }
+    Party::parse(&field.tag, &field.value).map(Some)
+}
+
+pub(crate) fn required<'a>(fields: &'a [fin::Field], tag: &str) -> Result<&'a str> {
+    fields.iter()
+        .find(|f| f.tag == tag)
+        .map(|f| f.value.as_str())
+        .ok_or_else(|| anyhow!("Missing mandatory field :{}:", tag))
 }
 
+/// Parse an MT103, either as a full FIN message or as a bare text block
 pub fn parse_mt103(raw: &str) -> Result<MT103Message> {
-    let mut reference = String::new();
-    let mut value_date = String::new();
-    let mut amount = 0u64;
-    let mut currency = String::new();
-    let mut debtor = String::new();
-    let mut creditor = String::new();
-
-    for line in raw.lines() {
-        if line.starts_with(":20:") {
-            reference = line[4..].trim().to_string();
-        } else if line.starts_with(":32A:") {
-            // Example: :32A:250703USD12345,
-            let date_part = &line[5..11];
-            let currency_part = &line[11..14];
-            let amount_part = line[14..].replace(",", "").trim().to_string();
-            value_date = date_part.to_string();
-            currency = currency_part.to_string();
-            amount = amount_part.parse::<u64>()
-                .map_err(|_| anyhow!("Invalid amount format"))?;
-        } else if line.starts_with(":50K:") {
-            debtor = line[5..].trim().to_string();
-        } else if line.starts_with(":59:") {
-            creditor = line[4..].trim().to_string();
+    let message = parse_fin(raw)?;
+    if let Some(mt) = message.message_type() {
+        if mt != "103" {
+            return Err(anyhow!("Expected an MT103, got MT{}", mt));
         }
     }
-
-    if reference.is_empty() || amount == 0 || debtor.is_empty() || creditor.is_empty() {
-        return Err(anyhow!("Invalid or incomplete MT103 message"));
+    let text = &message.fields;
+    let reference = required(text, "20")?.to_string();
+    let (value_date, currency, amount) = fields::parse_date_currency_amount(required(text, "32A")?)?;
+    if amount == 0 {
+        return Err(anyhow!("MT103 amount must be positive"));
     }
+    let ordering_customer = party_field(text, "50", &[Some('A'), Some('F'), Some('K')])?
+        .ok_or_else(|| anyhow!("Missing mandatory field :50a:"))?;
+    let beneficiary = party_field(text, "59", &[None, Some('A'), Some('F')])?
+        .ok_or_else(|| anyhow!("Missing mandatory field :59a:"))?;
 
     Ok(MT103Message {
+        sender: message.sender_bic(),
+        receiver: message.receiver_bic(),
+        uetr: message.user_header_value("121").map(str::to_string),
         reference,
+        bank_operation_code: message.field("23B").map(|f| f.value.clone()),
         value_date,
         currency,
         amount,
-        debtor,
-        creditor,
+        instructed_amount: message.field("33B").map(|f| fields::parse_currency_amount(&f.value)).transpose()?,
+        ordering_customer,
+        ordering_institution: party_field(text, "52", &[Some('A')])?,
+        account_with_institution: party_field(text, "57", &[Some('A')])?,
+        beneficiary,
+        remittance_info: message.field("70").map(|f| fields::parse_narrative("70", &f.value, 4)).transpose()?,
+        charges: message.field("71A").map(|f| ChargeBearer::parse(&f.value)).transpose()?,
     })
 }
 
-pub fn mt103_to_findag_tx(mt: &MT103Message) -> Transaction {
-    // Create addresses from the debtor and creditor strings
-    let from = Address::new(mt.debtor.clone());
-    let to = Address::new(mt.creditor.clone());
-    
+impl MT103Message {
+    /// Generate the FIN message, e.g. to forward a payment settled on FinDAG
+    pub fn to_fin(&self, sender: &str, receiver: &str) -> Result<String> {
+        let mut message = FinMessage::input("103", sender, receiver)?;
+        if let Some(uetr) = &self.uetr {
+            message.user_header.push(("121".to_string(), uetr.clone()));
+        }
+        message.push_field("20", self.reference.clone());
+        message.push_field("23B", self.bank_operation_code.clone().unwrap_or_else(|| "CRED".to_string()));
+        message.push_field("32A", format!("{}{}{}", self.value_date, self.currency, fields::format_amount(self.amount, &self.currency)));
+        if let Some((currency, amount)) = &self.instructed_amount {
+            message.push_field("33B", format!("{}{}", currency, fields::format_amount(*amount, currency)));
+        }
+        message.push_field(&format!("50{}", self.ordering_customer.option_suffix()), self.ordering_customer.to_field_value());
+        if let Some(party) = &self.ordering_institution {
+            message.push_field(&format!("52{}", party.option_suffix()), party.to_field_value());
+        }
+        if let Some(party) = &self.account_with_institution {
+            message.push_field(&format!("57{}", party.option_suffix()), party.to_field_value());
+        }
+        message.push_field(&format!("59{}", self.beneficiary.option_suffix()), self.beneficiary.to_field_value());
+        if let Some(info) = &self.remittance_info {
+            message.push_field("70", info.clone());
+        }
+        let charges = self.charges.ok_or_else(|| anyhow!("MT103 needs :71A: details of charges"))?;
+        message.push_field("71A", charges.code());
+        Ok(message.to_fin())
+    }
+}
+
+/// Identifiers a party can be mapped by, most specific first
+fn party_candidates(party: &Party) -> Vec<(IdentifierKind, &str)> {
+    let mut candidates = Vec::new();
+    if let Some(account) = &party.account {
+        candidates.push((IdentifierKind::Account, account.as_str()));
+    }
+    if let Some(lei) = party.party_identifier.as_deref().filter(|id| is_valid_lei(id)) {
+        candidates.push((IdentifierKind::Lei, lei));
+    }
+    if let Some(bic) = &party.bic {
+        candidates.push((IdentifierKind::Bic, bic.as_str()));
+    }
+    if candidates.is_empty() {
+        let identifier = party.identifier();
+        candidates.push((IdentifierKind::classify(identifier), identifier));
+    }
+    candidates
+}
+
+/// Convert an MT103 into a FinDAG transaction signed by the SWIFT gateway key.
+/// The ordering customer must map to an account of the institution bound to SWIFT.
+pub fn mt103_to_findag_tx(mt: &MT103Message, registry: &IngestionRegistry, signer: &GatewaySigner) -> Result<Transaction> {
+    if signer.adapter != Adapter::Swift {
+        return Err(anyhow!("{} gateway key cannot sign SWIFT conversions", signer.adapter.protocol()));
+    }
+    let from = registry.debit_account(Adapter::Swift, &party_candidates(&mt.ordering_customer)).map_err(|e| anyhow!(e))?;
+    let to = registry.credit_account(&party_candidates(&mt.beneficiary)).map_err(|e| anyhow!(e))?;
+
     let amount = mt.amount;
     
     // Create payload with currency information
@@ -71,28 +165,47 @@ pub fn mt103_to_findag_tx(mt: &MT103Message) -> Transaction {
     let mut hashtimer_array = [0u8; 32];
     hashtimer_array.copy_from_slice(&hashtimer);
 
-    // Create a dummy signature and public key for now
-    // In a real implementation, these would be properly signed
-    let dummy_signature = Signature::from_bytes(&[0u8; 64]);
-    let dummy_public_key = VerifyingKey::from_bytes(&[0u8; 32]).unwrap();
-
-    Transaction {
+    let mut tx = Transaction {
         from,
         to,
         amount,
         payload,
         findag_time: 0, // Will be set by the system
         hashtimer: hashtimer_array,
-        signature: dummy_signature,
-        public_key: dummy_public_key,
+        signature: Signature::from_bytes(&[0u8; 64]), // Set with bridge_protocol by the gateway signer
+        public_key: signer.public_key(),
         shard_id: crate::core::types::ShardId(0),
         source_shard: None,
         dest_shard: None,
         target_chain: None,
         bridge_protocol: None,
-    }
+        valid_after: None,
+        valid_until: None,
+        multisig_signatures: Vec::new(),
+    };
+    signer.sign(&mut tx);
+    Ok(tx)
 }
 
+/// Full FIN MT103 with decimal amount, option F/A parties and multi-line remittance
+pub const EXAMPLE_MT103_FIN: &str = "{1:F01BNPAFRPPAXXX0000000000}{2:I103DEUTDEFFXXXXN}{3:{108:MUR-42}{121:8a562c67-ca16-48ba-b074-65581be6f011}}{4:
+:20:PAY-2025-0703-1
+:23B:CRED
+:32A:250703EUR1234,56
+:33B:EUR1234,56
+:50F:/FR1420041010050500013M02606
+1/SUPPLIER AND SONS SARL
+2/12 AVENUE DE L OPERA
+3/FR/PARIS
+:52A:BNPAFRPP
+:57A:DEUTDEFF
+:59A:/DE89370400440532013000
+DEUTDEFF
+:70:INVOICE 2025-117
+PO 4471
+:71A:SHA
+-}{5:{CHK:A1B2C3D4E5F6}}";
+
 pub const EXAMPLE_MT103: &str = r#"
 :20:REFERENCE12345
 :32A:250703USD123456,
@@ -109,19 +222,26 @@ mod tests {
         let mt = parse_mt103(EXAMPLE_MT103).expect("Should parse valid MT103");
         assert_eq!(mt.reference, "REFERENCE12345");
         assert_eq!(mt.currency, "USD");
-        assert_eq!(mt.amount, 123456);
-        assert_eq!(mt.debtor, "ALICE_BANK");
-        assert_eq!(mt.creditor, "BOB_BANK");
+        assert_eq!(mt.amount, 12_345_600);
+        assert_eq!(mt.ordering_customer.identifier(), "ALICE_BANK");
+        assert_eq!(mt.beneficiary.identifier(), "BOB_BANK");
     }
 
     #[test]
     fn test_convert_mt103_to_findag_tx() {
-        let mt = parse_mt103(EXAMPLE_MT103).unwrap();
-        let findag_tx = mt103_to_findag_tx(&mt);
+        let (registry, key) = crate::core::ingestion::test_registry();
+        let signer = GatewaySigner::new(Adapter::Swift, key);
+        let mt = parse_mt103(EXAMPLE_MT103_FIN).unwrap();
+        let findag_tx = mt103_to_findag_tx(&mt, &registry, &signer).unwrap();
 
-        assert_eq!(findag_tx.amount, 123456);
-        assert_eq!(findag_tx.payload, b"currency:USD");
-        assert_ne!(findag_tx.hashtimer, [0u8; 32]);
+        assert_eq!(findag_tx.amount, 123_456);
+        assert_eq!(findag_tx.payload, b"currency:EUR");
+        assert_eq!(findag_tx.bridge_protocol.as_deref(), Some("SWIFT"));
+        assert_eq!(registry.verify(&findag_tx), Some(Ok(())));
+
+        // Unmapped ordering customers are refused instead of getting a made-up address
+        let unmapped = parse_mt103(EXAMPLE_MT103).unwrap();
+        assert!(mt103_to_findag_tx(&unmapped, &registry, &signer).is_err());
     }
 
     #[test]
@@ -142,21 +262,60 @@ mod tests {
         let mt = parse_mt103(eur_message).expect("Should parse valid MT103");
         assert_eq!(mt.reference, "REFERENCE67890");
         assert_eq!(mt.currency, "EUR");
-        assert_eq!(mt.amount, 50000);
-        assert_eq!(mt.debtor, "EURO_BANK");
-        assert_eq!(mt.creditor, "AMERICAN_BANK");
+        assert_eq!(mt.amount, 5_000_000);
+        assert_eq!(mt.ordering_customer.identifier(), "EURO_BANK");
+        assert_eq!(mt.beneficiary.identifier(), "AMERICAN_BANK");
     }
 
     #[test]
     fn test_hashtimer_generation() {
-        let mt = parse_mt103(EXAMPLE_MT103).unwrap();
-        let findag_tx = mt103_to_findag_tx(&mt);
+        let (registry, key) = crate::core::ingestion::test_registry();
+        let signer = GatewaySigner::new(Adapter::Swift, key);
+        let mt = parse_mt103(EXAMPLE_MT103_FIN).unwrap();
+        let findag_tx = mt103_to_findag_tx(&mt, &registry, &signer).unwrap();
         
         // Verify hashtimer is not all zeros
         assert_ne!(findag_tx.hashtimer, [0u8; 32]);
         
         // Verify hashtimer is consistent for same reference
-        let findag_tx2 = mt103_to_findag_tx(&mt);
+        let findag_tx2 = mt103_to_findag_tx(&mt, &registry, &signer).unwrap();
         assert_eq!(findag_tx.hashtimer, findag_tx2.hashtimer);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_parse_fin_mt103_options_and_decimals() {
+        let mt = parse_mt103(EXAMPLE_MT103_FIN).expect("Should parse FIN MT103");
+        assert_eq!(mt.sender.as_deref(), Some("BNPAFRPP"));
+        assert_eq!(mt.receiver.as_deref(), Some("DEUTDEFF"));
+        assert_eq!(mt.uetr.as_deref(), Some("8a562c67-ca16-48ba-b074-65581be6f011"));
+        assert_eq!(mt.amount, 123_456);
+        assert_eq!(mt.instructed_amount, Some(("EUR".to_string(), 123_456)));
+        assert_eq!(mt.ordering_customer.account.as_deref(), Some("FR1420041010050500013M02606"));
+        assert_eq!(mt.ordering_customer.name.as_deref(), Some("SUPPLIER AND SONS SARL"));
+        assert_eq!(mt.ordering_institution.as_ref().unwrap().bic.as_deref(), Some("BNPAFRPP"));
+        assert_eq!(mt.beneficiary.bic.as_deref(), Some("DEUTDEFF"));
+        assert_eq!(mt.beneficiary.account.as_deref(), Some("DE89370400440532013000"));
+        assert_eq!(mt.remittance_info.as_deref(), Some("INVOICE 2025-117\nPO 4471"));
+        assert_eq!(mt.charges, Some(ChargeBearer::Shared));
+
+        // Precision follows the currency: JPY has no minor unit, EUR two decimals
+        assert!(parse_mt103(&EXAMPLE_MT103_FIN.replace("EUR1234,56\n:33B", "JPY1234,5\n:33B")).is_err());
+        assert!(parse_mt103(&EXAMPLE_MT103_FIN.replace("EUR1234,56\n:33B", "EUR1234,567\n:33B")).is_err());
+        assert!(parse_mt103(&EXAMPLE_MT103_FIN.replace("EUR1234,56\n:33B", "EUR1234\n:33B")).is_err());
+        assert!(parse_mt103(&EXAMPLE_MT103_FIN.replace("I103", "I202")).is_err());
+    }
+
+    #[test]
+    fn test_generate_mt103_and_mt910() {
+        let mt = parse_mt103(EXAMPLE_MT103_FIN).unwrap();
+        let regenerated = parse_mt103(&mt.to_fin("BNPAFRPP", "DEUTDEFF").unwrap()).unwrap();
+        assert_eq!(regenerated, mt);
+
+        let confirmation = MT910Confirmation::from_mt103(&mt, "CONF-1", "DE89370400440532013000");
+        let fin = parse_fin(&confirmation.to_fin("DEUTDEFF", "BNPAFRPP").unwrap()).unwrap();
+        assert_eq!(fin.message_type(), Some("910"));
+        assert_eq!(fin.field("21").unwrap().value, "PAY-2025-0703-1");
+        assert_eq!(fin.field("32A").unwrap().value, "250703EUR1234,56");
+        assert_eq!(fin.field("50F").unwrap().value, mt.ordering_customer.to_field_value());
+    }
+}
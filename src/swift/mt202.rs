@@ -0,0 +1,168 @@
+use anyhow::{anyhow, Result};
+use super::fields::{self, Party};
+use super::fin::parse_fin;
+use super::{party_field, required};
+
+/// Customer credit transfer an MT202COV covers (sequence B)
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnderlyingCustomerCredit {
+    pub ordering_customer: Party,                // :50a:
+    pub ordering_institution: Option<Party>,     // :52a:
+    pub intermediary_institution: Option<Party>, // :56a:
+    pub account_with_institution: Option<Party>, // :57a:
+    pub beneficiary: Party,                      // :59a:
+    pub remittance_info: Option<String>,         // :70:
+    pub instructed_amount: Option<(String, u64)>, // :33B:
+}
+
+/// MT202 general financial institution transfer, or MT202COV when it carries the
+/// underlying customer credit. Amounts are in minor units of the currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MT202Message {
+    pub sender: Option<String>,
+    pub receiver: Option<String>,
+    pub uetr: Option<String>,
+    pub reference: String,         // :20:
+    pub related_reference: String, // :21:
+    pub value_date: String,        // :32A: YYMMDD
+    pub currency: String,
+    pub amount: u64,
+    pub ordering_institution: Option<Party>,      // :52a:
+    pub senders_correspondent: Option<Party>,     // :53a:
+    pub receivers_correspondent: Option<Party>,   // :54a:
+    pub intermediary: Option<Party>,              // :56a:
+    pub account_with_institution: Option<Party>,  // :57a:
+    pub beneficiary_institution: Party,           // :58a:
+    pub sender_to_receiver_info: Option<String>,  // :72:
+    pub underlying: Option<UnderlyingCustomerCredit>,
+}
+
+impl MT202Message {
+    pub fn is_cover(&self) -> bool {
+        self.underlying.is_some()
+    }
+}
+
+const INSTITUTION: &[Option<char>] = &[Some('A'), Some('D')];
+const INSTITUTION_OR_LOCATION: &[Option<char>] = &[Some('A'), Some('B'), Some('D')];
+
+/// Parse an MT202 or MT202COV. A cover message is flagged with `{119:COV}` in the
+/// user header and its sequence B starts at the ordering customer field.
+pub fn parse_mt202(raw: &str) -> Result<MT202Message> {
+    let message = parse_fin(raw)?;
+    if let Some(mt) = message.message_type() {
+        if mt != "202" {
+            return Err(anyhow!("Expected an MT202, got MT{}", mt));
+        }
+    }
+    let split = message.fields.iter()
+        .position(|f| f.tag.starts_with("50"))
+        .unwrap_or(message.fields.len());
+    let (general, customer) = message.fields.split_at(split);
+    let flagged_cover = message.user_header_value("119") == Some("COV");
+    if flagged_cover && customer.is_empty() {
+        return Err(anyhow!("MT202COV is missing the underlying customer credit (sequence B)"));
+    }
+    if !flagged_cover && !customer.is_empty() {
+        return Err(anyhow!("MT202 carries customer fields but is not flagged {{119:COV}}"));
+    }
+
+    let (value_date, currency, amount) = fields::parse_date_currency_amount(required(general, "32A")?)?;
+    if amount == 0 {
+        return Err(anyhow!("MT202 amount must be positive"));
+    }
+    let underlying = if customer.is_empty() {
+        None
+    } else {
+        Some(UnderlyingCustomerCredit {
+            ordering_customer: party_field(customer, "50", &[Some('A'), Some('F'), Some('K')])?
+                .ok_or_else(|| anyhow!("Missing mandatory field :50a: in sequence B"))?,
+            ordering_institution: party_field(customer, "52", INSTITUTION)?,
+            intermediary_institution: party_field(customer, "56", INSTITUTION)?,
+            account_with_institution: party_field(customer, "57", INSTITUTION_OR_LOCATION)?,
+            beneficiary: party_field(customer, "59", &[None, Some('A'), Some('F')])?
+                .ok_or_else(|| anyhow!("Missing mandatory field :59a: in sequence B"))?,
+            remittance_info: narrative(customer, "70", 4)?,
+            instructed_amount: customer.iter()
+                .find(|f| f.tag == "33B")
+                .map(|f| fields::parse_currency_amount(&f.value))
+                .transpose()?,
+        })
+    };
+
+    Ok(MT202Message {
+        sender: message.sender_bic(),
+        receiver: message.receiver_bic(),
+        uetr: message.user_header_value("121").map(str::to_string),
+        reference: required(general, "20")?.to_string(),
+        related_reference: required(general, "21")?.to_string(),
+        value_date,
+        currency,
+        amount,
+        ordering_institution: party_field(general, "52", INSTITUTION)?,
+        senders_correspondent: party_field(general, "53", INSTITUTION_OR_LOCATION)?,
+        receivers_correspondent: party_field(general, "54", INSTITUTION_OR_LOCATION)?,
+        intermediary: party_field(general, "56", INSTITUTION)?,
+        account_with_institution: party_field(general, "57", INSTITUTION_OR_LOCATION)?,
+        beneficiary_institution: party_field(general, "58", INSTITUTION)?
+            .ok_or_else(|| anyhow!("Missing mandatory field :58a:"))?,
+        sender_to_receiver_info: narrative(general, "72", 6)?,
+        underlying,
+    })
+}
+
+fn narrative(text: &[super::fin::Field], tag: &str, max_lines: usize) -> Result<Option<String>> {
+    text.iter()
+        .find(|f| f.tag == tag)
+        .map(|f| fields::parse_narrative(tag, &f.value, max_lines))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MT202_COV: &str = "{1:F01DEUTDEFFAXXX0000000000}{2:I202CHASUS33XXXXN}{3:{119:COV}{121:e1f3a1c2-5b4d-4c8e-9f10-2a3b4c5d6e7f}}{4:
+:20:COVER-0001
+:21:PAY-REF-77
+:32A:250704USD1250000,5
+:52A:DEUTDEFF
+:57A:CHASUS33
+:58A:/998877
+BNPAFRPP
+:50F:/DE89370400440532013000
+1/CLIENT HOLDINGS SA
+2/TAUNUSANLAGE 12
+3/DE/FRANKFURT
+:52A:DEUTDEFF
+:57A:BNPAFRPP
+:59:/FR1420041010050500013M02606
+BENEFICIARY CORP
+1 RUE DE LA PAIX
+:70:INVOICE 2025-117
+:33B:USD1250000,50
+-}";
+
+    #[test]
+    fn test_parse_mt202_cov() {
+        let mt = parse_mt202(MT202_COV).unwrap();
+        assert!(mt.is_cover());
+        assert_eq!(mt.sender.as_deref(), Some("DEUTDEFF"));
+        assert_eq!(mt.receiver.as_deref(), Some("CHASUS33"));
+        assert_eq!(mt.amount, 125_000_050);
+        assert_eq!(mt.beneficiary_institution.account.as_deref(), Some("998877"));
+        assert_eq!(mt.beneficiary_institution.bic.as_deref(), Some("BNPAFRPP"));
+        let underlying = mt.underlying.unwrap();
+        assert_eq!(underlying.ordering_customer.name.as_deref(), Some("CLIENT HOLDINGS SA"));
+        assert_eq!(underlying.ordering_customer.address, vec!["2/TAUNUSANLAGE 12", "3/DE/FRANKFURT"]);
+        assert_eq!(underlying.account_with_institution.unwrap().bic.as_deref(), Some("BNPAFRPP"));
+        assert_eq!(underlying.beneficiary.address, vec!["1 RUE DE LA PAIX"]);
+        assert_eq!(underlying.instructed_amount, Some(("USD".to_string(), 125_000_050)));
+
+        // Plain MT202 must not carry customer fields, and a COV needs them
+        assert!(parse_mt202(&MT202_COV.replace("{119:COV}", "")).is_err());
+        let plain = MT202_COV.split("\n:50F:").next().unwrap().to_string() + "\n-}";
+        assert!(parse_mt202(&plain).is_err());
+        assert!(!parse_mt202(&plain.replace("{119:COV}", "")).unwrap().is_cover());
+    }
+}
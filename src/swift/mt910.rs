@@ -0,0 +1,64 @@
+use anyhow::{anyhow, Result};
+use super::fields::{self, Party};
+use super::fin::FinMessage;
+use super::MT103Message;
+
+/// MT910 confirmation of credit, sent to the account owner once an incoming
+/// payment is booked. Amounts are in minor units of the currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MT910Confirmation {
+    pub reference: String,         // :20:
+    pub related_reference: String, // :21: the credited payment's reference
+    pub account: String,           // :25: account credited
+    pub booked_at: Option<String>, // :13D: YYMMDDhhmm+hhmm
+    pub value_date: String,        // :32A: YYMMDD
+    pub currency: String,
+    pub amount: u64,
+    pub ordering_customer: Option<Party>,    // :50a:
+    pub ordering_institution: Option<Party>, // :52a:
+    pub intermediary: Option<Party>,         // :56a:
+    pub sender_to_receiver_info: Option<String>, // :72:
+}
+
+impl MT910Confirmation {
+    /// Confirm the credit of an MT103 to `account`
+    pub fn from_mt103(payment: &MT103Message, reference: &str, account: &str) -> Self {
+        MT910Confirmation {
+            reference: reference.to_string(),
+            related_reference: payment.reference.clone(),
+            account: account.to_string(),
+            booked_at: None,
+            value_date: payment.value_date.clone(),
+            currency: payment.currency.clone(),
+            amount: payment.amount,
+            ordering_customer: Some(payment.ordering_customer.clone()),
+            ordering_institution: payment.ordering_institution.clone(),
+            intermediary: None,
+            sender_to_receiver_info: None,
+        }
+    }
+
+    /// Generate the FIN message from the account servicer `sender` to the account owner `receiver`
+    pub fn to_fin(&self, sender: &str, receiver: &str) -> Result<String> {
+        if self.ordering_customer.is_none() && self.ordering_institution.is_none() {
+            return Err(anyhow!("MT910 needs an ordering customer (:50a:) or ordering institution (:52a:)"));
+        }
+        let mut message = FinMessage::input("910", sender, receiver)?;
+        message.push_field("20", self.reference.clone());
+        message.push_field("21", self.related_reference.clone());
+        message.push_field("25", self.account.clone());
+        if let Some(booked_at) = &self.booked_at {
+            message.push_field("13D", booked_at.clone());
+        }
+        message.push_field("32A", format!("{}{}{}", self.value_date, self.currency, fields::format_amount(self.amount, &self.currency)));
+        for (base, party) in [("50", &self.ordering_customer), ("52", &self.ordering_institution), ("56", &self.intermediary)] {
+            if let Some(party) = party {
+                message.push_field(&format!("{}{}", base, party.option_suffix()), party.to_field_value());
+            }
+        }
+        if let Some(info) = &self.sender_to_receiver_info {
+            message.push_field("72", info.clone());
+        }
+        Ok(message.to_fin())
+    }
+}
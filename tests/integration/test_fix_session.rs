@@ -0,0 +1,201 @@
+#[cfg(test)]
+mod tests {
+    use findag::fix::acceptor::FixAcceptor;
+    use findag::fix::message::{next_frame, tags, FixMessage, FIX_4_4};
+    use findag::core::tx_status::TxStatusRegistry;
+    use findag::fix::gateway::FixOrderGateway;
+    use findag::fix::session::{sending_time_now, FixApplication, RejectApplication, SessionConfig};
+    use findag::fix::store::MessageStore;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Minimal FIX initiator over a raw socket
+    struct Initiator {
+        stream: TcpStream,
+        buffer: Vec<u8>,
+        next_seq: u64,
+        comp_id: &'static str,
+    }
+
+    impl Initiator {
+        async fn connect(addr: SocketAddr, next_seq: u64) -> Self {
+            let stream = TcpStream::connect(addr).await.unwrap();
+            Initiator { stream, buffer: Vec::new(), next_seq, comp_id: "CLIENT1" }
+        }
+
+        async fn send_with_seq(&mut self, message: FixMessage, seq: u64) {
+            let mut fields = vec![
+                (tags::SENDER_COMP_ID, self.comp_id.to_string()),
+                (tags::TARGET_COMP_ID, "FINDAG".to_string()),
+                (tags::MSG_SEQ_NUM, seq.to_string()),
+                (tags::SENDING_TIME, sending_time_now()),
+            ];
+            fields.extend(message.fields);
+            let raw = FixMessage { msg_type: message.msg_type, fields }.encode(FIX_4_4);
+            self.stream.write_all(raw.as_bytes()).await.unwrap();
+        }
+
+        async fn send(&mut self, message: FixMessage) {
+            let seq = self.next_seq;
+            self.next_seq += 1;
+            self.send_with_seq(message, seq).await;
+        }
+
+        async fn receive(&mut self) -> Option<FixMessage> {
+            let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+            loop {
+                if let Some(frame) = next_frame(&mut self.buffer).unwrap() {
+                    let (begin_string, message) = FixMessage::decode(&frame).unwrap();
+                    assert_eq!(begin_string, FIX_4_4);
+                    return Some(message);
+                }
+                let mut chunk = [0u8; 4096];
+                match tokio::time::timeout_at(deadline, self.stream.read(&mut chunk)).await {
+                    Ok(Ok(n)) if n > 0 => self.buffer.extend_from_slice(&chunk[..n]),
+                    _ => return None,
+                }
+            }
+        }
+
+        async fn logon(&mut self) -> FixMessage {
+            self.send(FixMessage::new("A").with(tags::ENCRYPT_METHOD, 0).with(tags::HEART_BT_INT, 30)).await;
+            self.receive().await.unwrap()
+        }
+    }
+
+    async fn start_acceptor(store: Arc<MessageStore>, application: Arc<dyn FixApplication>) -> (SocketAddr, FixAcceptor) {
+        let config = SessionConfig {
+            begin_string: FIX_4_4.to_string(),
+            sender_comp_id: "FINDAG".to_string(),
+            allowed_targets: vec!["CLIENT1".to_string(), "CLIENT2".to_string()],
+        };
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let acceptor = FixAcceptor::new(config, store, application);
+        tokio::spawn(acceptor.clone().run(listener));
+        (addr, acceptor)
+    }
+
+    #[tokio::test]
+    async fn test_fix_session_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(MessageStore::open(dir.path()).unwrap());
+        let (addr, _) = start_acceptor(store, Arc::new(RejectApplication)).await;
+
+        let mut client = Initiator::connect(addr, 1).await;
+        let logon = client.logon().await;
+        assert_eq!(logon.msg_type, "A");
+        assert_eq!(logon.seq_num(), Some(1));
+        assert_eq!(logon.get(tags::HEART_BT_INT), Some("30"));
+
+        // A second connection on the same session is refused
+        let mut duplicate = Initiator::connect(addr, 99).await;
+        duplicate.send(FixMessage::new("A").with(tags::ENCRYPT_METHOD, 0).with(tags::HEART_BT_INT, 30)).await;
+        assert!(duplicate.receive().await.is_none());
+
+        client.send(FixMessage::new("1").with(tags::TEST_REQ_ID, "PING-1")).await;
+        let heartbeat = client.receive().await.unwrap();
+        assert_eq!((heartbeat.msg_type.as_str(), heartbeat.get(tags::TEST_REQ_ID)), ("0", Some("PING-1")));
+
+        client.send(FixMessage::new("D").with(11, "ORD-1")).await;
+        let reject = client.receive().await.unwrap();
+        assert_eq!(reject.msg_type, "j");
+        assert_eq!(reject.get_u64(tags::REF_SEQ_NUM), Some(3));
+
+        // Skipping 4 makes the acceptor ask for it
+        client.send_with_seq(FixMessage::new("0"), 5).await;
+        let resend = client.receive().await.unwrap();
+        assert_eq!(resend.msg_type, "2");
+        assert_eq!((resend.get_u64(tags::BEGIN_SEQ_NO), resend.get_u64(tags::END_SEQ_NO)), (Some(4), Some(0)));
+        client.send_with_seq(
+            FixMessage::new("4").with(tags::POSS_DUP_FLAG, "Y").with(tags::GAP_FILL_FLAG, "Y").with(tags::NEW_SEQ_NO, 6),
+            4,
+        ).await;
+        client.next_seq = 6;
+
+        // Our ResendRequest replays the reject and gap-fills the session messages
+        client.send(FixMessage::new("2").with(tags::BEGIN_SEQ_NO, 1).with(tags::END_SEQ_NO, 0)).await;
+        let gap_fill = client.receive().await.unwrap();
+        assert_eq!((gap_fill.msg_type.as_str(), gap_fill.seq_num(), gap_fill.get_u64(tags::NEW_SEQ_NO)), ("4", Some(1), Some(3)));
+        let replayed = client.receive().await.unwrap();
+        assert_eq!((replayed.msg_type.as_str(), replayed.seq_num()), ("j", Some(3)));
+        assert!(replayed.flag(tags::POSS_DUP_FLAG));
+        assert!(replayed.get(tags::ORIG_SENDING_TIME).is_some());
+        let trailing = client.receive().await.unwrap();
+        assert_eq!((trailing.msg_type.as_str(), trailing.seq_num(), trailing.get_u64(tags::NEW_SEQ_NO)), ("4", Some(4), Some(5)));
+
+        client.send(FixMessage::new("5")).await;
+        let logout = client.receive().await.unwrap();
+        assert_eq!((logout.msg_type.as_str(), logout.seq_num()), ("5", Some(5)));
+        assert!(client.receive().await.is_none());
+
+        // Sequence numbers continue on reconnect; a stale MsgSeqNum is refused
+        let mut stale = Initiator::connect(addr, 1).await;
+        let refused = stale.logon().await;
+        assert_eq!(refused.msg_type, "5");
+        assert!(refused.get(tags::TEXT).unwrap().contains("MsgSeqNum too low"));
+
+        let mut resumed = Initiator::connect(addr, 8).await;
+        let logon = resumed.logon().await;
+        assert_eq!((logon.msg_type.as_str(), logon.seq_num()), ("A", Some(7)));
+    }
+
+    fn order(cl_ord_id: &str, account: &str, side: &str, qty: u64, price: &str) -> FixMessage {
+        FixMessage::new("D")
+            .with(11, cl_ord_id)
+            .with(1, account)
+            .with(55, "EUR/USD")
+            .with(54, side)
+            .with(38, qty)
+            .with(44, price)
+    }
+
+    #[tokio::test]
+    async fn test_fix_order_fills_follow_settlement() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(MessageStore::open(dir.path()).unwrap());
+        let registry = Arc::new(TxStatusRegistry::new(100));
+        let (gateway, mut trades) = FixOrderGateway::new("IT");
+        let (addr, acceptor) = start_acceptor(store, gateway.clone()).await;
+        gateway.attach(acceptor);
+        gateway.spawn_status_listener(registry.clone());
+
+        let mut buyer = Initiator::connect(addr, 1).await;
+        buyer.logon().await;
+        let mut seller = Initiator::connect(addr, 1).await;
+        seller.comp_id = "CLIENT2";
+        seller.logon().await;
+
+        buyer.send(order("B1", "ALICE", "1", 1_000, "1.1")).await;
+        let new = buyer.receive().await.unwrap();
+        assert_eq!((new.get(150), new.get(39), new.get(151)), (Some("0"), Some("0"), Some("1000")));
+        seller.send(order("S1", "BOB", "2", 400, "1.05")).await;
+        assert_eq!(seller.receive().await.unwrap().get(150), Some("0"));
+
+        // The match only becomes a fill once its settlement transaction is final
+        let trade = trades.recv().await.unwrap();
+        assert_eq!((trade.qty, trade.price), (400, 1.1));
+        gateway.settlement_submitted(&trade.match_id, [9; 32]);
+        registry.mark_pending([9; 32]);
+        registry.mark_included([1; 32], &[[9; 32]]);
+        buyer.send(FixMessage::new("H").with(11, "B1")).await;
+        let status = buyer.receive().await.unwrap();
+        assert_eq!((status.get(150), status.get(14)), (Some("I"), Some("0")));
+
+        registry.mark_round_finalized(3, &[[1; 32]]);
+        let fill = buyer.receive().await.unwrap();
+        assert_eq!((fill.get(150), fill.get(39), fill.get(32), fill.get(31)), (Some("1"), Some("1"), Some("400"), Some("1.1")));
+        let fill = seller.receive().await.unwrap();
+        assert_eq!((fill.get(150), fill.get(39), fill.get(151)), (Some("2"), Some("2"), Some("0")));
+
+        // The remainder can be canceled; the filled order cannot
+        buyer.send(FixMessage::new("F").with(11, "B2").with(41, "B1")).await;
+        let canceled = buyer.receive().await.unwrap();
+        assert_eq!((canceled.get(150), canceled.get(39), canceled.get(14)), (Some("4"), Some("4"), Some("400")));
+        seller.send(FixMessage::new("F").with(11, "S2").with(41, "S1")).await;
+        assert_eq!(seller.receive().await.unwrap().msg_type, "9");
+    }
+}